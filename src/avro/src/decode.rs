@@ -169,6 +169,13 @@ pub fn decode<'a, R: Read>(schema: SchemaNode<'a>, reader: &'a mut R) -> Result<
             let j = serde_json::from_str(s.as_str())?;
             Ok(Value::Json(j))
         }
+        SchemaPiece::Uuid => {
+            let s = decode_string(reader)?;
+            let u = s
+                .parse()
+                .map_err(|_| DecodeError::new(format!("not a valid uuid: {}", s)))?;
+            Ok(Value::Uuid(u))
+        }
         SchemaPiece::Array(inner) => {
             let mut items = Vec::new();
 
@@ -151,6 +151,10 @@ pub fn encode_ref(value: &Value, schema: SchemaNode, buffer: &mut Vec<u8>) {
         Value::Json(j) => {
             encode_bytes(&j.to_string(), buffer);
         }
+        Value::Uuid(u) => {
+            let u_str = u.to_string();
+            encode_bytes(&u_str, buffer);
+        }
     }
 }
 
@@ -626,6 +626,9 @@ impl<'a> SchemaResolver<'a> {
                     (SchemaPiece::TimestampMicro, SchemaPiece::TimestampMicro) => {
                         SchemaPieceOrNamed::Piece(SchemaPiece::TimestampMicro)
                     }
+                    (SchemaPiece::Uuid, SchemaPiece::Uuid) => {
+                        SchemaPieceOrNamed::Piece(SchemaPiece::Uuid)
+                    }
                     (b, SchemaPiece::Bytes)
                         if b == &SchemaPiece::Bytes || b == &SchemaPiece::String =>
                     {
@@ -178,6 +178,10 @@ pub enum SchemaPiece {
     String,
     /// A `string` Avro schema that is tagged as representing JSON data
     Json,
+    /// A `string` Avro schema with a logical type of `uuid`.
+    ///
+    /// https://avro.apache.org/docs/current/spec.html#UUID
+    Uuid,
     /// A `array` Avro schema. Avro arrays are required to have the same type for each element.
     /// This variant holds the `Schema` for the array element type.
     Array(Box<SchemaPieceOrNamed>),
@@ -359,6 +363,7 @@ impl<'a> From<&'a SchemaPiece> for SchemaKind {
             SchemaPiece::ResolveRecord { .. } => SchemaKind::Record,
             SchemaPiece::ResolveEnum { .. } => SchemaKind::Enum,
             SchemaPiece::Json => SchemaKind::String,
+            SchemaPiece::Uuid => SchemaKind::String,
         }
     }
 }
@@ -400,6 +405,7 @@ impl<'a> From<&'a types::Value> for SchemaKind {
             types::Value::Enum(_, _) => SchemaKind::Enum,
             types::Value::Fixed(_, _) => SchemaKind::Fixed,
             types::Value::Json(_) => SchemaKind::String,
+            types::Value::Uuid(_) => SchemaKind::String,
         }
     }
 }
@@ -1068,12 +1074,18 @@ impl SchemaParser {
 
     fn parse_string(complex: &Map<String, Value>) -> SchemaPiece {
         const CONNECT_JSON: &str = "io.debezium.data.Json";
+        const AVRO_UUID: &str = "uuid";
 
         if let Some(serde_json::Value::String(name)) = complex.get("connect.name") {
             if CONNECT_JSON == name.as_str() {
                 return SchemaPiece::Json;
             }
         }
+        if let Some(name) = complex.get("logicalType") {
+            if name == AVRO_UUID {
+                return SchemaPiece::Uuid;
+            }
+        }
         debug!("parsing complex type as regular string: {:?}", complex);
         SchemaPiece::String
     }
@@ -1278,6 +1290,7 @@ impl<'a> SchemaSubtreeDeepCloner<'a> {
             SchemaPiece::TimestampMilli => SchemaPiece::TimestampMilli,
             SchemaPiece::TimestampMicro => SchemaPiece::TimestampMicro,
             SchemaPiece::Json => SchemaPiece::Json,
+            SchemaPiece::Uuid => SchemaPiece::Uuid,
             SchemaPiece::Decimal {
                 scale,
                 precision,
@@ -1478,6 +1491,10 @@ impl<'a> SchemaNode<'a> {
                 unscaled: s.clone().into_bytes(),
             }),
             (String(s), SchemaPiece::String) => AvroValue::String(s.clone()),
+            (String(s), SchemaPiece::Uuid) => match s.parse() {
+                Ok(uuid) => AvroValue::Uuid(uuid),
+                Err(_) => return Err(ParseSchemaError(format!("Invalid uuid default: {}", s))),
+            },
             (Object(map), SchemaPiece::Record { fields, .. }) => {
                 let field_values = fields
                     .iter()
@@ -1626,6 +1643,12 @@ impl<'a> Serialize for SchemaSerContext<'a> {
                     map.serialize_entry("connect.name", "io.debezium.data.Json")?;
                     map.end()
                 }
+                SchemaPiece::Uuid => {
+                    let mut map = serializer.serialize_map(Some(2))?;
+                    map.serialize_entry("type", "string")?;
+                    map.serialize_entry("logicalType", "uuid")?;
+                    map.end()
+                }
                 SchemaPiece::Record { .. }
                 | SchemaPiece::Enum { .. }
                 | SchemaPiece::Fixed { .. } => {
@@ -1706,7 +1729,8 @@ impl<'a> Serialize for SchemaSerContext<'a> {
                     | SchemaPiece::Array(_)
                     | SchemaPiece::Map(_)
                     | SchemaPiece::Union(_)
-                    | SchemaPiece::Json => {
+                    | SchemaPiece::Json
+                    | SchemaPiece::Uuid => {
                         unreachable!("Unexpected anonymous schema piece in named schema position")
                     }
                     SchemaPiece::ResolveIntLong
@@ -2067,6 +2091,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_uuid_schema() {
+        let schema = Schema::parse_str(r#"{"type": "string", "logicalType": "uuid"}"#).unwrap();
+        assert_eq!(schema.top_node().inner, &SchemaPiece::Uuid);
+        assert_eq!(
+            serde_json::to_string(&schema).unwrap(),
+            r#"{"type":"string","logicalType":"uuid"}"#
+        );
+    }
+
     #[test]
     fn test_decimal_schemas() {
         let schema = Schema::parse_str(
@@ -15,6 +15,7 @@ use std::u8;
 use chrono::{NaiveDate, NaiveDateTime};
 use failure::Fail;
 use serde_json::Value as JsonValue;
+use uuid::Uuid;
 
 use crate::schema::{RecordField, SchemaNode, SchemaPiece};
 
@@ -101,6 +102,8 @@ pub enum Value {
     /// This is not part of the Avro spec, but is emitted by Debezium,
     /// and distinguished by setting the `"connect.name"` property to `"io.debezium.data.Json"`.
     Json(serde_json::Value),
+    /// A `Uuid` coming from an avro Logical `uuid`.
+    Uuid(Uuid),
 }
 
 /// Any structure implementing the [ToAvro](trait.ToAvro.html) trait will be usable
@@ -338,6 +341,7 @@ impl Value {
                     )
             }
             (Value::Json(_), SchemaPiece::Json) => true,
+            (Value::Uuid(_), SchemaPiece::Uuid) => true,
             _ => false,
         }
     }
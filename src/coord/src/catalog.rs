@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::iter;
 use std::path::Path;
 use std::sync::{Arc, Mutex, MutexGuard};
@@ -24,6 +24,7 @@ use serde::{Deserialize, Serialize};
 use dataflow_types::{SinkConnector, SinkConnectorBuilder, SourceConnector};
 use expr::{GlobalId, Id, IdHumanizer, OptimizedRelationExpr, ScalarExpr};
 use repr::{RelationDesc, Row};
+use sql::ast::Privilege;
 use sql::names::{DatabaseSpecifier, FullName, PartialName};
 use sql::plan::{Params, Plan, PlanContext};
 use transform::Optimizer;
@@ -60,6 +61,11 @@ pub const MZ_TEMP_SCHEMA: &str = "mz_temp";
 /// The catalog also maintains special "ambient schemas": virtual schemas,
 /// implicitly present in all databases, that house various system views.
 /// The big examples of ambient schemas are `pg_catalog` and `mz_catalog`.
+///
+/// Finally, the catalog tracks roles, the owner of each item, and the
+/// privileges that have been granted on each item. The catalog records this
+/// information but does not enforce it; enforcement is the responsibility of
+/// the coordinator.
 #[derive(Debug)]
 pub struct Catalog {
     by_name: BTreeMap<String, Database>,
@@ -67,6 +73,7 @@ pub struct Catalog {
     indexes: HashMap<GlobalId, Vec<Vec<ScalarExpr>>>,
     ambient_schemas: BTreeMap<String, Schema>,
     temporary_schemas: HashMap<u32, Schema>,
+    roles: BTreeMap<String, Role>,
     storage: Arc<Mutex<storage::Connection>>,
     startup_time: SystemTime,
     nonce: u64,
@@ -105,12 +112,22 @@ pub struct Schema {
     items: BTreeMap<String, GlobalId>,
 }
 
+/// The superuser role that is created with the catalog.
+pub const DEFAULT_ROLE: &str = "materialize";
+
+#[derive(Debug, Clone)]
+pub struct Role {
+    pub superuser: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct CatalogEntry {
     item: CatalogItem,
     used_by: Vec<GlobalId>,
     id: GlobalId,
     name: FullName,
+    owner: Option<String>,
+    privileges: BTreeMap<String, BTreeSet<Privilege>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -213,6 +230,24 @@ impl CatalogItem {
     pub fn is_temporary(&self) -> bool {
         self.conn_id().is_some()
     }
+
+    /// Returns a clone of `self` with all references to the item named `from`
+    /// in its `create_sql` replaced with references to `to`.
+    fn rename_item_refs(&self, from: &FullName, to: &FullName) -> Result<CatalogItem, String> {
+        let do_rewrite = |create_sql: &mut String| -> Result<(), String> {
+            *create_sql = sql::normalize::create_statement_rename(create_sql, from, to)
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        };
+        let mut item = self.clone();
+        match &mut item {
+            CatalogItem::Source(source) => do_rewrite(&mut source.create_sql)?,
+            CatalogItem::View(view) => do_rewrite(&mut view.create_sql)?,
+            CatalogItem::Sink(sink) => do_rewrite(&mut sink.create_sql)?,
+            CatalogItem::Index(index) => do_rewrite(&mut index.create_sql)?,
+        }
+        Ok(item)
+    }
 }
 
 impl CatalogEntry {
@@ -257,6 +292,27 @@ impl CatalogEntry {
     pub fn used_by(&self) -> &[GlobalId] {
         &self.used_by
     }
+
+    /// Returns the name of the role that owns this catalog entry, if any.
+    ///
+    /// System items, and items created before ownership was tracked, have no
+    /// owner.
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+
+    /// Reports whether `role` holds `privilege` on this catalog entry, either
+    /// by virtue of owning the entry or by an explicit grant. All roles may
+    /// select from system items.
+    pub fn has_privilege(&self, role: &str, privilege: Privilege) -> bool {
+        (self.id.is_system() && privilege == Privilege::Select)
+            || self.owner() == Some(role)
+            || self
+                .privileges
+                .get(role)
+                .map(|privileges| privileges.contains(&privilege))
+                .unwrap_or(false)
+    }
 }
 
 impl Catalog {
@@ -275,6 +331,7 @@ impl Catalog {
             indexes: HashMap::new(),
             ambient_schemas: BTreeMap::new(),
             temporary_schemas: HashMap::new(),
+            roles: BTreeMap::new(),
             storage: Arc::new(Mutex::new(storage)),
             startup_time: SystemTime::now(),
             nonce: rand::random(),
@@ -313,6 +370,11 @@ impl Catalog {
             );
         }
 
+        let roles = catalog.storage().load_roles()?;
+        for (name, superuser) in roles {
+            catalog.roles.insert(name, Role { superuser });
+        }
+
         // Invoke callback so that it can install system items. This has to be
         // done after databases and schemas are loaded, but before any items, as
         // items might depend on these system items, but these system items
@@ -320,7 +382,7 @@ impl Catalog {
         f(&mut catalog);
 
         let items = catalog.storage().load_items()?;
-        for (id, name, def) in items {
+        for (id, name, owner, def) in items {
             // TODO(benesch): a better way of detecting when a view has depended
             // upon a non-existent logging view. This is fine for now because
             // the only goal is to produce a nicer error message; we'll bail out
@@ -342,7 +404,18 @@ impl Catalog {
                     }))
                 }
             };
-            catalog.insert_item(id, name, item);
+            catalog.insert_owned_item(id, name, item, owner);
+        }
+
+        let privileges = catalog.storage().load_privileges()?;
+        for (id, role, privilege) in privileges {
+            if let Some(entry) = catalog.by_id.get_mut(&id) {
+                entry
+                    .privileges
+                    .entry(role)
+                    .or_insert_with(BTreeSet::new)
+                    .insert(privilege);
+            }
         }
 
         Ok(catalog)
@@ -374,6 +447,20 @@ impl Catalog {
         self.storage().allocate_id()
     }
 
+    /// Durably records a batch of updates to tables, each identified by the
+    /// ID of the table, in a single transaction.
+    pub fn persist_table_updates<'a, I>(&mut self, writes: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (GlobalId, &'a [(Row, isize)])>,
+    {
+        self.storage().insert_table_updates(writes)
+    }
+
+    /// Loads the durably recorded contents of the table identified by `id`.
+    pub fn load_table_updates(&mut self, id: GlobalId) -> Result<Vec<(Row, isize)>, Error> {
+        self.storage().load_table_updates(id)
+    }
+
     pub fn resolve_schema(
         &self,
         current_database: &DatabaseSpecifier,
@@ -481,6 +568,11 @@ impl Catalog {
         &self.by_id[id]
     }
 
+    /// Returns the named role, if it exists.
+    pub fn role(&self, name: &str) -> Option<&Role> {
+        self.roles.get(name)
+    }
+
     /// Returns an iterator over the name of each database in the catalog.
     pub fn databases(&self) -> impl Iterator<Item = &str> {
         self.by_name.keys().map(String::as_str)
@@ -573,6 +665,16 @@ impl Catalog {
     }
 
     pub fn insert_item(&mut self, id: GlobalId, name: FullName, item: CatalogItem) {
+        self.insert_owned_item(id, name, item, None)
+    }
+
+    fn insert_owned_item(
+        &mut self,
+        id: GlobalId,
+        name: FullName,
+        item: CatalogItem,
+        owner: Option<String>,
+    ) {
         if !item.is_placeholder() {
             info!("create {} {} ({})", item.type_string(), name, id);
         }
@@ -582,6 +684,8 @@ impl Catalog {
             name,
             id,
             used_by: Vec::new(),
+            owner,
+            privileges: BTreeMap::new(),
         };
         for u in entry.uses() {
             match self.by_id.get_mut(&u) {
@@ -692,6 +796,7 @@ impl Catalog {
                         conn_id: Some(conn_id),
                         ..
                     }),
+                ..
             } = op
             {
                 if self.item_exists_in_temp_schemas(*conn_id, &name.item)
@@ -725,6 +830,11 @@ impl Catalog {
                 id: GlobalId,
                 name: FullName,
                 item: CatalogItem,
+                owner: Option<String>,
+            },
+            CreateRole {
+                name: String,
+                superuser: bool,
             },
             DropDatabase {
                 name: String,
@@ -734,6 +844,25 @@ impl Catalog {
                 schema_name: String,
             },
             DropItem(GlobalId),
+            DropRole {
+                name: String,
+            },
+            GrantPrivileges {
+                id: GlobalId,
+                role: String,
+                privileges: Vec<Privilege>,
+            },
+            RevokePrivileges {
+                id: GlobalId,
+                role: String,
+                privileges: Vec<Privilege>,
+            },
+            RenameItem {
+                id: GlobalId,
+                from: FullName,
+                to: FullName,
+                items: Vec<(GlobalId, CatalogItem)>,
+            },
         }
 
         let temporary_ids = self.temporary_ids(&ops)?;
@@ -765,7 +894,12 @@ impl Catalog {
                         schema_name,
                     }
                 }
-                Op::CreateItem { id, name, item } => {
+                Op::CreateItem {
+                    id,
+                    name,
+                    item,
+                    owner,
+                } => {
                     if item.is_temporary() {
                         if name.database != DatabaseSpecifier::Ambient
                             || name.schema != MZ_TEMP_SCHEMA
@@ -791,10 +925,25 @@ impl Catalog {
                         };
                         let schema_id = tx.load_schema_id(database_id, &name.schema)?;
                         let serialized_item = self.serialize_item(&item);
-                        tx.insert_item(id, schema_id, &name.item, &serialized_item)?;
+                        tx.insert_item(
+                            id,
+                            schema_id,
+                            &name.item,
+                            owner.as_deref(),
+                            &serialized_item,
+                        )?;
                     }
 
-                    Action::CreateItem { id, name, item }
+                    Action::CreateItem {
+                        id,
+                        name,
+                        item,
+                        owner,
+                    }
+                }
+                Op::CreateRole { name, superuser } => {
+                    tx.insert_role(&name, superuser)?;
+                    Action::CreateRole { name, superuser }
                 }
                 Op::DropDatabase { name } => {
                     tx.remove_database(&name)?;
@@ -822,6 +971,98 @@ impl Catalog {
                     }
                     Action::DropItem(id)
                 }
+                Op::DropRole { name } => {
+                    if self
+                        .by_id
+                        .values()
+                        .any(|entry| entry.owner() == Some(name.as_str()))
+                    {
+                        return Err(Error::new(ErrorKind::RoleOwnsItems(name)));
+                    }
+                    tx.remove_role(&name)?;
+                    Action::DropRole { name }
+                }
+                Op::GrantPrivileges {
+                    id,
+                    role,
+                    privileges,
+                } => {
+                    if !self.roles.contains_key(&role) {
+                        return Err(Error::new(ErrorKind::UnknownRole(role)));
+                    }
+                    if !self.get_by_id(&id).item().is_temporary() {
+                        for privilege in &privileges {
+                            tx.insert_privilege(id, &role, *privilege)?;
+                        }
+                    }
+                    Action::GrantPrivileges {
+                        id,
+                        role,
+                        privileges,
+                    }
+                }
+                Op::RevokePrivileges {
+                    id,
+                    role,
+                    privileges,
+                } => {
+                    if !self.roles.contains_key(&role) {
+                        return Err(Error::new(ErrorKind::UnknownRole(role)));
+                    }
+                    if !self.get_by_id(&id).item().is_temporary() {
+                        for privilege in &privileges {
+                            tx.remove_privilege(id, &role, *privilege)?;
+                        }
+                    }
+                    Action::RevokePrivileges {
+                        id,
+                        role,
+                        privileges,
+                    }
+                }
+                Op::RenameItem { id, to_name } => {
+                    let entry = self.get_by_id(&id);
+                    let from = entry.name.clone();
+                    let to = FullName {
+                        database: from.database.clone(),
+                        schema: from.schema.clone(),
+                        item: to_name,
+                    };
+                    let conn_id = entry.item.conn_id().unwrap_or(SYSTEM_CONN_ID);
+                    if self.try_get(&to, conn_id).is_some() {
+                        return Err(Error::new(ErrorKind::ItemAlreadyExists(to.item)));
+                    }
+
+                    // Rewrite the definition of the renamed item, and of every
+                    // item that refers to the renamed item by name. Only
+                    // direct dependents can mention the item in their
+                    // definitions.
+                    let mut items = vec![];
+                    for id in iter::once(&id).chain(entry.used_by()) {
+                        let item = self
+                            .get_by_id(id)
+                            .item
+                            .rename_item_refs(&from, &to)
+                            .map_err(|detail| Error::new(ErrorKind::Corruption { detail }))?;
+                        if !item.is_temporary() {
+                            let name = if *id == entry.id {
+                                &to
+                            } else {
+                                self.get_by_id(id).name()
+                            };
+                            let serialized_item = self.serialize_item(&item);
+                            tx.update_item(*id, &name.item, &serialized_item)?;
+                        }
+                        items.push((*id, item));
+                    }
+
+                    Action::RenameItem {
+                        id,
+                        from,
+                        to,
+                        items,
+                    }
+                }
             })
         }
         tx.commit()?;
@@ -862,11 +1103,22 @@ impl Catalog {
                     OpStatus::CreatedSchema
                 }
 
-                Action::CreateItem { id, name, item } => {
-                    self.insert_item(id, name, item);
+                Action::CreateItem {
+                    id,
+                    name,
+                    item,
+                    owner,
+                } => {
+                    self.insert_owned_item(id, name, item, owner);
                     OpStatus::CreatedItem(id)
                 }
 
+                Action::CreateRole { name, superuser } => {
+                    info!("create role {}", name);
+                    self.roles.insert(name, Role { superuser });
+                    OpStatus::CreatedRole
+                }
+
                 Action::DropDatabase { name } => {
                     self.by_name.remove(&name);
                     OpStatus::DroppedDatabase
@@ -919,6 +1171,67 @@ impl Catalog {
                     }
                     OpStatus::DroppedItem(metadata)
                 }
+
+                Action::DropRole { name } => {
+                    info!("drop role {}", name);
+                    self.roles.remove(&name);
+                    for entry in self.by_id.values_mut() {
+                        entry.privileges.remove(&name);
+                    }
+                    OpStatus::DroppedRole
+                }
+
+                Action::GrantPrivileges {
+                    id,
+                    role,
+                    privileges,
+                } => {
+                    self.by_id
+                        .get_mut(&id)
+                        .expect("catalog out of sync")
+                        .privileges
+                        .entry(role)
+                        .or_insert_with(BTreeSet::new)
+                        .extend(privileges);
+                    OpStatus::GrantedPrivileges
+                }
+
+                Action::RevokePrivileges {
+                    id,
+                    role,
+                    privileges,
+                } => {
+                    let entry = self.by_id.get_mut(&id).expect("catalog out of sync");
+                    if let Some(granted) = entry.privileges.get_mut(&role) {
+                        for privilege in &privileges {
+                            granted.remove(privilege);
+                        }
+                        if granted.is_empty() {
+                            entry.privileges.remove(&role);
+                        }
+                    }
+                    OpStatus::RevokedPrivileges
+                }
+
+                Action::RenameItem {
+                    id,
+                    from,
+                    to,
+                    items,
+                } => {
+                    info!("rename {} to {}", from, to);
+                    let conn_id = self.by_id[&id].item.conn_id().unwrap_or(SYSTEM_CONN_ID);
+                    let schema = self
+                        .get_schema_mut(&from.database, &from.schema, conn_id)
+                        .expect("catalog out of sync");
+                    schema.items.remove(&from.item);
+                    schema.items.insert(to.item.clone(), id);
+                    self.by_id.get_mut(&id).expect("catalog out of sync").name = to.clone();
+                    for (id, item) in items {
+                        self.by_id.get_mut(&id).expect("catalog out of sync").item = item;
+                    }
+                    OpStatus::RenamedItem { id, from, to }
+                }
             })
             .collect())
     }
@@ -964,6 +1277,12 @@ impl Catalog {
         let stmt = sql::parse::parse(create_sql)?.into_element();
         let plan = sql::plan::plan(&pcx, &self.for_system_session(), stmt, &params)?;
         Ok(match plan {
+            Plan::CreateTable { table, .. } => CatalogItem::Source(Source {
+                create_sql: table.create_sql,
+                plan_cx: pcx,
+                connector: SourceConnector::Local,
+                desc: table.desc,
+            }),
             Plan::CreateSource { source, .. } => CatalogItem::Source(Source {
                 create_sql: source.create_sql,
                 plan_cx: pcx,
@@ -1043,6 +1362,12 @@ pub enum Op {
         id: GlobalId,
         name: FullName,
         item: CatalogItem,
+        /// The role that owns the item, if any.
+        owner: Option<String>,
+    },
+    CreateRole {
+        name: String,
+        superuser: bool,
     },
     DropDatabase {
         name: String,
@@ -1055,6 +1380,27 @@ pub enum Op {
     /// IDs come from the output of `plan_remove`; otherwise consistency rules
     /// may be violated.
     DropItem(GlobalId),
+    /// Removes the named role, along with any privileges granted to it. It is
+    /// an error to drop a role that still owns items.
+    DropRole {
+        name: String,
+    },
+    GrantPrivileges {
+        id: GlobalId,
+        role: String,
+        privileges: Vec<Privilege>,
+    },
+    RevokePrivileges {
+        id: GlobalId,
+        role: String,
+        privileges: Vec<Privilege>,
+    },
+    /// Renames the identified item, and rewrites the `create_sql` of the
+    /// items that depend upon it to refer to the new name.
+    RenameItem {
+        id: GlobalId,
+        to_name: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -1062,9 +1408,18 @@ pub enum OpStatus {
     CreatedDatabase,
     CreatedSchema,
     CreatedItem(GlobalId),
+    CreatedRole,
     DroppedDatabase,
     DroppedSchema,
     DroppedItem(CatalogEntry),
+    DroppedRole,
+    GrantedPrivileges,
+    RevokedPrivileges,
+    RenamedItem {
+        id: GlobalId,
+        from: FullName,
+        to: FullName,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1185,6 +1540,13 @@ impl sql::catalog::Catalog for ConnCatalog<'_> {
     fn get_item_by_id(&self, id: &GlobalId) -> &dyn sql::catalog::CatalogItem {
         self.catalog.get_by_id(id)
     }
+
+    fn resolve_role(&self, role_name: &str) -> Result<(), failure::Error> {
+        match self.catalog.roles.get(role_name) {
+            Some(_) => Ok(()),
+            None => Err(Error::new(ErrorKind::UnknownRole(role_name.into())).into()),
+        }
+    }
 }
 
 impl sql::catalog::CatalogItem for CatalogEntry {
@@ -24,9 +24,12 @@ pub(crate) enum ErrorKind {
     UnknownDatabase(String),
     UnknownSchema(String),
     UnknownItem(String),
+    UnknownRole(String),
     DatabaseAlreadyExists(String),
     SchemaAlreadyExists(String),
     ItemAlreadyExists(String),
+    RoleAlreadyExists(String),
+    RoleOwnsItems(String),
     UnacceptableSchemaName(String),
     ReadOnlySystemSchema(String),
     SchemaNotEmpty(String),
@@ -59,9 +62,12 @@ impl std::error::Error for Error {
             | ErrorKind::UnknownDatabase(_)
             | ErrorKind::UnknownSchema(_)
             | ErrorKind::UnknownItem(_)
+            | ErrorKind::UnknownRole(_)
             | ErrorKind::DatabaseAlreadyExists(_)
             | ErrorKind::SchemaAlreadyExists(_)
             | ErrorKind::ItemAlreadyExists(_)
+            | ErrorKind::RoleAlreadyExists(_)
+            | ErrorKind::RoleOwnsItems(_)
             | ErrorKind::UnacceptableSchemaName(_)
             | ErrorKind::ReadOnlySystemSchema(_)
             | ErrorKind::SchemaNotEmpty(_)
@@ -81,6 +87,7 @@ impl fmt::Display for Error {
             ErrorKind::UnknownDatabase(name) => write!(f, "unknown database '{}'", name),
             ErrorKind::UnknownSchema(name) => write!(f, "unknown schema '{}'", name),
             ErrorKind::UnknownItem(name) => write!(f, "unknown catalog item '{}'", name),
+            ErrorKind::UnknownRole(name) => write!(f, "unknown role '{}'", name),
             ErrorKind::DatabaseAlreadyExists(name) => {
                 write!(f, "database '{}' already exists", name)
             }
@@ -88,6 +95,12 @@ impl fmt::Display for Error {
             ErrorKind::ItemAlreadyExists(name) => {
                 write!(f, "catalog item '{}' already exists", name)
             }
+            ErrorKind::RoleAlreadyExists(name) => write!(f, "role '{}' already exists", name),
+            ErrorKind::RoleOwnsItems(name) => write!(
+                f,
+                "role '{}' cannot be dropped because it owns catalog items",
+                name
+            ),
             ErrorKind::UnacceptableSchemaName(name) => {
                 write!(f, "unacceptable schema name '{}'", name)
             }
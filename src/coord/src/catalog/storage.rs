@@ -9,12 +9,18 @@
 
 use std::path::Path;
 
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
 use rusqlite::params;
 use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, Value, ValueRef};
 use serde::{Deserialize, Serialize};
 
 use expr::GlobalId;
 use ore::cast::CastFrom;
+use repr::adt::decimal::Significand;
+use repr::adt::interval::Interval;
+use repr::strconv;
+use repr::{Datum, Row, RowPacker};
+use sql::ast::Privilege;
 use sql::names::{DatabaseSpecifier, FullName};
 
 use crate::catalog::error::{Error, ErrorKind};
@@ -81,6 +87,43 @@ const MIGRATIONS: &[&str] = &[
         offset blob NOT NULL,
         PRIMARY KEY (sid, vid, pid, timestamp)
     );",
+    // Adds a log of the updates applied to each table, so that the contents of
+    // tables survive restarts.
+    //
+    // Introduced in v0.4.0.
+    "CREATE TABLE table_updates (
+        gid blob NOT NULL,
+        data blob NOT NULL,
+        diff integer NOT NULL
+    );
+    CREATE INDEX table_updates_gid ON table_updates (gid);",
+    // Adds roles, item ownership, and per-item privileges.
+    //
+    // Introduced in v0.4.0.
+    //
+    // Items created before this migration were created by the only user that
+    // could exist at the time, and so are assigned to the `materialize` role.
+    "CREATE TABLE roles (
+        name      text PRIMARY KEY,
+        superuser bool NOT NULL
+    );
+    INSERT INTO roles VALUES ('materialize', 1);
+    ALTER TABLE items ADD COLUMN owner text;
+    UPDATE items SET owner = 'materialize';
+    CREATE TABLE privileges (
+        gid       blob NOT NULL,
+        role      text NOT NULL,
+        privilege text NOT NULL,
+        PRIMARY KEY (gid, role, privilege)
+    );",
+    // Discards the log of table updates written by earlier development builds,
+    // which recorded rows in their in-memory format.
+    //
+    // Introduced in v0.4.0.
+    //
+    // The in-memory format of rows is not stable across versions, so rows are
+    // now recorded in the versioned format described by `PersistedRow`.
+    "DELETE FROM table_updates;",
     // Add new migrations here.
     //
     // Migrations should be preceded with a comment of the following form:
@@ -171,10 +214,22 @@ impl Connection {
             .collect()
     }
 
-    pub fn load_items(&self) -> Result<Vec<(GlobalId, FullName, Vec<u8>)>, Error> {
+    pub fn load_roles(&self) -> Result<Vec<(String, bool)>, Error> {
+        self.inner
+            .prepare("SELECT name, superuser FROM roles")?
+            .query_and_then(params![], |row| -> Result<_, Error> {
+                let name: String = row.get(0)?;
+                let superuser: bool = row.get(1)?;
+                Ok((name, superuser))
+            })?
+            .collect()
+    }
+
+    pub fn load_items(&self) -> Result<Vec<(GlobalId, FullName, Option<String>, Vec<u8>)>, Error> {
         self.inner
             .prepare(
-                "SELECT items.gid, databases.name, schemas.name, items.name, items.definition
+                "SELECT items.gid, databases.name, schemas.name, items.name, items.owner,
+                    items.definition
                 FROM items
                 JOIN schemas ON items.schema_id = schemas.id
                 JOIN databases ON schemas.database_id = databases.id
@@ -185,7 +240,8 @@ impl Connection {
                 let database: Option<String> = row.get(1)?;
                 let schema: String = row.get(2)?;
                 let item: String = row.get(3)?;
-                let definition: Vec<u8> = row.get(4)?;
+                let owner: Option<String> = row.get(4)?;
+                let definition: Vec<u8> = row.get(5)?;
                 Ok((
                     id.0,
                     FullName {
@@ -193,12 +249,30 @@ impl Connection {
                         schema,
                         item,
                     },
+                    owner,
                     definition,
                 ))
             })?
             .collect()
     }
 
+    pub fn load_privileges(&self) -> Result<Vec<(GlobalId, String, Privilege)>, Error> {
+        self.inner
+            .prepare("SELECT gid, role, privilege FROM privileges")?
+            .query_and_then(params![], |row| -> Result<_, Error> {
+                let id: SqlVal<GlobalId> = row.get(0)?;
+                let role: String = row.get(1)?;
+                let privilege: String = row.get(2)?;
+                let privilege = parse_privilege(&privilege).ok_or_else(|| {
+                    Error::new(ErrorKind::Corruption {
+                        detail: format!("unknown privilege {}", privilege),
+                    })
+                })?;
+                Ok((id.0, role, privilege))
+            })?
+            .collect()
+    }
+
     pub fn allocate_id(&mut self) -> Result<GlobalId, Error> {
         let tx = self.inner.transaction()?;
         // SQLite doesn't support u64s, so we constrain ourselves to the more
@@ -214,6 +288,48 @@ impl Connection {
         Ok(GlobalId::User(id as u64))
     }
 
+    /// Appends a batch of updates to the logs of updates for the tables that
+    /// they identify, in a single transaction.
+    pub fn insert_table_updates<'a, I>(&mut self, writes: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (GlobalId, &'a [(Row, isize)])>,
+    {
+        let tx = self.inner.transaction()?;
+        for (id, updates) in writes {
+            insert_table_updates(&tx, id, updates)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Loads the contents of the table identified by `id`.
+    ///
+    /// The log of updates for the table is consolidated in the process, so
+    /// that the log does not grow without bound across restarts.
+    pub fn load_table_updates(&mut self, id: GlobalId) -> Result<Vec<(Row, isize)>, Error> {
+        let tx = self.inner.transaction()?;
+        let updates = tx
+            .prepare(
+                "SELECT data, SUM(diff) FROM table_updates
+                WHERE gid = ?
+                GROUP BY data
+                HAVING SUM(diff) <> 0",
+            )?
+            .query_and_then(params![SqlVal(&id)], |row| -> Result<_, Error> {
+                let data: SqlVal<PersistedRow> = row.get(0)?;
+                let diff: i64 = row.get(1)?;
+                Ok((data.0.into_row()?, diff as isize))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        tx.execute(
+            "DELETE FROM table_updates WHERE gid = ?",
+            params![SqlVal(&id)],
+        )?;
+        insert_table_updates(&tx, id, &updates)?;
+        tx.commit()?;
+        Ok(updates)
+    }
+
     pub fn transaction(&mut self) -> Result<Transaction, Error> {
         Ok(Transaction {
             inner: self.inner.transaction()?,
@@ -282,19 +398,35 @@ impl Transaction<'_> {
         }
     }
 
+    pub fn insert_role(&self, name: &str, superuser: bool) -> Result<(), Error> {
+        match self
+            .inner
+            .prepare_cached("INSERT INTO roles (name, superuser) VALUES (?, ?)")?
+            .execute(params![name, superuser])
+        {
+            Ok(_) => Ok(()),
+            Err(err) if is_constraint_violation(&err) => {
+                Err(Error::new(ErrorKind::RoleAlreadyExists(name.to_owned())))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
     pub fn insert_item(
         &self,
         id: GlobalId,
         schema_id: i64,
         item_name: &str,
+        owner: Option<&str>,
         item: &[u8],
     ) -> Result<(), Error> {
         match self
             .inner
             .prepare_cached(
-                "INSERT INTO items (gid, schema_id, name, definition) VALUES (?, ?, ?, ?)",
+                "INSERT INTO items (gid, schema_id, name, owner, definition)
+                VALUES (?, ?, ?, ?, ?)",
             )?
-            .execute(params![SqlVal(&id), schema_id, item_name, item])
+            .execute(params![SqlVal(&id), schema_id, item_name, owner, item])
         {
             Ok(_) => Ok(()),
             Err(err) if is_constraint_violation(&err) => Err(Error::new(
@@ -304,6 +436,24 @@ impl Transaction<'_> {
         }
     }
 
+    pub fn update_item(&self, id: GlobalId, item_name: &str, item: &[u8]) -> Result<(), Error> {
+        match self
+            .inner
+            .prepare_cached("UPDATE items SET name = ?, definition = ? WHERE gid = ?")?
+            .execute(params![item_name, item, SqlVal(id)])
+        {
+            Ok(1) => Ok(()),
+            Ok(n) => {
+                assert_eq!(n, 0);
+                Err(Error::new(ErrorKind::UnknownItem(id.to_string())))
+            }
+            Err(err) if is_constraint_violation(&err) => Err(Error::new(
+                ErrorKind::ItemAlreadyExists(item_name.to_owned()),
+            )),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     pub fn remove_database(&self, name: &str) -> Result<(), Error> {
         let n = self
             .inner
@@ -337,17 +487,255 @@ impl Transaction<'_> {
             .execute(params![SqlVal(id)])?;
         assert!(n <= 1);
         if n == 1 {
+            self.inner
+                .prepare_cached("DELETE FROM table_updates WHERE gid = ?")?
+                .execute(params![SqlVal(id)])?;
+            self.inner
+                .prepare_cached("DELETE FROM privileges WHERE gid = ?")?
+                .execute(params![SqlVal(id)])?;
             Ok(())
         } else {
             Err(Error::new(ErrorKind::UnknownItem(id.to_string())))
         }
     }
 
+    pub fn remove_role(&self, name: &str) -> Result<(), Error> {
+        let n = self
+            .inner
+            .prepare_cached("DELETE FROM roles WHERE name = ?")?
+            .execute(params![name])?;
+        assert!(n <= 1);
+        if n == 1 {
+            self.inner
+                .prepare_cached("DELETE FROM privileges WHERE role = ?")?
+                .execute(params![name])?;
+            Ok(())
+        } else {
+            Err(Error::new(ErrorKind::UnknownRole(name.to_owned())))
+        }
+    }
+
+    pub fn insert_privilege(
+        &self,
+        id: GlobalId,
+        role: &str,
+        privilege: Privilege,
+    ) -> Result<(), Error> {
+        self.inner
+            .prepare_cached(
+                "INSERT OR IGNORE INTO privileges (gid, role, privilege) VALUES (?, ?, ?)",
+            )?
+            .execute(params![SqlVal(id), role, privilege.to_string()])?;
+        Ok(())
+    }
+
+    pub fn remove_privilege(
+        &self,
+        id: GlobalId,
+        role: &str,
+        privilege: Privilege,
+    ) -> Result<(), Error> {
+        self.inner
+            .prepare_cached("DELETE FROM privileges WHERE gid = ? AND role = ? AND privilege = ?")?
+            .execute(params![SqlVal(id), role, privilege.to_string()])?;
+        Ok(())
+    }
+
     pub fn commit(self) -> Result<(), rusqlite::Error> {
         self.inner.commit()
     }
 }
 
+fn insert_table_updates(
+    tx: &rusqlite::Transaction,
+    id: GlobalId,
+    updates: &[(Row, isize)],
+) -> Result<(), Error> {
+    let mut stmt =
+        tx.prepare_cached("INSERT INTO table_updates (gid, data, diff) VALUES (?, ?, ?)")?;
+    for (row, diff) in updates {
+        stmt.execute(params![
+            SqlVal(&id),
+            SqlVal(PersistedRow::from_row(row)),
+            *diff as i64
+        ])?;
+    }
+    Ok(())
+}
+
+/// A row as it is recorded in the log of table updates.
+///
+/// Rows are not recorded in their in-memory format, which may change between
+/// versions. Each variant is a version of the format, so that later versions
+/// can introduce new formats while continuing to read rows in earlier ones.
+#[derive(Serialize, Deserialize)]
+enum PersistedRow {
+    V1(Vec<PersistedDatum>),
+}
+
+/// A datum in version 1 of the [`PersistedRow`] format.
+///
+/// Variants must not be removed or changed, only added.
+#[derive(Serialize, Deserialize)]
+enum PersistedDatum {
+    Null,
+    Bool(bool),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    /// The bits of the float, which, unlike its value, JSON can represent
+    /// exactly.
+    Float32(u32),
+    Float64(u64),
+    /// Days since January 1, 1 CE.
+    Date(i32),
+    /// Seconds and nanoseconds since midnight.
+    Time(u32, u32),
+    /// Seconds and nanoseconds since the Unix epoch.
+    Timestamp(i64, u32),
+    TimestampTz(i64, u32),
+    /// Months and nanoseconds. The nanoseconds are formatted as a string, as
+    /// they may not fit in a 64-bit integer.
+    Interval(i32, String),
+    /// The significand, formatted as a string, as it may not fit in a 64-bit
+    /// integer.
+    Decimal(String),
+    Bytes(Vec<u8>),
+    String(String),
+    Uuid(String),
+    List(Vec<PersistedDatum>),
+    Dict(Vec<(String, PersistedDatum)>),
+    JsonNull,
+}
+
+impl PersistedRow {
+    fn from_row(row: &Row) -> PersistedRow {
+        PersistedRow::V1(row.iter().map(PersistedDatum::from_datum).collect())
+    }
+
+    fn into_row(self) -> Result<Row, Error> {
+        let mut packer = RowPacker::new();
+        match self {
+            PersistedRow::V1(datums) => {
+                for datum in datums {
+                    datum.push(&mut packer).map_err(|detail| {
+                        Error::new(ErrorKind::Corruption {
+                            detail: format!("invalid table update: {}", detail),
+                        })
+                    })?;
+                }
+            }
+        }
+        Ok(packer.finish())
+    }
+}
+
+impl PersistedDatum {
+    fn from_datum(datum: Datum) -> PersistedDatum {
+        match datum {
+            Datum::Null => PersistedDatum::Null,
+            Datum::False => PersistedDatum::Bool(false),
+            Datum::True => PersistedDatum::Bool(true),
+            Datum::Int16(i) => PersistedDatum::Int16(i),
+            Datum::Int32(i) => PersistedDatum::Int32(i),
+            Datum::Int64(i) => PersistedDatum::Int64(i),
+            Datum::Float32(f) => PersistedDatum::Float32(f.into_inner().to_bits()),
+            Datum::Float64(f) => PersistedDatum::Float64(f.into_inner().to_bits()),
+            Datum::Date(d) => PersistedDatum::Date(d.num_days_from_ce()),
+            Datum::Time(t) => PersistedDatum::Time(t.num_seconds_from_midnight(), t.nanosecond()),
+            Datum::Timestamp(ts) => {
+                PersistedDatum::Timestamp(ts.timestamp(), ts.timestamp_subsec_nanos())
+            }
+            Datum::TimestampTz(ts) => {
+                PersistedDatum::TimestampTz(ts.timestamp(), ts.timestamp_subsec_nanos())
+            }
+            Datum::Interval(iv) => PersistedDatum::Interval(iv.months, iv.duration.to_string()),
+            Datum::Decimal(d) => PersistedDatum::Decimal(d.as_i128().to_string()),
+            Datum::Bytes(b) => PersistedDatum::Bytes(b.to_vec()),
+            Datum::String(s) => PersistedDatum::String(s.to_owned()),
+            Datum::Uuid(u) => PersistedDatum::Uuid(u.to_string()),
+            Datum::List(list) => {
+                PersistedDatum::List(list.iter().map(PersistedDatum::from_datum).collect())
+            }
+            Datum::Dict(dict) => PersistedDatum::Dict(
+                dict.iter()
+                    .map(|(k, v)| (k.to_owned(), PersistedDatum::from_datum(v)))
+                    .collect(),
+            ),
+            Datum::JsonNull => PersistedDatum::JsonNull,
+            Datum::Dummy => unreachable!("dummy datums are never written to tables"),
+        }
+    }
+
+    fn push(self, packer: &mut RowPacker) -> Result<(), String> {
+        let invalid = |what: &str| format!("invalid {}", what);
+        match self {
+            PersistedDatum::Null => packer.push(Datum::Null),
+            PersistedDatum::Bool(b) => packer.push(Datum::from(b)),
+            PersistedDatum::Int16(i) => packer.push(Datum::from(i)),
+            PersistedDatum::Int32(i) => packer.push(Datum::from(i)),
+            PersistedDatum::Int64(i) => packer.push(Datum::from(i)),
+            PersistedDatum::Float32(bits) => packer.push(Datum::from(f32::from_bits(bits))),
+            PersistedDatum::Float64(bits) => packer.push(Datum::from(f64::from_bits(bits))),
+            PersistedDatum::Date(days) => {
+                let d =
+                    NaiveDate::from_num_days_from_ce_opt(days).ok_or_else(|| invalid("date"))?;
+                packer.push(Datum::Date(d))
+            }
+            PersistedDatum::Time(secs, nanos) => {
+                let t = NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+                    .ok_or_else(|| invalid("time"))?;
+                packer.push(Datum::Time(t))
+            }
+            PersistedDatum::Timestamp(secs, nanos) => {
+                let ts = NaiveDateTime::from_timestamp_opt(secs, nanos)
+                    .ok_or_else(|| invalid("timestamp"))?;
+                packer.push(Datum::Timestamp(ts))
+            }
+            PersistedDatum::TimestampTz(secs, nanos) => {
+                let ts = NaiveDateTime::from_timestamp_opt(secs, nanos)
+                    .ok_or_else(|| invalid("timestamptz"))?;
+                packer.push(Datum::TimestampTz(DateTime::from_utc(ts, Utc)))
+            }
+            PersistedDatum::Interval(months, duration) => {
+                let duration = duration.parse().map_err(|_| invalid("interval"))?;
+                packer.push(Datum::Interval(Interval { months, duration }))
+            }
+            PersistedDatum::Decimal(significand) => {
+                let significand = significand.parse().map_err(|_| invalid("decimal"))?;
+                packer.push(Datum::Decimal(Significand::new(significand)))
+            }
+            PersistedDatum::Bytes(b) => packer.push(Datum::Bytes(&b)),
+            PersistedDatum::String(s) => packer.push(Datum::String(&s)),
+            PersistedDatum::Uuid(u) => {
+                let u = strconv::parse_uuid(&u).map_err(|_| invalid("uuid"))?;
+                packer.push(Datum::Uuid(u))
+            }
+            PersistedDatum::List(datums) => packer.push_list_with(|packer| {
+                datums.into_iter().try_for_each(|datum| datum.push(packer))
+            })?,
+            PersistedDatum::Dict(entries) => packer.push_dict_with(|packer| {
+                entries.into_iter().try_for_each(|(key, datum)| {
+                    packer.push(Datum::String(&key));
+                    datum.push(packer)
+                })
+            })?,
+            PersistedDatum::JsonNull => packer.push(Datum::JsonNull),
+        }
+        Ok(())
+    }
+}
+
+fn parse_privilege(s: &str) -> Option<Privilege> {
+    match s {
+        "SELECT" => Some(Privilege::Select),
+        "INSERT" => Some(Privilege::Insert),
+        "UPDATE" => Some(Privilege::Update),
+        "DELETE" => Some(Privilege::Delete),
+        _ => None,
+    }
+}
+
 fn is_constraint_violation(err: &rusqlite::Error) -> bool {
     match err {
         rusqlite::Error::SqliteFailure(err, _) => {
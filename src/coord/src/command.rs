@@ -12,8 +12,10 @@ use std::pin::Pin;
 
 use derivative::Derivative;
 
-use dataflow_types::{PeekResponse, Update};
-use sql::ast::Statement;
+use dataflow_types::{PeekResponse, TailResponse};
+use expr::GlobalId;
+use repr::{RelationDesc, Row};
+use sql::ast::{CopyFormat, ObjectType, Statement};
 
 use crate::session::Session;
 
@@ -23,6 +25,9 @@ pub enum Command {
     /// Notify the coordinator of a new client session.
     Startup {
         session: Session,
+        /// Whether the session's user was authenticated. Sessions of
+        /// authenticated users are rejected unless the user names a role.
+        authenticated: bool,
         tx: futures::channel::oneshot::Sender<Response<Vec<StartupMessage>>>,
     },
 
@@ -44,6 +49,16 @@ pub enum Command {
         tx: futures::channel::oneshot::Sender<Response<ExecuteResponse>>,
     },
 
+    /// Insert rows that the client streamed in response to
+    /// [`ExecuteResponse::CopyFrom`]. All of the rows of a single `COPY`
+    /// statement are inserted together.
+    CopyRows {
+        id: GlobalId,
+        rows: Vec<Row>,
+        session: Session,
+        tx: futures::channel::oneshot::Sender<Response<()>>,
+    },
+
     /// Cancel the query currently running on another connection.
     CancelRequest { conn_id: u32 },
 
@@ -77,14 +92,28 @@ pub enum StartupMessage {
 pub enum ExecuteResponse {
     /// The active transaction was rolled back.
     AbortedTransaction,
+    /// The requested object was altered.
+    AlteredObject(ObjectType),
     /// The active transaction was committed.
     CommittedTransaction,
+    /// The client should stream rows in the specified format, which are to be
+    /// inserted into the specified columns of the table `id`, whose shape is
+    /// described by `desc`. The rows are delivered via
+    /// [`Command::CopyRows`].
+    CopyFrom {
+        id: GlobalId,
+        columns: Vec<usize>,
+        format: CopyFormat,
+        desc: RelationDesc,
+    },
     /// The requested database was created.
     CreatedDatabase { existed: bool },
     /// The requested schema was created.
     CreatedSchema { existed: bool },
     /// The requested index was created.
     CreatedIndex { existed: bool },
+    /// The requested role was created.
+    CreatedRole,
     /// The requested sink was created.
     CreatedSink { existed: bool },
     /// The requested source was created.
@@ -109,10 +138,16 @@ pub enum ExecuteResponse {
     DroppedIndex,
     /// The requested sink wqs dropped.
     DroppedSink,
+    /// The requested roles were dropped.
+    DroppedRole,
     /// The provided query was empty.
     EmptyQuery,
+    /// The requested privileges were granted.
+    GrantedPrivilege,
     /// The specified number of rows were inserted into the requested table.
     Inserted(usize),
+    /// The requested privileges were revoked.
+    RevokedPrivilege,
     /// Rows will be delivered via the specified future.
     SendingRows(#[derivative(Debug = "ignore")] RowsFuture),
     /// The specified variable was set to a new value.
@@ -122,7 +157,7 @@ pub enum ExecuteResponse {
     /// Updates to the requested source or view will be streamed to the
     /// contained receiver.
     Tailing {
-        rx: comm::mpsc::Receiver<Vec<Update>>,
+        rx: comm::mpsc::Receiver<TailResponse>,
     },
     /// The specified number of rows were updated in the requested table.
     Updated(usize),
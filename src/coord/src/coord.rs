@@ -17,9 +17,10 @@
 //! must accumulate to the same value as would an un-compacted trace.
 
 use std::cmp;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::convert::TryInto;
 use std::iter;
+use std::mem;
 use std::os::unix::ffi::OsStringExt;
 use std::path::Path;
 use std::thread;
@@ -27,7 +28,7 @@ use std::time::{Duration, Instant};
 
 use failure::{bail, ResultExt};
 use futures::executor::block_on;
-use futures::future::{self, TryFutureExt};
+use futures::future::{self, FutureExt, TryFutureExt};
 use futures::sink::SinkExt;
 use futures::stream::{self, StreamExt, TryStreamExt};
 use timely::progress::frontier::Antichain;
@@ -37,8 +38,9 @@ use dataflow::logging::materialized::MaterializedEvent;
 use dataflow::{SequencedCommand, WorkerFeedback, WorkerFeedbackWithMeta};
 use dataflow_types::logging::LoggingConfig;
 use dataflow_types::{
-    AvroOcfSinkConnector, DataflowDesc, IndexDesc, KafkaSinkConnector, PeekResponse, PeekWhen,
-    SinkConnector, TailSinkConnector, Timestamp, TimestampSourceUpdate, Update,
+    AvroOcfSinkConnector, DataflowDesc, ExternalSourceConnector, IndexDesc, KafkaSinkConnector,
+    PeekResponse, PeekWhen, SinkConnector, SinkConnectorBuilder, SourceConnector,
+    TailSinkConnector, Timestamp, TimestampSourceUpdate, Update,
 };
 use expr::{
     GlobalId, Id, IdHumanizer, NullaryFunc, RelationExpr, RowSetFinishing, ScalarExpr,
@@ -47,10 +49,10 @@ use expr::{
 use ore::collections::CollectionExt;
 use ore::thread::JoinHandleExt;
 use repr::{ColumnName, Datum, RelationDesc, RelationType, Row, RowPacker};
-use sql::ast::{ExplainOptions, ExplainStage, ObjectType, Statement};
+use sql::ast::{CopyFormat, ExplainOptions, ExplainStage, ObjectType, Privilege, Statement};
 use sql::catalog::Catalog as _;
 use sql::names::{DatabaseSpecifier, FullName};
-use sql::plan::{MutationKind, Params, Plan, PlanContext};
+use sql::plan::{MutationKind, Params, Plan, PlanContext, TailFrom};
 use transform::Optimizer;
 
 use crate::catalog::{self, Catalog, CatalogItem, SinkConnectorState};
@@ -61,6 +63,10 @@ use crate::{sink_connector, Command, ExecuteResponse, Response, StartupMessage};
 
 use arrangement_state::{ArrangementFrontiers, Frontiers};
 
+/// The longest that a write to a table is buffered before it is recorded,
+/// while the coordinator is busy with other messages.
+const MAX_WRITE_DELAY: Duration = Duration::from_millis(10);
+
 pub enum Message {
     Command(Command),
     Worker(WorkerFeedbackWithMeta),
@@ -80,6 +86,13 @@ pub enum Message {
         id: GlobalId,
         result: Result<SinkConnector, failure::Error>,
     },
+    SendDiffs {
+        session: Session,
+        tx: ClientTransmitter<ExecuteResponse>,
+        id: GlobalId,
+        rows: Result<PeekResponse, comm::Error>,
+        kind: MutationKind,
+    },
     Shutdown,
 }
 
@@ -134,6 +147,16 @@ where
     closed_up_to: Timestamp,
     /// Whether or not the most recent operation was a read.
     last_op_was_read: bool,
+    /// For each table with an `UPDATE` or `DELETE` in progress, the `UPDATE`
+    /// and `DELETE` statements against that table that are waiting for it to
+    /// finish.
+    pending_read_then_writes: HashMap<GlobalId, VecDeque<DeferredReadThenWrite>>,
+    /// Writes to tables that have been sequenced, but not yet durably recorded
+    /// or sent to the dataflow workers. See
+    /// [`Coordinator::flush_pending_writes`].
+    pending_writes: Vec<PendingWrite>,
+    /// When the oldest write in `pending_writes` was sequenced.
+    pending_writes_since: Option<Instant>,
 }
 
 impl<C> Coordinator<C>
@@ -197,6 +220,9 @@ where
             closed_up_to: 1,
             read_lower_bound: 1,
             last_op_was_read: false,
+            pending_read_then_writes: HashMap::new(),
+            pending_writes: vec![],
+            pending_writes_since: None,
         };
 
         let catalog_entries: Vec<_> = coord
@@ -204,6 +230,7 @@ where
             .iter()
             .map(|entry| (entry.id(), entry.name().clone(), entry.item().clone()))
             .collect();
+        let mut tables = HashSet::new();
         for (id, name, item) in catalog_entries {
             // Mirror each recovered catalog entry.
             broadcast(
@@ -240,7 +267,26 @@ where
                     coord.handle_sink_connector_ready(id, connector);
                 }
                 CatalogItem::Index(index) => match id {
-                    GlobalId::User(_) => coord.create_index_dataflow(name.to_string(), id, index),
+                    GlobalId::User(_) => {
+                        let on_entry = coord.catalog.get_by_id(&index.on);
+                        match on_entry.item() {
+                            // The first index on a table is its primary index,
+                            // which is where the table's contents live.
+                            CatalogItem::Source(catalog::Source {
+                                connector: dataflow_types::SourceConnector::Local,
+                                desc,
+                                ..
+                            }) if !index.on.is_system() && tables.insert(index.on) => {
+                                let table_name = on_entry.name().to_string();
+                                let desc = desc.clone();
+                                coord.create_table_input(table_name.clone(), id, &index, &desc);
+                                coord.replay_table(index.on).with_context(|e| {
+                                    format!("restoring contents of table {}: {}", table_name, e)
+                                })?;
+                            }
+                            _ => coord.create_index_dataflow(name.to_string(), id, index),
+                        }
+                    }
                     GlobalId::System(_) => {
                         // TODO(benesch): a smarter way to determine whether this system index
                         // is on a logging source or a logging view. Probably logging sources
@@ -367,19 +413,52 @@ where
             cmd_stream.boxed(),
         ]);
 
-        while let Some(msg) = block_on(messages.next()) {
+        loop {
+            // Writes are buffered while further messages are immediately
+            // available, and then recorded together, so that a burst of
+            // writes costs one catalog transaction rather than one each.
+            let msg = match messages.next().now_or_never() {
+                Some(msg) => msg,
+                None => {
+                    self.flush_pending_writes();
+                    block_on(messages.next())
+                }
+            };
+            let msg = match msg {
+                Some(msg) => msg,
+                None => break,
+            };
             match msg {
-                Message::Command(Command::Startup { session, tx }) => {
-                    let mut messages = vec![];
-                    let catalog = self.catalog.for_session(&session);
-                    if catalog
-                        .resolve_database(catalog.default_database())
-                        .is_err()
-                    {
-                        messages.push(StartupMessage::UnknownSessionDatabase);
-                    }
+                Message::Command(Command::Startup {
+                    mut session,
+                    authenticated,
+                    tx,
+                }) => {
+                    // The temporary schema is created even if the session is
+                    // rejected, as it is dropped when the connection
+                    // terminates.
                     self.catalog.create_temporary_schema(session.conn_id());
-                    ClientTransmitter::new(tx).send(Ok(messages), session)
+                    if self.catalog.role(session.user()).is_none() && authenticated {
+                        let err =
+                            failure::format_err!("role \"{}\" does not exist", session.user());
+                        ClientTransmitter::new(tx).send(Err(err), session)
+                    } else {
+                        // Without authentication, any client can claim any
+                        // user name, so users that are not roles act as the
+                        // default role.
+                        if self.catalog.role(session.user()).is_none() {
+                            session.set_user(catalog::DEFAULT_ROLE.into());
+                        }
+                        let mut messages = vec![];
+                        let catalog = self.catalog.for_session(&session);
+                        if catalog
+                            .resolve_database(catalog.default_database())
+                            .is_err()
+                        {
+                            messages.push(StartupMessage::UnknownSessionDatabase);
+                        }
+                        ClientTransmitter::new(tx).send(Ok(messages), session)
+                    }
                 }
                 Message::Command(Command::Execute {
                     portal_name,
@@ -441,10 +520,20 @@ where
                     tx,
                     result,
                     params,
-                } => match result.and_then(|stmt| self.handle_statement(&session, stmt, &params)) {
-                    Ok((pcx, plan)) => self.sequence_plan(&internal_cmd_tx, tx, session, pcx, plan),
-                    Err(e) => tx.send(Err(e), session),
-                },
+                } => {
+                    let result = result
+                        .and_then(|stmt| self.handle_statement(&session, stmt, &params))
+                        .and_then(|(pcx, plan)| {
+                            self.check_privileges(&session, &plan)?;
+                            Ok((pcx, plan))
+                        });
+                    match result {
+                        Ok((pcx, plan)) => {
+                            self.sequence_plan(&internal_cmd_tx, tx, session, pcx, plan)
+                        }
+                        Err(e) => tx.send(Err(e), session),
+                    }
+                }
 
                 Message::SinkConnectorReady {
                     session,
@@ -476,6 +565,25 @@ where
                     }
                 },
 
+                Message::SendDiffs {
+                    session,
+                    tx,
+                    id,
+                    rows,
+                    kind,
+                } => match self.sequence_read_then_write_diffs(id, rows, kind) {
+                    Ok((updates, response)) => self.sequence_send_diffs(
+                        id,
+                        updates,
+                        session,
+                        WriteResponder::ReadThenWrite(tx, response),
+                    ),
+                    Err(e) => {
+                        tx.send(Err(e), session);
+                        self.finish_read_then_write(id);
+                    }
+                },
+
                 Message::Command(Command::Describe {
                     name,
                     stmt,
@@ -486,6 +594,24 @@ where
                     let _ = tx.send(Response { result, session });
                 }
 
+                Message::Command(Command::CopyRows {
+                    id,
+                    rows,
+                    session,
+                    tx,
+                }) => {
+                    let tx = ClientTransmitter::new(tx);
+                    match self.sequence_copy_rows(id, rows) {
+                        Ok(updates) => self.sequence_send_diffs(
+                            id,
+                            updates,
+                            session,
+                            WriteResponder::CopyRows(tx),
+                        ),
+                        Err(e) => tx.send(Err(e), session),
+                    }
+                }
+
                 Message::Command(Command::CancelRequest { conn_id }) => {
                     self.sequence_cancel(conn_id);
                 }
@@ -541,12 +667,20 @@ where
                     );
                 }
                 Message::Shutdown => {
+                    self.flush_pending_writes();
                     ts_tx.send(TimestampMessage::Shutdown).unwrap();
                     self.shutdown();
                     break;
                 }
             }
 
+            // Don't let a steady stream of messages delay writes indefinitely.
+            if let Some(since) = self.pending_writes_since {
+                if since.elapsed() >= MAX_WRITE_DELAY {
+                    self.flush_pending_writes();
+                }
+            }
+
             let mut next_ts = self.get_ts();
             if next_ts <= self.read_lower_bound {
                 next_ts = self.read_lower_bound + 1;
@@ -633,10 +767,10 @@ where
 
             Plan::CreateTable {
                 name,
-                desc,
+                table,
                 if_not_exists,
             } => tx.send(
-                self.sequence_create_table(pcx, name, desc, if_not_exists),
+                self.sequence_create_table(pcx, session.user(), name, table, if_not_exists),
                 session,
             ),
 
@@ -646,7 +780,14 @@ where
                 if_not_exists,
                 materialized,
             } => tx.send(
-                self.sequence_create_source(pcx, name, source, if_not_exists, materialized),
+                self.sequence_create_source(
+                    pcx,
+                    session.user(),
+                    name,
+                    source,
+                    if_not_exists,
+                    materialized,
+                ),
                 session,
             ),
 
@@ -677,10 +818,10 @@ where
             } => tx.send(
                 self.sequence_create_view(
                     pcx,
+                    &session,
                     name,
                     view,
                     replace,
-                    session.conn_id(),
                     materialize,
                     if_not_exists,
                 ),
@@ -692,7 +833,7 @@ where
                 index,
                 if_not_exists,
             } => tx.send(
-                self.sequence_create_index(pcx, name, index, if_not_exists),
+                self.sequence_create_index(pcx, session.user(), name, index, if_not_exists),
                 session,
             ),
 
@@ -708,6 +849,43 @@ where
 
             Plan::DropItems { items, ty } => tx.send(self.sequence_drop_items(items, ty), session),
 
+            Plan::AlterNoop { object_type } => {
+                tx.send(Ok(ExecuteResponse::AlteredObject(object_type)), session)
+            }
+
+            Plan::AlterItemRename {
+                id,
+                to_name,
+                object_type,
+            } => tx.send(
+                self.sequence_alter_item_rename(id, to_name, object_type),
+                session,
+            ),
+
+            Plan::CreateRole { name, superuser } => {
+                tx.send(self.sequence_create_role(name, superuser), session)
+            }
+
+            Plan::DropRoles { names } => tx.send(self.sequence_drop_roles(names), session),
+
+            Plan::GrantPrivileges {
+                privileges,
+                ids,
+                roles,
+            } => tx.send(
+                self.sequence_grant_privileges(privileges, ids, roles),
+                session,
+            ),
+
+            Plan::RevokePrivileges {
+                privileges,
+                ids,
+                roles,
+            } => tx.send(
+                self.sequence_revoke_privileges(privileges, ids, roles),
+                session,
+            ),
+
             Plan::EmptyQuery => tx.send(Ok(ExecuteResponse::EmptyQuery), session),
 
             Plan::ShowAllVariables => tx.send(self.sequence_show_all_variables(&session), session),
@@ -747,16 +925,19 @@ where
             ),
 
             Plan::Tail {
-                id,
+                from,
                 ts,
                 with_snapshot,
+                emit_progress,
             } => tx.send(
-                self.sequence_tail(session.conn_id(), id, with_snapshot, ts),
+                self.sequence_tail(session.conn_id(), from, with_snapshot, ts, emit_progress),
                 session,
             ),
 
             Plan::SendRows(rows) => tx.send(Ok(send_immediate_rows(rows)), session),
 
+            Plan::ShowCreate { row, .. } => tx.send(Ok(send_immediate_rows(vec![row])), session),
+
             Plan::ExplainPlan {
                 raw_plan,
                 decorrelated_plan,
@@ -774,15 +955,49 @@ where
                 session,
             ),
 
+            Plan::Insert { id, values } => match self.sequence_insert(id, values) {
+                Ok((updates, response)) => self.sequence_send_diffs(
+                    id,
+                    updates,
+                    session,
+                    WriteResponder::Execute(tx, response),
+                ),
+                Err(e) => tx.send(Err(e), session),
+            },
+
+            Plan::CopyFrom {
+                id,
+                columns,
+                format,
+            } => tx.send(self.sequence_copy_from(id, columns, format), session),
+
+            Plan::ReadThenWrite {
+                id,
+                selection,
+                kind,
+            } => self.sequence_read_then_write(
+                internal_cmd_tx.clone(),
+                tx,
+                session,
+                id,
+                selection,
+                kind,
+            ),
+
             Plan::SendDiffs {
                 id,
                 updates,
                 affected_rows,
                 kind,
-            } => tx.send(
-                self.sequence_send_diffs(id, updates, affected_rows, kind),
-                session,
-            ),
+            } => {
+                let response = mutation_response(kind, affected_rows);
+                self.sequence_send_diffs(
+                    id,
+                    updates,
+                    session,
+                    WriteResponder::Execute(tx, response),
+                )
+            }
 
             Plan::ShowViews {
                 ids,
@@ -835,16 +1050,17 @@ where
     fn sequence_create_table(
         &mut self,
         pcx: PlanContext,
+        owner: &str,
         name: FullName,
-        desc: RelationDesc,
+        table: sql::plan::Table,
         if_not_exists: bool,
     ) -> Result<ExecuteResponse, failure::Error> {
         let source_id = self.catalog.allocate_id()?;
         let source = catalog::Source {
-            create_sql: "TODO (see #2755)".to_string(),
+            create_sql: table.create_sql,
             plan_cx: pcx,
             connector: dataflow_types::SourceConnector::Local,
-            desc,
+            desc: table.desc,
         };
         let index_id = self.catalog.allocate_id()?;
         let mut index_name = name.clone();
@@ -860,28 +1076,18 @@ where
                 id: source_id,
                 name: name.clone(),
                 item: CatalogItem::Source(source.clone()),
+                owner: Some(owner.into()),
             },
             catalog::Op::CreateItem {
                 id: index_id,
                 name: index_name,
                 item: CatalogItem::Index(index.clone()),
+                owner: Some(owner.into()),
             },
         ]) {
             Ok(_) => {
                 self.views.insert(source_id, ViewState::new(false, vec![]));
-                broadcast(
-                    &mut self.broadcast_tx,
-                    SequencedCommand::CreateLocalInput {
-                        name: name.to_string(),
-                        index_id,
-                        index: IndexDesc {
-                            on_id: index.on,
-                            keys: index.keys.clone(),
-                        },
-                        on_type: source.desc.typ().clone(),
-                    },
-                );
-                self.insert_index(index_id, &index, self.logical_compaction_window_ms);
+                self.create_table_input(name.to_string(), index_id, &index, &source.desc);
                 Ok(ExecuteResponse::CreatedTable { existed: false })
             }
             Err(_) if if_not_exists => Ok(ExecuteResponse::CreatedTable { existed: true }),
@@ -889,9 +1095,58 @@ where
         }
     }
 
+    /// Instructs the dataflow layer to create the local input that receives
+    /// the updates for a table, and the primary index in which they are
+    /// arranged.
+    fn create_table_input(
+        &mut self,
+        name: String,
+        index_id: GlobalId,
+        index: &catalog::Index,
+        desc: &RelationDesc,
+    ) {
+        broadcast(
+            &mut self.broadcast_tx,
+            SequencedCommand::CreateLocalInput {
+                name,
+                index_id,
+                index: IndexDesc {
+                    on_id: index.on,
+                    keys: index.keys.clone(),
+                },
+                on_type: desc.typ().clone(),
+            },
+        );
+        self.insert_index(index_id, &index, self.logical_compaction_window_ms);
+    }
+
+    /// Replays the durably recorded contents of the table identified by `id`
+    /// into its local input.
+    fn replay_table(&mut self, id: GlobalId) -> Result<(), failure::Error> {
+        let updates = self.catalog.load_table_updates(id)?;
+        if updates.is_empty() {
+            return Ok(());
+        }
+        let timestamp = self.get_write_ts();
+        let updates = updates
+            .into_iter()
+            .map(|(row, diff)| Update {
+                row,
+                diff,
+                timestamp,
+            })
+            .collect();
+        broadcast(
+            &mut self.broadcast_tx,
+            SequencedCommand::Insert { id, updates },
+        );
+        Ok(())
+    }
+
     fn sequence_create_source(
         &mut self,
         pcx: PlanContext,
+        owner: &str,
         name: FullName,
         source: sql::plan::Source,
         if_not_exists: bool,
@@ -908,6 +1163,7 @@ where
             id: source_id,
             name: name.clone(),
             item: CatalogItem::Source(source.clone()),
+            owner: Some(owner.into()),
         }];
         let (index_id, index) = if materialized {
             let mut index_name = name.clone();
@@ -923,6 +1179,7 @@ where
                 id: index_id,
                 name: index_name,
                 item: CatalogItem::Index(index.clone()),
+                owner: Some(owner.into()),
             });
             (Some(index_id), Some(index))
         } else {
@@ -995,6 +1252,7 @@ where
                 with_snapshot,
                 as_of,
             }),
+            owner: Some(session.user().into()),
         };
         match self.catalog_transact(vec![op]) {
             Ok(()) => (),
@@ -1029,10 +1287,10 @@ where
     fn sequence_create_view(
         &mut self,
         pcx: PlanContext,
+        session: &Session,
         name: FullName,
         view: sql::plan::View,
         replace: Option<GlobalId>,
-        conn_id: u32,
         materialize: bool,
         if_not_exists: bool,
     ) -> Result<ExecuteResponse, failure::Error> {
@@ -1049,12 +1307,18 @@ where
             plan_cx: pcx,
             optimized_expr,
             desc,
-            conn_id: if view.temporary { Some(conn_id) } else { None },
+            conn_id: if view.temporary {
+                Some(session.conn_id())
+            } else {
+                None
+            },
         };
+        let owner = session.user();
         ops.push(catalog::Op::CreateItem {
             id: view_id,
             name: name.clone(),
             item: CatalogItem::View(view.clone()),
+            owner: Some(owner.into()),
         });
         let (index_id, index) = if materialize {
             let mut index_name = name.clone();
@@ -1071,6 +1335,7 @@ where
                 id: index_id,
                 name: index_name,
                 item: CatalogItem::Index(index.clone()),
+                owner: Some(owner.into()),
             });
             (Some(index_id), Some(index))
         } else {
@@ -1099,6 +1364,7 @@ where
     fn sequence_create_index(
         &mut self,
         pcx: PlanContext,
+        owner: &str,
         name: FullName,
         index: sql::plan::Index,
         if_not_exists: bool,
@@ -1114,6 +1380,7 @@ where
             id,
             name: name.clone(),
             item: CatalogItem::Index(index.clone()),
+            owner: Some(owner.into()),
         };
         match self.catalog_transact(vec![op]) {
             Ok(()) => {
@@ -1158,6 +1425,78 @@ where
         })
     }
 
+    fn sequence_create_role(
+        &mut self,
+        name: String,
+        superuser: bool,
+    ) -> Result<ExecuteResponse, failure::Error> {
+        self.catalog_transact(vec![catalog::Op::CreateRole { name, superuser }])?;
+        Ok(ExecuteResponse::CreatedRole)
+    }
+
+    fn sequence_drop_roles(
+        &mut self,
+        names: Vec<String>,
+    ) -> Result<ExecuteResponse, failure::Error> {
+        let ops = names
+            .into_iter()
+            .map(|name| catalog::Op::DropRole { name })
+            .collect();
+        self.catalog_transact(ops)?;
+        Ok(ExecuteResponse::DroppedRole)
+    }
+
+    fn sequence_grant_privileges(
+        &mut self,
+        privileges: Vec<Privilege>,
+        ids: Vec<GlobalId>,
+        roles: Vec<String>,
+    ) -> Result<ExecuteResponse, failure::Error> {
+        let mut ops = vec![];
+        for id in ids {
+            for role in &roles {
+                ops.push(catalog::Op::GrantPrivileges {
+                    id,
+                    role: role.clone(),
+                    privileges: privileges.clone(),
+                });
+            }
+        }
+        self.catalog_transact(ops)?;
+        Ok(ExecuteResponse::GrantedPrivilege)
+    }
+
+    fn sequence_revoke_privileges(
+        &mut self,
+        privileges: Vec<Privilege>,
+        ids: Vec<GlobalId>,
+        roles: Vec<String>,
+    ) -> Result<ExecuteResponse, failure::Error> {
+        let mut ops = vec![];
+        for id in ids {
+            for role in &roles {
+                ops.push(catalog::Op::RevokePrivileges {
+                    id,
+                    role: role.clone(),
+                    privileges: privileges.clone(),
+                });
+            }
+        }
+        self.catalog_transact(ops)?;
+        Ok(ExecuteResponse::RevokedPrivilege)
+    }
+
+    fn sequence_alter_item_rename(
+        &mut self,
+        id: GlobalId,
+        to_name: String,
+        object_type: ObjectType,
+    ) -> Result<ExecuteResponse, failure::Error> {
+        let op = catalog::Op::RenameItem { id, to_name };
+        self.catalog_transact(vec![op])?;
+        Ok(ExecuteResponse::AlteredObject(object_type))
+    }
+
     fn sequence_show_all_variables(
         &mut self,
         session: &Session,
@@ -1359,33 +1698,65 @@ where
     fn sequence_tail(
         &mut self,
         conn_id: u32,
-        source_id: GlobalId,
+        from: TailFrom,
         with_snapshot: bool,
         ts: Option<Timestamp>,
+        emit_progress: bool,
     ) -> Result<ExecuteResponse, failure::Error> {
-        // Determine the frontier of updates to tail *from*.
-        // Updates greater or equal to this frontier will be produced.
-        let frontier = self.determine_frontier(ts, source_id)?;
-        let sink_name = format!(
-            "tail-source-{}",
-            self.catalog
-                .humanize_id(Id::Global(source_id))
-                .expect("Source id is known to exist in catalog")
-        );
         let sink_id = self.catalog.allocate_id()?;
-        self.active_tails.insert(conn_id, sink_id);
         let (tx, rx) = self.switchboard.mpsc_limited(self.num_timely_workers);
-
-        self.create_sink_dataflow(
-            sink_name,
-            sink_id,
-            source_id,
+        let connector = move |frontier| {
             SinkConnector::Tail(TailSinkConnector {
                 tx,
                 frontier,
                 strict: !with_snapshot,
-            }),
-        );
+                emit_progress,
+            })
+        };
+
+        match from {
+            TailFrom::Id(source_id) => {
+                // Determine the frontier of updates to tail *from*.
+                // Updates greater or equal to this frontier will be produced.
+                let frontier = self.determine_frontier(ts, source_id)?;
+                let sink_name = format!(
+                    "tail-source-{}",
+                    self.catalog
+                        .humanize_id(Id::Global(source_id))
+                        .expect("Source id is known to exist in catalog")
+                );
+                self.create_sink_dataflow(sink_name, sink_id, source_id, connector(frontier));
+            }
+            TailFrom::Query { expr, desc } => {
+                // Build a transient view that computes the query, which is
+                // torn down along with the sink that reads from it. Like a
+                // peek's transient dataflow, the view imports any existing
+                // arrangements of its inputs that the optimizer chose to use.
+                let expr = self.optimizer.optimize(expr, self.catalog.indexes())?;
+                let frontier = match ts {
+                    Some(ts) => Antichain::from_elem(
+                        self.determine_timestamp(expr.as_ref(), PeekWhen::AtTimestamp(ts))?,
+                    ),
+                    None => self.determine_query_frontier(expr.as_ref()),
+                };
+                let view_id = self.catalog.allocate_id()?;
+                let view = catalog::View {
+                    create_sql: "<none>".into(),
+                    plan_cx: PlanContext::default(),
+                    optimized_expr: expr,
+                    desc: desc.clone(),
+                    conn_id: None,
+                };
+                let mut dataflow = DataflowDesc::new(format!("tail-query-{}", view_id));
+                dataflow.set_as_of(frontier.clone());
+                self.build_view_collection(&view_id, &view, &mut dataflow);
+                dataflow.add_sink_export(sink_id, view_id, desc, connector(frontier));
+                self.validate_dataflow(&mut dataflow);
+                self.broadcast_dataflow_creation(dataflow);
+            }
+        }
+
+        self.active_tails.insert(conn_id, sink_id);
         Ok(ExecuteResponse::Tailing { rx })
     }
 
@@ -1438,33 +1809,280 @@ where
         Ok(send_immediate_rows(rows))
     }
 
+    /// Plans the rows inserted into table `id` by an `INSERT` statement, and
+    /// the response to send once they are written.
+    fn sequence_insert(
+        &mut self,
+        id: GlobalId,
+        values: RelationExpr,
+    ) -> Result<(Vec<(Row, isize)>, ExecuteResponse), failure::Error> {
+        let values = self
+            .optimizer
+            .optimize(values, self.catalog.indexes())?
+            .into_inner();
+        let rows = match values {
+            RelationExpr::Constant { rows, typ: _ } => rows,
+            _ => bail!("INSERT statements must contain only constant values"),
+        };
+
+        let desc = self.catalog.get_by_id(&id).desc()?;
+        for (row, _diff) in &rows {
+            check_not_null(row, desc)?;
+        }
+
+        let affected_rows = rows.iter().map(|(_row, diff)| *diff as usize).sum();
+        Ok((rows, ExecuteResponse::Inserted(affected_rows)))
+    }
+
+    fn sequence_copy_from(
+        &mut self,
+        id: GlobalId,
+        columns: Vec<usize>,
+        format: CopyFormat,
+    ) -> Result<ExecuteResponse, failure::Error> {
+        let desc = self.catalog.get_by_id(&id).desc()?.clone();
+        Ok(ExecuteResponse::CopyFrom {
+            id,
+            columns,
+            format,
+            desc,
+        })
+    }
+
+    /// Converts the rows decoded by `COPY FROM` into updates to table
+    /// `id`.
+    fn sequence_copy_rows(
+        &mut self,
+        id: GlobalId,
+        rows: Vec<Row>,
+    ) -> Result<Vec<(Row, isize)>, failure::Error> {
+        let desc = match self.catalog.try_get_by_id(id) {
+            Some(entry) => entry.desc()?.clone(),
+            None => bail!("table was dropped while the statement was running"),
+        };
+        for row in &rows {
+            check_not_null(row, &desc)?;
+        }
+        Ok(rows.into_iter().map(|row| (row, 1)).collect())
+    }
+
+    /// Sequences an `UPDATE` or `DELETE` statement by reading the rows of
+    /// table `id` selected by `selection` and then writing the corresponding
+    /// diffs back to the table.
+    ///
+    /// The read happens at a fresh read timestamp, and so observes all prior
+    /// writes. The rows are collected asynchronously; the diffs are applied
+    /// when the main coordinator thread receives a [`Message::SendDiffs`].
+    ///
+    /// Only one `UPDATE` or `DELETE` against a table is in progress at a
+    /// time. Otherwise, two statements could read the same rows and both
+    /// retract them. Statements against a table with a statement in progress
+    /// are deferred until [`Coordinator::finish_read_then_write`] is called.
+    fn sequence_read_then_write(
+        &mut self,
+        mut internal_cmd_tx: futures::channel::mpsc::UnboundedSender<Message>,
+        tx: ClientTransmitter<ExecuteResponse>,
+        session: Session,
+        id: GlobalId,
+        selection: RelationExpr,
+        kind: MutationKind,
+    ) {
+        if let Some(deferred) = self.pending_read_then_writes.get_mut(&id) {
+            deferred.push_back(DeferredReadThenWrite {
+                internal_cmd_tx,
+                tx,
+                session,
+                selection,
+                kind,
+            });
+            return;
+        }
+
+        let ts = self.get_read_ts();
+        let finishing = RowSetFinishing {
+            order_by: vec![],
+            limit: None,
+            offset: 0,
+            project: (0..selection.arity()).collect(),
+        };
+        let rows = match self.sequence_peek(
+            session.conn_id(),
+            selection,
+            PeekWhen::AtTimestamp(ts),
+            finishing,
+            true,
+        ) {
+            Ok(ExecuteResponse::SendingRows(rows)) => rows,
+            Ok(_) => unreachable!("sequence_peek always returns SendingRows"),
+            Err(e) => {
+                tx.send(Err(e), session);
+                return;
+            }
+        };
+        self.pending_read_then_writes.insert(id, VecDeque::new());
+        tokio::spawn(async move {
+            internal_cmd_tx
+                .send(Message::SendDiffs {
+                    session,
+                    tx,
+                    id,
+                    rows: rows.await,
+                    kind,
+                })
+                .await
+                .expect("sending to internal_cmd_tx cannot fail");
+        });
+    }
+
+    /// Marks the `UPDATE` or `DELETE` in progress against table `id` as
+    /// finished, and starts the next statement against that table that was
+    /// deferred, if any.
+    fn finish_read_then_write(&mut self, id: GlobalId) {
+        let mut deferred = match self.pending_read_then_writes.remove(&id) {
+            Some(deferred) => deferred,
+            None => return,
+        };
+        while let Some(next) = deferred.pop_front() {
+            self.sequence_read_then_write(
+                next.internal_cmd_tx,
+                next.tx,
+                next.session,
+                id,
+                next.selection,
+                next.kind,
+            );
+            if let Some(pending) = self.pending_read_then_writes.get_mut(&id) {
+                // The statement is now in progress, and the remaining
+                // statements wait for it to finish. Otherwise, it failed
+                // immediately, and the next statement can start.
+                pending.append(&mut deferred);
+                return;
+            }
+        }
+    }
+
+    /// Converts the rows read by [`Coordinator::sequence_read_then_write`]
+    /// into diffs against table `id`, and the response to send once they are
+    /// written.
+    fn sequence_read_then_write_diffs(
+        &mut self,
+        id: GlobalId,
+        rows: Result<PeekResponse, comm::Error>,
+        kind: MutationKind,
+    ) -> Result<(Vec<(Row, isize)>, ExecuteResponse), failure::Error> {
+        let rows = match rows? {
+            PeekResponse::Rows(rows) => rows,
+            PeekResponse::Error(e) => bail!("{}", e),
+            PeekResponse::Canceled => bail!("canceling statement due to user request"),
+        };
+
+        let desc = match self.catalog.try_get_by_id(id) {
+            Some(entry) => entry.desc()?.clone(),
+            None => bail!("table was dropped while the statement was running"),
+        };
+        let arity = desc.arity();
+        let mut updates = vec![];
+        for row in &rows {
+            let datums: Vec<_> = row.iter().collect();
+            updates.push((Row::pack(&datums[..arity]), -1));
+            if let MutationKind::Update = kind {
+                let new_row = Row::pack(&datums[arity..]);
+                check_not_null(&new_row, &desc)?;
+                updates.push((new_row, 1));
+            }
+        }
+        Ok((updates, mutation_response(kind, rows.len())))
+    }
+
+    /// Queues `updates` to table `id` to be written by the next call to
+    /// [`Coordinator::flush_pending_writes`], which responds to the client via
+    /// `responder`.
     fn sequence_send_diffs(
         &mut self,
         id: GlobalId,
         updates: Vec<(Row, isize)>,
-        affected_rows: usize,
-        kind: MutationKind,
-    ) -> Result<ExecuteResponse, failure::Error> {
-        let timestamp = self.get_write_ts();
-        let updates = updates
+        session: Session,
+        responder: WriteResponder,
+    ) {
+        self.pending_writes_since.get_or_insert_with(Instant::now);
+        self.pending_writes.push(PendingWrite {
+            id,
+            updates,
+            session,
+            responder,
+        });
+    }
+
+    /// Writes all pending table updates.
+    ///
+    /// The updates are recorded durably in a single catalog transaction
+    /// before they become visible, so that a write that has been acknowledged
+    /// survives a restart. They are then sent to the dataflow workers at a
+    /// single write timestamp, and each client is sent its response.
+    fn flush_pending_writes(&mut self) {
+        self.pending_writes_since = None;
+        if self.pending_writes.is_empty() {
+            return;
+        }
+        // A table may have been dropped after the write to it was sequenced.
+        let (writes, dropped): (Vec<_>, Vec<_>) = mem::replace(&mut self.pending_writes, vec![])
             .into_iter()
-            .map(|(row, diff)| Update {
-                row,
-                diff,
-                timestamp,
-            })
-            .collect();
+            .partition(|write| self.catalog.try_get_by_id(write.id).is_some());
+        for write in dropped {
+            let id = write.id;
+            let finishes_read_then_write = write.finishes_read_then_write();
+            write.respond(Err(failure::format_err!(
+                "table was dropped while the statement was running"
+            )));
+            if finishes_read_then_write {
+                self.finish_read_then_write(id);
+            }
+        }
 
-        broadcast(
-            &mut self.broadcast_tx,
-            SequencedCommand::Insert { id, updates },
+        let persisted = self.catalog.persist_table_updates(
+            writes
+                .iter()
+                .map(|write| (write.id, write.updates.as_slice())),
         );
+        if let Err(e) = persisted {
+            for write in writes {
+                let id = write.id;
+                let finishes_read_then_write = write.finishes_read_then_write();
+                write.respond(Err(failure::format_err!("{}", e)));
+                if finishes_read_then_write {
+                    self.finish_read_then_write(id);
+                }
+            }
+            return;
+        }
 
-        Ok(match kind {
-            MutationKind::Delete => ExecuteResponse::Deleted(affected_rows),
-            MutationKind::Insert => ExecuteResponse::Inserted(affected_rows),
-            MutationKind::Update => ExecuteResponse::Updated(affected_rows),
-        })
+        let timestamp = self.get_write_ts();
+        let mut finished_read_then_writes = vec![];
+        for mut write in writes {
+            let updates = write
+                .updates
+                .drain(..)
+                .map(|(row, diff)| Update {
+                    row,
+                    diff,
+                    timestamp,
+                })
+                .collect();
+            broadcast(
+                &mut self.broadcast_tx,
+                SequencedCommand::Insert {
+                    id: write.id,
+                    updates,
+                },
+            );
+            if write.finishes_read_then_write() {
+                finished_read_then_writes.push(write.id);
+            }
+            write.respond(Ok(()));
+        }
+        for id in finished_read_then_writes {
+            self.finish_read_then_write(id);
+        }
     }
 
     fn sequence_show_views(
@@ -1534,6 +2152,10 @@ where
                     let name = self.catalog.humanize_id(expr::Id::Global(*id)).unwrap();
                     self.report_catalog_update(*id, name, true);
                 }
+                catalog::OpStatus::RenamedItem { id, from, to } => {
+                    self.report_catalog_update(*id, from.to_string(), false);
+                    self.report_catalog_update(*id, to.to_string(), true);
+                }
                 catalog::OpStatus::DroppedItem(entry) => {
                     self.report_catalog_update(entry.id(), entry.name().to_string(), false);
                     match entry.item() {
@@ -1814,6 +2436,7 @@ where
         // Update catalog entry with sink connector.
         let entry = self.catalog.get_by_id(&id);
         let name = entry.name().clone();
+        let owner = entry.owner().map(|owner| owner.to_owned());
         let mut sink = match entry.item() {
             CatalogItem::Sink(sink) => sink.clone(),
             _ => unreachable!(),
@@ -1825,6 +2448,7 @@ where
                 id,
                 name: name.clone(),
                 item: CatalogItem::Sink(sink.clone()),
+                owner,
             },
         ];
         self.catalog
@@ -2212,6 +2836,39 @@ where
         Ok(frontier)
     }
 
+    /// Determine the frontier of updates to start a `TAIL` of a query from,
+    /// when no `AS OF` timestamp was requested.
+    ///
+    /// If all of the query's inputs are queryable, this is the latest
+    /// timestamp at which they are all complete, as for a `TAIL` of a
+    /// materialized view. Otherwise the tail starts from the beginning of
+    /// time, as for a `TAIL` of a non-materialized view.
+    fn determine_query_frontier(&mut self, source: &RelationExpr) -> Antichain<u64> {
+        let mut uses_ids = Vec::new();
+        source.global_uses(&mut uses_ids);
+        let queryable = uses_ids.iter().all(|id| {
+            self.views
+                .get(id)
+                .map(|view_state| view_state.queryable)
+                .unwrap_or(false)
+        });
+        if !queryable {
+            // TODO: This should more carefully consider `since` frontiers of its input.
+            // This will be forcibly corrected if any inputs are compacted.
+            return Antichain::from_elem(0);
+        }
+        let uses_ids = uses_ids
+            .into_iter()
+            .flat_map(|id| self.find_dependent_indexes(&id))
+            .collect::<Vec<_>>();
+        let upper = self.indexes.greatest_open_upper(uses_ids);
+        if let Some(ts) = upper.elements().get(0) {
+            Antichain::from_elem(ts.saturating_sub(1))
+        } else {
+            Antichain::from_elem(Timestamp::max_value())
+        }
+    }
+
     /// Updates the upper frontier of a named view.
     fn update_upper(&mut self, name: &GlobalId, mut changes: ChangeBatch<Timestamp>) {
         if let Some(index_state) = self.indexes.get_mut(name) {
@@ -2312,25 +2969,197 @@ where
         params: &sql::plan::Params,
     ) -> Result<(PlanContext, sql::plan::Plan), failure::Error> {
         let pcx = PlanContext::default();
-        match sql::plan::plan(
-            &pcx,
-            &self.catalog.for_session(session),
-            stmt.clone(),
-            params,
-        ) {
-            Ok(plan) => Ok((pcx, plan)),
-            Err(err) => match self.symbiosis {
-                Some(ref mut postgres) if postgres.can_handle(&stmt) => {
-                    let plan = block_on(postgres.execute(
-                        &pcx,
-                        &self.catalog.for_session(session),
-                        &stmt,
-                    ))?;
-                    Ok((pcx, plan))
+
+        // When running in symbiosis mode, route any statements that modify
+        // tables to Postgres, so that Postgres's copy of each table stays in
+        // sync with ours.
+        if let Some(ref mut postgres) = self.symbiosis {
+            if postgres.can_handle(&stmt) {
+                let plan =
+                    block_on(postgres.execute(&pcx, &self.catalog.for_session(session), &stmt))?;
+                return Ok((pcx, plan));
+            }
+        }
+
+        let plan = sql::plan::plan(&pcx, &self.catalog.for_session(session), stmt, params)?;
+        Ok((pcx, plan))
+    }
+
+    /// Verifies that the user that established `session` is permitted to
+    /// execute `plan`.
+    ///
+    /// Users whose role is a superuser may execute any plan. Other users may
+    /// only read from items that they own or have been granted `SELECT` on,
+    /// may only write to tables that they own or have been granted the
+    /// corresponding privilege on, and may only alter or drop items that they
+    /// own. A user whose role has been dropped since the session was
+    /// established is treated as an ordinary user.
+    fn check_privileges(&mut self, session: &Session, plan: &Plan) -> Result<(), failure::Error> {
+        let role = session.user();
+        match self.catalog.role(role) {
+            Some(catalog::Role { superuser: true }) => return Ok(()),
+            Some(catalog::Role { superuser: false }) | None => (),
+        }
+
+        match plan {
+            Plan::CreateDatabase { .. } => bail!("permission denied to create database"),
+            Plan::CreateSchema { .. } => bail!("permission denied to create schema"),
+            Plan::CreateRole { .. } => bail!("permission denied to create role"),
+            Plan::DropDatabase { .. } => bail!("permission denied to drop database"),
+            Plan::DropSchema { .. } => bail!("permission denied to drop schema"),
+            Plan::DropRoles { .. } => bail!("permission denied to drop role"),
+            // Sources and sinks that read or write the local file system
+            // would otherwise give any role access to the files that
+            // materialized can access.
+            Plan::CreateSource { source, .. } => match &source.connector {
+                SourceConnector::External {
+                    connector: ExternalSourceConnector::File(_),
+                    ..
                 }
-                _ => Err(err),
+                | SourceConnector::External {
+                    connector: ExternalSourceConnector::AvroOcf(_),
+                    ..
+                } => bail!("permission denied to create file source"),
+                SourceConnector::External { .. } | SourceConnector::Local => (),
             },
+            Plan::CreateView { view, replace, .. } => {
+                let mut uses = vec![];
+                view.expr.global_uses(&mut uses);
+                self.require_privilege(role, &uses, Privilege::Select)?;
+                if let Some(id) = replace {
+                    self.require_drop_ownership(role, &[*id])?;
+                }
+            }
+            Plan::CreateSink { sink, .. } => match &sink.connector_builder {
+                SinkConnectorBuilder::AvroOcf(_) | SinkConnectorBuilder::File(_) => {
+                    bail!("permission denied to create file sink")
+                }
+                SinkConnectorBuilder::Kafka(_) | SinkConnectorBuilder::Kinesis(_) => {
+                    self.require_privilege(role, &[sink.from], Privilege::Select)?
+                }
+            },
+            Plan::CreateIndex { index, .. } => self.require_ownership(role, &[index.on])?,
+            Plan::DropItems { items, .. } => self.require_drop_ownership(role, items)?,
+            Plan::AlterItemRename { id, .. } => self.require_ownership(role, &[*id])?,
+            Plan::GrantPrivileges { ids, .. } | Plan::RevokePrivileges { ids, .. } => {
+                self.require_ownership(role, ids)?
+            }
+            Plan::Peek { source, .. } => {
+                let mut uses = vec![];
+                source.global_uses(&mut uses);
+                self.require_privilege(role, &uses, Privilege::Select)?;
+            }
+            Plan::Tail { from, .. } => match from {
+                TailFrom::Id(id) => self.require_privilege(role, &[*id], Privilege::Select)?,
+                TailFrom::Query { expr, .. } => {
+                    let mut uses = vec![];
+                    expr.global_uses(&mut uses);
+                    self.require_privilege(role, &uses, Privilege::Select)?;
+                }
+            },
+            Plan::Insert { id, values } => {
+                self.require_privilege(role, &[*id], Privilege::Insert)?;
+                let mut uses = vec![];
+                values.global_uses(&mut uses);
+                self.require_privilege(role, &uses, Privilege::Select)?;
+            }
+            Plan::CopyFrom { id, .. } => self.require_privilege(role, &[*id], Privilege::Insert)?,
+            Plan::ReadThenWrite {
+                id,
+                selection,
+                kind,
+            } => {
+                self.require_privilege(role, &[*id], mutation_privilege(kind))?;
+                let mut uses = vec![];
+                selection.global_uses(&mut uses);
+                uses.retain(|u| u != id);
+                self.require_privilege(role, &uses, Privilege::Select)?;
+            }
+            Plan::SendDiffs { id, kind, .. } => {
+                self.require_privilege(role, &[*id], mutation_privilege(kind))?
+            }
+            // The statement that created an item can contain secrets, like
+            // the credentials in a source's connection string.
+            Plan::ShowCreate { id, .. } => {
+                self.require_privilege(role, &[*id], Privilege::Select)?
+            }
+            Plan::ExplainPlan {
+                decorrelated_plan, ..
+            } => {
+                let mut uses = vec![];
+                decorrelated_plan.global_uses(&mut uses);
+                self.require_privilege(role, &uses, Privilege::Select)?;
+            }
+            Plan::CreateTable { .. }
+            | Plan::AlterNoop { .. }
+            | Plan::EmptyQuery
+            | Plan::ShowAllVariables
+            | Plan::ShowVariable(_)
+            | Plan::SetVariable { .. }
+            | Plan::StartTransaction
+            | Plan::CommitTransaction
+            | Plan::AbortTransaction
+            | Plan::SendRows(_)
+            | Plan::ShowViews { .. } => (),
         }
+        Ok(())
+    }
+
+    /// Returns an error unless `role` holds `privilege` on each of the items
+    /// identified by `ids`.
+    fn require_privilege(
+        &self,
+        role: &str,
+        ids: &[GlobalId],
+        privilege: Privilege,
+    ) -> Result<(), failure::Error> {
+        for id in ids {
+            let entry = self.catalog.get_by_id(id);
+            if !entry.has_privilege(role, privilege) {
+                bail!(
+                    "permission denied for {} {}",
+                    entry.item().type_string(),
+                    entry.name()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an error unless `role` owns each of the items identified by
+    /// `ids`.
+    fn require_ownership(&self, role: &str, ids: &[GlobalId]) -> Result<(), failure::Error> {
+        for id in ids {
+            let entry = self.catalog.get_by_id(id);
+            if entry.owner() != Some(role) {
+                bail!(
+                    "must be owner of {} {}",
+                    entry.item().type_string(),
+                    entry.name()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Like `require_ownership`, but additionally requires ownership of any
+    /// items that would be dropped in cascade with the items identified by
+    /// `ids`.
+    fn require_drop_ownership(
+        &mut self,
+        role: &str,
+        ids: &[GlobalId],
+    ) -> Result<(), failure::Error> {
+        let ids: Vec<_> = self
+            .catalog
+            .drop_items_ops(ids)
+            .into_iter()
+            .filter_map(|op| match op {
+                catalog::Op::DropItem(id) => Some(id),
+                _ => None,
+            })
+            .collect();
+        self.require_ownership(role, &ids)
     }
 
     fn handle_describe(
@@ -2364,6 +3193,39 @@ fn broadcast(tx: &mut comm::broadcast::Sender<SequencedCommand>, cmd: SequencedC
     block_on(tx.send(cmd)).unwrap();
 }
 
+/// The response to an `INSERT`, `UPDATE`, or `DELETE` that affected
+/// `affected_rows` rows.
+fn mutation_response(kind: MutationKind, affected_rows: usize) -> ExecuteResponse {
+    match kind {
+        MutationKind::Delete => ExecuteResponse::Deleted(affected_rows),
+        MutationKind::Insert => ExecuteResponse::Inserted(affected_rows),
+        MutationKind::Update => ExecuteResponse::Updated(affected_rows),
+    }
+}
+
+/// Verifies that `row`, which is destined for the table described by `desc`,
+/// does not contain nulls in any non-nullable columns.
+fn check_not_null(row: &Row, desc: &RelationDesc) -> Result<(), failure::Error> {
+    for (datum, (name, typ)) in row.iter().zip(desc.iter()) {
+        if datum.is_null() && !typ.nullable {
+            bail!(
+                "null value in column \"{}\" violates not-null constraint",
+                name.map(|n| n.as_str()).unwrap_or("?column?")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the privilege required to apply a mutation of the given kind.
+fn mutation_privilege(kind: &MutationKind) -> Privilege {
+    match kind {
+        MutationKind::Insert => Privilege::Insert,
+        MutationKind::Update => Privilege::Update,
+        MutationKind::Delete => Privilege::Delete,
+    }
+}
+
 /// Constructs an [`ExecuteResponse`] that that will send some rows to the
 /// client immediately, as opposed to asking the dataflow layer to send along
 /// the rows after some computation.
@@ -2373,6 +3235,57 @@ fn send_immediate_rows(rows: Vec<Row>) -> ExecuteResponse {
     ExecuteResponse::SendingRows(Box::pin(rx.err_into()))
 }
 
+/// An `UPDATE` or `DELETE` statement that is waiting for the statement in
+/// progress against the same table to finish.
+struct DeferredReadThenWrite {
+    internal_cmd_tx: futures::channel::mpsc::UnboundedSender<Message>,
+    tx: ClientTransmitter<ExecuteResponse>,
+    session: Session,
+    selection: RelationExpr,
+    kind: MutationKind,
+}
+
+/// A write to a table that is waiting for
+/// [`Coordinator::flush_pending_writes`].
+struct PendingWrite {
+    id: GlobalId,
+    updates: Vec<(Row, isize)>,
+    session: Session,
+    responder: WriteResponder,
+}
+
+impl PendingWrite {
+    /// Reports whether the write is the last step of an `UPDATE` or `DELETE`,
+    /// whose table is locked until the write is applied.
+    fn finishes_read_then_write(&self) -> bool {
+        match self.responder {
+            WriteResponder::ReadThenWrite(..) => true,
+            WriteResponder::Execute(..) | WriteResponder::CopyRows(..) => false,
+        }
+    }
+
+    /// Sends the response to the client that issued the write.
+    fn respond(self, result: Result<(), failure::Error>) {
+        match self.responder {
+            WriteResponder::Execute(tx, response) | WriteResponder::ReadThenWrite(tx, response) => {
+                tx.send(result.map(|()| response), self.session)
+            }
+            WriteResponder::CopyRows(tx) => tx.send(result, self.session),
+        }
+    }
+}
+
+/// How to respond to the client that issued a [`PendingWrite`].
+enum WriteResponder {
+    /// An `INSERT` statement, or the diffs of a [`Plan::SendDiffs`].
+    Execute(ClientTransmitter<ExecuteResponse>, ExecuteResponse),
+    /// An `UPDATE` or `DELETE` statement sequenced by
+    /// [`Coordinator::sequence_read_then_write`].
+    ReadThenWrite(ClientTransmitter<ExecuteResponse>, ExecuteResponse),
+    /// The rows of a `COPY FROM` statement.
+    CopyRows(ClientTransmitter<()>),
+}
+
 /// Per-view state.
 pub struct ViewState {
     /// Only views, not sources, on which the view depends
@@ -21,6 +21,7 @@ use std::fmt;
 
 use failure::bail;
 
+use repr::adt::datetime::Timezone;
 use repr::{Datum, Row, ScalarType};
 use sql::plan::Params;
 
@@ -82,14 +83,16 @@ const SQL_SAFE_UPDATES: ServerVar<&bool> = ServerVar {
     description: "Prohibits SQL statements that may be overly destructive (CockroachDB).",
 };
 
-const TIMEZONE: ServerVar<&str> = ServerVar {
+const TIMEZONE: ServerVar<&Timezone> = ServerVar {
     name: unicase::Ascii::new("TimeZone"),
-    value: "UTC",
-    description: "Sets the time zone for displaying and interpreting time stamps (PostgreSQL).",
+    value: &Timezone::Tz(chrono_tz::Tz::UTC),
+    description: "Sets the time zone for displaying time stamps (PostgreSQL).",
 };
 
 const DUMMY_CONNECTION_ID: u32 = 0;
 
+const DUMMY_USER: &str = "materialize";
+
 /// A `Session` holds SQL state that is attached to a session.
 pub struct Session {
     application_name: SessionVar<str>,
@@ -100,8 +103,10 @@ pub struct Session {
     search_path: ServerVar<&'static [&'static str]>,
     server_version: ServerVar<&'static str>,
     sql_safe_updates: SessionVar<bool>,
-    timezone: ServerVar<&'static str>,
+    timezone: SessionVar<Timezone>,
     conn_id: u32,
+    /// The name of the user that established the session.
+    user: String,
     /// The current state of the the session's transaction
     transaction: TransactionStatus,
     /// A map from statement names to SQL queries
@@ -116,6 +121,7 @@ pub struct Session {
 impl fmt::Debug for Session {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Session")
+            .field("user", &self.user)
             .field("application_name", &self.application_name())
             .field("client_encoding", &self.client_encoding())
             .field("database", &self.database())
@@ -133,8 +139,9 @@ impl fmt::Debug for Session {
 }
 
 impl Session {
-    /// Given a connection id, provides a new session with default values.
-    pub fn new(conn_id: u32) -> Session {
+    /// Given a connection id and the name of the connecting user, provides a
+    /// new session with default values.
+    pub fn new(conn_id: u32, user: String) -> Session {
         assert_ne!(conn_id, DUMMY_CONNECTION_ID);
         Session {
             application_name: SessionVar::new(&APPLICATION_NAME),
@@ -145,8 +152,9 @@ impl Session {
             search_path: SEARCH_PATH,
             server_version: SERVER_VERSION,
             sql_safe_updates: SessionVar::new(&SQL_SAFE_UPDATES),
-            timezone: TIMEZONE,
+            timezone: SessionVar::new(&TIMEZONE),
             conn_id,
+            user,
             transaction: TransactionStatus::Idle,
             prepared_statements: HashMap::new(),
             portals: HashMap::new(),
@@ -165,8 +173,9 @@ impl Session {
             search_path: SEARCH_PATH,
             server_version: SERVER_VERSION,
             sql_safe_updates: SessionVar::new(&SQL_SAFE_UPDATES),
-            timezone: TIMEZONE,
+            timezone: SessionVar::new(&TIMEZONE),
             conn_id: DUMMY_CONNECTION_ID,
+            user: DUMMY_USER.into(),
             transaction: TransactionStatus::Idle,
             prepared_statements: HashMap::new(),
             portals: HashMap::new(),
@@ -178,6 +187,16 @@ impl Session {
         self.conn_id
     }
 
+    /// Returns the name of the user that established the session.
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Sets the name of the user that the session acts as.
+    pub fn set_user(&mut self, user: String) {
+        self.user = user;
+    }
+
     /// Returns all configuration parameters and their current values for this
     /// session.
     pub fn vars(&self) -> Vec<&dyn Var> {
@@ -203,6 +222,7 @@ impl Session {
             &self.client_encoding,
             &self.date_style,
             &self.server_version,
+            &self.timezone,
         ]
     }
 
@@ -265,15 +285,7 @@ impl Session {
         } else if name == SQL_SAFE_UPDATES.name {
             self.sql_safe_updates.set(value)
         } else if name == TIMEZONE.name {
-            if unicase::Ascii::new(value) != TIMEZONE.value {
-                bail!(
-                    "parameter {} can only be set to {}",
-                    TIMEZONE.name,
-                    TIMEZONE.value
-                );
-            } else {
-                Ok(())
-            }
+            self.timezone.set(value)
         } else {
             bail!("unknown parameter: {}", name)
         }
@@ -320,8 +332,8 @@ impl Session {
     }
 
     /// Returns the value of the `timezone` configuration parameter.
-    pub fn timezone(&self) -> &'static str {
-        self.timezone.value
+    pub fn timezone(&self) -> Timezone {
+        *self.timezone.value()
     }
 
     /// Put the session into a transaction
@@ -15,6 +15,8 @@ use std::borrow::Borrow;
 
 use failure::bail;
 
+use repr::adt::datetime::Timezone;
+
 /// A `Var` represents a configuration parameter of an arbitrary type.
 pub trait Var {
     /// Returns the name of the configuration parameter.
@@ -168,3 +170,33 @@ impl Var for SessionVar<i32> {
         self.parent.description
     }
 }
+
+impl SessionVar<Timezone> {
+    pub fn set(&mut self, value: &str) -> Result<(), failure::Error> {
+        match value.parse() {
+            Ok(value) => {
+                self.value = Some(value);
+                Ok(())
+            }
+            Err(_) => bail!(
+                "invalid value for parameter {}: \"{}\"",
+                self.parent.name,
+                value
+            ),
+        }
+    }
+}
+
+impl Var for SessionVar<Timezone> {
+    fn name(&self) -> &'static str {
+        &self.parent.name
+    }
+
+    fn value(&self) -> String {
+        SessionVar::value(self).to_string()
+    }
+
+    fn description(&self) -> &'static str {
+        self.parent.description
+    }
+}
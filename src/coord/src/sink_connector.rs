@@ -16,9 +16,10 @@ use rdkafka::client::DefaultClientContext;
 use rdkafka::config::ClientConfig;
 
 use dataflow_types::{
-    AvroOcfSinkConnector, AvroOcfSinkConnectorBuilder, KafkaSinkConnector,
-    KafkaSinkConnectorBuilder, KafkaSinkConsistencyConnector, SinkConnector, SinkConnectorBuilder,
-    Timestamp,
+    AvroOcfSinkConnector, AvroOcfSinkConnectorBuilder, FileSinkConnector, FileSinkConnectorBuilder,
+    KafkaSinkConnector, KafkaSinkConnectorBuilder, KafkaSinkConsistencyConnector, KafkaSinkFormat,
+    KafkaSinkFormatBuilder, KinesisSinkConnector, KinesisSinkConnectorBuilder, SinkConnector,
+    SinkConnectorBuilder, SinkEnvelope, Timestamp,
 };
 use expr::GlobalId;
 use ore::collections::CollectionExt;
@@ -33,23 +34,24 @@ pub async fn build(
     match builder {
         SinkConnectorBuilder::Kafka(k) => build_kafka(k, with_snapshot, frontier, id).await,
         SinkConnectorBuilder::AvroOcf(a) => build_avro_ocf(a, with_snapshot, frontier, id),
+        SinkConnectorBuilder::File(f) => build_file(f, with_snapshot, frontier),
+        SinkConnectorBuilder::Kinesis(k) => build_kinesis(k, with_snapshot, frontier).await,
     }
 }
 
-async fn register_kafka_topic(
+async fn create_kafka_topic(
     client: &AdminClient<DefaultClientContext>,
     topic: &str,
     replication_factor: i32,
-    ccsr: &ccsr::Client,
-    schema: &str,
-) -> Result<i32, failure::Error> {
+    topic_config: &[(&str, &str)],
+) -> Result<(), failure::Error> {
+    let mut new_topic = NewTopic::new(&topic, 1, TopicReplication::Fixed(replication_factor));
+    for (key, value) in topic_config {
+        new_topic = new_topic.set(key, value);
+    }
     let res = client
         .create_topics(
-            &[NewTopic::new(
-                &topic,
-                1,
-                TopicReplication::Fixed(replication_factor),
-            )],
+            &[new_topic],
             &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
         )
         .await
@@ -64,6 +66,18 @@ async fn register_kafka_topic(
     }
     res.into_element()
         .map_err(|(_, e)| format_err!("error creating topic {} for sink: {}", topic, e))?;
+    Ok(())
+}
+
+async fn register_kafka_topic(
+    client: &AdminClient<DefaultClientContext>,
+    topic: &str,
+    replication_factor: i32,
+    topic_config: &[(&str, &str)],
+    ccsr: &ccsr::Client,
+    schema: &str,
+) -> Result<i32, failure::Error> {
+    create_kafka_topic(client, topic, replication_factor, topic_config).await?;
 
     // Publish value schema for the topic.
     //
@@ -92,43 +106,110 @@ async fn build_kafka(
     let client = config
         .create::<AdminClient<_>>()
         .expect("creating admin client failed");
-    let ccsr = ccsr::ClientConfig::new(builder.schema_registry_url).build();
-
-    let schema_id = register_kafka_topic(
-        &client,
-        &topic,
-        builder.replication_factor as i32,
-        &ccsr,
-        &builder.value_schema,
-    )
-    .await
-    .with_context(|e| format!("error registering kafka topic for sink: {}", e))?;
-
-    let consistency = if let Some(consistency_value_schema) = builder.consistency_value_schema {
-        let consistency_topic = format!("{}-consistency", topic);
-        let consistency_schema_id = register_kafka_topic(
-            &client,
-            &consistency_topic,
-            builder.replication_factor as i32,
-            &ccsr,
-            &consistency_value_schema,
-        )
-        .await
-        .with_context(|e| format!("error registering kafka consistency topic for sink: {}", e))?;
-
-        Some(KafkaSinkConsistencyConnector {
-            topic: consistency_topic,
-            schema_id: consistency_schema_id,
-        })
-    } else {
-        None
+
+    // Upsert sinks only emit the latest value for each key, so their topics
+    // are compacted to retain only those values.
+    let topic_config: &[(&str, &str)] = match builder.envelope {
+        SinkEnvelope::Debezium => &[],
+        SinkEnvelope::Upsert { .. } => &[("cleanup.policy", "compact")],
+    };
+
+    let (format, consistency) = match builder.format {
+        KafkaSinkFormatBuilder::Avro {
+            schema_registry_url,
+            key_schema,
+            value_schema,
+            consistency_value_schema,
+        } => {
+            let ccsr = ccsr::ClientConfig::new(schema_registry_url).build();
+
+            let schema_id = register_kafka_topic(
+                &client,
+                &topic,
+                builder.replication_factor as i32,
+                topic_config,
+                &ccsr,
+                &value_schema,
+            )
+            .await
+            .with_context(|e| format!("error registering kafka topic for sink: {}", e))?;
+
+            let key_schema_id = match key_schema {
+                Some(key_schema) => Some(
+                    ccsr.publish_schema(&format!("{}-key", topic), &key_schema)
+                        .await
+                        .with_context(|e| {
+                            format!(
+                                "unable to publish key schema to registry in kafka sink: {}",
+                                e
+                            )
+                        })?,
+                ),
+                None => None,
+            };
+
+            let consistency = if let Some(consistency_value_schema) = consistency_value_schema {
+                let consistency_topic = format!("{}-consistency", topic);
+                let consistency_schema_id = register_kafka_topic(
+                    &client,
+                    &consistency_topic,
+                    builder.replication_factor as i32,
+                    &[],
+                    &ccsr,
+                    &consistency_value_schema,
+                )
+                .await
+                .with_context(|e| {
+                    format!("error registering kafka consistency topic for sink: {}", e)
+                })?;
+
+                Some(KafkaSinkConsistencyConnector {
+                    topic: consistency_topic,
+                    schema_id: consistency_schema_id,
+                })
+            } else {
+                None
+            };
+
+            (
+                KafkaSinkFormat::Avro {
+                    schema_id,
+                    key_schema_id,
+                },
+                consistency,
+            )
+        }
+        KafkaSinkFormatBuilder::Protobuf(encoding) => {
+            create_kafka_topic(
+                &client,
+                &topic,
+                builder.replication_factor as i32,
+                topic_config,
+            )
+            .await
+            .with_context(|e| format!("error creating kafka topic for sink: {}", e))?;
+            (KafkaSinkFormat::Protobuf(encoding), None)
+        }
+        KafkaSinkFormatBuilder::Json => {
+            create_kafka_topic(
+                &client,
+                &topic,
+                builder.replication_factor as i32,
+                topic_config,
+            )
+            .await
+            .with_context(|e| format!("error creating kafka topic for sink: {}", e))?;
+            (KafkaSinkFormat::Json, None)
+        }
     };
 
     Ok(SinkConnector::Kafka(KafkaSinkConnector {
-        schema_id,
+        format,
+        envelope: builder.envelope,
         topic,
         url: builder.broker_url,
         consistency,
+        exactly_once: builder.exactly_once,
         fuel: builder.fuel,
         frontier,
         strict: !with_snapshot,
@@ -177,3 +258,68 @@ fn build_avro_ocf(
         strict: !with_snapshot,
     }))
 }
+
+fn build_file(
+    builder: FileSinkConnectorBuilder,
+    with_snapshot: bool,
+    frontier: Antichain<Timestamp>,
+) -> Result<SinkConnector, failure::Error> {
+    // Unlike Avro OCF sinks, file sinks append to the named file directly, so
+    // that they can be followed by tools that expect a fixed path.
+    let _ = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(&builder.path)
+        .map_err(|e| {
+            format_err!(
+                "unable to open file sink file {} : {}",
+                builder.path.display(),
+                e
+            )
+        })?;
+    Ok(SinkConnector::File(FileSinkConnector {
+        path: builder.path,
+        format: builder.format,
+        consolidated_snapshots: builder.consolidated_snapshots,
+        frontier,
+        strict: !with_snapshot,
+    }))
+}
+
+async fn build_kinesis(
+    builder: KinesisSinkConnectorBuilder,
+    with_snapshot: bool,
+    frontier: Antichain<Timestamp>,
+) -> Result<SinkConnector, failure::Error> {
+    // Unlike Kafka topics, Kinesis streams are not created by the sink, as
+    // their shard count determines their capacity and cost. Check that the
+    // stream exists up front, rather than failing once the sink is running.
+    let client = aws_util::kinesis::kinesis_client(
+        builder.region.clone(),
+        builder.access_key_id.clone(),
+        builder.secret_access_key.clone(),
+        builder.token.clone(),
+    )
+    .await
+    .map_err(|e| format_err!("creating Kinesis client for sink: {:#}", e))?;
+    aws_util::kinesis::get_shard_ids(&client, &builder.stream_name)
+        .await
+        .map_err(|e| {
+            format_err!(
+                "error describing Kinesis stream {} for sink: {:#}",
+                builder.stream_name,
+                e
+            )
+        })?;
+    Ok(SinkConnector::Kinesis(KinesisSinkConnector {
+        stream_name: builder.stream_name,
+        region: builder.region,
+        access_key_id: builder.access_key_id,
+        secret_access_key: builder.secret_access_key,
+        token: builder.token,
+        format: builder.format,
+        partition_key_indices: builder.partition_key_indices,
+        frontier,
+        strict: !with_snapshot,
+    }))
+}
@@ -178,6 +178,7 @@ enum RtTimestampConnector {
     File(RtFileConnector),
     Ocf(RtFileConnector),
     Kinesis(RtKinesisConnector),
+    Postgres(RtPostgresConnector),
 }
 
 enum ByoTimestampConnector {
@@ -329,6 +330,9 @@ struct ByoKinesisConnector {}
 /// Data consumer stub for File source with RT consistency
 struct RtFileConnector {}
 
+/// Data consumer stub for Postgres source with RT consistency
+struct RtPostgresConnector {}
+
 /// Data consumer stub for File source with BYO consistency
 struct ByoFileConnector<Out, Err> {
     stream: Receiver<Result<Out, Err>>,
@@ -1140,6 +1144,10 @@ impl Timestamper {
                     connector: RtTimestampConnector::Kinesis(connector),
                     _max_ts_batch: max_ts_batch,
                 }),
+            ExternalSourceConnector::Postgres(_) => Some(RtTimestampConsumer {
+                connector: RtTimestampConnector::Postgres(RtPostgresConnector {}),
+                _max_ts_batch: max_ts_batch,
+            }),
         }
     }
 
@@ -1381,6 +1389,10 @@ impl Timestamper {
                     None => None,
                 }
             }
+            ExternalSourceConnector::Postgres(_) => {
+                error!("BYO consistency is not supported for Postgres sources");
+                None
+            }
         }
     }
 
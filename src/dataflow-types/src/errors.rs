@@ -16,12 +16,14 @@ use serde::{Deserialize, Serialize};
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum SourceError {
     FileIO(String),
+    Postgres(String),
 }
 
 impl Display for SourceError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             SourceError::FileIO(e) => write!(f, "File IO: {}", e),
+            SourceError::Postgres(e) => write!(f, "Postgres: {}", e),
         }
     }
 }
@@ -76,6 +76,15 @@ pub struct Update {
     pub diff: isize,
 }
 
+/// A message from a `TAIL` sink to the client that is tailing.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TailResponse {
+    /// A batch of updates to the tailed relation.
+    Rows(Vec<Update>),
+    /// All updates at times less than the contained timestamp have been sent.
+    Progress(Timestamp),
+}
+
 /// A description of view or index to be added to the local context
 /// for a dataflow
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -278,11 +287,15 @@ pub enum DataEncoding {
         regex: Regex,
     },
     Protobuf(ProtobufEncoding),
+    Json(JsonEncoding),
     Bytes,
     Text,
     AvroOcf {
         reader_schema: String,
     },
+    /// Rows that are decoded by the source itself, as for PostgreSQL
+    /// replication, whose description is known up front.
+    Postgres(RelationDesc),
 }
 
 impl DataEncoding {
@@ -291,7 +304,7 @@ impl DataEncoding {
     pub fn desc(&self, envelope: &Envelope) -> Result<RelationDesc, failure::Error> {
         // Add columns for the key, if using the upsert envelope.
         let key_desc = match envelope {
-            Envelope::Upsert(key_encoding) => {
+            Envelope::Upsert(UpsertKey::Encoding(key_encoding)) => {
                 let key_desc = key_encoding.desc(&Envelope::None)?;
 
                 // It doesn't make sense for the key to have keys.
@@ -367,7 +380,19 @@ impl DataEncoding {
                 .fold(key_desc, |desc, i| {
                     desc.with_nonnull_column(format!("column{}", i), ScalarType::String)
                 }),
+            DataEncoding::Json(JsonEncoding { columns }) => {
+                if columns.is_empty() {
+                    key_desc.with_nonnull_column("data", ScalarType::Jsonb)
+                } else {
+                    // Fields may be missing from any given message, so every
+                    // declared column is nullable.
+                    columns.iter().fold(key_desc, |desc, (name, ty)| {
+                        desc.with_column(name.clone(), ColumnType::new(ty.clone()).nullable(true))
+                    })
+                }
+            }
             DataEncoding::Text => key_desc.with_nonnull_column("text", ScalarType::String),
+            DataEncoding::Postgres(desc) => desc.clone(),
         })
     }
 
@@ -379,7 +404,9 @@ impl DataEncoding {
             DataEncoding::Protobuf(_) => "Protobuf",
             DataEncoding::Regex { .. } => "Regex",
             DataEncoding::Csv(_) => "Csv",
+            DataEncoding::Json(_) => "Json",
             DataEncoding::Text => "Text",
+            DataEncoding::Postgres(_) => "Postgres",
         }
     }
 }
@@ -400,6 +427,16 @@ pub struct CsvEncoding {
     pub delimiter: u8,
 }
 
+/// Encoding in JSON format.
+///
+/// If `columns` is empty, each message is decoded into a single `jsonb`
+/// column. Otherwise, each of the named top-level fields of each message is
+/// extracted and cast to the corresponding type.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonEncoding {
+    pub columns: Vec<(ColumnName, ScalarType)>,
+}
+
 /// Encoding in Protobuf format.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ProtobufEncoding {
@@ -431,7 +468,7 @@ pub struct SinkDesc {
 pub enum Envelope {
     None,
     Debezium(DebeziumDeduplicationStrategy),
-    Upsert(DataEncoding),
+    Upsert(UpsertKey),
 }
 
 impl Envelope {
@@ -439,11 +476,28 @@ impl Envelope {
         match self {
             Envelope::None => avro::EnvelopeType::None,
             Envelope::Debezium { .. } => avro::EnvelopeType::Debezium,
-            Envelope::Upsert(_) => avro::EnvelopeType::Upsert,
+            Envelope::Upsert(UpsertKey::Encoding(_)) => avro::EnvelopeType::Upsert,
+            // Values are decoded as plain rows, from which the key is then
+            // projected.
+            Envelope::Upsert(UpsertKey::Columns { .. }) => avro::EnvelopeType::None,
         }
     }
 }
 
+/// Describes how the upsert envelope determines the key of each record.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum UpsertKey {
+    /// The key is decoded from the record's key with the given encoding. A
+    /// record with an empty value deletes the row with the same key.
+    Encoding(DataEncoding),
+    /// The key is projected from the decoded value. A record whose
+    /// `value_indices` columns are all null deletes the row with the same key.
+    Columns {
+        key_indices: Vec<usize>,
+        value_indices: Vec<usize>,
+    },
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SourceConnector {
     External {
@@ -463,6 +517,7 @@ pub enum ExternalSourceConnector {
     Kinesis(KinesisSourceConnector),
     File(FileSourceConnector),
     AvroOcf(FileSourceConnector),
+    Postgres(PostgresSourceConnector),
 }
 
 impl ExternalSourceConnector {
@@ -472,6 +527,7 @@ impl ExternalSourceConnector {
             Self::File(_) => vec![("mz_line_no".into(), ColumnType::new(ScalarType::Int64))],
             Self::Kinesis(_) => vec![],
             Self::AvroOcf(_) => vec![("mz_obj_no".into(), ColumnType::new(ScalarType::Int64))],
+            Self::Postgres(_) => vec![],
         }
     }
 
@@ -482,6 +538,7 @@ impl ExternalSourceConnector {
             ExternalSourceConnector::Kinesis(_) => "kinesis",
             ExternalSourceConnector::File(_) => "file",
             ExternalSourceConnector::AvroOcf(_) => "avro-ocf",
+            ExternalSourceConnector::Postgres(_) => "postgres",
         }
     }
 }
@@ -573,11 +630,26 @@ pub struct FileSourceConnector {
     pub tail: bool,
 }
 
+/// A table mirrored from PostgreSQL via logical replication.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PostgresSourceConnector {
+    /// The libpq connection string of the upstream database.
+    pub conn: String,
+    /// The publication whose changes are replicated.
+    pub publication: String,
+    /// The schema of the replicated table.
+    pub namespace: String,
+    /// The name of the replicated table.
+    pub table: String,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SinkConnector {
     Kafka(KafkaSinkConnector),
     Tail(TailSinkConnector),
     AvroOcf(AvroOcfSinkConnector),
+    File(FileSinkConnector),
+    Kinesis(KinesisSinkConnector),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -590,8 +662,12 @@ pub struct KafkaSinkConsistencyConnector {
 pub struct KafkaSinkConnector {
     pub url: Url,
     pub topic: String,
-    pub schema_id: i32,
+    pub format: KafkaSinkFormat,
+    pub envelope: SinkEnvelope,
     pub consistency: Option<KafkaSinkConsistencyConnector>,
+    /// Whether the sink writes each timestamp's updates, along with the
+    /// timestamp's consistency messages, in a single Kafka transaction.
+    pub exactly_once: bool,
     // Maximum number of records the sink will attempt to send each time it is
     // invoked
     pub fuel: usize,
@@ -599,6 +675,35 @@ pub struct KafkaSinkConnector {
     pub strict: bool,
 }
 
+/// The format in which a Kafka sink encodes its updates.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkFormat {
+    /// Avro, with the writer schema published to a schema registry under
+    /// `schema_id`. Upsert sinks also publish the schema of their keys under
+    /// `key_schema_id`.
+    Avro {
+        schema_id: i32,
+        key_schema_id: Option<i32>,
+    },
+    /// Protobuf, with each update wrapped in an envelope message.
+    Protobuf(ProtobufEncoding),
+    /// JSON, with each row encoded as an object mapping column names to
+    /// values.
+    Json,
+}
+
+/// The shape of the messages that a sink emits for its updates.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SinkEnvelope {
+    /// Each update is emitted as a Debezium-style pair of the row before and
+    /// after the update.
+    Debezium,
+    /// Each update is emitted as a message keyed by the columns of the row at
+    /// `key_indices`, whose value is the latest row for that key, or nothing
+    /// if the key was deleted.
+    Upsert { key_indices: Vec<usize> },
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct AvroOcfSinkConnector {
     pub path: PathBuf,
@@ -606,10 +711,59 @@ pub struct AvroOcfSinkConnector {
     pub strict: bool,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileSinkConnector {
+    pub path: PathBuf,
+    pub format: FileSinkFormat,
+    /// Whether to write the full contents of the collection at each
+    /// timestamp at which it changes, rather than the changes themselves.
+    pub consolidated_snapshots: bool,
+    pub frontier: Antichain<Timestamp>,
+    pub strict: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KinesisSinkConnector {
+    pub stream_name: String,
+    pub region: Region,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    pub token: Option<String>,
+    pub format: KinesisSinkFormat,
+    /// The indices of the columns from which the partition key of each
+    /// record is derived.
+    pub partition_key_indices: Vec<usize>,
+    pub frontier: Antichain<Timestamp>,
+    pub strict: bool,
+}
+
+/// The format in which a Kinesis sink encodes its updates.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KinesisSinkFormat {
+    /// Protobuf, with each update wrapped in an envelope message.
+    Protobuf(ProtobufEncoding),
+    /// JSON, with each update encoded as an object with `before` and `after`
+    /// fields.
+    Json,
+}
+
+/// The format in which a file sink writes its updates, one per line.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FileSinkFormat {
+    /// CSV, with the timestamp and diff of each update in the first two
+    /// fields, optionally preceded by a header row.
+    Csv { header_row: bool, delimiter: u8 },
+    /// JSON, with each update encoded as an object with `timestamp`, `diff`,
+    /// and `row` fields.
+    Json,
+}
+
 impl SinkConnector {
     pub fn get_frontier(&self) -> Antichain<Timestamp> {
         match self {
             SinkConnector::AvroOcf(avro) => avro.frontier.clone(),
+            SinkConnector::File(file) => file.frontier.clone(),
+            SinkConnector::Kinesis(kinesis) => kinesis.frontier.clone(),
             SinkConnector::Kafka(kafka) => kafka.frontier.clone(),
             SinkConnector::Tail(tail) => tail.frontier.clone(),
         }
@@ -618,15 +772,20 @@ impl SinkConnector {
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TailSinkConnector {
-    pub tx: comm::mpsc::Sender<Vec<Update>>,
+    pub tx: comm::mpsc::Sender<TailResponse>,
     pub frontier: Antichain<Timestamp>,
     pub strict: bool,
+    /// Whether to send a `TailResponse::Progress` message whenever the input
+    /// frontier advances.
+    pub emit_progress: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SinkConnectorBuilder {
     Kafka(KafkaSinkConnectorBuilder),
     AvroOcf(AvroOcfSinkConnectorBuilder),
+    File(FileSinkConnectorBuilder),
+    Kinesis(KinesisSinkConnectorBuilder),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -635,16 +794,46 @@ pub struct AvroOcfSinkConnectorBuilder {
     pub file_name_suffix: String,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileSinkConnectorBuilder {
+    pub path: PathBuf,
+    pub format: FileSinkFormat,
+    pub consolidated_snapshots: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KinesisSinkConnectorBuilder {
+    pub stream_name: String,
+    pub region: Region,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    pub token: Option<String>,
+    pub format: KinesisSinkFormat,
+    pub partition_key_indices: Vec<usize>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct KafkaSinkConnectorBuilder {
     pub broker_url: Url,
-    pub schema_registry_url: Url,
-    pub value_schema: String,
+    pub format: KafkaSinkFormatBuilder,
+    pub envelope: SinkEnvelope,
     pub topic_prefix: String,
     pub topic_suffix: String,
     pub replication_factor: u32,
+    pub exactly_once: bool,
     pub fuel: usize,
-    pub consistency_value_schema: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkFormatBuilder {
+    Avro {
+        schema_registry_url: Url,
+        key_schema: Option<String>,
+        value_schema: String,
+        consistency_value_schema: Option<String>,
+    },
+    Protobuf(ProtobufEncoding),
+    Json,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
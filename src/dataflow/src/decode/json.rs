@@ -0,0 +1,207 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::borrow::Cow;
+use std::mem;
+
+use async_trait::async_trait;
+use failure::{bail, format_err};
+use log::error;
+use serde_json::Value;
+
+use dataflow_types::{Diff, JsonEncoding, Timestamp};
+use repr::adt::char;
+use repr::adt::jsonb::JsonbPacker;
+use repr::{strconv, ColumnName, Datum, Row, RowPacker, ScalarType};
+
+use super::{DecoderState, PushSession};
+use crate::metrics::EVENTS_COUNTER;
+
+pub struct JsonDecoderState {
+    columns: Vec<(ColumnName, ScalarType)>,
+    packer: RowPacker,
+    events_success: i64,
+    events_error: i64,
+}
+
+impl JsonDecoderState {
+    pub fn new(encoding: JsonEncoding) -> Self {
+        JsonDecoderState {
+            columns: encoding.columns,
+            packer: RowPacker::new(),
+            events_success: 0,
+            events_error: 0,
+        }
+    }
+
+    /// Decodes a JSON message into a row, appending `aux_num` as an additional
+    /// column if it is present.
+    fn decode(&mut self, bytes: &[u8], aux_num: Option<i64>) -> Result<Row, failure::Error> {
+        match self.decode_inner(bytes, aux_num) {
+            Ok(row) => {
+                self.events_success += 1;
+                Ok(row)
+            }
+            Err(err) => {
+                self.events_error += 1;
+                Err(err)
+            }
+        }
+    }
+
+    fn decode_inner(&mut self, bytes: &[u8], aux_num: Option<i64>) -> Result<Row, failure::Error> {
+        // If decoding fails partway through the message, the partially-packed
+        // row is discarded along with the packer.
+        let mut packer = mem::take(&mut self.packer);
+        if self.columns.is_empty() {
+            packer = JsonbPacker::new(packer).pack_slice(bytes)?;
+        } else {
+            let object = match serde_json::from_slice(bytes)? {
+                Value::Object(object) => object,
+                _ => bail!("expected JSON object"),
+            };
+            for (name, ty) in &self.columns {
+                packer = match object.get(name.as_str()) {
+                    None | Some(Value::Null) => {
+                        packer.push(Datum::Null);
+                        packer
+                    }
+                    Some(value) => pack_value(packer, value, ty)
+                        .map_err(|e| format_err!("decoding field \"{}\": {}", name, e))?,
+                };
+            }
+        }
+        if let Some(aux_num) = aux_num {
+            packer.push(Datum::from(aux_num));
+        }
+        let row = packer.finish_and_reuse();
+        self.packer = packer;
+        Ok(row)
+    }
+}
+
+/// Packs the JSON value `value` into `packer` as a datum of type `ty`.
+///
+/// JSON strings are cast from their contents, and all other JSON values are
+/// cast from their JSON representation, as if the value had been extracted
+/// with the `->>` operator and then cast to `ty`.
+fn pack_value(
+    mut packer: RowPacker,
+    value: &Value,
+    ty: &ScalarType,
+) -> Result<RowPacker, failure::Error> {
+    if let ScalarType::Jsonb = ty {
+        return JsonbPacker::new(packer).pack_serde_json(value.clone());
+    }
+    let text = match value {
+        Value::String(s) => Cow::Borrowed(s.as_str()),
+        _ => Cow::Owned(value.to_string()),
+    };
+    let text = &*text;
+    match ty {
+        ScalarType::Bool => packer.push(Datum::from(strconv::parse_bool(text)?)),
+        ScalarType::Int16 => packer.push(Datum::from(strconv::parse_int16(text)?)),
+        ScalarType::Int32 => packer.push(Datum::from(strconv::parse_int32(text)?)),
+        ScalarType::Int64 => packer.push(Datum::from(strconv::parse_int64(text)?)),
+        ScalarType::Float32 => packer.push(Datum::from(strconv::parse_float32(text)?)),
+        ScalarType::Float64 => packer.push(Datum::from(strconv::parse_float64(text)?)),
+        ScalarType::Decimal(_, scale) => {
+            let d = strconv::parse_decimal(text)?;
+            let significand = d
+                .rescale(*scale)
+                .ok_or_else(|| format_err!("decimal {} out of range for scale {}", d, scale))?;
+            packer.push(Datum::from(significand))
+        }
+        ScalarType::Date => packer.push(Datum::Date(strconv::parse_date(text)?)),
+        ScalarType::Time => packer.push(Datum::Time(strconv::parse_time(text)?)),
+        ScalarType::Timestamp => packer.push(Datum::Timestamp(strconv::parse_timestamp(text)?)),
+        ScalarType::TimestampTz => {
+            packer.push(Datum::TimestampTz(strconv::parse_timestamptz(text)?))
+        }
+        ScalarType::Interval => packer.push(Datum::Interval(strconv::parse_interval(text)?)),
+        ScalarType::Bytes => packer.push(Datum::Bytes(&strconv::parse_bytes(text)?)),
+        ScalarType::String => packer.push(Datum::String(text)),
+        ScalarType::Char { length } => {
+            let text = match length {
+                Some(length) => char::truncate(text, *length, true)
+                    .ok_or_else(|| format_err!("value too long for type character({})", length))?,
+                None => text,
+            };
+            packer.push(Datum::String(char::strip_padding(text)))
+        }
+        ScalarType::VarChar { length } => {
+            let text = match length {
+                Some(length) => char::truncate(text, *length, true).ok_or_else(|| {
+                    format_err!("value too long for type character varying({})", length)
+                })?,
+                None => text,
+            };
+            packer.push(Datum::String(text))
+        }
+        ScalarType::Uuid => packer.push(Datum::Uuid(strconv::parse_uuid(text)?)),
+        ScalarType::Jsonb => unreachable!(),
+        ScalarType::List(_) | ScalarType::Array(_) | ScalarType::Record { .. } => {
+            bail!("cannot decode JSON into a column of type {}", ty)
+        }
+    }
+    Ok(packer)
+}
+
+#[async_trait(?Send)]
+impl DecoderState for JsonDecoderState {
+    /// Reset number of success and failures with decoding
+    fn reset_event_count(&mut self) {
+        self.events_success = 0;
+        self.events_error = 0;
+    }
+
+    async fn decode_key(&mut self, bytes: &[u8]) -> Result<Row, String> {
+        self.decode(bytes, None)
+            .map_err(|e| format!("JSON deserialization error: {}", e))
+    }
+
+    /// give a session a key-value pair
+    async fn give_key_value<'a>(
+        &mut self,
+        key: Row,
+        bytes: &[u8],
+        aux_num: Option<i64>,
+        session: &mut PushSession<'a, (Row, Option<Row>, Timestamp)>,
+        time: Timestamp,
+    ) {
+        match self.decode(bytes, aux_num) {
+            Ok(row) => session.give((key, Some(row), time)),
+            Err(err) => error!("JSON deserialization error: {}", err),
+        }
+    }
+
+    /// give a session a plain value
+    async fn give_value<'a>(
+        &mut self,
+        bytes: &[u8],
+        aux_num: Option<i64>,
+        session: &mut PushSession<'a, (Row, Timestamp, Diff)>,
+        time: Timestamp,
+    ) {
+        match self.decode(bytes, aux_num) {
+            Ok(row) => session.give((row, time, 1)),
+            Err(err) => error!("JSON deserialization error: {}", err),
+        }
+    }
+
+    /// Register number of success and failures with decoding
+    fn log_error_count(&self) {
+        if self.events_success > 0 {
+            EVENTS_COUNTER.json.success.inc_by(self.events_success);
+        }
+        if self.events_error > 0 {
+            EVENTS_COUNTER.json.error.inc_by(self.events_error);
+        }
+    }
+}
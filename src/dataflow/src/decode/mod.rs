@@ -26,6 +26,7 @@ use repr::{Row, RowPacker};
 
 mod avro;
 mod csv;
+mod json;
 mod protobuf;
 mod regex;
 
@@ -90,7 +91,9 @@ where
                         ))
                     }
                 }
-                Envelope::Upsert(_) => unreachable!("Upsert is not supported for AvroOCF"),
+                Envelope::Upsert(_) => {
+                    unreachable!("Upsert sources are decoded without an envelope")
+                }
             }
             .unwrap_or_else(|e| {
                 // TODO(#489): Handle this in a better way,
@@ -432,7 +435,7 @@ where
     let worker_index = stream.scope().index();
     match (encoding, envelope) {
         (_, Envelope::Upsert(_)) => {
-            unreachable!("Internal error: Upsert is not supported yet on non-Kafka sources.")
+            unreachable!("Internal error: Upsert sources are decoded without an envelope.")
         }
         (DataEncoding::Csv(enc), Envelope::None) => {
             csv(stream, enc.header_row, enc.n_cols, enc.delimiter, operators)
@@ -478,6 +481,9 @@ where
         (DataEncoding::AvroOcf { .. }, _) => {
             unreachable!("Internal error: Cannot decode Avro OCF separately from reading")
         }
+        (DataEncoding::Postgres(_), _) => {
+            unreachable!("Internal error: Cannot decode Postgres separately from reading")
+        }
         (_, Envelope::Debezium(_)) => unreachable!(
             "Internal error: A non-Avro Debezium-envelope source should not have been created."
         ),
@@ -488,6 +494,12 @@ where
             &op_name,
             SourceOutput::<Vec<u8>, Vec<u8>>::value_contract(),
         ),
+        (DataEncoding::Json(enc), Envelope::None) => decode_values_inner(
+            stream,
+            json::JsonDecoderState::new(enc),
+            &op_name,
+            SourceOutput::<Vec<u8>, Vec<u8>>::value_contract(),
+        ),
         (DataEncoding::Bytes, Envelope::None) => decode_values_inner(
             stream,
             OffsetDecoderState::from(bytes_to_datum),
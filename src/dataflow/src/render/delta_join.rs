@@ -12,6 +12,7 @@
 use differential_dataflow::lattice::Lattice;
 use dogsdogsdogs::altneu::AltNeu;
 use timely::dataflow::Scope;
+use timely::progress::timestamp::Refines;
 
 use dataflow_types::{DataflowError, Timestamp};
 use expr::{RelationExpr, ScalarExpr};
@@ -22,7 +23,8 @@ use crate::operator::CollectionExt;
 
 impl<G> Context<G, RelationExpr, Row, Timestamp>
 where
-    G: Scope<Timestamp = Timestamp>,
+    G: Scope,
+    G::Timestamp: Lattice + Refines<Timestamp>,
 {
     /// Renders `RelationExpr:Join` using dogs^3 delta query dataflows.
     pub fn render_delta_join<F>(
@@ -0,0 +1,136 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashSet;
+
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::operators::iterate::Variable;
+use differential_dataflow::Collection;
+use timely::dataflow::Scope;
+use timely::order::Product;
+use timely::progress::timestamp::Refines;
+
+use dataflow_types::{DataflowError, Timestamp};
+use expr::{Id, RelationExpr};
+use repr::Row;
+
+use crate::operator::CollectionExt;
+use crate::render::context::Context;
+
+/// A timestamp of a scope in which `RelationExpr`s can be rendered.
+///
+/// Rendering an `Iterate` introduces a nested iterative scope, whose timestamp
+/// is a `Product` of the outer timestamp and an iteration counter. Rendering
+/// must not nest these scopes arbitrarily deep, as each level would be a new
+/// type to instantiate the renderer for, and so the behavior that varies with
+/// the scope is collected here.
+pub trait RenderTimestamp: timely::progress::Timestamp + Lattice + Refines<Timestamp> {
+    /// Returns a time that is strictly less than `self`, if one exists.
+    ///
+    /// Delta joins use this to break ties between updates to their inputs.
+    fn step_back(&self) -> Self;
+
+    /// Renders `relation_expr`, which must be an `Iterate`, in `scope`.
+    fn render_iterate<G>(
+        context: &Context<G, RelationExpr, Row, Timestamp>,
+        relation_expr: &RelationExpr,
+        scope: &mut G,
+        worker_index: usize,
+    ) -> (Collection<G, Row>, Collection<G, DataflowError>)
+    where
+        G: Scope<Timestamp = Self>;
+}
+
+impl RenderTimestamp for Timestamp {
+    fn step_back(&self) -> Self {
+        self.saturating_sub(1)
+    }
+
+    fn render_iterate<G>(
+        context: &Context<G, RelationExpr, Row, Timestamp>,
+        relation_expr: &RelationExpr,
+        scope: &mut G,
+        worker_index: usize,
+    ) -> (Collection<G, Row>, Collection<G, DataflowError>)
+    where
+        G: Scope<Timestamp = Self>,
+    {
+        if let RelationExpr::Iterate { id, body } = relation_expr {
+            // Determine the collections the body reads from outside of the
+            // iteration, which must be brought into the iterative scope.
+            let mut bound = HashSet::new();
+            bound.insert(Id::Local(*id));
+            body.visit(&mut |e| {
+                if let RelationExpr::Let { id, .. } = e {
+                    bound.insert(Id::Local(*id));
+                }
+            });
+            let mut free = Vec::new();
+            let mut recursive = Vec::new();
+            body.visit(&mut |e| {
+                if let RelationExpr::Get { id: get_id, .. } = e {
+                    if *get_id == Id::Local(*id) {
+                        if !recursive.contains(e) {
+                            recursive.push(e.clone());
+                        }
+                    } else if !bound.contains(get_id) && !free.contains(e) {
+                        free.push(e.clone());
+                    }
+                }
+            });
+
+            scope.iterative::<u64, _, _>(|child| {
+                let mut inner = Context::<_, RelationExpr, Row, Timestamp>::new();
+                for get in free {
+                    let (oks, errs) = context
+                        .collection(&get)
+                        .unwrap_or_else(|| panic!("Collection {:?} not rendered", get));
+                    inner
+                        .collections
+                        .insert(get, (oks.enter(child), errs.enter(child)));
+                }
+
+                // The body reads its own output from the previous iteration.
+                // Errors are not fed back, as those of the final iteration
+                // are exactly those produced by the body from its fixed point.
+                let variable = Variable::new(child, Product::new(Default::default(), 1));
+                for get in recursive {
+                    inner
+                        .collections
+                        .insert(get, ((*variable).clone(), Collection::empty(child)));
+                }
+
+                inner.ensure_rendered(body, child, worker_index);
+                let (oks, errs) = inner.collection(body).unwrap();
+                variable.set(&oks);
+                (oks.leave(), errs.leave())
+            })
+        } else {
+            panic!("render_iterate called on non-iterate RelationExpr");
+        }
+    }
+}
+
+impl RenderTimestamp for Product<Timestamp, u64> {
+    fn step_back(&self) -> Self {
+        Product::new(self.outer.saturating_sub(1), self.inner.saturating_sub(1))
+    }
+
+    fn render_iterate<G>(
+        _context: &Context<G, RelationExpr, Row, Timestamp>,
+        _relation_expr: &RelationExpr,
+        _scope: &mut G,
+        _worker_index: usize,
+    ) -> (Collection<G, Row>, Collection<G, DataflowError>)
+    where
+        G: Scope<Timestamp = Self>,
+    {
+        panic!("Attempt to render nested iteration");
+    }
+}
@@ -100,6 +100,7 @@
 
 use std::any::Any;
 use std::collections::HashMap;
+use std::hash::Hash;
 use std::io::BufRead;
 use std::rc::Rc;
 use std::rc::Weak;
@@ -118,6 +119,7 @@ use timely::dataflow::Scope;
 use timely::dataflow::Stream;
 use timely::progress::Antichain;
 use timely::worker::Worker as TimelyWorker;
+use timely::ExchangeData;
 
 use timely::dataflow::channels::pact::{Exchange, Pipeline};
 
@@ -130,6 +132,7 @@ use ore::iter::IteratorExt;
 use repr::{Datum, RelationType, Row, RowArena};
 
 use self::context::{ArrangementFlavor, Context};
+use self::iterate::RenderTimestamp;
 use super::sink;
 use super::source;
 use super::source::FileReadStyle;
@@ -146,6 +149,7 @@ use source::SourceOutput;
 mod arrange_by;
 mod context;
 mod delta_join;
+mod iterate;
 mod join;
 mod reduce;
 mod threshold;
@@ -306,14 +310,24 @@ pub(crate) fn build_dataflow<A: Allocate>(
                         worker_count: worker_peers,
                     };
 
-                    let capability = if let Envelope::Upsert(key_encoding) = envelope {
+                    let capability = if let Envelope::Upsert(UpsertKey::Encoding(key_encoding)) =
+                        envelope
+                    {
                         match connector {
-                            ExternalSourceConnector::Kafka(_) => {
-                                let (source, capability) =
-                                    source::create_source::<_, KafkaSourceInfo>(
-                                        source_config,
-                                        connector,
-                                    );
+                            ExternalSourceConnector::Kafka(_)
+                            | ExternalSourceConnector::Kinesis(_) => {
+                                let (source, capability) = match connector {
+                                    ExternalSourceConnector::Kafka(_) => {
+                                        source::create_source::<_, KafkaSourceInfo>(
+                                            source_config,
+                                            connector,
+                                        )
+                                    }
+                                    ExternalSourceConnector::Kinesis(kc) => {
+                                        source::kinesis(source_config, kc)
+                                    }
+                                    _ => unreachable!(),
+                                };
 
                                 // This operator changes the timestamp from capability to message payload,
                                 // and applies `as_of` frontier compaction. The compaction is important as
@@ -360,9 +374,26 @@ pub(crate) fn build_dataflow<A: Allocate>(
                                 );
                                 capability
                             }
-                            _ => unreachable!("Upsert envelope unsupported for non-Kafka sources"),
+                            _ => unreachable!(
+                                "Upsert envelope with a key encoding is only supported \
+                                 for Kafka and Kinesis sources"
+                            ),
                         }
                     } else {
+                        // Sources that upsert by the columns of their values
+                        // are decoded without an envelope. The decoder must
+                        // not eagerly filter or project the decoded rows, as
+                        // that would change which rows are upserted.
+                        let decode_envelope = match &envelope {
+                            Envelope::Upsert(_) => Envelope::None,
+                            envelope => envelope.clone(),
+                        };
+                        let mut no_operators = None;
+                        let decode_operators = match &envelope {
+                            Envelope::Upsert(_) => &mut no_operators,
+                            _ => &mut src.operators,
+                        };
+
                         let (stream, capability) =
                             if let ExternalSourceConnector::AvroOcf(c) = connector {
                                 // Distribute read responsibility among workers.
@@ -398,12 +429,29 @@ pub(crate) fn build_dataflow<A: Allocate>(
                                 (
                                     decode_avro_values(
                                         &source,
-                                        &envelope,
+                                        &decode_envelope,
                                         reader_schema,
                                         &dataflow.debug_name,
                                     ),
                                     capability,
                                 )
+                            } else if let ExternalSourceConnector::Postgres(c) = connector {
+                                let desc = match encoding {
+                                    DataEncoding::Postgres(desc) => desc,
+                                    _ => unreachable!(
+                                        "Internal error: \
+                                         Postgres sources must use the Postgres encoding"
+                                    ),
+                                };
+                                let ((ok_source, err_source), capability) =
+                                    source::postgres(source_config, c, desc);
+                                err_collection = err_collection.concat(
+                                    &err_source
+                                        .map(DataflowError::SourceError)
+                                        .pass_through("Postgres-errors")
+                                        .as_collection(),
+                                );
+                                (ok_source, capability)
                             } else {
                                 let ((ok_source, err_source), capability) = match connector {
                                     ExternalSourceConnector::Kafka(_) => {
@@ -428,7 +476,8 @@ pub(crate) fn build_dataflow<A: Allocate>(
                                             |file| Ok(std::io::BufReader::new(file).split(b'\n'));
                                         source::file(source_config, c.path, read_style, ctor)
                                     }
-                                    ExternalSourceConnector::AvroOcf(_) => unreachable!(),
+                                    ExternalSourceConnector::AvroOcf(_)
+                                    | ExternalSourceConnector::Postgres(_) => unreachable!(),
                                 };
                                 err_collection = err_collection.concat(
                                     &err_source
@@ -443,8 +492,8 @@ pub(crate) fn build_dataflow<A: Allocate>(
                                     &ok_source,
                                     encoding,
                                     &dataflow.debug_name,
-                                    &envelope,
-                                    &mut src.operators,
+                                    &decode_envelope,
+                                    decode_operators,
                                     fast_forwarded,
                                 );
 
@@ -464,7 +513,24 @@ pub(crate) fn build_dataflow<A: Allocate>(
                                     }
                                 })
                             }
-                            Envelope::Upsert(_) => unreachable!(),
+                            Envelope::Upsert(UpsertKey::Columns {
+                                key_indices,
+                                value_indices,
+                            }) => {
+                                // Deduplicate records by key, then upsert arrange them.
+                                let prepared = prepare_upsert_by_columns(
+                                    &stream,
+                                    key_indices,
+                                    value_indices,
+                                    as_of_frontier.clone(),
+                                );
+                                arrange_from_upsert(
+                                    &prepared,
+                                    &format!("UpsertArrange: {}", src_id.to_string()),
+                                )
+                                .as_collection(|_key, row| row.clone())
+                            }
+                            Envelope::Upsert(UpsertKey::Encoding(_)) => unreachable!(),
                         };
 
                         // Implement source filtering and projection.
@@ -701,10 +767,10 @@ pub(crate) fn build_dataflow<A: Allocate>(
                 // TODO(benesch): errors should stream out through the sink,
                 // if we figure out a protocol for that.
 
-                let sink_shutdown = match sink.connector {
+                let sink_shutdown: Option<Box<dyn Any>> = match sink.connector {
                     SinkConnector::Kafka(c) => {
                         let button = sink::kafka(&collection.inner, sink_id, c, sink.from.1);
-                        Some(button)
+                        Some(Box::new(button.press_on_drop()))
                     }
                     SinkConnector::Tail(c) => {
                         sink::tail(&collection.inner, sink_id, c);
@@ -714,10 +780,18 @@ pub(crate) fn build_dataflow<A: Allocate>(
                         sink::avro_ocf(&collection.inner, sink_id, c, sink.from.1);
                         None
                     }
+                    SinkConnector::File(c) => {
+                        sink::file(&collection.inner, sink_id, c, sink.from.1);
+                        None
+                    }
+                    SinkConnector::Kinesis(c) => {
+                        let button = sink::kinesis(&collection.inner, sink_id, c, sink.from.1);
+                        Some(Box::new(button.press_on_drop()))
+                    }
                 };
 
                 if let Some(sink_token) = sink_shutdown {
-                    needed_sink_tokens.push(sink_token.press_on_drop());
+                    needed_sink_tokens.push(sink_token);
                 }
 
                 let tokens = Rc::new((
@@ -737,18 +811,21 @@ pub(crate) fn build_dataflow<A: Allocate>(
 /// entries with the same `key` and `time`. We are able to reduce this to
 /// at most one record for each pair, by retaining only the record with the
 /// greatest offset: its action summarizes the sequence of many actions that
-/// occur at the same moment and so are not distinguishable.
-fn prepare_upsert_by_max_offset<G>(
-    stream: &Stream<G, (SourceOutput<Vec<u8>, Vec<u8>>, Timestamp)>,
-) -> Stream<G, ((Vec<u8>, (Vec<u8>, Option<i64>)), Timestamp)>
+/// occur at the same moment and so are not distinguishable. Records without
+/// an offset are ordered by their arrival.
+fn prepare_upsert_by_max_offset<G, K, V>(
+    stream: &Stream<G, (SourceOutput<K, V>, Timestamp)>,
+) -> Stream<G, ((K, (V, Option<i64>)), Timestamp)>
 where
     G: Scope<Timestamp = Timestamp>,
+    K: ExchangeData + Hash + Eq,
+    V: ExchangeData + Default,
 {
     stream.unary_frontier(
-        Exchange::new(move |x: &(SourceOutput<Vec<u8>, Vec<u8>>, Timestamp)| x.0.key.hashed()),
+        Exchange::new(move |x: &(SourceOutput<K, V>, Timestamp)| x.0.key.hashed()),
         "UpsertCompaction",
         |_cap, _info| {
-            let mut values = HashMap::<_, HashMap<_, (Vec<u8>, Option<i64>)>>::new();
+            let mut values = HashMap::<_, HashMap<_, (V, Option<i64>)>>::new();
             let mut vector = Vec::new();
 
             move |input, output| {
@@ -770,14 +847,9 @@ where
                             .entry(key)
                             .or_insert_with(Default::default);
 
-                        if let Some(new_offset) = position {
-                            if let Some(offset) = value.1 {
-                                if offset < new_offset {
-                                    *value = (val, position);
-                                }
-                            } else {
-                                *value = (val, position);
-                            }
+                        match (value.1, position) {
+                            (Some(offset), Some(new_offset)) if offset >= new_offset => (),
+                            _ => *value = (val, position),
                         }
                     }
                 });
@@ -798,9 +870,40 @@ where
     )
 }
 
-impl<G> Context<G, RelationExpr, Row, Timestamp>
+/// Produces at most one `(key, value, time)` entry for each `(key, time)`
+/// pair from a stream of decoded rows.
+///
+/// The key of each row is formed from the columns in `key_indices`. A row whose
+/// `value_indices` columns are all null is a tombstone, and is presented as a
+/// `None` value. As the rows carry no offsets, the last row to arrive for each
+/// `(key, time)` pair wins.
+fn prepare_upsert_by_columns<G>(
+    stream: &Stream<G, (Row, Timestamp, Diff)>,
+    key_indices: Vec<usize>,
+    value_indices: Vec<usize>,
+    as_of_frontier: Antichain<Timestamp>,
+) -> Stream<G, (Row, Option<Row>, Timestamp)>
 where
     G: Scope<Timestamp = Timestamp>,
+{
+    let keyed = stream.map({
+        let mut row_packer = repr::RowPacker::new();
+        move |(row, mut time, _diff)| {
+            let datums = row.unpack();
+            let key = row_packer.pack(key_indices.iter().map(|i| datums[*i]));
+            let tombstone = value_indices.iter().all(|i| datums[*i].is_null());
+            let value = if tombstone { None } else { Some(row) };
+            time.advance_by(as_of_frontier.borrow());
+            (SourceOutput::new(key, value, None), time)
+        }
+    });
+    prepare_upsert_by_max_offset(&keyed).map(|((key, (value, _position)), time)| (key, value, time))
+}
+
+impl<G> Context<G, RelationExpr, Row, Timestamp>
+where
+    G: Scope,
+    G::Timestamp: RenderTimestamp,
 {
     /// Ensures the context contains an entry for `relation_expr`.
     ///
@@ -865,6 +968,12 @@ where
                     }
                 }
 
+                RelationExpr::Iterate { .. } => {
+                    let collections =
+                        G::Timestamp::render_iterate(self, relation_expr, scope, worker_index);
+                    self.collections.insert(relation_expr.clone(), collections);
+                }
+
                 RelationExpr::Project { input, outputs } => {
                     self.ensure_rendered(input, scope, worker_index);
                     let outputs = outputs.clone();
@@ -991,7 +1100,7 @@ where
                             }
                             expr::JoinImplementation::DeltaQuery(_orders) => self
                                 .render_delta_join(input, predicates, scope, worker_index, |t| {
-                                    t.saturating_sub(1)
+                                    t.step_back()
                                 }),
                             expr::JoinImplementation::Unimplemented => {
                                 panic!("Attempt to render unimplemented join");
@@ -1038,7 +1147,7 @@ where
                                 &[],
                                 scope,
                                 worker_index,
-                                |t| t.saturating_sub(1),
+                                |t| t.step_back(),
                             );
                             self.collections.insert(relation_expr.clone(), collection);
                         }
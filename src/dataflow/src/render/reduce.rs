@@ -548,7 +548,8 @@ fn accumulable_hierarchical(func: &AggregateFunc) -> (bool, bool) {
         | AggregateFunc::Any
         | AggregateFunc::All
         | AggregateFunc::Dummy => (true, false),
-        AggregateFunc::MaxInt32
+        AggregateFunc::MaxInt16
+        | AggregateFunc::MaxInt32
         | AggregateFunc::MaxInt64
         | AggregateFunc::MaxFloat32
         | AggregateFunc::MaxFloat64
@@ -558,6 +559,7 @@ fn accumulable_hierarchical(func: &AggregateFunc) -> (bool, bool) {
         | AggregateFunc::MaxDate
         | AggregateFunc::MaxTimestamp
         | AggregateFunc::MaxTimestampTz
+        | AggregateFunc::MinInt16
         | AggregateFunc::MinInt32
         | AggregateFunc::MinInt64
         | AggregateFunc::MinFloat32
@@ -568,6 +570,8 @@ fn accumulable_hierarchical(func: &AggregateFunc) -> (bool, bool) {
         | AggregateFunc::MinDate
         | AggregateFunc::MinTimestamp
         | AggregateFunc::MinTimestampTz => (false, true),
-        AggregateFunc::JsonbAgg => (false, false),
+        AggregateFunc::JsonbAgg | AggregateFunc::ArrayAgg | AggregateFunc::Window { .. } => {
+            (false, false)
+        }
     }
 }
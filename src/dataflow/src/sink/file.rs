@@ -0,0 +1,158 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+
+use differential_dataflow::hashable::Hashable;
+use log::error;
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::Operator;
+use timely::dataflow::{Scope, Stream};
+
+use dataflow_types::{Diff, FileSinkConnector, FileSinkFormat, Timestamp};
+use expr::GlobalId;
+use interchange::{csv, json};
+use repr::{RelationDesc, Row};
+
+enum LineEncoder {
+    Csv(csv::Encoder),
+    Json(json::Encoder),
+}
+
+impl LineEncoder {
+    fn encode_update(&self, row: &Row, time: Timestamp, diff: Diff) -> Vec<u8> {
+        match self {
+            LineEncoder::Csv(encoder) => encoder.encode_update(row, time, diff as i64),
+            LineEncoder::Json(encoder) => {
+                let mut line = encoder.encode_update(row, time, diff as i64);
+                line.push(b'\n');
+                line
+            }
+        }
+    }
+}
+
+pub fn file<G>(
+    stream: &Stream<G, (Row, Timestamp, Diff)>,
+    id: GlobalId,
+    connector: FileSinkConnector,
+    desc: RelationDesc,
+) where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let (encoder, header_row) = match connector.format {
+        FileSinkFormat::Csv {
+            header_row,
+            delimiter,
+        } => (
+            LineEncoder::Csv(csv::Encoder::new(desc, delimiter)),
+            header_row,
+        ),
+        FileSinkFormat::Json => (LineEncoder::Json(json::Encoder::new(desc)), false),
+    };
+    let sink_hash = id.hashed();
+
+    let res = OpenOptions::new().append(true).open(&connector.path);
+    let (mut writer, mut header) = match res {
+        Ok(f) => {
+            // The header row is only written to files that do not yet contain
+            // any updates, so that restarts do not repeat it.
+            let header = match &encoder {
+                LineEncoder::Csv(encoder)
+                    if header_row && f.metadata().map(|m| m.len() == 0).unwrap_or(false) =>
+                {
+                    Some(encoder.encode_header())
+                }
+                _ => None,
+            };
+            (Some(BufWriter::new(f)), header)
+        }
+        Err(e) => {
+            error!("creating file sink writer failed: {}", e);
+            (None, None)
+        }
+    };
+
+    // In consolidated snapshot mode, updates are buffered until their
+    // timestamp is closed, and then the full contents of the collection as of
+    // that timestamp are written out.
+    let mut contents: BTreeMap<Row, Diff> = BTreeMap::new();
+    let mut pending: BTreeMap<Timestamp, Vec<(Row, Diff)>> = BTreeMap::new();
+
+    stream.sink(
+        Exchange::new(move |_| sink_hash),
+        &format!("file-{}", id),
+        move |input| {
+            let writer = match writer.as_mut() {
+                Some(writer) => writer,
+                None => return,
+            };
+
+            let should_emit = |time: &Timestamp| {
+                if connector.strict {
+                    connector.frontier.less_than(time)
+                } else {
+                    connector.frontier.less_equal(time)
+                }
+            };
+            let mut lines = vec![];
+
+            input.for_each(|_, rows| {
+                for (row, time, diff) in rows.iter() {
+                    if connector.consolidated_snapshots {
+                        // Updates that are not emitted must still be applied
+                        // to the contents of the collection.
+                        pending.entry(*time).or_default().push((row.clone(), *diff));
+                    } else if should_emit(time) {
+                        lines.push(encoder.encode_update(row, *time, *diff));
+                    }
+                }
+            });
+
+            if connector.consolidated_snapshots {
+                let frontier = input.frontier();
+                let complete: Vec<_> = pending
+                    .keys()
+                    .filter(|time| !frontier.less_equal(time))
+                    .copied()
+                    .collect();
+                for time in complete {
+                    for (row, diff) in pending.remove(&time).expect("pending time exists") {
+                        *contents.entry(row).or_insert(0) += diff;
+                    }
+                    contents.retain(|_, count| *count != 0);
+                    if should_emit(&time) {
+                        for (row, count) in &contents {
+                            lines.push(encoder.encode_update(row, time, *count));
+                        }
+                    }
+                }
+            }
+
+            if lines.is_empty() {
+                return;
+            }
+            if let Some(header) = header.take() {
+                if let Err(e) = writer.write_all(&header) {
+                    error!("writing header to file sink failed: {}", e);
+                }
+            }
+            for line in lines {
+                if let Err(e) = writer.write_all(&line) {
+                    error!("appending to file sink failed: {}", e);
+                }
+            }
+            if let Err(e) = writer.flush() {
+                error!("flushing bytes to file sink failed: {}", e);
+            }
+        },
+    )
+}
@@ -8,11 +8,14 @@
 // by the Apache License, Version 2.0.
 
 use std::cell::RefCell;
+use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, VecDeque};
 use std::iter::Iterator;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
 use std::sync::Arc;
+use std::thread;
 use std::time::Duration;
 
 use differential_dataflow::hashable::Hashable;
@@ -27,15 +30,18 @@ use rdkafka::client::ClientContext;
 use rdkafka::config::ClientConfig;
 use rdkafka::error::{KafkaError, RDKafkaError};
 use rdkafka::message::Message;
-use rdkafka::producer::{BaseRecord, DeliveryResult, ProducerContext, ThreadedProducer};
+use rdkafka::producer::{BaseRecord, DeliveryResult, Producer, ProducerContext, ThreadedProducer};
 use timely::dataflow::channels::pact::Exchange;
 use timely::dataflow::operators::generic::FrontieredInputHandle;
 use timely::dataflow::{Scope, Stream};
 use timely::progress::frontier::MutableAntichain;
+use timely::scheduling::SyncActivator;
 
-use dataflow_types::{Diff, KafkaSinkConnector, Timestamp};
+use dataflow_types::{Diff, KafkaSinkConnector, KafkaSinkFormat, SinkEnvelope, Timestamp};
 use expr::GlobalId;
-use interchange::avro::{self, DiffPair, Encoder};
+use interchange::avro::{self, DiffPair};
+use interchange::json;
+use interchange::protobuf;
 use repr::{RelationDesc, Row};
 
 use super::util::sink_reschedule;
@@ -137,6 +143,70 @@ pub enum SinkConsistencyState {
     End,
 }
 
+/// The maximum number of timestamps that an exactly-once sink writes in a
+/// single transaction.
+const MAX_TRANSACTION_TIMESTAMPS: usize = 1024;
+
+/// The state of the Kafka transaction of an exactly-once sink.
+///
+/// Exactly-once sinks write the BEGIN consistency message, the data messages,
+/// and the END consistency message for each timestamp in a transaction, so
+/// that consumers with `read_committed` isolation see either all of a
+/// timestamp's updates or none of them. All of the complete timestamps that
+/// are queued when a transaction is open are written in that transaction, up
+/// to `MAX_TRANSACTION_TIMESTAMPS`.
+///
+/// Initializing and committing transactions block until the transaction
+/// coordinator responds, so they happen on a separate thread, which
+/// activates the sink operator once they are done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransactionState {
+    /// Transactions are being initialized for the producer.
+    Initializing,
+    /// No transaction is open.
+    Idle,
+    /// A transaction is open, and the updates at `time` are being written
+    /// to it. `timestamps` is the number of timestamps written to the
+    /// transaction, including `time`.
+    Open { time: Timestamp, timestamps: usize },
+    /// The END message for the transaction's last timestamp has been queued,
+    /// and the transaction is committed once it has been sent.
+    Ending(Timestamp),
+    /// The transaction is being committed.
+    Committing(Timestamp),
+}
+
+/// Blocking logic to initialize and commit the transactions of an
+/// exactly-once sink's producer, intended for its own thread.
+///
+/// Transactions are initialized immediately, and a transaction is committed
+/// each time a request is received on `commits`. The result of each call is
+/// sent on `results`, after which the sink operator is activated.
+fn transaction_task(
+    producer: Arc<ThreadedProducer<SinkProducerContext>>,
+    commits: Receiver<()>,
+    results: Sender<Result<(), KafkaError>>,
+    activator: SyncActivator,
+) {
+    let send = |result| {
+        if results.send(result).is_err() {
+            return false;
+        }
+        // The sink operator may have been dropped in the meantime, in which
+        // case there is nothing left to activate.
+        let _ = activator.activate();
+        true
+    };
+    if !send(producer.init_transactions(Duration::from_secs(30))) {
+        return;
+    }
+    for () in commits {
+        if !send(producer.commit_transaction(Duration::from_secs(60))) {
+            return;
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SinkConsistencyInfo {
     // Name of the consistency topic
@@ -195,13 +265,177 @@ impl SinkConsistencyInfo {
     }
 }
 
+/// Encodes updates in the format of a Kafka sink.
+enum SinkEncoder {
+    Avro {
+        encoder: avro::Encoder,
+        schema_id: i32,
+    },
+    AvroUpsert {
+        encoder: avro::UpsertEncoder,
+        key_schema_id: i32,
+        value_schema_id: i32,
+    },
+    Protobuf(protobuf::Encoder),
+    Json(json::Encoder),
+}
+
+impl SinkEncoder {
+    fn new(
+        desc: RelationDesc,
+        format: &KafkaSinkFormat,
+        envelope: &SinkEnvelope,
+        include_transaction: bool,
+    ) -> Self {
+        match format {
+            KafkaSinkFormat::Avro {
+                schema_id,
+                key_schema_id,
+            } => match envelope {
+                SinkEnvelope::Debezium => SinkEncoder::Avro {
+                    encoder: avro::Encoder::new(desc, include_transaction),
+                    schema_id: *schema_id,
+                },
+                SinkEnvelope::Upsert { key_indices } => SinkEncoder::AvroUpsert {
+                    encoder: avro::UpsertEncoder::new(desc, key_indices.clone()),
+                    key_schema_id: key_schema_id.expect("upsert sinks publish a key schema"),
+                    value_schema_id: *schema_id,
+                },
+            },
+            KafkaSinkFormat::Protobuf(encoding) => {
+                let descriptors = protobuf::decode_descriptors(&encoding.descriptors)
+                    .expect("protobuf sink descriptors validated during planning");
+                SinkEncoder::Protobuf(
+                    protobuf::Encoder::new(&desc, &encoding.message_name, &descriptors)
+                        .expect("protobuf sink message validated during planning"),
+                )
+            }
+            KafkaSinkFormat::Json => SinkEncoder::Json(json::Encoder::new(desc)),
+        }
+    }
+
+    fn encode(
+        &self,
+        diff_pair: DiffPair<&Row>,
+        transaction_id: Option<String>,
+    ) -> Result<Vec<u8>, failure::Error> {
+        match self {
+            SinkEncoder::Avro { encoder, schema_id } => {
+                Ok(encoder.encode_unchecked(*schema_id, diff_pair, transaction_id))
+            }
+            SinkEncoder::AvroUpsert { .. } => {
+                unreachable!("upsert encoders do not encode diff pairs")
+            }
+            SinkEncoder::Protobuf(encoder) => encoder.encode(diff_pair),
+            SinkEncoder::Json(encoder) => Ok(encoder.encode_diff_pair(diff_pair)),
+        }
+    }
+
+    /// Encodes the key and value of the message for an update to `row`.
+    ///
+    /// Debezium-style messages have no key. Upsert messages are keyed by the
+    /// key columns of `row`, and have no value if `diff` is negative, which
+    /// deletes the key. Upsert messages do not include the transaction ID.
+    fn encode_message(
+        &self,
+        envelope: &SinkEnvelope,
+        row: &Row,
+        diff: Diff,
+        transaction_id: Option<String>,
+    ) -> Result<(Option<Vec<u8>>, Option<Vec<u8>>), failure::Error> {
+        match (envelope, self) {
+            (SinkEnvelope::Debezium, _) => {
+                let diff_pair = if diff < 0 {
+                    DiffPair {
+                        before: Some(row),
+                        after: None,
+                    }
+                } else {
+                    DiffPair {
+                        before: None,
+                        after: Some(row),
+                    }
+                };
+                Ok((None, Some(self.encode(diff_pair, transaction_id)?)))
+            }
+            (SinkEnvelope::Upsert { key_indices }, SinkEncoder::Json(encoder)) => {
+                let key = encoder.encode_key(row, key_indices);
+                let value = if diff > 0 {
+                    Some(encoder.encode_row(row))
+                } else {
+                    None
+                };
+                Ok((Some(key), value))
+            }
+            (
+                SinkEnvelope::Upsert { .. },
+                SinkEncoder::AvroUpsert {
+                    encoder,
+                    key_schema_id,
+                    value_schema_id,
+                },
+            ) => {
+                let key = encoder.encode_key_unchecked(*key_schema_id, row);
+                let value = if diff > 0 {
+                    Some(encoder.encode_value_unchecked(*value_schema_id, row))
+                } else {
+                    None
+                };
+                Ok((Some(key), value))
+            }
+            (SinkEnvelope::Upsert { .. }, _) => {
+                unreachable!("upsert envelope only supported for Avro and JSON sinks")
+            }
+        }
+    }
+}
+
+/// Returns the number of messages that a sink with the given envelope sends
+/// for an update with multiplicity `diff`.
+fn message_count(envelope: &SinkEnvelope, diff: Diff) -> Diff {
+    match envelope {
+        // For diffs other than +/- 1, we send repeated copies of the
+        // record [diff] times. Since the format and envelope capture the
+        // "polarity" of the update, we need to remember how many times to
+        // send the data.
+        SinkEnvelope::Debezium => diff.abs(),
+        // Each upsert message replaces the previous value for its key, so it
+        // is only ever sent once.
+        SinkEnvelope::Upsert { .. } => 1,
+    }
+}
+
+/// Combines the updates at a single, complete timestamp into at most one
+/// update per key: an insertion of the new row for keys that were inserted or
+/// updated, or a retraction of the old row for keys that were deleted.
+fn upsert_updates(updates: Vec<(Row, Diff)>, key_indices: &[usize]) -> Vec<(Row, Diff)> {
+    let mut by_key = BTreeMap::new();
+    for (row, diff) in updates {
+        let key = {
+            let datums = row.unpack();
+            Row::pack(key_indices.iter().map(|i| datums[*i]))
+        };
+        match by_key.entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert((row, diff.signum()));
+            }
+            Entry::Occupied(mut entry) => {
+                if diff > 0 {
+                    entry.insert((row, 1));
+                }
+            }
+        }
+    }
+    by_key.into_iter().map(|(_key, update)| update).collect()
+}
+
 // TODO@jldlaughlin: What guarantees does this sink support? #1728
 pub fn kafka<G>(
     stream: &Stream<G, (Row, Timestamp, Diff)>,
     id: GlobalId,
     connector: KafkaSinkConnector,
     desc: RelationDesc,
-) -> ShutdownButton<ThreadedProducer<SinkProducerContext>>
+) -> ShutdownButton<Arc<ThreadedProducer<SinkProducerContext>>>
 where
     G: Scope<Timestamp = Timestamp>,
 {
@@ -232,6 +466,13 @@ where
     // TODO(rkhaitan): experiment with different settings for this value to see
     // if it makes a big difference
     config.set("queue.buffering.max.ms", &format!("{}", 10));
+
+    if connector.exactly_once {
+        // Each instance of the sink writes to its own topic, so the topic's
+        // name is a unique transactional ID.
+        config.set("transactional.id", &connector.topic);
+    }
+
     let sink_metrics = SinkMetrics::new(
         &connector.topic,
         &id.to_string(),
@@ -239,15 +480,27 @@ where
     );
 
     let shutdown = Arc::new(AtomicBool::new(false));
-    let producer = Rc::new(RefCell::new(Some(
+    let producer = Arc::new(
         config
             .create_with_context::<_, ThreadedProducer<_>>(SinkProducerContext::new(
                 &sink_metrics,
                 &shutdown,
             ))
             .expect("creating kafka producer for kafka sinks failed"),
-    )));
+    );
+    let transaction_producer = producer.clone();
+    let producer = Rc::new(RefCell::new(Some(producer)));
     let mut queue: VecDeque<(Row, Timestamp, Diff)> = VecDeque::new();
+    // Updates for upsert and exactly-once sinks are held back until their
+    // timestamp is complete, so that the updates to each key at that
+    // timestamp can be combined into a single message, and so that each
+    // timestamp's updates can be written in a single transaction.
+    let mut pending: BTreeMap<Timestamp, Vec<(Row, Diff)>> = BTreeMap::new();
+    let mut transaction = if connector.exactly_once {
+        TransactionState::Initializing
+    } else {
+        TransactionState::Idle
+    };
     let mut vector = Vec::new();
     let mut encoded_buffer = None;
 
@@ -260,7 +513,12 @@ where
         None
     };
 
-    let encoder = Encoder::new(desc, consistency.is_some());
+    let encoder = SinkEncoder::new(
+        desc,
+        &connector.format,
+        &connector.envelope,
+        consistency.is_some(),
+    );
     let name = format!("kafka-{}", id);
     sink_reschedule(
         &stream,
@@ -274,6 +532,18 @@ where
                 stream.scope().activator_for(&info.address[..]),
             );
 
+            let (commit_requests, transaction_results) = if connector.exactly_once {
+                let (commit_tx, commit_rx) = mpsc::channel();
+                let (result_tx, result_rx) = mpsc::channel();
+                let activator = stream.scope().sync_activator_for(&info.address[..]);
+                thread::spawn(move || {
+                    transaction_task(transaction_producer, commit_rx, result_tx, activator)
+                });
+                (Some(commit_tx), Some(result_rx))
+            } else {
+                (None, None)
+            };
+
             let ret = move |input: &mut FrontieredInputHandle<_, (Row, Timestamp, Diff), _>| {
                 if shutdown.load(Ordering::SeqCst) {
                     error!(
@@ -309,7 +579,20 @@ where
                             continue;
                         }
 
-                        queue.push_back((row, time, diff));
+                        let is_upsert = match connector.envelope {
+                            SinkEnvelope::Upsert { .. } => true,
+                            SinkEnvelope::Debezium => false,
+                        };
+                        if is_upsert || connector.exactly_once {
+                            pending.entry(time).or_default().push((row, diff));
+                        } else {
+                            queue.push_back((row, time, diff));
+                        }
+                        if connector.exactly_once {
+                            // Consistency messages for exactly-once sinks are
+                            // only queued once the timestamp is complete.
+                            continue;
+                        }
                         if let Some(consistency) = &mut consistency {
                             // Note that since a single differential message
                             // turns into |diff| messages we need to increment
@@ -331,7 +614,117 @@ where
                     }
                 });
 
-                if let Some(consistency) = &mut consistency {
+                if !pending.is_empty() {
+                    let frontier = input.frontier();
+                    let complete: Vec<_> = pending
+                        .keys()
+                        .filter(|time| !frontier.less_equal(time))
+                        .copied()
+                        .collect();
+                    for time in complete {
+                        let mut updates = pending.remove(&time).expect("pending time exists");
+                        if let SinkEnvelope::Upsert { key_indices } = &connector.envelope {
+                            updates = upsert_updates(updates, key_indices);
+                        }
+                        updates.retain(|(_, diff)| *diff != 0);
+                        if let Some(consistency) = &mut consistency {
+                            if connector.exactly_once && !updates.is_empty() {
+                                let count: Diff = updates
+                                    .iter()
+                                    .map(|(_, diff)| message_count(&connector.envelope, *diff))
+                                    .sum();
+                                consistency.update_timestamp_count(time, count as i64);
+                            }
+                        }
+                        for (row, diff) in updates {
+                            queue.push_back((row, time, diff));
+                        }
+                    }
+                }
+
+                if let Some(transaction_results) = &transaction_results {
+                    if let TransactionState::Initializing | TransactionState::Committing(_) =
+                        transaction
+                    {
+                        match transaction_results.try_recv() {
+                            Ok(Ok(())) => transaction = TransactionState::Idle,
+                            Ok(Err(e)) => {
+                                match transaction {
+                                    TransactionState::Committing(time) => error!(
+                                        "unable to commit transaction for timestamp {} in {}: {}",
+                                        time, name, e
+                                    ),
+                                    _ => error!(
+                                        "unable to initialize transactions in {}: {}",
+                                        name, e
+                                    ),
+                                }
+                                shutdown.store(true, Ordering::SeqCst);
+                                return false;
+                            }
+                            Err(TryRecvError::Empty) => (),
+                            Err(TryRecvError::Disconnected) => {
+                                error!("transaction thread for {} exited unexpectedly", name);
+                                shutdown.store(true, Ordering::SeqCst);
+                                return false;
+                            }
+                        }
+                    }
+                }
+
+                if connector.exactly_once {
+                    let consistency = consistency
+                        .as_mut()
+                        .expect("exactly-once sinks have a consistency topic");
+                    if transaction == TransactionState::Idle && consistency.queue.is_empty() {
+                        if let Some((_, time, _)) = queue.front() {
+                            if let Err(e) = producer.begin_transaction() {
+                                error!("unable to begin transaction in {}: {}", name, e);
+                                shutdown.store(true, Ordering::SeqCst);
+                                return false;
+                            }
+                            consistency
+                                .queue
+                                .push_back((SinkConsistencyState::Begin, *time, None));
+                            transaction = TransactionState::Open {
+                                time: *time,
+                                timestamps: 1,
+                            };
+                        }
+                    }
+                    if let TransactionState::Open { time, timestamps } = transaction {
+                        let done = encoded_buffer.is_none()
+                            && queue.front().map(|(_, t, _)| *t != time).unwrap_or(true);
+                        if done {
+                            let count = consistency
+                                .timestamp_counts
+                                .remove(&time)
+                                .expect("open transaction has a message count");
+                            consistency.queue.push_back((
+                                SinkConsistencyState::End,
+                                time,
+                                Some(count),
+                            ));
+                            transaction = match queue.front() {
+                                // Write the next complete timestamp in the
+                                // same transaction, rather than committing a
+                                // transaction per timestamp.
+                                Some((_, next, _)) if timestamps < MAX_TRANSACTION_TIMESTAMPS => {
+                                    consistency.queue.push_back((
+                                        SinkConsistencyState::Begin,
+                                        *next,
+                                        None,
+                                    ));
+                                    TransactionState::Open {
+                                        time: *next,
+                                        timestamps: timestamps + 1,
+                                    }
+                                }
+                                _ => TransactionState::Ending(time),
+                            };
+                        }
+                    }
+                } else if let Some(consistency) = &mut consistency {
                     // Find the timestamps that are now complete (meaning all
                     // timestamps t !<= input_frontier. For each closed timestamp
                     // send a END message in the consistency topic
@@ -343,7 +736,9 @@ where
                                 .queue
                                 .push_back((SinkConsistencyState::End, *k, Some(*v)));
                         });
+                }
 
+                if let Some(consistency) = &mut consistency {
                     // Send a bounded number of queued consistency messages to
                     // the consistency topic
                     for _ in 0..connector.fuel {
@@ -391,14 +786,53 @@ where
                     }
                 }
 
+                if let (TransactionState::Ending(time), Some(consistency), Some(commit_requests)) =
+                    (transaction, &consistency, &commit_requests)
+                {
+                    if consistency.queue.is_empty() {
+                        // Committing blocks until all of the transaction's
+                        // messages have been delivered, so it happens on the
+                        // transaction thread.
+                        if commit_requests.send(()).is_err() {
+                            error!("transaction thread for {} exited unexpectedly", name);
+                            shutdown.store(true, Ordering::SeqCst);
+                            return false;
+                        }
+                        transaction = TransactionState::Committing(time);
+                    }
+                }
+
+                // Exactly-once sinks only send the data messages for the
+                // timestamp of their open transaction, once its BEGIN message
+                // has been sent.
+                let can_send_data = match (transaction, &consistency) {
+                    _ if !connector.exactly_once => true,
+                    (TransactionState::Open { .. }, Some(consistency)) => {
+                        consistency.queue.is_empty()
+                    }
+                    _ => false,
+                };
+                let data_fuel = if can_send_data { connector.fuel } else { 0 };
+
                 // Send a bounded number of records to Kafka from the queue. This
                 // loop has explicitly been designed so that each iteration sends
                 // at most one record to Kafka
-                for _ in 0..connector.fuel {
-                    let (encoded, count) = if let Some((encoded, count)) = encoded_buffer.take() {
+                for _ in 0..data_fuel {
+                    let (key, value, count) = if let Some((key, value, count)) =
+                        encoded_buffer.take()
+                    {
                         // We still need to send more copies of this record.
-                        (encoded, count)
+                        (key, value, count)
                     } else if let Some((row, time, diff)) = queue.pop_front() {
+                        if let TransactionState::Open { time: t, .. } = transaction {
+                            if time != t {
+                                // The next update belongs to the next
+                                // transaction.
+                                queue.push_front((row, time, diff));
+                                break;
+                            }
+                        }
+
                         // Convert a previously queued (Row, Diff) to a Avro diff
                         // envelope record
                         if diff == 0 {
@@ -411,30 +845,30 @@ where
                             None => None,
                         };
 
-                        let diff_pair = if diff < 0 {
-                            DiffPair {
-                                before: Some(&row),
-                                after: None,
-                            }
-                        } else {
-                            DiffPair {
-                                before: None,
-                                after: Some(&row),
-                            }
-                        };
-
-                        let buf = encoder.encode_unchecked(connector.schema_id, diff_pair, time);
-                        // For diffs other than +/- 1, we send repeated copies of the
-                        // Avro record [diff] times. Since the format and envelope
-                        // capture the "polarity" of the update, we need to remember
-                        // how many times to send the data.
-                        (buf, diff.abs())
+                        let (key, value) =
+                            match encoder.encode_message(&connector.envelope, &row, diff, time) {
+                                Ok(message) => message,
+                                Err(e) => {
+                                    // We've received an error that will recur if
+                                    // we try to send this record again.
+                                    error!("unable to encode record in {}: {}", name, e);
+                                    shutdown.store(true, Ordering::SeqCst);
+                                    return false;
+                                }
+                            };
+                        (key, value, message_count(&connector.envelope, diff))
                     } else {
                         // Nothing left for us to do
                         break;
                     };
 
-                    let record = BaseRecord::<&Vec<u8>, _>::to(&connector.topic).payload(&encoded);
+                    let mut record = BaseRecord::<Vec<u8>, Vec<u8>>::to(&connector.topic);
+                    if let Some(key) = &key {
+                        record = record.key(key);
+                    }
+                    if let Some(value) = &value {
+                        record = record.payload(value);
+                    }
                     if let Err((e, _)) = producer.send(record) {
                         sink_metrics.message_send_errors_counter.inc();
                         error!("unable to produce in {}: {}", name, e);
@@ -446,7 +880,7 @@ where
                             // https://github.com/edenhill/librdkafka/blob/master/examples/producer.c#L188-L208
                             // only retries on QueueFull so we will keep that
                             // convention here.
-                            encoded_buffer = Some((encoded, count));
+                            encoded_buffer = Some((key, value, count));
                             activator.activate_after(Duration::from_secs(60));
                             return true;
                         } else {
@@ -461,7 +895,7 @@ where
                     // Cache the Avro encoded data if we need to send again and
                     // remember how many more times we need to send it
                     if count > 1 {
-                        encoded_buffer = Some((encoded, count - 1));
+                        encoded_buffer = Some((key, value, count - 1));
                     }
                 }
 
@@ -469,7 +903,17 @@ where
 
                 sink_metrics.rows_queued.set(queue.len() as u64);
                 sink_metrics.messages_in_flight.set(in_flight as u64);
-                if encoded_buffer.is_some() || !queue.is_empty() {
+                if let TransactionState::Initializing | TransactionState::Committing(_) =
+                    transaction
+                {
+                    // The transaction thread activates this operator once it
+                    // is done.
+                    return true;
+                }
+                if encoded_buffer.is_some()
+                    || !queue.is_empty()
+                    || transaction != TransactionState::Idle
+                {
                     // We need timely to reschedule this operator as we have pending
                     // items that we need to send to Kafka
                     activator.activate();
@@ -0,0 +1,357 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::{HashSet, VecDeque};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use differential_dataflow::hashable::Hashable;
+use differential_dataflow::operators::arrange::ShutdownButton;
+use futures::executor::block_on;
+use lazy_static::lazy_static;
+use log::error;
+use prometheus::{
+    register_int_counter_vec, register_uint_gauge_vec, IntCounter, IntCounterVec, UIntGauge,
+    UIntGaugeVec,
+};
+use rusoto_core::RusotoError;
+use rusoto_kinesis::{
+    Kinesis, KinesisClient, PutRecordsError, PutRecordsInput, PutRecordsRequestEntry,
+};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::FrontieredInputHandle;
+use timely::dataflow::{Scope, Stream};
+
+use dataflow_types::{Diff, KinesisSinkConnector, KinesisSinkFormat, Timestamp};
+use expr::GlobalId;
+use interchange::avro::DiffPair;
+use interchange::json;
+use interchange::protobuf;
+use repr::{RelationDesc, Row};
+
+use super::util::sink_reschedule;
+
+/// The maximum number of records that Kinesis accepts in a single PutRecords
+/// request.
+const MAX_RECORDS_PER_REQUEST: usize = 500;
+
+/// The delay before the first retry of records that Kinesis failed to write.
+/// The delay doubles with each consecutive failure, up to `MAX_BACKOFF`.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Per-Kinesis sink metrics.
+struct SinkMetrics {
+    records_sent_counter: IntCounter,
+    record_errors_counter: IntCounter,
+    request_errors_counter: IntCounter,
+    rows_queued: UIntGauge,
+}
+
+impl SinkMetrics {
+    fn new(stream_name: &str, sink_id: &str, worker_id: &str) -> SinkMetrics {
+        lazy_static! {
+            static ref RECORDS_SENT_COUNTER: IntCounterVec = register_int_counter_vec!(
+                "mz_kinesis_sink_records_sent_total",
+                "The number of records the Kinesis sink successfully wrote to the stream",
+                &["stream_name", "sink_id", "worker_id"]
+            )
+            .unwrap();
+            static ref RECORD_ERRORS_COUNTER: IntCounterVec = register_int_counter_vec!(
+                "mz_kinesis_sink_record_errors_total",
+                "The number of records that Kinesis failed to write, each of which is retried",
+                &["stream_name", "sink_id", "worker_id"]
+            )
+            .unwrap();
+            static ref REQUEST_ERRORS_COUNTER: IntCounterVec = register_int_counter_vec!(
+                "mz_kinesis_sink_request_errors_total",
+                "The number of PutRecords requests from the Kinesis sink that failed entirely",
+                &["stream_name", "sink_id", "worker_id"]
+            )
+            .unwrap();
+            static ref ROWS_QUEUED: UIntGaugeVec = register_uint_gauge_vec!(
+                "mz_kinesis_sink_rows_queued",
+                "The current number of rows queued by the Kinesis sink operator",
+                &["stream_name", "sink_id", "worker_id"]
+            )
+            .unwrap();
+        }
+        let labels = &[stream_name, sink_id, worker_id];
+        SinkMetrics {
+            records_sent_counter: RECORDS_SENT_COUNTER.with_label_values(labels),
+            record_errors_counter: RECORD_ERRORS_COUNTER.with_label_values(labels),
+            request_errors_counter: REQUEST_ERRORS_COUNTER.with_label_values(labels),
+            rows_queued: ROWS_QUEUED.with_label_values(labels),
+        }
+    }
+}
+
+/// Encodes updates in the format of a Kinesis sink.
+enum SinkEncoder {
+    Protobuf(protobuf::Encoder),
+    Json(json::Encoder),
+}
+
+impl SinkEncoder {
+    fn new(desc: RelationDesc, format: &KinesisSinkFormat) -> Self {
+        match format {
+            KinesisSinkFormat::Protobuf(encoding) => {
+                let descriptors = protobuf::decode_descriptors(&encoding.descriptors)
+                    .expect("protobuf sink descriptors validated during planning");
+                SinkEncoder::Protobuf(
+                    protobuf::Encoder::new(&desc, &encoding.message_name, &descriptors)
+                        .expect("protobuf sink message validated during planning"),
+                )
+            }
+            KinesisSinkFormat::Json => SinkEncoder::Json(json::Encoder::new(desc)),
+        }
+    }
+
+    fn encode(&self, row: &Row, diff: Diff) -> Result<Vec<u8>, failure::Error> {
+        let diff_pair = if diff < 0 {
+            DiffPair {
+                before: Some(row),
+                after: None,
+            }
+        } else {
+            DiffPair {
+                before: None,
+                after: Some(row),
+            }
+        };
+        match self {
+            SinkEncoder::Protobuf(encoder) => encoder.encode(diff_pair),
+            SinkEncoder::Json(encoder) => Ok(encoder.encode_diff_pair(diff_pair)),
+        }
+    }
+}
+
+/// Derives the partition key of the record for `row` from the columns at
+/// `indices`.
+///
+/// Kinesis limits partition keys to 256 characters, so the columns are hashed
+/// rather than encoded directly.
+fn partition_key(row: &Row, indices: &[usize]) -> String {
+    let datums = row.unpack();
+    let key = Row::pack(indices.iter().map(|i| datums[*i]));
+    format!("{:016x}", key.hashed())
+}
+
+pub fn kinesis<G>(
+    stream: &Stream<G, (Row, Timestamp, Diff)>,
+    id: GlobalId,
+    connector: KinesisSinkConnector,
+    desc: RelationDesc,
+) -> ShutdownButton<KinesisClient>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    // As with Kafka sinks, exactly one worker writes all the data to the
+    // stream.
+    let sink_hash = id.hashed();
+
+    let sink_metrics = SinkMetrics::new(
+        &connector.stream_name,
+        &id.to_string(),
+        &stream.scope().index().to_string(),
+    );
+
+    let client = match block_on(aws_util::kinesis::kinesis_client(
+        connector.region.clone(),
+        connector.access_key_id.clone(),
+        connector.secret_access_key.clone(),
+        connector.token.clone(),
+    )) {
+        Ok(client) => Some(client),
+        Err(e) => {
+            error!("creating Kinesis client for sink {} failed: {:#}", id, e);
+            None
+        }
+    };
+    let client = Rc::new(RefCell::new(client));
+
+    let encoder = SinkEncoder::new(desc, &connector.format);
+    let mut queue: VecDeque<(Row, Diff)> = VecDeque::new();
+    // Encoded records that have not yet been written to the stream, including
+    // records that Kinesis failed to write and that are awaiting a retry.
+    let mut records: VecDeque<PutRecordsRequestEntry> = VecDeque::new();
+    let mut backoff = INITIAL_BACKOFF;
+    let mut retry_at: Option<Instant> = None;
+    let mut vector = Vec::new();
+
+    let name = format!("kinesis-{}", id);
+    sink_reschedule(
+        &stream,
+        Exchange::new(move |_| sink_hash),
+        name.clone(),
+        |info| {
+            let activator = stream.scope().activator_for(&info.address[..]);
+            let shutdown_button = ShutdownButton::new(
+                client.clone(),
+                stream.scope().activator_for(&info.address[..]),
+            );
+
+            let ret = move |input: &mut FrontieredInputHandle<_, (Row, Timestamp, Diff), _>| {
+                let kinesis_client = match &*client.borrow() {
+                    Some(kinesis_client) => kinesis_client.clone(),
+                    None => return false,
+                };
+
+                input.for_each(|_, rows| {
+                    rows.swap(&mut vector);
+
+                    for (row, time, diff) in vector.drain(..) {
+                        let should_emit = if connector.strict {
+                            connector.frontier.less_than(&time)
+                        } else {
+                            connector.frontier.less_equal(&time)
+                        };
+
+                        if should_emit && diff != 0 {
+                            queue.push_back((row, diff));
+                        }
+                    }
+                });
+
+                if let Some(at) = retry_at {
+                    let now = Instant::now();
+                    if now < at {
+                        sink_metrics.rows_queued.set(queue.len() as u64);
+                        activator.activate_after(at - now);
+                        return true;
+                    }
+                    retry_at = None;
+                }
+
+                // Encode enough queued rows to fill a single request. As with
+                // Kafka sinks, a row with a diff other than +/- 1 is written as
+                // |diff| copies of the same record.
+                while records.len() < MAX_RECORDS_PER_REQUEST {
+                    let (row, diff) = match queue.pop_front() {
+                        Some(update) => update,
+                        None => break,
+                    };
+                    let data = match encoder.encode(&row, diff) {
+                        Ok(data) => Bytes::from(data),
+                        Err(e) => {
+                            // We've received an error that will recur if we
+                            // try to send this record again.
+                            error!("unable to encode record in {}: {}", name, e);
+                            *client.borrow_mut() = None;
+                            return false;
+                        }
+                    };
+                    let key = partition_key(&row, &connector.partition_key_indices);
+                    for _ in 0..diff.abs() {
+                        records.push_back(PutRecordsRequestEntry {
+                            data: data.clone(),
+                            explicit_hash_key: None,
+                            partition_key: key.clone(),
+                        });
+                    }
+                }
+
+                if !records.is_empty() {
+                    // Kinesis does not preserve the order of records written
+                    // in the same request, so each request includes at most
+                    // one record per partition key. Records for keys that
+                    // already appear in the request wait, in order, for a
+                    // later request.
+                    let mut keys = HashSet::new();
+                    let mut batch = Vec::new();
+                    let mut deferred = VecDeque::new();
+                    while batch.len() < MAX_RECORDS_PER_REQUEST {
+                        let record = match records.pop_front() {
+                            Some(record) => record,
+                            None => break,
+                        };
+                        if keys.insert(record.partition_key.clone()) {
+                            batch.push(record);
+                        } else {
+                            deferred.push_back(record);
+                        }
+                    }
+                    deferred.append(&mut records);
+                    records = deferred;
+                    let res = block_on(kinesis_client.put_records(PutRecordsInput {
+                        records: batch.clone(),
+                        stream_name: connector.stream_name.clone(),
+                    }));
+                    let failed = match res {
+                        Ok(output) => {
+                            // Kinesis reports failures, like exceeding a
+                            // shard's throughput limit, for each record.
+                            let sent = batch.len();
+                            let failed: Vec<_> = batch
+                                .into_iter()
+                                .zip(output.records)
+                                .filter(|(_, result)| result.error_code.is_some())
+                                .map(|(record, _)| record)
+                                .collect();
+                            sink_metrics
+                                .records_sent_counter
+                                .inc_by((sent - failed.len()) as i64);
+                            sink_metrics
+                                .record_errors_counter
+                                .inc_by(failed.len() as i64);
+                            failed
+                        }
+                        Err(e) => {
+                            sink_metrics.request_errors_counter.inc();
+                            error!("unable to write to Kinesis stream in {}: {}", name, e);
+                            match e {
+                                RusotoError::HttpDispatch(_)
+                                | RusotoError::Service(
+                                    PutRecordsError::ProvisionedThroughputExceeded(_),
+                                ) => batch,
+                                _ => {
+                                    // We've received an error that is not
+                                    // transient.
+                                    *client.borrow_mut() = None;
+                                    return false;
+                                }
+                            }
+                        }
+                    };
+                    if failed.is_empty() {
+                        backoff = INITIAL_BACKOFF;
+                    } else {
+                        // Retry the failed records before any others, once
+                        // the backoff has elapsed. No later record shares a
+                        // partition key with a failed record, so this does
+                        // not reorder the updates to any key.
+                        for record in failed.into_iter().rev() {
+                            records.push_front(record);
+                        }
+                        retry_at = Some(Instant::now() + backoff);
+                        activator.activate_after(backoff);
+                        backoff = cmp::min(backoff * 2, MAX_BACKOFF);
+                        sink_metrics.rows_queued.set(queue.len() as u64);
+                        return true;
+                    }
+                }
+
+                sink_metrics.rows_queued.set(queue.len() as u64);
+                if !queue.is_empty() || !records.is_empty() {
+                    // We need timely to reschedule this operator as we have
+                    // pending items that we need to send to Kinesis.
+                    activator.activate();
+                    return true;
+                }
+
+                false
+            };
+
+            (ret, shutdown_button)
+        },
+    )
+}
@@ -8,10 +8,14 @@
 // by the Apache License, Version 2.0.
 
 mod avro_ocf;
+mod file;
 mod kafka;
+mod kinesis;
 mod tail;
 mod util;
 
 pub use avro_ocf::avro_ocf;
+pub use file::file;
 pub use kafka::kafka;
+pub use kinesis::kinesis;
 pub use tail::tail;
@@ -8,13 +8,13 @@
 // by the Apache License, Version 2.0.
 
 use timely::dataflow::channels::pact::Pipeline;
-use timely::dataflow::operators::Operator;
+use timely::dataflow::operators::{Exchange, Operator};
 use timely::dataflow::{Scope, Stream};
 
 use futures::executor::block_on;
 use futures::sink::SinkExt;
 
-use dataflow_types::{Diff, TailSinkConnector, Timestamp, Update};
+use dataflow_types::{Diff, TailResponse, TailSinkConnector, Timestamp, Update};
 use expr::GlobalId;
 use repr::Row;
 
@@ -25,6 +25,19 @@ pub fn tail<G>(
 ) where
     G: Scope<Timestamp = Timestamp>,
 {
+    // A progress message is only accurate if it cannot overtake an update
+    // sent by another worker, so when progress messages are requested, all
+    // updates are routed to the single worker that sends them.
+    let stream = if connector.emit_progress {
+        stream.exchange(|_| 0)
+    } else {
+        stream.clone()
+    };
+    let emit_progress = connector.emit_progress && stream.scope().index() == 0;
+    // Progress is only reported once the input frontier advances beyond the
+    // frontier that the tail starts from.
+    let mut reported_progress = connector.frontier.elements().first().copied();
+
     let mut tx = block_on(connector.tx.connect()).expect("tail transmitter failed");
     stream.sink(Pipeline, &format!("tail-{}", id), move |input| {
         input.for_each(|_, rows| {
@@ -48,7 +61,19 @@ pub fn tail<G>(
             // completes. Hopefully it's just a quick write to a kernel buffer,
             // but perhaps not if the batch gets too large? We may need to do
             // something smarter, like offloading to a networking thread.
-            block_on(tx.send(results)).expect("tail send failed");
+            block_on(tx.send(TailResponse::Rows(results))).expect("tail send failed");
         });
+
+        if emit_progress {
+            // Timestamps are totally ordered, so the frontier has at most one
+            // element. An empty frontier means the input is complete, which
+            // ends the tail.
+            if let Some(time) = input.frontier().frontier().first().copied() {
+                if reported_progress.map_or(true, |reported| reported < time) {
+                    block_on(tx.send(TailResponse::Progress(time))).expect("tail send failed");
+                    reported_progress = Some(time);
+                }
+            }
+        }
     })
 }
@@ -181,12 +181,13 @@ where
                     for record in get_records_output.records {
                         let data = record.data.as_ref().to_vec();
                         bytes_read += data.len() as i64;
-                        // We don't do anything with keys; just send vec![] for now.
-                        // Kinesis doesn't have "primary keys" but it does have "partition keys" and "sequence numbers"; maybe
-                        // one or both of those could be useful...
+                        // Kinesis doesn't have "primary keys", but records
+                        // are keyed by their partition key, which the upsert
+                        // envelope can use to identify records.
+                        let key = record.partition_key.into_bytes();
                         output
                             .session(&cap)
-                            .give(SourceOutput::new(vec![], data, None));
+                            .give(SourceOutput::new(key, data, None));
                         events_success += 1;
                     }
                     downgrade_capability(cap, &name);
@@ -43,12 +43,14 @@ use crate::server::{
 mod file;
 mod kafka;
 mod kinesis;
+mod postgres;
 mod util;
 
 use differential_dataflow::Hashable;
 pub use file::{file, read_file_task, FileReadStyle};
 pub use kafka::KafkaSourceInfo;
 pub use kinesis::kinesis;
+pub use postgres::postgres;
 
 /// Shared configuration information for all source types.
 pub struct SourceConfig<'a, G> {
@@ -0,0 +1,528 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::convert::TryInto;
+use std::mem;
+use std::str;
+use std::sync::mpsc::{SyncSender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use failure::{bail, format_err, ResultExt};
+use timely::dataflow::{Scope, Stream};
+use timely::scheduling::SyncActivator;
+use uuid::Uuid;
+
+use dataflow_types::{Diff, PostgresSourceConnector, SourceError, Timestamp};
+use repr::adt::char::{strip_padding, truncate};
+use repr::{strconv, Datum, RelationDesc, Row, RowPacker, ScalarType};
+
+use self::replication::{ReplicationClient, ReplicationMessage};
+use super::util::source;
+use super::{SourceConfig, SourceStatus, SourceToken};
+use crate::operator::StreamExt;
+
+mod replication;
+
+/// How often the replication connection reports its progress to PostgreSQL
+/// and checks whether the source has been dropped.
+const STATUS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The maximum number of upstream transactions to emit per invocation.
+const MAX_TRANSACTIONS_PER_INVOCATION: usize = 1024;
+
+/// The updates of one upstream transaction, or an error that ended
+/// replication.
+type Message = Result<Vec<(Row, Diff)>, String>;
+
+/// Creates a timely dataflow source operator that mirrors the PostgreSQL
+/// table described by `connector`, whose columns are described by `desc`.
+///
+/// The active worker takes a snapshot of the table and then streams its
+/// changes from a temporary logical replication slot, decoding the `pgoutput`
+/// protocol. The updates of each upstream transaction are emitted at a single
+/// timestamp.
+///
+/// Each source mirrors a single table, and uses its own replication
+/// connection and replication slot.
+pub fn postgres<G>(
+    config: SourceConfig<G>,
+    connector: PostgresSourceConnector,
+    desc: RelationDesc,
+) -> (
+    (Stream<G, (Row, Timestamp, Diff)>, Stream<G, SourceError>),
+    Option<SourceToken>,
+)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let SourceConfig {
+        name,
+        id,
+        scope,
+        active,
+        timestamp_frequency,
+        ..
+    } = config;
+
+    let (stream, capability) = source(id, None, scope, name, move |info| {
+        let activator = scope.activator_for(&info.address[..]);
+        let (tx, rx) = std::sync::mpsc::sync_channel(MAX_TRANSACTIONS_PER_INVOCATION);
+        if active {
+            let activator = Arc::new(Mutex::new(scope.sync_activator_for(&info.address[..])));
+            let column_types = desc
+                .typ()
+                .column_types
+                .iter()
+                .map(|c| c.scalar_type.clone())
+                .collect();
+            thread::spawn(|| replicate_task(connector, column_types, tx, activator));
+        }
+        let mut last_downgrade = Instant::now();
+        move |cap, output| {
+            let mut next_activation_duration = timestamp_frequency;
+            let mut transactions_read = 0;
+            loop {
+                match rx.try_recv() {
+                    Ok(Ok(updates)) => {
+                        let ts = *cap.time();
+                        let mut session = output.session(&cap);
+                        for (row, diff) in updates {
+                            session.give(Ok((row, ts, diff)));
+                        }
+                    }
+                    Ok(Err(err)) => {
+                        output.session(&cap).give(Err(err));
+                        return SourceStatus::Done;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return SourceStatus::Done,
+                }
+                transactions_read += 1;
+                if transactions_read == MAX_TRANSACTIONS_PER_INVOCATION {
+                    next_activation_duration = Duration::default();
+                    break;
+                }
+            }
+
+            // This is a real-time source, so timestamps can be closed as soon
+            // as wall-clock time passes them.
+            if last_downgrade.elapsed() >= timestamp_frequency {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Time went backwards")
+                    .as_millis() as u64;
+                if now > *cap.time() {
+                    cap.downgrade(&now);
+                }
+                last_downgrade = Instant::now();
+            }
+
+            activator.activate_after(next_activation_duration);
+            SourceStatus::Alive
+        }
+    });
+
+    let (ok_stream, err_stream) = stream.map_fallible(|r| r.map_err(SourceError::Postgres));
+
+    if active {
+        ((ok_stream, err_stream), Some(capability))
+    } else {
+        ((ok_stream, err_stream), None)
+    }
+}
+
+/// Blocking logic to replicate a table, intended for its own thread.
+fn replicate_task(
+    connector: PostgresSourceConnector,
+    column_types: Vec<ScalarType>,
+    tx: SyncSender<Message>,
+    activator: Arc<Mutex<SyncActivator>>,
+) {
+    let mut runtime = match tokio::runtime::Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            send(&tx, &activator, Err(format!("creating runtime: {}", e)));
+            return;
+        }
+    };
+    if let Err(e) = runtime.block_on(replicate(&connector, &column_types, &tx, &activator)) {
+        send(&tx, &activator, Err(e.to_string()));
+    }
+}
+
+/// Sends `message` to the source operator and activates it.
+///
+/// Returns false if the source operator has gone away, probably due to `DROP
+/// SOURCE`.
+fn send(tx: &SyncSender<Message>, activator: &Mutex<SyncActivator>, message: Message) -> bool {
+    if tx.send(message).is_err() {
+        return false;
+    }
+    activator
+        .lock()
+        .expect("activator lock poisoned")
+        .activate()
+        .expect("activation failed");
+    true
+}
+
+async fn replicate(
+    connector: &PostgresSourceConnector,
+    column_types: &[ScalarType],
+    tx: &SyncSender<Message>,
+    activator: &Mutex<SyncActivator>,
+) -> Result<(), failure::Error> {
+    let mut client = ReplicationClient::connect(&connector.conn)
+        .await
+        .with_context(|e| format!("connecting to PostgreSQL: {}", e))?;
+
+    let table = format!(
+        "{}.{}",
+        quote_identifier(&connector.namespace),
+        quote_identifier(&connector.table)
+    );
+    let slot = format!("materialize_{}", Uuid::new_v4().to_simple());
+
+    // The slot is created at the start of the transaction that takes the
+    // snapshot, and the transaction uses the slot's snapshot, so that every
+    // change to the table is reflected in exactly one of the two. The slot is
+    // temporary, so PostgreSQL drops it, and stops retaining WAL for it, once
+    // the connection is closed.
+    client
+        .simple_query("BEGIN READ ONLY ISOLATION LEVEL REPEATABLE READ")
+        .await?;
+    let slot_info = client
+        .simple_query(&format!(
+            "CREATE_REPLICATION_SLOT {} TEMPORARY LOGICAL pgoutput USE_SNAPSHOT",
+            slot
+        ))
+        .await?;
+    let consistent_point = match slot_info.first().and_then(|row| row.get(1)) {
+        Some(Some(lsn)) => lsn.clone(),
+        _ => bail!("CREATE_REPLICATION_SLOT did not return a consistent point"),
+    };
+    let mut packer = RowPacker::new();
+    let mut snapshot = vec![];
+    for row in client
+        .simple_query(&format!("SELECT * FROM {}", table))
+        .await?
+    {
+        if row.len() != column_types.len() {
+            bail!(
+                "table {} has {} columns, but {} were expected",
+                table,
+                row.len(),
+                column_types.len()
+            );
+        }
+        for (value, ty) in row.iter().zip(column_types) {
+            match value {
+                None => packer.push(Datum::Null),
+                Some(text) => push_text(&mut packer, ty, text)?,
+            }
+        }
+        snapshot.push((packer.finish_and_reuse(), 1));
+    }
+    client.simple_query("COMMIT").await?;
+    if !send(tx, activator, Ok(snapshot)) {
+        return Ok(());
+    }
+
+    let mut decoder = PgoutputDecoder {
+        namespace: &connector.namespace,
+        table: &connector.table,
+        column_types,
+        rel_id: None,
+        updates: vec![],
+        packer,
+    };
+    client
+        .start_replication(&format!(
+            "START_REPLICATION SLOT {} LOGICAL {} \
+             (\"proto_version\" '1', \"publication_names\" {})",
+            slot,
+            consistent_point,
+            quote_literal(&quote_identifier(&connector.publication))
+        ))
+        .await?;
+    // The position in the WAL before which every change has been sent to the
+    // source operator.
+    let mut lsn = 0;
+    let mut last_status = Instant::now();
+    loop {
+        // Waiting for the next message times out, so that the slot's progress
+        // is reported, and the source's liveness checked, even when the table
+        // is idle.
+        if let Ok(data) = tokio::time::timeout(STATUS_INTERVAL, client.recv_copy_data()).await {
+            let data = data?;
+            match ReplicationMessage::parse(&data)? {
+                ReplicationMessage::XLogData { lsn: start, data } => {
+                    if let Some(updates) = decoder.decode(data)? {
+                        if !send(tx, activator, Ok(updates)) {
+                            return Ok(());
+                        }
+                    }
+                    lsn = start;
+                }
+                ReplicationMessage::Keepalive { reply: true } => {
+                    client.send_status_update(lsn).await?;
+                    last_status = Instant::now();
+                }
+                ReplicationMessage::Keepalive { reply: false } => (),
+            }
+        }
+        if last_status.elapsed() >= STATUS_INTERVAL {
+            // Probe the channel even when there were no changes, so that the
+            // slot is released promptly after the source is dropped.
+            if tx.send(Ok(vec![])).is_err() {
+                return Ok(());
+            }
+            client.send_status_update(lsn).await?;
+            last_status = Instant::now();
+        }
+    }
+}
+
+fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// Packs the PostgreSQL text representation of a value of type `ty`.
+fn push_text(packer: &mut RowPacker, ty: &ScalarType, text: &str) -> Result<(), failure::Error> {
+    match ty {
+        ScalarType::Bool => packer.push(Datum::from(strconv::parse_bool(text)?)),
+        ScalarType::Int16 => packer.push(Datum::Int16(strconv::parse_int16(text)?)),
+        ScalarType::Int32 => packer.push(Datum::Int32(strconv::parse_int32(text)?)),
+        ScalarType::Int64 => packer.push(Datum::Int64(strconv::parse_int64(text)?)),
+        ScalarType::Float32 => packer.push(Datum::from(strconv::parse_float32(text)?)),
+        ScalarType::Float64 => packer.push(Datum::from(strconv::parse_float64(text)?)),
+        ScalarType::Decimal(_, scale) => {
+            let d = strconv::parse_decimal(text)?;
+            let significand = d
+                .rescale(*scale)
+                .ok_or_else(|| format_err!("decimal {} out of range for scale {}", d, scale))?;
+            packer.push(Datum::from(significand))
+        }
+        ScalarType::Date => packer.push(Datum::Date(strconv::parse_date(text)?)),
+        ScalarType::Time => packer.push(Datum::Time(strconv::parse_time(text)?)),
+        ScalarType::Timestamp => packer.push(Datum::Timestamp(strconv::parse_timestamp(text)?)),
+        ScalarType::TimestampTz => {
+            packer.push(Datum::TimestampTz(strconv::parse_timestamptz(text)?))
+        }
+        ScalarType::Interval => packer.push(Datum::Interval(strconv::parse_interval(text)?)),
+        ScalarType::Bytes => packer.push(Datum::Bytes(&strconv::parse_bytes(text)?)),
+        ScalarType::String => packer.push(Datum::String(text)),
+        ScalarType::Char { length } => {
+            let text = match length {
+                Some(length) => truncate(text, *length, true)
+                    .ok_or_else(|| format_err!("value too long for type character({})", length))?,
+                None => text,
+            };
+            packer.push(Datum::String(strip_padding(text)))
+        }
+        ScalarType::VarChar { length } => {
+            let text = match length {
+                Some(length) => truncate(text, *length, true).ok_or_else(|| {
+                    format_err!("value too long for type character varying({})", length)
+                })?,
+                None => text,
+            };
+            packer.push(Datum::String(text))
+        }
+        ScalarType::Uuid => packer.push(Datum::Uuid(strconv::parse_uuid(text)?)),
+        ScalarType::Jsonb => packer.extend_by_row(&strconv::parse_jsonb(text)?.into_row()),
+        _ => bail!("cannot mirror PostgreSQL values of type {}", ty),
+    }
+    Ok(())
+}
+
+/// Decodes the messages of the `pgoutput` logical replication protocol into
+/// updates to a single table.
+///
+/// See: https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html
+struct PgoutputDecoder<'a> {
+    namespace: &'a str,
+    table: &'a str,
+    column_types: &'a [ScalarType],
+    /// The OID of the table, once its relation message has been decoded.
+    rel_id: Option<u32>,
+    /// The updates of the transaction being decoded.
+    updates: Vec<(Row, Diff)>,
+    packer: RowPacker,
+}
+
+impl<'a> PgoutputDecoder<'a> {
+    /// Decodes one message, returning the updates of a transaction once its
+    /// commit message is decoded.
+    fn decode(&mut self, data: &[u8]) -> Result<Option<Vec<(Row, Diff)>>, failure::Error> {
+        let mut reader = Reader { buf: data };
+        match reader.read_u8()? {
+            b'B' => self.updates.clear(),
+            b'C' => return Ok(Some(mem::replace(&mut self.updates, vec![]))),
+            b'R' => {
+                let rel_id = reader.read_u32()?;
+                let namespace = reader.read_cstr()?;
+                let name = reader.read_cstr()?;
+                if namespace == self.namespace && name == self.table {
+                    let _replica_identity = reader.read_u8()?;
+                    let ncols = reader.read_i16()? as usize;
+                    if ncols != self.column_types.len() {
+                        bail!(
+                            "table {}.{} now has {} columns, but {} were expected",
+                            self.namespace,
+                            self.table,
+                            ncols,
+                            self.column_types.len()
+                        );
+                    }
+                    self.rel_id = Some(rel_id);
+                }
+            }
+            tag @ b'I' | tag @ b'U' | tag @ b'D' => {
+                if Some(reader.read_u32()?) != self.rel_id {
+                    return Ok(None);
+                }
+                // Updates and deletes only carry the complete old row if the
+                // table has `REPLICA IDENTITY FULL`.
+                let old = match (tag, reader.read_u8()?) {
+                    (b'I', b'N') => None,
+                    (b'U', b'O') | (b'D', b'O') => Some(self.read_tuple(&mut reader, None)?),
+                    (b'U', b'K') | (b'U', b'N') | (b'D', b'K') => bail!(
+                        "table {}.{} must have REPLICA IDENTITY FULL",
+                        self.namespace,
+                        self.table
+                    ),
+                    (_, kind) => bail!("unexpected tuple kind {}", kind as char),
+                };
+                if tag == b'U' && reader.read_u8()? != b'N' {
+                    bail!("missing new tuple in update");
+                }
+                let new = match tag {
+                    b'D' => None,
+                    _ => Some(self.read_tuple(&mut reader, old.as_ref())?),
+                };
+                if let Some(old) = old {
+                    self.updates.push((old, -1));
+                }
+                if let Some(new) = new {
+                    self.updates.push((new, 1));
+                }
+            }
+            b'T' => {
+                let nrels = reader.read_i32()?;
+                let _options = reader.read_u8()?;
+                for _ in 0..nrels {
+                    if Some(reader.read_u32()?) == self.rel_id {
+                        bail!("table {}.{} was truncated", self.namespace, self.table);
+                    }
+                }
+            }
+            // Origin and type messages carry nothing of interest.
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    /// Reads a row, taking the values that PostgreSQL omits because they are
+    /// unchanged TOAST values from `old`.
+    fn read_tuple(
+        &mut self,
+        reader: &mut Reader,
+        old: Option<&Row>,
+    ) -> Result<Row, failure::Error> {
+        let ncols = reader.read_i16()? as usize;
+        if ncols != self.column_types.len() {
+            bail!(
+                "tuple has {} columns, but {} were expected",
+                ncols,
+                self.column_types.len()
+            );
+        }
+        let old = old.map(|old| old.unpack());
+        for (i, ty) in self.column_types.iter().enumerate() {
+            match reader.read_u8()? {
+                b'n' => self.packer.push(Datum::Null),
+                b'u' => match &old {
+                    Some(old) => self.packer.push(old[i]),
+                    None => bail!("unchanged TOAST value without an old row"),
+                },
+                b't' => {
+                    let len = reader.read_i32()? as usize;
+                    let text = str::from_utf8(reader.read_bytes(len)?)?;
+                    push_text(&mut self.packer, ty, text)?;
+                }
+                kind => bail!("unexpected column kind {}", kind as char),
+            }
+        }
+        Ok(self.packer.finish_and_reuse())
+    }
+}
+
+/// A cursor over the fields of a `pgoutput` or replication protocol message.
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], failure::Error> {
+        if self.buf.len() < n {
+            bail!("unexpected end of message");
+        }
+        let (bytes, rest) = self.buf.split_at(n);
+        self.buf = rest;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, failure::Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_i16(&mut self) -> Result<i16, failure::Error> {
+        Ok(i16::from_be_bytes(self.read_bytes(2)?.try_into().unwrap()))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, failure::Error> {
+        Ok(i32::from_be_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, failure::Error> {
+        Ok(u32::from_be_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, failure::Error> {
+        Ok(u64::from_be_bytes(self.read_bytes(8)?.try_into().unwrap()))
+    }
+
+    fn read_rest(&mut self) -> &'a [u8] {
+        let rest = self.buf;
+        self.buf = &[];
+        rest
+    }
+
+    fn read_cstr(&mut self) -> Result<&'a str, failure::Error> {
+        match self.buf.iter().position(|b| *b == 0) {
+            Some(n) => {
+                let s = str::from_utf8(&self.buf[..n])?;
+                self.buf = &self.buf[n + 1..];
+                Ok(s)
+            }
+            None => bail!("unterminated string in message"),
+        }
+    }
+}
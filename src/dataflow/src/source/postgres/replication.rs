@@ -0,0 +1,365 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A minimal client for PostgreSQL's streaming replication protocol.
+//!
+//! tokio-postgres can neither open replication connections nor handle the
+//! CopyBoth mode that `START_REPLICATION` enters, so this module speaks just
+//! enough of the frontend/backend protocol to create a replication slot, read
+//! a snapshot of a table, and stream the slot's changes.
+//!
+//! See: https://www.postgresql.org/docs/current/protocol-replication.html
+
+use std::convert::TryInto;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use failure::{bail, format_err, ResultExt};
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use postgres_protocol::authentication;
+use postgres_protocol::authentication::sasl::{self, ChannelBinding, ScramSha256};
+use postgres_protocol::message::frontend;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
+use tokio_postgres::config::Host;
+use tokio_util::codec::{Decoder, Encoder, Framed};
+
+use super::Reader;
+
+/// The number of seconds between the Unix epoch and the PostgreSQL epoch,
+/// 2000-01-01.
+const PG_EPOCH_OFFSET_SECS: u64 = 946_684_800;
+
+trait AsyncStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<S> AsyncStream for S where S: AsyncRead + AsyncWrite + Unpin + Send {}
+
+/// A message from the replication stream.
+pub enum ReplicationMessage<'a> {
+    /// Output of the replication slot's output plugin, which starts at `lsn`.
+    XLogData { lsn: u64, data: &'a [u8] },
+    /// A heartbeat, which asks for a status update if `reply` is set.
+    Keepalive { reply: bool },
+}
+
+impl<'a> ReplicationMessage<'a> {
+    /// Parses the contents of a CopyData message from the replication stream.
+    pub fn parse(buf: &'a [u8]) -> Result<ReplicationMessage<'a>, failure::Error> {
+        let mut reader = Reader { buf };
+        match reader.read_u8()? {
+            b'w' => {
+                let lsn = reader.read_u64()?;
+                let _wal_end = reader.read_u64()?;
+                let _clock = reader.read_u64()?;
+                Ok(ReplicationMessage::XLogData {
+                    lsn,
+                    data: reader.read_rest(),
+                })
+            }
+            b'k' => {
+                let _wal_end = reader.read_u64()?;
+                let _clock = reader.read_u64()?;
+                Ok(ReplicationMessage::Keepalive {
+                    reply: reader.read_u8()? == 1,
+                })
+            }
+            tag => bail!("unexpected replication message {}", tag as char),
+        }
+    }
+}
+
+/// A connection to PostgreSQL in logical replication mode.
+pub struct ReplicationClient {
+    framed: Framed<Box<dyn AsyncStream>, Codec>,
+}
+
+impl ReplicationClient {
+    /// Opens a logical replication connection to the database described by
+    /// the connection string `conn`.
+    pub async fn connect(conn: &str) -> Result<ReplicationClient, failure::Error> {
+        let config: tokio_postgres::Config = conn.parse()?;
+        let user = match config.get_user() {
+            Some(user) => user,
+            None => bail!("PostgreSQL connection string must specify a user"),
+        };
+        let port = config.get_ports().first().copied().unwrap_or(5432);
+        let stream: Box<dyn AsyncStream> = match config.get_hosts().first() {
+            Some(Host::Tcp(host)) => Box::new(TcpStream::connect((host.as_str(), port)).await?),
+            #[cfg(unix)]
+            Some(Host::Unix(path)) => {
+                Box::new(UnixStream::connect(path.join(format!(".s.PGSQL.{}", port))).await?)
+            }
+            None => bail!("PostgreSQL connection string must specify a host"),
+        };
+        let mut client = ReplicationClient {
+            framed: Framed::new(stream, Codec),
+        };
+
+        let mut params = vec![("user", user), ("replication", "database")];
+        if let Some(dbname) = config.get_dbname() {
+            params.push(("database", dbname));
+        }
+        let mut buf = BytesMut::new();
+        frontend::startup_message(params, &mut buf)?;
+        client.framed.send(buf).await?;
+        client
+            .authenticate(user, config.get_password())
+            .await
+            .with_context(|e| format!("authenticating: {}", e))?;
+        client.wait_until_ready().await?;
+        Ok(client)
+    }
+
+    async fn authenticate(
+        &mut self,
+        user: &str,
+        password: Option<&[u8]>,
+    ) -> Result<(), failure::Error> {
+        let require_password = || password.ok_or_else(|| format_err!("password required"));
+        let mut scram = None;
+        loop {
+            let (tag, body) = self.recv().await?;
+            let mut reader = Reader { buf: &body };
+            if tag != b'R' {
+                bail!("unexpected message {} during authentication", tag as char);
+            }
+            let mut buf = BytesMut::new();
+            match reader.read_i32()? {
+                // AuthenticationOk.
+                0 => return Ok(()),
+                // AuthenticationCleartextPassword.
+                3 => frontend::password_message(require_password()?, &mut buf)?,
+                // AuthenticationMD5Password.
+                5 => {
+                    let salt = reader.read_bytes(4)?.try_into().unwrap();
+                    let hash = authentication::md5_hash(user.as_bytes(), require_password()?, salt);
+                    frontend::password_message(hash.as_bytes(), &mut buf)?;
+                }
+                // AuthenticationSASL.
+                10 => {
+                    let mut supported = false;
+                    loop {
+                        match reader.read_cstr()? {
+                            "" => break,
+                            mechanism => supported |= mechanism == sasl::SCRAM_SHA_256,
+                        }
+                    }
+                    if !supported {
+                        bail!("no supported SASL mechanism");
+                    }
+                    let s = ScramSha256::new(require_password()?, ChannelBinding::unsupported());
+                    frontend::sasl_initial_response(sasl::SCRAM_SHA_256, s.message(), &mut buf)?;
+                    scram = Some(s);
+                }
+                // AuthenticationSASLContinue.
+                11 => {
+                    let s = scram
+                        .as_mut()
+                        .ok_or_else(|| format_err!("unexpected SASLContinue message"))?;
+                    s.update(reader.read_rest())?;
+                    frontend::sasl_response(s.message(), &mut buf)?;
+                }
+                // AuthenticationSASLFinal.
+                12 => {
+                    let s = scram
+                        .as_mut()
+                        .ok_or_else(|| format_err!("unexpected SASLFinal message"))?;
+                    s.finish(reader.read_rest())?;
+                }
+                code => bail!("unsupported authentication method {}", code),
+            }
+            if !buf.is_empty() {
+                self.framed.send(buf).await?;
+            }
+        }
+    }
+
+    /// Runs `sql` with the simple query protocol and returns the text of the
+    /// values of the rows that it produced.
+    pub async fn simple_query(
+        &mut self,
+        sql: &str,
+    ) -> Result<Vec<Vec<Option<String>>>, failure::Error> {
+        self.send_query(sql).await?;
+        let mut rows = vec![];
+        loop {
+            let (tag, body) = self.recv().await?;
+            match tag {
+                // DataRow.
+                b'D' => {
+                    let mut reader = Reader { buf: &body };
+                    let ncols = reader.read_i16()?;
+                    let mut row = Vec::with_capacity(ncols as usize);
+                    for _ in 0..ncols {
+                        match reader.read_i32()? {
+                            -1 => row.push(None),
+                            len => {
+                                let value = reader.read_bytes(len as usize)?;
+                                row.push(Some(String::from_utf8(value.to_vec())?));
+                            }
+                        }
+                    }
+                    rows.push(row);
+                }
+                // ReadyForQuery.
+                b'Z' => return Ok(rows),
+                // RowDescription, CommandComplete, and EmptyQueryResponse.
+                b'T' | b'C' | b'I' => (),
+                tag => bail!("unexpected message {} in response to query", tag as char),
+            }
+        }
+    }
+
+    /// Runs the `START_REPLICATION` command `sql`, after which the changes of
+    /// the replication slot can be read with [`ReplicationClient::recv_copy_data`].
+    pub async fn start_replication(&mut self, sql: &str) -> Result<(), failure::Error> {
+        self.send_query(sql).await?;
+        match self.recv().await? {
+            // CopyBothResponse.
+            (b'W', _) => Ok(()),
+            (tag, _) => bail!("unexpected message {} in response to query", tag as char),
+        }
+    }
+
+    /// Receives the contents of the next CopyData message from the
+    /// replication stream.
+    pub async fn recv_copy_data(&mut self) -> Result<Bytes, failure::Error> {
+        match self.recv().await? {
+            (b'd', body) => Ok(body),
+            (b'c', _) => bail!("PostgreSQL ended the replication stream"),
+            (tag, _) => bail!("unexpected message {} in replication stream", tag as char),
+        }
+    }
+
+    /// Reports to PostgreSQL that all changes before `lsn` have been
+    /// processed, so that the replication slot no longer retains their WAL.
+    pub async fn send_status_update(&mut self, lsn: u64) -> Result<(), failure::Error> {
+        let clock = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_micros() as u64
+            - PG_EPOCH_OFFSET_SECS * 1_000_000;
+        // A CopyData message that contains a standby status update, which
+        // is 34 bytes long.
+        let mut buf = BytesMut::with_capacity(39);
+        buf.put_u8(b'd');
+        buf.put_i32(4 + 34);
+        buf.put_u8(b'r');
+        // The write, flush, and apply positions.
+        for _ in 0..3 {
+            buf.put_u64(lsn);
+        }
+        buf.put_u64(clock);
+        // Don't request a reply.
+        buf.put_u8(0);
+        self.framed.send(buf).await?;
+        Ok(())
+    }
+
+    async fn send_query(&mut self, sql: &str) -> Result<(), failure::Error> {
+        let mut buf = BytesMut::new();
+        frontend::query(sql, &mut buf)?;
+        self.framed.send(buf).await?;
+        Ok(())
+    }
+
+    /// Waits for the ReadyForQuery message that ends the startup of the
+    /// connection.
+    async fn wait_until_ready(&mut self) -> Result<(), failure::Error> {
+        loop {
+            match self.recv().await? {
+                // ReadyForQuery.
+                (b'Z', _) => return Ok(()),
+                // BackendKeyData.
+                (b'K', _) => (),
+                (tag, _) => bail!("unexpected message {} during startup", tag as char),
+            }
+        }
+    }
+
+    /// Receives the next message that is not a notice or parameter status,
+    /// failing if it is an error.
+    async fn recv(&mut self) -> Result<(u8, Bytes), failure::Error> {
+        loop {
+            let (tag, body) = match self.framed.next().await {
+                Some(message) => message?,
+                None => bail!("PostgreSQL closed the replication connection"),
+            };
+            match tag {
+                // ErrorResponse.
+                b'E' => bail!("{}", error_message(&body)),
+                // NoticeResponse and ParameterStatus.
+                b'N' | b'S' => (),
+                _ => return Ok((tag, body)),
+            }
+        }
+    }
+}
+
+/// Extracts the human-readable message from the body of an ErrorResponse.
+fn error_message(body: &[u8]) -> String {
+    let mut reader = Reader { buf: body };
+    let mut severity = "ERROR";
+    let mut message = "unknown error";
+    while let Ok(field) = reader.read_u8() {
+        let value = match reader.read_cstr() {
+            Ok(value) => value,
+            Err(_) => break,
+        };
+        match field {
+            b'S' => severity = value,
+            b'M' => message = value,
+            _ => (),
+        }
+    }
+    format!("{}: {}", severity, message)
+}
+
+/// A Tokio codec that splits a stream of backend messages into their tags and
+/// bodies, and writes frontend messages that are already encoded.
+struct Codec;
+
+impl Decoder for Codec {
+    type Item = (u8, Bytes);
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<(u8, Bytes)>, io::Error> {
+        if src.len() < 5 {
+            return Ok(None);
+        }
+        let len = u32::from_be_bytes(src[1..5].try_into().unwrap()) as usize;
+        if len < 4 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid message length",
+            ));
+        }
+        if src.len() < len + 1 {
+            src.reserve(len + 1 - src.len());
+            return Ok(None);
+        }
+        let tag = src[0];
+        src.advance(5);
+        Ok(Some((tag, src.split_to(len - 4).freeze())))
+    }
+}
+
+impl Encoder for Codec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn encode(&mut self, item: BytesMut, dst: &mut BytesMut) -> Result<(), io::Error> {
+        dst.extend_from_slice(&item);
+        Ok(())
+    }
+}
@@ -121,7 +121,7 @@ impl RelationExpr {
                     | Negate { .. }
                     | Threshold { .. }
                     | ArrangeBy { .. } => false,
-                    Join { .. } | Union { .. } => true,
+                    Join { .. } | Union { .. } | Iterate { .. } => true,
                     Let { value, .. } => {
                         // only the value child goes in a different chain
                         (node.expr as *const RelationExpr) == ((&**value) as *const RelationExpr)
@@ -145,6 +145,10 @@ impl RelationExpr {
             if let Let { id, value, .. } = node.expr {
                 local_id_chain.insert(id, expr_chain(value));
             }
+            // the body of an iteration refers to the iteration itself
+            if let Iterate { id, .. } = node.expr {
+                local_id_chain.insert(id, node.chain);
+            }
         }
 
         for ExplanationNode {
@@ -191,6 +195,7 @@ impl RelationExpr {
                     .unwrap(),
                 },
                 Let { id, .. } => write!(pretty, "Let %{}", local_id_chain[id]).unwrap(),
+                Iterate { body, .. } => write!(pretty, "Iterate %{}", expr_chain(body)).unwrap(),
                 Project { outputs, .. } => {
                     write!(pretty, "Project {}", Bracketed("(", ")", Indices(outputs))).unwrap()
                 }
@@ -9,6 +9,7 @@
 
 #![allow(missing_docs)]
 
+use std::cmp::Ordering;
 use std::fmt;
 use std::iter;
 
@@ -19,13 +20,26 @@ use serde::{Deserialize, Serialize};
 
 use repr::adt::decimal::Significand;
 use repr::adt::regex::Regex as ReprRegex;
-use repr::{ColumnType, Datum, RelationType, Row, RowArena, ScalarType};
+use repr::{ColumnName, ColumnType, Datum, DatumList, RelationType, Row, RowArena, ScalarType};
 
+use crate::relation::{compare_columns, ColumnOrder};
 use crate::scalar::func::jsonb_stringify;
 
 // TODO(jamii) be careful about overflow in sum/avg
 // see https://timely.zulipchat.com/#narrow/stream/186635-engineering/topic/additional.20work/near/163507435
 
+fn max_int16<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let x: Option<i16> = datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .map(|d| d.unwrap_int16())
+        .max();
+    Datum::from(x)
+}
+
 fn max_int32<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -148,6 +162,18 @@ where
     Datum::from(x)
 }
 
+fn min_int16<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let x: Option<i16> = datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .map(|d| d.unwrap_int16())
+        .min();
+    Datum::from(x)
+}
+
 fn min_int32<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -387,8 +413,300 @@ where
     Datum::List(datum.unwrap_list())
 }
 
+fn array_agg<'a, I>(datums: I, temp_storage: &'a RowArena) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    temp_storage.make_datum(|packer| {
+        packer.push_list(datums.into_iter());
+    })
+}
+
+/// Evaluates a window function over the rows of one partition.
+///
+/// Each input datum is a record of three fields: the input row itself (as a
+/// record), a record of the values of the `ORDER BY` expressions, and a record
+/// of the arguments to the window function. The output is a list containing
+/// one record per input row, whose fields are the input row and the value of
+/// the window function for that row.
+///
+/// The entire partition is recomputed whenever any of its rows change, so
+/// the cost of maintaining a window function is proportional to the size of
+/// the partitions that change. Aggregates over frames that grow from row to
+/// row, like the default frame, are computed by extending the previous row's
+/// value where the aggregate allows it, rather than by reaggregating the
+/// entire frame.
+fn window<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    func: &WindowFunc,
+    order_by: &[ColumnOrder],
+    frame: &WindowFrame,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut rows: Vec<(Datum<'a>, Vec<Datum<'a>>, Vec<Datum<'a>>)> = datums
+        .into_iter()
+        .map(|d| {
+            let mut fields = d.unwrap_list().iter();
+            let row = fields.next().unwrap();
+            let order = fields.next().unwrap().unwrap_list().iter().collect();
+            let args = fields.next().unwrap().unwrap_list().iter().collect();
+            (row, order, args)
+        })
+        .collect();
+    // The sort is stable, so rows that are peers according to `order_by`
+    // retain their (arbitrary, but deterministic) input order.
+    rows.sort_by(|a, b| compare_columns(order_by, &a.1, &b.1, || Ordering::Equal));
+
+    // Peer groups are maximal runs of rows that are equal according to
+    // `order_by`. Without an `ORDER BY`, all rows in the partition are peers.
+    let mut peer_group_starts = Vec::with_capacity(rows.len());
+    let mut peer_group_ends = vec![0; rows.len()];
+    for i in 0..rows.len() {
+        if i > 0
+            && compare_columns(order_by, &rows[i - 1].1, &rows[i].1, || Ordering::Equal)
+                == Ordering::Equal
+        {
+            peer_group_starts.push(peer_group_starts[i - 1]);
+        } else {
+            peer_group_starts.push(i);
+        }
+    }
+    for i in (0..rows.len()).rev() {
+        if i + 1 < rows.len() && peer_group_starts[i + 1] == peer_group_starts[i] {
+            peer_group_ends[i] = peer_group_ends[i + 1];
+        } else {
+            peer_group_ends[i] = i + 1;
+        }
+    }
+
+    let mut results = Vec::with_capacity(rows.len());
+    let mut dense_rank = 0;
+    // The bounds of the previous row's frame and the aggregate over it.
+    let mut prev_frame: Option<(usize, usize, Datum<'a>)> = None;
+    for i in 0..rows.len() {
+        if peer_group_starts[i] == i {
+            dense_rank += 1;
+        }
+        let result = match func {
+            WindowFunc::RowNumber => Datum::Int64(i as i64 + 1),
+            WindowFunc::Rank => Datum::Int64(peer_group_starts[i] as i64 + 1),
+            WindowFunc::DenseRank => Datum::Int64(dense_rank),
+            WindowFunc::LagLead { lag } => {
+                let args = &rows[i].2;
+                let offset = match args[1] {
+                    Datum::Null => None,
+                    offset => Some(i64::from(offset.unwrap_int32())),
+                };
+                match offset {
+                    None => Datum::Null,
+                    Some(offset) => {
+                        let target = if *lag {
+                            i as i64 - offset
+                        } else {
+                            i as i64 + offset
+                        };
+                        if target >= 0 && (target as usize) < rows.len() {
+                            rows[target as usize].2[0]
+                        } else {
+                            args[2]
+                        }
+                    }
+                }
+            }
+            WindowFunc::FirstValue | WindowFunc::LastValue | WindowFunc::Aggregate(_) => {
+                let (start, end) =
+                    frame.bounds(i, rows.len(), &peer_group_starts, &peer_group_ends);
+                let frame_rows = &rows[start..end];
+                match func {
+                    WindowFunc::FirstValue => frame_rows.first().map_or(Datum::Null, |r| r.2[0]),
+                    WindowFunc::LastValue => frame_rows.last().map_or(Datum::Null, |r| r.2[0]),
+                    WindowFunc::Aggregate(func) => {
+                        let extended = match prev_frame {
+                            Some((prev_start, prev_end, prev))
+                                if prev_start == start && prev_end <= end =>
+                            {
+                                let new_rows = rows[prev_end..end].iter();
+                                let new_args = new_rows.map(|(_, _, args)| args[0]);
+                                func.extend(prev, new_args, temp_storage)
+                            }
+                            _ => None,
+                        };
+                        let result = extended.unwrap_or_else(|| {
+                            func.eval(frame_rows.iter().map(|(_, _, args)| args[0]), temp_storage)
+                        });
+                        prev_frame = Some((start, end, result));
+                        result
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        };
+        results.push(result);
+    }
+
+    temp_storage.make_datum(|packer| {
+        packer.push_list_with(|packer| {
+            for ((row, _, _), result) in rows.iter().zip(results) {
+                packer.push_list(&[*row, result]);
+            }
+        })
+    })
+}
+
+/// A function that computes a value for each row of a window partition.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub enum WindowFunc {
+    RowNumber,
+    Rank,
+    DenseRank,
+    /// `lag(value, offset, default)` if `lag` is true, otherwise
+    /// `lead(value, offset, default)`.
+    LagLead {
+        lag: bool,
+    },
+    FirstValue,
+    LastValue,
+    /// An ordinary aggregate function evaluated over the rows of the frame.
+    Aggregate(Box<AggregateFunc>),
+}
+
+impl WindowFunc {
+    /// The type of the value computed for each row, given the types of the
+    /// arguments to the window function.
+    pub fn output_type(&self, arg_types: &[ScalarType]) -> ScalarType {
+        match self {
+            WindowFunc::RowNumber | WindowFunc::Rank | WindowFunc::DenseRank => ScalarType::Int64,
+            WindowFunc::LagLead { .. } | WindowFunc::FirstValue | WindowFunc::LastValue => {
+                arg_types[0].clone()
+            }
+            WindowFunc::Aggregate(func) => {
+                func.output_type(ColumnType::new(arg_types[0].clone()))
+                    .scalar_type
+            }
+        }
+    }
+}
+
+impl fmt::Display for WindowFunc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowFunc::RowNumber => f.write_str("row_number"),
+            WindowFunc::Rank => f.write_str("rank"),
+            WindowFunc::DenseRank => f.write_str("dense_rank"),
+            WindowFunc::LagLead { lag: true } => f.write_str("lag"),
+            WindowFunc::LagLead { lag: false } => f.write_str("lead"),
+            WindowFunc::FirstValue => f.write_str("first_value"),
+            WindowFunc::LastValue => f.write_str("last_value"),
+            WindowFunc::Aggregate(func) => write!(f, "{}", func),
+        }
+    }
+}
+
+/// The set of rows, relative to the current row, over which a frame-aware
+/// window function is computed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound,
+    pub end_bound: WindowFrameBound,
+}
+
+impl Default for WindowFrame {
+    /// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`, as in PostgreSQL.
+    fn default() -> WindowFrame {
+        WindowFrame {
+            units: WindowFrameUnits::Range,
+            start_bound: WindowFrameBound::UnboundedPreceding,
+            end_bound: WindowFrameBound::CurrentRow,
+        }
+    }
+}
+
+impl WindowFrame {
+    /// Returns the half-open range of row indexes that make up the frame of
+    /// row `i` in a partition of `len` rows.
+    fn bounds(
+        &self,
+        i: usize,
+        len: usize,
+        peer_group_starts: &[usize],
+        peer_group_ends: &[usize],
+    ) -> (usize, usize) {
+        let start = match (&self.units, &self.start_bound) {
+            (_, WindowFrameBound::UnboundedPreceding) => 0,
+            (_, WindowFrameBound::UnboundedFollowing) => len,
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => i,
+            (WindowFrameUnits::Range, WindowFrameBound::CurrentRow) => peer_group_starts[i],
+            (_, WindowFrameBound::OffsetPreceding(n)) => i.saturating_sub(*n as usize),
+            (_, WindowFrameBound::OffsetFollowing(n)) => i.saturating_add(*n as usize).min(len),
+        };
+        let end = match (&self.units, &self.end_bound) {
+            (_, WindowFrameBound::UnboundedPreceding) => 0,
+            (_, WindowFrameBound::UnboundedFollowing) => len,
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => i + 1,
+            (WindowFrameUnits::Range, WindowFrameBound::CurrentRow) => peer_group_ends[i],
+            (_, WindowFrameBound::OffsetPreceding(n)) => (i + 1).saturating_sub(*n as usize),
+            (_, WindowFrameBound::OffsetFollowing(n)) => {
+                (i + 1).saturating_add(*n as usize).min(len)
+            }
+        };
+        (start, end.max(start))
+    }
+}
+
+impl fmt::Display for WindowFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} between {} and {}",
+            self.units, self.start_bound, self.end_bound
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub enum WindowFrameUnits {
+    Rows,
+    /// Only `UNBOUNDED` and `CURRENT ROW` bounds are supported in `RANGE` mode.
+    Range,
+}
+
+impl fmt::Display for WindowFrameUnits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowFrameUnits::Rows => f.write_str("rows"),
+            WindowFrameUnits::Range => f.write_str("range"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub enum WindowFrameBound {
+    UnboundedPreceding,
+    OffsetPreceding(u64),
+    CurrentRow,
+    OffsetFollowing(u64),
+    UnboundedFollowing,
+}
+
+impl fmt::Display for WindowFrameBound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowFrameBound::UnboundedPreceding => f.write_str("unbounded preceding"),
+            WindowFrameBound::OffsetPreceding(n) => write!(f, "{} preceding", n),
+            WindowFrameBound::CurrentRow => f.write_str("current row"),
+            WindowFrameBound::OffsetFollowing(n) => write!(f, "{} following", n),
+            WindowFrameBound::UnboundedFollowing => f.write_str("unbounded following"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum AggregateFunc {
+    MaxInt16,
     MaxInt32,
     MaxInt64,
     MaxFloat32,
@@ -399,6 +717,7 @@ pub enum AggregateFunc {
     MaxDate,
     MaxTimestamp,
     MaxTimestampTz,
+    MinInt16,
     MinInt32,
     MinInt64,
     MinFloat32,
@@ -419,11 +738,22 @@ pub enum AggregateFunc {
     Any,
     All,
     JsonbAgg,
+    ArrayAgg,
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
     /// of a reduce operator.
     Dummy,
+    /// Computes a window function over all rows of a partition.
+    ///
+    /// See [`window`] for the format of the input and output datums.
+    Window {
+        func: WindowFunc,
+        /// The ordering of the rows within the partition, in terms of the
+        /// fields of the `ORDER BY` record of each input datum.
+        order_by: Vec<ColumnOrder>,
+        frame: WindowFrame,
+    },
 }
 
 impl AggregateFunc {
@@ -432,6 +762,7 @@ impl AggregateFunc {
         I: IntoIterator<Item = Datum<'a>>,
     {
         match self {
+            AggregateFunc::MaxInt16 => max_int16(datums),
             AggregateFunc::MaxInt32 => max_int32(datums),
             AggregateFunc::MaxInt64 => max_int64(datums),
             AggregateFunc::MaxFloat32 => max_float32(datums),
@@ -442,6 +773,7 @@ impl AggregateFunc {
             AggregateFunc::MaxDate => max_date(datums),
             AggregateFunc::MaxTimestamp => max_timestamp(datums),
             AggregateFunc::MaxTimestampTz => max_timestamptz(datums),
+            AggregateFunc::MinInt16 => min_int16(datums),
             AggregateFunc::MinInt32 => min_int32(datums),
             AggregateFunc::MinInt64 => min_int64(datums),
             AggregateFunc::MinFloat32 => min_float32(datums),
@@ -462,7 +794,70 @@ impl AggregateFunc {
             AggregateFunc::Any => any(datums),
             AggregateFunc::All => all(datums),
             AggregateFunc::JsonbAgg => jsonb_agg(datums, temp_storage),
+            AggregateFunc::ArrayAgg => array_agg(datums, temp_storage),
             AggregateFunc::Dummy => Datum::Dummy,
+            AggregateFunc::Window {
+                func,
+                order_by,
+                frame,
+            } => window(datums, temp_storage, func, order_by, frame),
+        }
+    }
+
+    /// Computes the aggregate over a set of datums from the aggregate over a
+    /// subset of them, `prev`, and the remaining datums, or returns `None` if
+    /// the aggregate cannot be computed this way.
+    fn extend<'a, I>(
+        &self,
+        prev: Datum<'a>,
+        datums: I,
+        temp_storage: &'a RowArena,
+    ) -> Option<Datum<'a>>
+    where
+        I: IntoIterator<Item = Datum<'a>>,
+    {
+        match self {
+            // These aggregates produce a value of the same type as their
+            // input, and aggregating a partial result together with the
+            // remaining datums gives the same result as aggregating all of the
+            // datums.
+            AggregateFunc::MaxInt16
+            | AggregateFunc::MaxInt32
+            | AggregateFunc::MaxInt64
+            | AggregateFunc::MaxFloat32
+            | AggregateFunc::MaxFloat64
+            | AggregateFunc::MaxDecimal
+            | AggregateFunc::MaxBool
+            | AggregateFunc::MaxString
+            | AggregateFunc::MaxDate
+            | AggregateFunc::MaxTimestamp
+            | AggregateFunc::MaxTimestampTz
+            | AggregateFunc::MinInt16
+            | AggregateFunc::MinInt32
+            | AggregateFunc::MinInt64
+            | AggregateFunc::MinFloat32
+            | AggregateFunc::MinFloat64
+            | AggregateFunc::MinDecimal
+            | AggregateFunc::MinBool
+            | AggregateFunc::MinString
+            | AggregateFunc::MinDate
+            | AggregateFunc::MinTimestamp
+            | AggregateFunc::MinTimestampTz
+            | AggregateFunc::SumInt32
+            | AggregateFunc::SumInt64
+            | AggregateFunc::SumFloat32
+            | AggregateFunc::SumFloat64
+            | AggregateFunc::SumDecimal
+            | AggregateFunc::Any
+            | AggregateFunc::All => Some(self.eval(iter::once(prev).chain(datums), temp_storage)),
+            AggregateFunc::Count | AggregateFunc::CountAll => {
+                let count = self.eval(datums, temp_storage).unwrap_int64();
+                Some(Datum::Int64(prev.unwrap_int64() + count))
+            }
+            AggregateFunc::JsonbAgg
+            | AggregateFunc::ArrayAgg
+            | AggregateFunc::Dummy
+            | AggregateFunc::Window { .. } => None,
         }
     }
 
@@ -472,6 +867,7 @@ impl AggregateFunc {
             AggregateFunc::Any => Datum::False,
             AggregateFunc::All => Datum::True,
             AggregateFunc::Dummy => Datum::Dummy,
+            AggregateFunc::Window { .. } => Datum::List(DatumList::empty()),
             _ => Datum::Null,
         }
     }
@@ -488,10 +884,30 @@ impl AggregateFunc {
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
             AggregateFunc::JsonbAgg => ScalarType::Jsonb,
+            AggregateFunc::ArrayAgg => ScalarType::Array(Box::new(input_type.scalar_type)),
+            AggregateFunc::Window { func, .. } => {
+                let mut fields = match input_type.scalar_type {
+                    ScalarType::Record { fields } => fields,
+                    _ => unreachable!("window function input must be a record"),
+                };
+                let arg_types = match fields.pop() {
+                    Some((_name, ScalarType::Record { fields })) => fields,
+                    _ => unreachable!("window function arguments must be a record"),
+                };
+                let arg_types: Vec<_> = arg_types.into_iter().map(|(_, t)| t).collect();
+                let row_type = fields.swap_remove(0).1;
+                ScalarType::List(Box::new(ScalarType::Record {
+                    fields: vec![
+                        (ColumnName::from("row"), row_type),
+                        (ColumnName::from("value"), func.output_type(&arg_types)),
+                    ],
+                }))
+            }
             _ => input_type.scalar_type,
         };
         let nullable = match self {
             AggregateFunc::Count | AggregateFunc::CountAll => false,
+            AggregateFunc::Window { .. } => false,
             // max/min/sum return null on empty sets
             _ => true,
         };
@@ -542,6 +958,14 @@ fn jsonb_array_elements<'a>(a: Datum<'a>, temp_storage: &'a RowArena, stringify:
     }
 }
 
+fn unnest_list(a: Datum) -> Vec<Row> {
+    let mut row_packer = repr::RowPacker::new();
+    match a {
+        Datum::List(list) => list.iter().map(|e| row_packer.pack(&[e])).collect(),
+        _ => vec![],
+    }
+}
+
 fn regexp_extract(a: Datum, r: &AnalyzedRegex) -> Option<Row> {
     let mut row_packer = repr::RowPacker::new();
     match a {
@@ -575,6 +999,7 @@ fn generate_series<'a>(typ: &ScalarType, start: Datum<'a>, stop: Datum<'a>) -> V
 impl fmt::Display for AggregateFunc {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            AggregateFunc::MaxInt16 => f.write_str("max"),
             AggregateFunc::MaxInt32 => f.write_str("max"),
             AggregateFunc::MaxInt64 => f.write_str("max"),
             AggregateFunc::MaxFloat32 => f.write_str("max"),
@@ -585,6 +1010,7 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::MaxDate => f.write_str("max"),
             AggregateFunc::MaxTimestamp => f.write_str("max"),
             AggregateFunc::MaxTimestampTz => f.write_str("max"),
+            AggregateFunc::MinInt16 => f.write_str("min"),
             AggregateFunc::MinInt32 => f.write_str("min"),
             AggregateFunc::MinInt64 => f.write_str("min"),
             AggregateFunc::MinFloat32 => f.write_str("min"),
@@ -605,7 +1031,22 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::Any => f.write_str("any"),
             AggregateFunc::All => f.write_str("all"),
             AggregateFunc::JsonbAgg => f.write_str("jsonb_agg"),
+            AggregateFunc::ArrayAgg => f.write_str("array_agg"),
             AggregateFunc::Dummy => f.write_str("dummy"),
+            AggregateFunc::Window {
+                func,
+                order_by,
+                frame,
+            } => {
+                write!(f, "window({}, order_by=[", func)?;
+                for (i, order) in order_by.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", order)?;
+                }
+                write!(f, "], {})", frame)
+            }
         }
     }
 }
@@ -687,6 +1128,8 @@ pub enum TableFunc {
     // ScalarType is either Int32 or Int64.
     // TODO(justin): should also possibly be Timestamp{,Tz}.
     GenerateSeries(ScalarType),
+    UnnestList { el_typ: ScalarType },
+    UnnestArray { el_typ: ScalarType },
 }
 
 impl TableFunc {
@@ -700,6 +1143,7 @@ impl TableFunc {
             TableFunc::RegexpExtract(a) => regexp_extract(datums[0], a).into_iter().collect(),
             TableFunc::CsvExtract(n_cols) => csv_extract(datums[0], *n_cols).into_iter().collect(),
             TableFunc::GenerateSeries(typ) => generate_series(typ, datums[0], datums[1]),
+            TableFunc::UnnestList { .. } | TableFunc::UnnestArray { .. } => unnest_list(datums[0]),
         }
     }
 
@@ -728,6 +1172,9 @@ impl TableFunc {
                 .take(*n_cols)
                 .collect(),
             TableFunc::GenerateSeries(typ) => vec![ColumnType::new(typ.clone())],
+            TableFunc::UnnestList { el_typ } | TableFunc::UnnestArray { el_typ } => {
+                vec![ColumnType::new(el_typ.clone()).nullable(true)]
+            }
         })
     }
 
@@ -739,6 +1186,8 @@ impl TableFunc {
             TableFunc::RegexpExtract(a) => a.capture_groups_len(),
             TableFunc::CsvExtract(n_cols) => *n_cols,
             TableFunc::GenerateSeries(_) => 1,
+            TableFunc::UnnestList { .. } => 1,
+            TableFunc::UnnestArray { .. } => 1,
         }
     }
 }
@@ -756,6 +1205,8 @@ impl fmt::Display for TableFunc {
                 f.write_fmt(format_args!("csv_extract({}, _)", n_cols))
             }
             TableFunc::GenerateSeries(_) => f.write_str("generate_series"),
+            TableFunc::UnnestList { .. } => f.write_str("unnest_list"),
+            TableFunc::UnnestArray { .. } => f.write_str("unnest"),
         }
     }
 }
@@ -58,6 +58,23 @@ pub enum RelationExpr {
         /// The result of the `Let`, evaluated with `name` bound to `value`.
         body: Box<RelationExpr>,
     },
+    /// Iterate a dataflow to a fixed point.
+    ///
+    /// The body is evaluated with `id` bound to the result of the previous
+    /// evaluation of the body, starting from the empty collection, until the
+    /// result stops changing. Bodies whose results never stop changing will
+    /// iterate forever.
+    ///
+    /// The runtime memory footprint of this operator is zero, though the
+    /// operators in its body must retain state for each iteration.
+    Iterate {
+        /// The identifier to be used in `Get` variants to retrieve the
+        /// previous iterate.
+        id: LocalId,
+        /// The collection to iterate, evaluated with `id` bound to its own
+        /// previous result.
+        body: Box<RelationExpr>,
+    },
     /// Project out some columns from a dataflow
     ///
     /// The runtime memory footprint of this operator is zero.
@@ -231,6 +248,7 @@ impl RelationExpr {
             }
             RelationExpr::Get { typ, .. } => typ.clone(),
             RelationExpr::Let { body, .. } => body.typ(),
+            RelationExpr::Iterate { body, .. } => body.typ(),
             RelationExpr::Project { input, outputs } => {
                 let input_typ = input.typ();
                 let mut output_typ = RelationType::new(
@@ -721,6 +739,9 @@ impl RelationExpr {
                 f(value)?;
                 f(body)?;
             }
+            RelationExpr::Iterate { body, .. } => {
+                f(body)?;
+            }
             RelationExpr::Project { input, .. } => {
                 f(input)?;
             }
@@ -798,6 +819,9 @@ impl RelationExpr {
                 f(value)?;
                 f(body)?;
             }
+            RelationExpr::Iterate { body, .. } => {
+                f(body)?;
+            }
             RelationExpr::Project { input, .. } => {
                 f(input)?;
             }
@@ -942,6 +966,7 @@ impl RelationExpr {
                 value: _,
                 body: _,
             }
+            | RelationExpr::Iterate { id: _, body: _ }
             | RelationExpr::Project {
                 input: _,
                 outputs: _,
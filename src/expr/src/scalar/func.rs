@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::cmp::{self, Ordering};
+use std::cmp;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::str;
@@ -16,10 +16,12 @@ use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike,
 use encoding::label::encoding_from_whatwg_label;
 use encoding::DecoderTrap;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use ore::collections::CollectionExt;
 use ore::result::ResultExt;
-use repr::adt::datetime::DateTimeUnits;
+use repr::adt::char;
+use repr::adt::datetime::{DateTimeUnits, Timezone};
 use repr::adt::decimal::MAX_DECIMAL_PRECISION;
 use repr::adt::interval::Interval;
 use repr::adt::jsonb::JsonbRef;
@@ -90,6 +92,13 @@ fn not<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(!a.unwrap_bool())
 }
 
+fn abs_int16<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_int16()
+        .checked_abs()
+        .map(Datum::from)
+        .ok_or(EvalError::Int16OutOfRange)
+}
+
 fn abs_int32<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_int32().abs())
 }
@@ -125,6 +134,32 @@ fn cast_bool_to_string_implicit<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::String(strconv::format_bool_static(a.unwrap_bool()))
 }
 
+fn cast_int16_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+    let mut buf = String::new();
+    strconv::format_int16(&mut buf, a.unwrap_int16());
+    Datum::String(temp_storage.push_string(buf))
+}
+
+fn cast_int16_to_float32<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(f32::from(a.unwrap_int16()))
+}
+
+fn cast_int16_to_float64<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(f64::from(a.unwrap_int16()))
+}
+
+fn cast_int16_to_int32<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(i32::from(a.unwrap_int16()))
+}
+
+fn cast_int16_to_int64<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(i64::from(a.unwrap_int16()))
+}
+
+fn cast_int16_to_decimal<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(i128::from(a.unwrap_int16()))
+}
+
 fn cast_int32_to_bool<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_int32() != 0)
 }
@@ -145,6 +180,13 @@ fn cast_int32_to_float64<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(f64::from(a.unwrap_int32()))
 }
 
+fn cast_int32_to_int16<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    match i16::try_from(a.unwrap_int32()) {
+        Ok(n) => Ok(Datum::from(n)),
+        Err(_) => Err(EvalError::IntegerOutOfRange),
+    }
+}
+
 fn cast_int32_to_int64<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(i64::from(a.unwrap_int32()))
 }
@@ -157,6 +199,13 @@ fn cast_int64_to_bool<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_int64() != 0)
 }
 
+fn cast_int64_to_int16<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    match i16::try_from(a.unwrap_int64()) {
+        Ok(n) => Ok(Datum::from(n)),
+        Err(_) => Err(EvalError::IntegerOutOfRange),
+    }
+}
+
 fn cast_int64_to_int32<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     match i32::try_from(a.unwrap_int64()) {
         Ok(n) => Ok(Datum::from(n)),
@@ -184,6 +233,15 @@ fn cast_int64_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'
     Datum::String(temp_storage.push_string(buf))
 }
 
+fn cast_float32_to_int32<'a>(a: Datum<'a>) -> Datum<'a> {
+    let f = a.unwrap_float32();
+    if f > (i32::max_value() as f32) || f < (i32::min_value() as f32) {
+        Datum::Null
+    } else {
+        Datum::from(f as i32)
+    }
+}
+
 fn cast_float32_to_int64<'a>(a: Datum<'a>) -> Datum<'a> {
     // TODO(benesch): this is undefined behavior if the f32 doesn't fit in an
     // i64 (https://github.com/rust-lang/rust/issues/10184).
@@ -216,6 +274,10 @@ fn cast_float32_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum
     Datum::String(temp_storage.push_string(buf))
 }
 
+fn cast_float64_to_float32<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_float64() as f32)
+}
+
 fn cast_float64_to_int32<'a>(a: Datum<'a>) -> Datum<'a> {
     let f = a.unwrap_float64();
     if f > (i32::max_value() as f64) || f < (i32::min_value() as f64) {
@@ -291,6 +353,12 @@ fn cast_string_to_bytes<'a>(
     Ok(Datum::Bytes(temp_storage.push_bytes(bytes)))
 }
 
+fn cast_string_to_int16<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    strconv::parse_int16(a.unwrap_str())
+        .map(Datum::Int16)
+        .err_into()
+}
+
 fn cast_string_to_int32<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     strconv::parse_int32(a.unwrap_str())
         .map(Datum::Int32)
@@ -316,15 +384,10 @@ fn cast_string_to_float64<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
 }
 
 fn cast_string_to_decimal<'a>(a: Datum<'a>, scale: u8) -> Result<Datum<'a>, EvalError> {
-    strconv::parse_decimal(a.unwrap_str())
-        .map(|d| {
-            Datum::from(match d.scale().cmp(&scale) {
-                Ordering::Less => d.significand() * 10_i128.pow(u32::from(scale - d.scale())),
-                Ordering::Equal => d.significand(),
-                Ordering::Greater => d.significand() / 10_i128.pow(u32::from(d.scale() - scale)),
-            })
-        })
-        .err_into()
+    strconv::parse_decimal(a.unwrap_str())?
+        .rescale(scale)
+        .map(Datum::from)
+        .ok_or(EvalError::NumericFieldOverflow)
 }
 
 fn cast_string_to_date<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
@@ -357,6 +420,52 @@ fn cast_string_to_interval<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
         .err_into()
 }
 
+fn cast_string_to_uuid<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    strconv::parse_uuid(a.unwrap_str())
+        .map(Datum::Uuid)
+        .err_into()
+}
+
+fn cast_string_to_char<'a>(
+    a: Datum<'a>,
+    length: Option<usize>,
+    fail_on_len: bool,
+) -> Result<Datum<'a>, EvalError> {
+    let s = match length {
+        Some(length) => truncate_string(a.unwrap_str(), length, fail_on_len, "character")?,
+        None => a.unwrap_str(),
+    };
+    Ok(Datum::String(char::strip_padding(s)))
+}
+
+fn cast_string_to_varchar<'a>(
+    a: Datum<'a>,
+    length: Option<usize>,
+    fail_on_len: bool,
+) -> Result<Datum<'a>, EvalError> {
+    match length {
+        Some(length) => Ok(Datum::String(truncate_string(
+            a.unwrap_str(),
+            length,
+            fail_on_len,
+            "character varying",
+        )?)),
+        None => Ok(a),
+    }
+}
+
+fn truncate_string<'a>(
+    s: &'a str,
+    length: usize,
+    fail_on_len: bool,
+    type_name: &str,
+) -> Result<&'a str, EvalError> {
+    char::truncate(s, length, fail_on_len).ok_or_else(|| EvalError::StringValueTooLong {
+        target_type: type_name.into(),
+        length,
+    })
+}
+
 fn cast_date_to_timestamp<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::Timestamp(a.unwrap_date().and_hms(0, 0, 0))
 }
@@ -454,6 +563,24 @@ fn cast_bytes_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'
     Datum::String(temp_storage.push_string(buf))
 }
 
+fn cast_bytes_to_uuid<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let bytes = a.unwrap_bytes();
+    match Uuid::from_slice(bytes) {
+        Ok(uuid) => Ok(Datum::Uuid(uuid)),
+        Err(_) => Err(EvalError::InvalidUuidLength(bytes.len())),
+    }
+}
+
+fn cast_uuid_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+    let mut buf = String::new();
+    strconv::format_uuid(&mut buf, a.unwrap_uuid());
+    Datum::String(temp_storage.push_string(buf))
+}
+
+fn cast_uuid_to_bytes<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+    Datum::Bytes(temp_storage.push_bytes(a.unwrap_uuid().as_bytes().to_vec()))
+}
+
 // TODO(jamii): it would be much more efficient to skip the intermediate
 // repr::jsonb::Jsonb.
 fn cast_string_to_jsonb<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
@@ -515,6 +642,13 @@ fn cast_jsonb_to_bool<'a>(a: Datum<'a>) -> Datum<'a> {
     }
 }
 
+fn add_int16<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_int16()
+        .checked_add(b.unwrap_int16())
+        .ok_or(EvalError::NumericFieldOverflow)
+        .map(Datum::from)
+}
+
 fn add_int32<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     a.unwrap_int32()
         .checked_add(b.unwrap_int32())
@@ -770,6 +904,13 @@ fn add_interval<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError>
         .map(Datum::from)
 }
 
+fn sub_int16<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_int16()
+        .checked_sub(b.unwrap_int16())
+        .ok_or(EvalError::NumericFieldOverflow)
+        .map(Datum::from)
+}
+
 fn sub_int32<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     a.unwrap_int32()
         .checked_sub(b.unwrap_int32())
@@ -835,6 +976,13 @@ fn sub_time_interval<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::Time(t)
 }
 
+fn mul_int16<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_int16()
+        .checked_mul(b.unwrap_int16())
+        .ok_or(EvalError::NumericFieldOverflow)
+        .map(Datum::from)
+}
+
 fn mul_int32<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     a.unwrap_int32()
         .checked_mul(b.unwrap_int32())
@@ -861,6 +1009,18 @@ fn mul_decimal<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_decimal() * b.unwrap_decimal())
 }
 
+fn div_int16<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let b = b.unwrap_int16();
+    if b == 0 {
+        Err(EvalError::DivisionByZero)
+    } else {
+        a.unwrap_int16()
+            .checked_div(b)
+            .map(Datum::from)
+            .ok_or(EvalError::Int16OutOfRange)
+    }
+}
+
 fn div_int32<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     let b = b.unwrap_int32();
     if b == 0 {
@@ -906,6 +1066,16 @@ fn div_decimal<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     }
 }
 
+fn mod_int16<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let b = b.unwrap_int16();
+    if b == 0 {
+        Err(EvalError::DivisionByZero)
+    } else {
+        // Like PostgreSQL, `i16::MIN % -1` is zero rather than an overflow.
+        Ok(Datum::from(a.unwrap_int16().checked_rem(b).unwrap_or(0)))
+    }
+}
+
 fn mod_int32<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     let b = b.unwrap_int32();
     if b == 0 {
@@ -951,6 +1121,13 @@ fn mod_decimal<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     }
 }
 
+fn neg_int16<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_int16()
+        .checked_neg()
+        .map(Datum::from)
+        .ok_or(EvalError::Int16OutOfRange)
+}
+
 fn neg_int32<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(-a.unwrap_int32())
 }
@@ -1191,6 +1368,18 @@ fn jsonb_delete_string<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowAren
     }
 }
 
+fn array_index<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    // SQL arrays are indexed starting from one.
+    let i = b.unwrap_int64();
+    if i < 1 {
+        return Datum::Null;
+    }
+    a.unwrap_list()
+        .iter()
+        .nth(i as usize - 1)
+        .unwrap_or(Datum::Null)
+}
+
 fn match_like_pattern<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     let haystack = a.unwrap_str();
     let needle = like_pattern::build_regex(b.unwrap_str())?;
@@ -1599,6 +1788,22 @@ where
     }
 }
 
+fn parse_timezone(tz: &str) -> Result<Timezone, EvalError> {
+    tz.parse()
+        .map_err(|_| EvalError::InvalidTimezone(tz.to_owned()))
+}
+
+/// Interprets the timestamp `ts` as a local time in the time zone `tz`,
+/// producing the corresponding instant.
+fn timezone_timestamp<'a>(tz: Datum<'a>, ts: NaiveDateTime) -> Result<Datum<'a>, EvalError> {
+    Ok(parse_timezone(tz.unwrap_str())?.to_utc(&ts).into())
+}
+
+/// Converts the instant `ts` to the local time in the time zone `tz`.
+fn timezone_timestamptz<'a>(tz: Datum<'a>, ts: DateTime<Utc>) -> Result<Datum<'a>, EvalError> {
+    Ok(parse_timezone(tz.unwrap_str())?.to_local(&ts).into())
+}
+
 fn to_timestamp<'a>(a: Datum<'a>) -> Datum<'a> {
     let f = a.unwrap_float64();
     if !f.is_finite() {
@@ -1672,6 +1877,7 @@ fn jsonb_pretty<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
 pub enum BinaryFunc {
     And,
     Or,
+    AddInt16,
     AddInt32,
     AddInt64,
     AddFloat32,
@@ -1683,6 +1889,7 @@ pub enum BinaryFunc {
     AddDateTime,
     AddTimeInterval,
     AddDecimal,
+    SubInt16,
     SubInt32,
     SubInt64,
     SubFloat32,
@@ -1697,16 +1904,19 @@ pub enum BinaryFunc {
     SubTime,
     SubTimeInterval,
     SubDecimal,
+    MulInt16,
     MulInt32,
     MulInt64,
     MulFloat32,
     MulFloat64,
     MulDecimal,
+    DivInt16,
     DivInt32,
     DivInt64,
     DivFloat32,
     DivFloat64,
     DivDecimal,
+    ModInt16,
     ModInt32,
     ModInt64,
     ModFloat32,
@@ -1727,6 +1937,8 @@ pub enum BinaryFunc {
     DatePartTimestampTz,
     DateTruncTimestamp,
     DateTruncTimestampTz,
+    TimezoneTimestamp,
+    TimezoneTimestampTz,
     CastFloat32ToDecimal,
     CastFloat64ToDecimal,
     TextConcat,
@@ -1742,6 +1954,7 @@ pub enum BinaryFunc {
     TrimLeading,
     TrimTrailing,
     EncodedBytesCharLength,
+    ArrayIndex,
 }
 
 impl BinaryFunc {
@@ -1766,6 +1979,7 @@ impl BinaryFunc {
         match self {
             BinaryFunc::And => and(datums, temp_storage, a_expr, b_expr),
             BinaryFunc::Or => or(datums, temp_storage, a_expr, b_expr),
+            BinaryFunc::AddInt16 => eager!(add_int16),
             BinaryFunc::AddInt32 => eager!(add_int32),
             BinaryFunc::AddInt64 => eager!(add_int64),
             BinaryFunc::AddFloat32 => Ok(eager!(add_float32)),
@@ -1777,6 +1991,7 @@ impl BinaryFunc {
             BinaryFunc::AddTimeInterval => Ok(eager!(add_time_interval)),
             BinaryFunc::AddDecimal => Ok(eager!(add_decimal)),
             BinaryFunc::AddInterval => eager!(add_interval),
+            BinaryFunc::SubInt16 => eager!(sub_int16),
             BinaryFunc::SubInt32 => eager!(sub_int32),
             BinaryFunc::SubInt64 => eager!(sub_int64),
             BinaryFunc::SubFloat32 => Ok(eager!(sub_float32)),
@@ -1791,16 +2006,19 @@ impl BinaryFunc {
             BinaryFunc::SubTime => Ok(eager!(sub_time)),
             BinaryFunc::SubTimeInterval => Ok(eager!(sub_time_interval)),
             BinaryFunc::SubDecimal => Ok(eager!(sub_decimal)),
+            BinaryFunc::MulInt16 => eager!(mul_int16),
             BinaryFunc::MulInt32 => eager!(mul_int32),
             BinaryFunc::MulInt64 => eager!(mul_int64),
             BinaryFunc::MulFloat32 => Ok(eager!(mul_float32)),
             BinaryFunc::MulFloat64 => Ok(eager!(mul_float64)),
             BinaryFunc::MulDecimal => Ok(eager!(mul_decimal)),
+            BinaryFunc::DivInt16 => eager!(div_int16),
             BinaryFunc::DivInt32 => eager!(div_int32),
             BinaryFunc::DivInt64 => eager!(div_int64),
             BinaryFunc::DivFloat32 => eager!(div_float32),
             BinaryFunc::DivFloat64 => eager!(div_float64),
             BinaryFunc::DivDecimal => eager!(div_decimal),
+            BinaryFunc::ModInt16 => eager!(mod_int16),
             BinaryFunc::ModInt32 => eager!(mod_int32),
             BinaryFunc::ModInt64 => eager!(mod_int64),
             BinaryFunc::ModFloat32 => eager!(mod_float32),
@@ -1830,6 +2048,12 @@ impl BinaryFunc {
             BinaryFunc::DateTruncTimestampTz => {
                 eager!(|a, b: Datum| date_trunc(a, b.unwrap_timestamptz()))
             }
+            BinaryFunc::TimezoneTimestamp => {
+                eager!(|a, b: Datum| timezone_timestamp(a, b.unwrap_timestamp()))
+            }
+            BinaryFunc::TimezoneTimestampTz => {
+                eager!(|a, b: Datum| timezone_timestamptz(a, b.unwrap_timestamptz()))
+            }
             BinaryFunc::CastFloat32ToDecimal => eager!(cast_float32_to_decimal),
             BinaryFunc::CastFloat64ToDecimal => eager!(cast_float64_to_decimal),
             BinaryFunc::TextConcat => Ok(eager!(text_concat_binary, temp_storage)),
@@ -1850,6 +2074,7 @@ impl BinaryFunc {
             BinaryFunc::TrimLeading => Ok(eager!(trim_leading)),
             BinaryFunc::TrimTrailing => Ok(eager!(trim_trailing)),
             BinaryFunc::EncodedBytesCharLength => eager!(encoded_bytes_char_length),
+            BinaryFunc::ArrayIndex => Ok(eager!(array_index)),
         }
     }
 
@@ -1857,8 +2082,8 @@ impl BinaryFunc {
         use BinaryFunc::*;
         let in_nullable = input1_type.nullable || input2_type.nullable;
         let is_div_mod = match self {
-            DivInt32 | ModInt32 | DivInt64 | ModInt64 | DivFloat32 | ModFloat32 | DivFloat64
-            | ModFloat64 | DivDecimal | ModDecimal => true,
+            DivInt16 | ModInt16 | DivInt32 | ModInt32 | DivInt64 | ModInt64 | DivFloat32
+            | ModFloat32 | DivFloat64 | ModFloat64 | DivDecimal | ModDecimal => true,
             _ => false,
         };
         match self {
@@ -1874,6 +2099,10 @@ impl BinaryFunc {
             ToCharTimestamp | ToCharTimestampTz | ConvertFrom | Trim | TrimLeading
             | TrimTrailing => ColumnType::new(ScalarType::String).nullable(in_nullable),
 
+            AddInt16 | SubInt16 | MulInt16 | DivInt16 | ModInt16 => {
+                ColumnType::new(ScalarType::Int16).nullable(in_nullable || is_div_mod)
+            }
+
             AddInt32 | SubInt32 | MulInt32 | DivInt32 | ModInt32 | EncodedBytesCharLength => {
                 ColumnType::new(ScalarType::Int32).nullable(in_nullable || is_div_mod)
             }
@@ -1945,15 +2174,16 @@ impl BinaryFunc {
             | AddTimeInterval
             | SubTimeInterval => input1_type,
 
-            AddDateInterval | SubDateInterval | AddDateTime | DateTruncTimestamp => {
-                ColumnType::new(ScalarType::Timestamp).nullable(true)
-            }
+            AddDateInterval | SubDateInterval | AddDateTime | DateTruncTimestamp
+            | TimezoneTimestampTz => ColumnType::new(ScalarType::Timestamp).nullable(true),
 
             DatePartInterval | DatePartTimestamp | DatePartTimestampTz => {
                 ColumnType::new(ScalarType::Float64).nullable(true)
             }
 
-            DateTruncTimestampTz => ColumnType::new(ScalarType::TimestampTz).nullable(true),
+            DateTruncTimestampTz | TimezoneTimestamp => {
+                ColumnType::new(ScalarType::TimestampTz).nullable(true)
+            }
 
             SubTime => ColumnType::new(ScalarType::Interval).nullable(true),
 
@@ -1972,6 +2202,11 @@ impl BinaryFunc {
             JsonbContainsString | JsonbContainsJsonb => {
                 ColumnType::new(ScalarType::Bool).nullable(in_nullable)
             }
+
+            ArrayIndex => match input1_type.scalar_type {
+                ScalarType::Array(elem_type) => ColumnType::new(*elem_type).nullable(true),
+                _ => unreachable!(),
+            },
         }
     }
 
@@ -1988,6 +2223,7 @@ impl BinaryFunc {
         match self {
             And
             | Or
+            | AddInt16
             | AddInt32
             | AddInt64
             | AddFloat32
@@ -2000,6 +2236,7 @@ impl BinaryFunc {
             | AddInterval
             | SubInterval
             | AddDecimal
+            | SubInt16
             | SubInt32
             | SubInt64
             | SubFloat32
@@ -2013,16 +2250,19 @@ impl BinaryFunc {
             | SubTime
             | SubTimeInterval
             | SubDecimal
+            | MulInt16
             | MulInt32
             | MulInt64
             | MulFloat32
             | MulFloat64
             | MulDecimal
+            | DivInt16
             | DivInt32
             | DivInt64
             | DivFloat32
             | DivFloat64
             | DivDecimal
+            | ModInt16
             | ModInt32
             | ModInt64
             | ModFloat32
@@ -2050,6 +2290,8 @@ impl BinaryFunc {
             | DatePartTimestampTz
             | DateTruncTimestamp
             | DateTruncTimestampTz
+            | TimezoneTimestamp
+            | TimezoneTimestampTz
             | CastFloat32ToDecimal
             | CastFloat64ToDecimal
             | RoundDecimal(_)
@@ -2057,7 +2299,8 @@ impl BinaryFunc {
             | Trim
             | TrimLeading
             | TrimTrailing
-            | EncodedBytesCharLength => false,
+            | EncodedBytesCharLength
+            | ArrayIndex => false,
         }
     }
 }
@@ -2067,6 +2310,7 @@ impl fmt::Display for BinaryFunc {
         match self {
             BinaryFunc::And => f.write_str("&&"),
             BinaryFunc::Or => f.write_str("||"),
+            BinaryFunc::AddInt16 => f.write_str("+"),
             BinaryFunc::AddInt32 => f.write_str("+"),
             BinaryFunc::AddInt64 => f.write_str("+"),
             BinaryFunc::AddFloat32 => f.write_str("+"),
@@ -2078,6 +2322,7 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::AddDateTime => f.write_str("+"),
             BinaryFunc::AddDateInterval => f.write_str("+"),
             BinaryFunc::AddTimeInterval => f.write_str("+"),
+            BinaryFunc::SubInt16 => f.write_str("-"),
             BinaryFunc::SubInt32 => f.write_str("-"),
             BinaryFunc::SubInt64 => f.write_str("-"),
             BinaryFunc::SubFloat32 => f.write_str("-"),
@@ -2092,16 +2337,19 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::SubDateInterval => f.write_str("-"),
             BinaryFunc::SubTime => f.write_str("-"),
             BinaryFunc::SubTimeInterval => f.write_str("-"),
+            BinaryFunc::MulInt16 => f.write_str("*"),
             BinaryFunc::MulInt32 => f.write_str("*"),
             BinaryFunc::MulInt64 => f.write_str("*"),
             BinaryFunc::MulFloat32 => f.write_str("*"),
             BinaryFunc::MulFloat64 => f.write_str("*"),
             BinaryFunc::MulDecimal => f.write_str("*"),
+            BinaryFunc::DivInt16 => f.write_str("/"),
             BinaryFunc::DivInt32 => f.write_str("/"),
             BinaryFunc::DivInt64 => f.write_str("/"),
             BinaryFunc::DivFloat32 => f.write_str("/"),
             BinaryFunc::DivFloat64 => f.write_str("/"),
             BinaryFunc::DivDecimal => f.write_str("/"),
+            BinaryFunc::ModInt16 => f.write_str("%"),
             BinaryFunc::ModInt32 => f.write_str("%"),
             BinaryFunc::ModInt64 => f.write_str("%"),
             BinaryFunc::ModFloat32 => f.write_str("%"),
@@ -2121,6 +2369,8 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::DatePartTimestampTz => f.write_str("date_parttstz"),
             BinaryFunc::DateTruncTimestamp => f.write_str("date_truncts"),
             BinaryFunc::DateTruncTimestampTz => f.write_str("date_trunctstz"),
+            BinaryFunc::TimezoneTimestamp => f.write_str("timezonets"),
+            BinaryFunc::TimezoneTimestampTz => f.write_str("timezonetstz"),
             BinaryFunc::CastFloat32ToDecimal => f.write_str("f32todec"),
             BinaryFunc::CastFloat64ToDecimal => f.write_str("f64todec"),
             BinaryFunc::TextConcat => f.write_str("||"),
@@ -2137,6 +2387,7 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::TrimLeading => f.write_str("ltrim"),
             BinaryFunc::TrimTrailing => f.write_str("rtrim"),
             BinaryFunc::EncodedBytesCharLength => f.write_str("length"),
+            BinaryFunc::ArrayIndex => f.write_str("array_index"),
         }
     }
 }
@@ -2149,6 +2400,7 @@ fn is_null<'a>(a: Datum<'a>) -> Datum<'a> {
 pub enum UnaryFunc {
     Not,
     IsNull,
+    NegInt16,
     NegInt32,
     NegInt64,
     NegFloat32,
@@ -2158,6 +2410,7 @@ pub enum UnaryFunc {
     SqrtFloat32,
     SqrtFloat64,
     SqrtDec(u8),
+    AbsInt16,
     AbsInt32,
     AbsInt64,
     AbsFloat32,
@@ -2165,11 +2418,19 @@ pub enum UnaryFunc {
     AbsDecimal,
     CastBoolToStringExplicit,
     CastBoolToStringImplicit,
+    CastInt16ToFloat32,
+    CastInt16ToFloat64,
+    CastInt16ToInt32,
+    CastInt16ToInt64,
+    CastInt16ToDecimal,
+    CastInt16ToString,
     CastInt32ToBool,
     CastInt32ToFloat32,
     CastInt32ToFloat64,
+    CastInt32ToInt16,
     CastInt32ToInt64,
     CastInt32ToString,
+    CastInt64ToInt16,
     CastInt64ToInt32,
     CastInt32ToDecimal,
     CastInt64ToBool,
@@ -2177,9 +2438,11 @@ pub enum UnaryFunc {
     CastInt64ToFloat32,
     CastInt64ToFloat64,
     CastInt64ToString,
+    CastFloat32ToInt32,
     CastFloat32ToInt64,
     CastFloat32ToFloat64,
     CastFloat32ToString,
+    CastFloat64ToFloat32,
     CastFloat64ToInt32,
     CastFloat64ToInt64,
     CastFloat64ToString,
@@ -2190,6 +2453,7 @@ pub enum UnaryFunc {
     CastSignificandToFloat64,
     CastStringToBool,
     CastStringToBytes,
+    CastStringToInt16,
     CastStringToInt32,
     CastStringToInt64,
     CastStringToFloat32,
@@ -2200,6 +2464,17 @@ pub enum UnaryFunc {
     CastStringToTimestampTz,
     CastStringToInterval,
     CastStringToDecimal(u8),
+    CastStringToUuid,
+    CastStringToChar {
+        length: Option<usize>,
+        fail_on_len: bool,
+    },
+    CastStringToVarChar {
+        length: Option<usize>,
+        fail_on_len: bool,
+    },
+    CastCharToString,
+    CastVarCharToString,
     CastDateToTimestamp,
     CastDateToTimestampTz,
     CastDateToString,
@@ -2214,6 +2489,9 @@ pub enum UnaryFunc {
     CastIntervalToString,
     CastIntervalToTime,
     CastBytesToString,
+    CastBytesToUuid,
+    CastUuidToString,
+    CastUuidToBytes,
     CastStringToJsonb,
     CastJsonbToString,
     CastJsonbOrNullToJsonb,
@@ -2237,6 +2515,8 @@ pub enum UnaryFunc {
     DatePartTimestampTz(DateTimeUnits),
     DateTruncTimestamp(DateTimeUnits),
     DateTruncTimestampTz(DateTimeUnits),
+    TimezoneTimestamp(Timezone),
+    TimezoneTimestampTz(Timezone),
     ToTimestamp,
     JsonbArrayLength,
     JsonbTypeof,
@@ -2266,12 +2546,14 @@ impl UnaryFunc {
         match self {
             UnaryFunc::Not => Ok(not(a)),
             UnaryFunc::IsNull => Ok(is_null(a)),
+            UnaryFunc::NegInt16 => neg_int16(a),
             UnaryFunc::NegInt32 => Ok(neg_int32(a)),
             UnaryFunc::NegInt64 => Ok(neg_int64(a)),
             UnaryFunc::NegFloat32 => Ok(neg_float32(a)),
             UnaryFunc::NegFloat64 => Ok(neg_float64(a)),
             UnaryFunc::NegDecimal => Ok(neg_decimal(a)),
             UnaryFunc::NegInterval => Ok(neg_interval(a)),
+            UnaryFunc::AbsInt16 => abs_int16(a),
             UnaryFunc::AbsInt32 => Ok(abs_int32(a)),
             UnaryFunc::AbsInt64 => Ok(abs_int64(a)),
             UnaryFunc::AbsFloat32 => Ok(abs_float32(a)),
@@ -2279,21 +2561,31 @@ impl UnaryFunc {
             UnaryFunc::AbsDecimal => Ok(abs_decimal(a)),
             UnaryFunc::CastBoolToStringExplicit => Ok(cast_bool_to_string_explicit(a)),
             UnaryFunc::CastBoolToStringImplicit => Ok(cast_bool_to_string_implicit(a)),
+            UnaryFunc::CastInt16ToFloat32 => Ok(cast_int16_to_float32(a)),
+            UnaryFunc::CastInt16ToFloat64 => Ok(cast_int16_to_float64(a)),
+            UnaryFunc::CastInt16ToInt32 => Ok(cast_int16_to_int32(a)),
+            UnaryFunc::CastInt16ToInt64 => Ok(cast_int16_to_int64(a)),
+            UnaryFunc::CastInt16ToDecimal => Ok(cast_int16_to_decimal(a)),
+            UnaryFunc::CastInt16ToString => Ok(cast_int16_to_string(a, temp_storage)),
             UnaryFunc::CastInt32ToBool => Ok(cast_int32_to_bool(a)),
             UnaryFunc::CastInt32ToFloat32 => Ok(cast_int32_to_float32(a)),
             UnaryFunc::CastInt32ToFloat64 => Ok(cast_int32_to_float64(a)),
+            UnaryFunc::CastInt32ToInt16 => cast_int32_to_int16(a),
             UnaryFunc::CastInt32ToInt64 => Ok(cast_int32_to_int64(a)),
             UnaryFunc::CastInt32ToDecimal => Ok(cast_int32_to_decimal(a)),
             UnaryFunc::CastInt32ToString => Ok(cast_int32_to_string(a, temp_storage)),
+            UnaryFunc::CastInt64ToInt16 => cast_int64_to_int16(a),
             UnaryFunc::CastInt64ToInt32 => cast_int64_to_int32(a),
             UnaryFunc::CastInt64ToBool => Ok(cast_int64_to_bool(a)),
             UnaryFunc::CastInt64ToDecimal => Ok(cast_int64_to_decimal(a)),
             UnaryFunc::CastInt64ToFloat32 => Ok(cast_int64_to_float32(a)),
             UnaryFunc::CastInt64ToFloat64 => Ok(cast_int64_to_float64(a)),
             UnaryFunc::CastInt64ToString => Ok(cast_int64_to_string(a, temp_storage)),
+            UnaryFunc::CastFloat32ToInt32 => Ok(cast_float32_to_int32(a)),
             UnaryFunc::CastFloat32ToInt64 => Ok(cast_float32_to_int64(a)),
             UnaryFunc::CastFloat32ToFloat64 => Ok(cast_float32_to_float64(a)),
             UnaryFunc::CastFloat32ToString => Ok(cast_float32_to_string(a, temp_storage)),
+            UnaryFunc::CastFloat64ToFloat32 => Ok(cast_float64_to_float32(a)),
             UnaryFunc::CastFloat64ToInt32 => Ok(cast_float64_to_int32(a)),
             UnaryFunc::CastFloat64ToInt64 => Ok(cast_float64_to_int64(a)),
             UnaryFunc::CastFloat64ToString => Ok(cast_float64_to_string(a, temp_storage)),
@@ -2303,6 +2595,7 @@ impl UnaryFunc {
             UnaryFunc::CastSignificandToFloat64 => Ok(cast_significand_to_float64(a)),
             UnaryFunc::CastStringToBool => cast_string_to_bool(a),
             UnaryFunc::CastStringToBytes => cast_string_to_bytes(a, temp_storage),
+            UnaryFunc::CastStringToInt16 => cast_string_to_int16(a),
             UnaryFunc::CastStringToInt32 => cast_string_to_int32(a),
             UnaryFunc::CastStringToInt64 => cast_string_to_int64(a),
             UnaryFunc::CastStringToFloat32 => cast_string_to_float32(a),
@@ -2313,6 +2606,16 @@ impl UnaryFunc {
             UnaryFunc::CastStringToTimestamp => cast_string_to_timestamp(a),
             UnaryFunc::CastStringToTimestampTz => cast_string_to_timestamptz(a),
             UnaryFunc::CastStringToInterval => cast_string_to_interval(a),
+            UnaryFunc::CastStringToUuid => cast_string_to_uuid(a),
+            UnaryFunc::CastStringToChar {
+                length,
+                fail_on_len,
+            } => cast_string_to_char(a, *length, *fail_on_len),
+            UnaryFunc::CastStringToVarChar {
+                length,
+                fail_on_len,
+            } => cast_string_to_varchar(a, *length, *fail_on_len),
+            UnaryFunc::CastCharToString | UnaryFunc::CastVarCharToString => Ok(a),
             UnaryFunc::CastDateToTimestamp => Ok(cast_date_to_timestamp(a)),
             UnaryFunc::CastDateToTimestampTz => Ok(cast_date_to_timestamptz(a)),
             UnaryFunc::CastDateToString => Ok(cast_date_to_string(a, temp_storage)),
@@ -2330,6 +2633,9 @@ impl UnaryFunc {
             UnaryFunc::CastIntervalToString => Ok(cast_interval_to_string(a, temp_storage)),
             UnaryFunc::CastIntervalToTime => Ok(cast_interval_to_time(a)),
             UnaryFunc::CastBytesToString => Ok(cast_bytes_to_string(a, temp_storage)),
+            UnaryFunc::CastBytesToUuid => cast_bytes_to_uuid(a),
+            UnaryFunc::CastUuidToString => Ok(cast_uuid_to_string(a, temp_storage)),
+            UnaryFunc::CastUuidToBytes => Ok(cast_uuid_to_bytes(a, temp_storage)),
             UnaryFunc::CastStringToJsonb => Ok(cast_string_to_jsonb(a, temp_storage)),
             UnaryFunc::CastJsonbOrNullToJsonb => Ok(cast_jsonb_or_null_to_jsonb(a)),
             UnaryFunc::CastJsonbToString => Ok(cast_jsonb_to_string(a, temp_storage)),
@@ -2364,6 +2670,8 @@ impl UnaryFunc {
             UnaryFunc::DateTruncTimestampTz(units) => {
                 date_trunc_inner(*units, a.unwrap_timestamptz())
             }
+            UnaryFunc::TimezoneTimestamp(tz) => Ok(tz.to_utc(&a.unwrap_timestamp()).into()),
+            UnaryFunc::TimezoneTimestampTz(tz) => Ok(tz.to_local(&a.unwrap_timestamptz()).into()),
             UnaryFunc::ToTimestamp => Ok(to_timestamp(a)),
             UnaryFunc::JsonbArrayLength => Ok(jsonb_array_length(a)),
             UnaryFunc::JsonbTypeof => Ok(jsonb_typeof(a)),
@@ -2392,6 +2700,7 @@ impl UnaryFunc {
 
             CastStringToBool => ColumnType::new(ScalarType::Bool).nullable(true),
             CastStringToBytes => ColumnType::new(ScalarType::Bytes).nullable(true),
+            CastStringToInt16 => ColumnType::new(ScalarType::Int16).nullable(true),
             CastStringToInt32 => ColumnType::new(ScalarType::Int32).nullable(true),
             CastStringToInt64 => ColumnType::new(ScalarType::Int64).nullable(true),
             CastStringToFloat32 => ColumnType::new(ScalarType::Float32).nullable(true),
@@ -2406,9 +2715,18 @@ impl UnaryFunc {
             CastStringToInterval | CastTimeToInterval => {
                 ColumnType::new(ScalarType::Interval).nullable(true)
             }
+            CastStringToUuid | CastBytesToUuid => ColumnType::new(ScalarType::Uuid).nullable(true),
+
+            CastStringToChar { length, .. } => {
+                ColumnType::new(ScalarType::Char { length: *length }).nullable(in_nullable)
+            }
+            CastStringToVarChar { length, .. } => {
+                ColumnType::new(ScalarType::VarChar { length: *length }).nullable(in_nullable)
+            }
 
             CastBoolToStringExplicit
             | CastBoolToStringImplicit
+            | CastInt16ToString
             | CastInt32ToString
             | CastInt64ToString
             | CastFloat32ToString
@@ -2420,31 +2738,45 @@ impl UnaryFunc {
             | CastTimestampTzToString
             | CastIntervalToString
             | CastBytesToString
+            | CastUuidToString
+            | CastCharToString
+            | CastVarCharToString
             | TrimWhitespace
             | TrimLeadingWhitespace
             | TrimTrailingWhitespace => ColumnType::new(ScalarType::String).nullable(in_nullable),
 
-            CastInt32ToFloat32 | CastInt64ToFloat32 | CastSignificandToFloat32 => {
+            CastInt16ToFloat32
+            | CastInt32ToFloat32
+            | CastInt64ToFloat32
+            | CastFloat64ToFloat32
+            | CastSignificandToFloat32 => {
                 ColumnType::new(ScalarType::Float32).nullable(in_nullable)
             }
 
-            CastInt32ToFloat64
+            CastInt16ToFloat64
+            | CastInt32ToFloat64
             | CastInt64ToFloat64
             | CastFloat32ToFloat64
             | CastSignificandToFloat64 => {
                 ColumnType::new(ScalarType::Float64).nullable(in_nullable)
             }
 
-            CastInt64ToInt32 | CastDecimalToInt32 => {
-                ColumnType::new(ScalarType::Int32).nullable(in_nullable)
+            CastInt32ToInt16 | CastInt64ToInt16 => {
+                ColumnType::new(ScalarType::Int16).nullable(in_nullable)
             }
 
-            CastFloat64ToInt32 => ColumnType::new(ScalarType::Int32).nullable(true),
+            CastInt16ToInt32 | CastInt64ToInt32 | CastDecimalToInt32 => {
+                ColumnType::new(ScalarType::Int32).nullable(in_nullable)
+            }
 
-            CastInt32ToInt64 | CastDecimalToInt64 | CastFloat32ToInt64 | CastFloat64ToInt64 => {
-                ColumnType::new(ScalarType::Int64).nullable(in_nullable)
+            CastFloat32ToInt32 | CastFloat64ToInt32 => {
+                ColumnType::new(ScalarType::Int32).nullable(true)
             }
 
+            CastInt16ToInt64 | CastInt32ToInt64 | CastDecimalToInt64 | CastFloat32ToInt64
+            | CastFloat64ToInt64 => ColumnType::new(ScalarType::Int64).nullable(in_nullable),
+
+            CastInt16ToDecimal => ColumnType::new(ScalarType::Decimal(5, 0)).nullable(in_nullable),
             CastInt32ToDecimal => ColumnType::new(ScalarType::Decimal(10, 0)).nullable(in_nullable),
             CastInt64ToDecimal => ColumnType::new(ScalarType::Decimal(20, 0)).nullable(in_nullable),
 
@@ -2454,6 +2786,8 @@ impl UnaryFunc {
 
             CastIntervalToTime => ColumnType::new(ScalarType::Time).nullable(in_nullable),
 
+            CastUuidToBytes => ColumnType::new(ScalarType::Bytes).nullable(in_nullable),
+
             CastDateToTimestamp | CastTimestampTzToTimestamp => {
                 ColumnType::new(ScalarType::Timestamp).nullable(in_nullable)
             }
@@ -2490,8 +2824,9 @@ impl UnaryFunc {
             SqrtFloat32 => ColumnType::new(ScalarType::Float32).nullable(true),
             SqrtFloat64 => ColumnType::new(ScalarType::Float64).nullable(true),
 
-            Not | NegInt32 | NegInt64 | NegFloat32 | NegFloat64 | NegDecimal | NegInterval
-            | AbsInt32 | AbsInt64 | AbsFloat32 | AbsFloat64 | AbsDecimal => input_type,
+            Not | NegInt16 | NegInt32 | NegInt64 | NegFloat32 | NegFloat64 | NegDecimal
+            | NegInterval | AbsInt16 | AbsInt32 | AbsInt64 | AbsFloat32 | AbsFloat64
+            | AbsDecimal => input_type,
 
             DatePartInterval(_) | DatePartTimestamp(_) | DatePartTimestampTz(_) => {
                 ColumnType::new(ScalarType::Float64).nullable(in_nullable)
@@ -2500,6 +2835,9 @@ impl UnaryFunc {
             DateTruncTimestamp(_) => ColumnType::new(ScalarType::Timestamp).nullable(true),
             DateTruncTimestampTz(_) => ColumnType::new(ScalarType::TimestampTz).nullable(true),
 
+            TimezoneTimestamp(_) => ColumnType::new(ScalarType::TimestampTz).nullable(in_nullable),
+            TimezoneTimestampTz(_) => ColumnType::new(ScalarType::Timestamp).nullable(in_nullable),
+
             ToTimestamp => ColumnType::new(ScalarType::TimestampTz).nullable(true),
 
             JsonbArrayLength => ColumnType::new(ScalarType::Int64).nullable(true),
@@ -2531,12 +2869,16 @@ impl UnaryFunc {
     pub fn preserves_uniqueness(&self) -> bool {
         match self {
             UnaryFunc::Not
+            | UnaryFunc::NegInt16
             | UnaryFunc::NegInt32
             | UnaryFunc::NegInt64
             | UnaryFunc::NegFloat32
             | UnaryFunc::NegFloat64
             | UnaryFunc::NegDecimal
             | UnaryFunc::CastBoolToStringExplicit
+            | UnaryFunc::CastInt16ToInt32
+            | UnaryFunc::CastInt16ToInt64
+            | UnaryFunc::CastInt16ToString
             | UnaryFunc::CastInt32ToInt64
             | UnaryFunc::CastInt32ToString
             | UnaryFunc::CastInt64ToString
@@ -2548,7 +2890,11 @@ impl UnaryFunc {
             | UnaryFunc::CastDateToTimestampTz
             | UnaryFunc::CastDateToString
             | UnaryFunc::CastTimeToInterval
-            | UnaryFunc::CastTimeToString => true,
+            | UnaryFunc::CastTimeToString
+            | UnaryFunc::CastUuidToString
+            | UnaryFunc::CastCharToString
+            | UnaryFunc::CastVarCharToString
+            | UnaryFunc::CastUuidToBytes => true,
             _ => false,
         }
     }
@@ -2559,12 +2905,14 @@ impl fmt::Display for UnaryFunc {
         match self {
             UnaryFunc::Not => f.write_str("!"),
             UnaryFunc::IsNull => f.write_str("isnull"),
+            UnaryFunc::NegInt16 => f.write_str("-"),
             UnaryFunc::NegInt32 => f.write_str("-"),
             UnaryFunc::NegInt64 => f.write_str("-"),
             UnaryFunc::NegFloat32 => f.write_str("-"),
             UnaryFunc::NegFloat64 => f.write_str("-"),
             UnaryFunc::NegDecimal => f.write_str("-"),
             UnaryFunc::NegInterval => f.write_str("-"),
+            UnaryFunc::AbsInt16 => f.write_str("abs"),
             UnaryFunc::AbsInt32 => f.write_str("abs"),
             UnaryFunc::AbsInt64 => f.write_str("abs"),
             UnaryFunc::AbsDecimal => f.write_str("abs"),
@@ -2572,21 +2920,31 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::AbsFloat64 => f.write_str("abs"),
             UnaryFunc::CastBoolToStringExplicit => f.write_str("booltostrex"),
             UnaryFunc::CastBoolToStringImplicit => f.write_str("booltostrim"),
+            UnaryFunc::CastInt16ToFloat32 => f.write_str("i16tof32"),
+            UnaryFunc::CastInt16ToFloat64 => f.write_str("i16tof64"),
+            UnaryFunc::CastInt16ToInt32 => f.write_str("i16toi32"),
+            UnaryFunc::CastInt16ToInt64 => f.write_str("i16toi64"),
+            UnaryFunc::CastInt16ToDecimal => f.write_str("i16todec"),
+            UnaryFunc::CastInt16ToString => f.write_str("i16tostr"),
             UnaryFunc::CastInt32ToBool => f.write_str("i32tobool"),
             UnaryFunc::CastInt32ToFloat32 => f.write_str("i32tof32"),
             UnaryFunc::CastInt32ToFloat64 => f.write_str("i32tof64"),
+            UnaryFunc::CastInt32ToInt16 => f.write_str("i32toi16"),
             UnaryFunc::CastInt32ToInt64 => f.write_str("i32toi64"),
             UnaryFunc::CastInt32ToString => f.write_str("i32tostr"),
             UnaryFunc::CastInt32ToDecimal => f.write_str("i32todec"),
+            UnaryFunc::CastInt64ToInt16 => f.write_str("i64toi16"),
             UnaryFunc::CastInt64ToInt32 => f.write_str("i64toi32"),
             UnaryFunc::CastInt64ToBool => f.write_str("i64tobool"),
             UnaryFunc::CastInt64ToDecimal => f.write_str("i64todec"),
             UnaryFunc::CastInt64ToFloat32 => f.write_str("i64tof32"),
             UnaryFunc::CastInt64ToFloat64 => f.write_str("i64tof64"),
             UnaryFunc::CastInt64ToString => f.write_str("i64tostr"),
+            UnaryFunc::CastFloat32ToInt32 => f.write_str("f32toi32"),
             UnaryFunc::CastFloat32ToInt64 => f.write_str("f32toi64"),
             UnaryFunc::CastFloat32ToFloat64 => f.write_str("f32tof64"),
             UnaryFunc::CastFloat32ToString => f.write_str("f32tostr"),
+            UnaryFunc::CastFloat64ToFloat32 => f.write_str("f64tof32"),
             UnaryFunc::CastFloat64ToInt32 => f.write_str("f64toi32"),
             UnaryFunc::CastFloat64ToInt64 => f.write_str("f64toi64"),
             UnaryFunc::CastFloat64ToString => f.write_str("f64tostr"),
@@ -2597,6 +2955,7 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::CastSignificandToFloat64 => f.write_str("dectof64"),
             UnaryFunc::CastStringToBool => f.write_str("strtobool"),
             UnaryFunc::CastStringToBytes => f.write_str("strtobytes"),
+            UnaryFunc::CastStringToInt16 => f.write_str("strtoi16"),
             UnaryFunc::CastStringToInt32 => f.write_str("strtoi32"),
             UnaryFunc::CastStringToInt64 => f.write_str("strtoi64"),
             UnaryFunc::CastStringToFloat32 => f.write_str("strtof32"),
@@ -2607,6 +2966,11 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::CastStringToTimestamp => f.write_str("strtots"),
             UnaryFunc::CastStringToTimestampTz => f.write_str("strtotstz"),
             UnaryFunc::CastStringToInterval => f.write_str("strtoiv"),
+            UnaryFunc::CastStringToUuid => f.write_str("strtouuid"),
+            UnaryFunc::CastStringToChar { .. } => f.write_str("strtochar"),
+            UnaryFunc::CastStringToVarChar { .. } => f.write_str("strtovarchar"),
+            UnaryFunc::CastCharToString => f.write_str("chartostr"),
+            UnaryFunc::CastVarCharToString => f.write_str("varchartostr"),
             UnaryFunc::CastDateToTimestamp => f.write_str("datetots"),
             UnaryFunc::CastDateToTimestampTz => f.write_str("datetotstz"),
             UnaryFunc::CastDateToString => f.write_str("datetostr"),
@@ -2621,6 +2985,9 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::CastIntervalToString => f.write_str("ivtostr"),
             UnaryFunc::CastIntervalToTime => f.write_str("ivtotime"),
             UnaryFunc::CastBytesToString => f.write_str("bytestostr"),
+            UnaryFunc::CastBytesToUuid => f.write_str("bytestouuid"),
+            UnaryFunc::CastUuidToString => f.write_str("uuidtostr"),
+            UnaryFunc::CastUuidToBytes => f.write_str("uuidtobytes"),
             UnaryFunc::CastStringToJsonb => f.write_str("strtojsonb"),
             UnaryFunc::CastJsonbOrNullToJsonb => f.write_str("jsonb?tojsonb"),
             UnaryFunc::CastJsonbToString => f.write_str("jsonbtostr"),
@@ -2647,6 +3014,8 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::DatePartTimestampTz(units) => write!(f, "date_part_{}_tstz", units),
             UnaryFunc::DateTruncTimestamp(units) => write!(f, "date_trunc_{}_ts", units),
             UnaryFunc::DateTruncTimestampTz(units) => write!(f, "date_trunc_{}_tstz", units),
+            UnaryFunc::TimezoneTimestamp(tz) => write!(f, "timezone_{}_ts", tz),
+            UnaryFunc::TimezoneTimestampTz(tz) => write!(f, "timezone_{}_tstz", tz),
             UnaryFunc::ToTimestamp => f.write_str("tots"),
             UnaryFunc::JsonbArrayLength => f.write_str("jsonb_array_length"),
             UnaryFunc::JsonbTypeof => f.write_str("jsonb_typeof"),
@@ -2844,6 +3213,9 @@ pub enum VariadicFunc {
         // we need to know this to type exprs with empty lists
         elem_type: ScalarType,
     },
+    ArrayCreate {
+        elem_type: ScalarType,
+    },
     RecordCreate {
         field_names: Vec<ColumnName>,
     },
@@ -2876,9 +3248,9 @@ impl VariadicFunc {
             VariadicFunc::Replace => Ok(eager!(replace, temp_storage)),
             VariadicFunc::JsonbBuildArray => Ok(eager!(jsonb_build_array, temp_storage)),
             VariadicFunc::JsonbBuildObject => Ok(eager!(jsonb_build_object, temp_storage)),
-            VariadicFunc::ListCreate { .. } | VariadicFunc::RecordCreate { .. } => {
-                Ok(eager!(list_create, temp_storage))
-            }
+            VariadicFunc::ListCreate { .. }
+            | VariadicFunc::ArrayCreate { .. }
+            | VariadicFunc::RecordCreate { .. } => Ok(eager!(list_create, temp_storage)),
         }
     }
 
@@ -2908,6 +3280,13 @@ impl VariadicFunc {
                 );
                 ColumnType::new(ScalarType::List(Box::new(elem_type.clone())))
             }
+            ArrayCreate { elem_type } => {
+                debug_assert!(
+                    input_types.iter().all(|t| t.scalar_type == *elem_type),
+                    "Args to ArrayCreate should have types that are compatible with the elem_type"
+                );
+                ColumnType::new(ScalarType::Array(Box::new(elem_type.clone())))
+            }
             RecordCreate { field_names } => ColumnType::new(ScalarType::Record {
                 fields: field_names
                     .clone()
@@ -2927,6 +3306,7 @@ impl VariadicFunc {
             | VariadicFunc::JsonbBuildArray
             | VariadicFunc::JsonbBuildObject
             | VariadicFunc::ListCreate { .. }
+            | VariadicFunc::ArrayCreate { .. }
             | VariadicFunc::RecordCreate { .. } => false,
             _ => true,
         }
@@ -2944,6 +3324,7 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::JsonbBuildArray => f.write_str("jsonb_build_array"),
             VariadicFunc::JsonbBuildObject => f.write_str("jsonb_build_object"),
             VariadicFunc::ListCreate { .. } => f.write_str("list_create"),
+            VariadicFunc::ArrayCreate { .. } => f.write_str("array_create"),
             VariadicFunc::RecordCreate { .. } => f.write_str("record_create"),
         }
     }
@@ -12,7 +12,7 @@ use std::mem;
 
 use serde::{Deserialize, Serialize};
 
-use repr::adt::datetime::DateTimeUnits;
+use repr::adt::datetime::{DateTimeUnits, Timezone};
 use repr::adt::regex::Regex;
 use repr::strconv::ParseError;
 use repr::{ColumnType, Datum, RelationType, Row, RowArena, ScalarType};
@@ -354,6 +354,25 @@ impl ScalarExpr {
                         },
                         Err(_) => ScalarExpr::literal_null(e.typ(&relation_type)),
                     }
+                } else if (*func == BinaryFunc::TimezoneTimestamp
+                    || *func == BinaryFunc::TimezoneTimestampTz)
+                    && expr1.is_literal()
+                {
+                    let tz = expr1.as_literal_str().unwrap();
+                    *e = match tz.parse::<Timezone>() {
+                        Ok(tz) => ScalarExpr::CallUnary {
+                            func: if *func == BinaryFunc::TimezoneTimestamp {
+                                UnaryFunc::TimezoneTimestamp(tz)
+                            } else {
+                                UnaryFunc::TimezoneTimestampTz(tz)
+                            },
+                            expr: Box::new(expr2.take()),
+                        },
+                        Err(_) => ScalarExpr::literal(
+                            Err(EvalError::InvalidTimezone(tz.to_owned())),
+                            e.typ(&relation_type),
+                        ),
+                    }
                 } else if *func == BinaryFunc::And && (expr1.is_literal() || expr2.is_literal()) {
                     // If we are here, not both inputs are literals.
                     if expr1.is_literal_false() || expr2.is_literal_true() {
@@ -532,6 +551,7 @@ impl ScalarExpr {
 pub enum EvalError {
     DivisionByZero,
     NumericFieldOverflow,
+    Int16OutOfRange,
     IntegerOutOfRange,
     IntervalOutOfRange,
     InvalidEncodingName(String),
@@ -543,6 +563,12 @@ pub enum EvalError {
     UnknownUnits(String),
     UnsupportedDateTimeUnits(DateTimeUnits),
     UnterminatedLikeEscapeSequence,
+    InvalidUuidLength(usize),
+    StringValueTooLong {
+        target_type: String,
+        length: usize,
+    },
+    InvalidTimezone(String),
     Parse(ParseError),
 }
 
@@ -551,6 +577,7 @@ impl std::fmt::Display for EvalError {
         match self {
             EvalError::DivisionByZero => f.write_str("division by zero"),
             EvalError::NumericFieldOverflow => f.write_str("numeric field overflow"),
+            EvalError::Int16OutOfRange => f.write_str("smallint out of range"),
             EvalError::IntegerOutOfRange => f.write_str("integer out of range"),
             EvalError::IntervalOutOfRange => f.write_str("interval out of range"),
             EvalError::InvalidEncodingName(name) => write!(f, "invalid encoding name '{}'", name),
@@ -570,6 +597,16 @@ impl std::fmt::Display for EvalError {
             EvalError::UnterminatedLikeEscapeSequence => {
                 f.write_str("unterminated escape sequence in LIKE")
             }
+            EvalError::InvalidUuidLength(len) => write!(
+                f,
+                "invalid length for uuid: expected 16 bytes, found {}",
+                len
+            ),
+            EvalError::StringValueTooLong {
+                target_type,
+                length,
+            } => write!(f, "value too long for type {}({})", target_type, length),
+            EvalError::InvalidTimezone(tz) => write!(f, "time zone \"{}\" not recognized", tz),
             EvalError::Parse(e) => e.fmt(f),
         }
     }
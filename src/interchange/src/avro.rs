@@ -28,6 +28,7 @@ use avro::schema::{
     SchemaPieceOrNamed,
 };
 use avro::types::{DecimalValue, Value};
+use repr::adt::char;
 use repr::adt::decimal::{Significand, MAX_DECIMAL_PRECISION};
 use repr::adt::jsonb::{JsonbPacker, JsonbRef};
 use repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, RowPacker, ScalarType};
@@ -250,6 +251,7 @@ fn validate_schema_2(schema: SchemaNode) -> Result<ScalarType> {
         SchemaPiece::String | SchemaPiece::Enum { .. } => ScalarType::String,
 
         SchemaPiece::Json => ScalarType::Jsonb,
+        SchemaPiece::Uuid => ScalarType::Uuid,
 
         _ => bail!("Unsupported type in schema: {:?}", schema.inner),
     })
@@ -320,6 +322,7 @@ fn first_mismatched_schema_types<'a>(
         }
         (SchemaPiece::Fixed { size: ai }, SchemaPiece::Fixed { size: bi }) if ai == bi => None,
         (SchemaPiece::Json, SchemaPiece::Json) => None,
+        (SchemaPiece::Uuid, SchemaPiece::Uuid) => None,
         _ => Some((a, b)),
     }
 }
@@ -361,6 +364,7 @@ fn pack_value(v: Value, mut row: RowPacker, n: SchemaNode) -> Result<RowPacker>
             }
         }
         Value::Json(j) => row = JsonbPacker::new(row).pack_serde_json(j)?,
+        Value::Uuid(u) => row.push(Datum::Uuid(u)),
         other @ Value::Fixed(..)
         | other @ Value::Array(_)
         | other @ Value::Map(_)
@@ -862,55 +866,7 @@ impl Decoder {
 ///     variant is always the null variant, and the second and last variant
 ///     is the non-null variant.
 fn build_schema(columns: &[(ColumnName, ColumnType)], include_transaction: bool) -> Schema {
-    let mut fields = Vec::new();
-    for (name, typ) in columns.iter() {
-        let mut field_type = match &typ.scalar_type {
-            ScalarType::Bool => json!("boolean"),
-            ScalarType::Int32 => json!("int"),
-            ScalarType::Int64 => json!("long"),
-            ScalarType::Float32 => json!("float"),
-            ScalarType::Float64 => json!("double"),
-            ScalarType::Decimal(p, s) => json!({
-                "type": "bytes",
-                "logicalType": "decimal",
-                "precision": p,
-                "scale": s,
-            }),
-            ScalarType::Date => json!({
-                "type": "int",
-                "logicalType": "date",
-            }),
-            ScalarType::Time => json!({
-                "type": "long",
-                "logicalType": "time-micros",
-            }),
-            ScalarType::Timestamp | ScalarType::TimestampTz => json!({
-                "type": "long",
-                "connect.name": "io.debezium.time.MicroTimestamp",
-                "logicalType": "timestamp-micros"
-            }),
-            ScalarType::Interval => json!({
-                "type": "fixed",
-                "size": 12,
-                "logicalType": "duration"
-            }),
-            ScalarType::Bytes => json!("bytes"),
-            ScalarType::String => json!("string"),
-            ScalarType::Jsonb => json!({
-                "type": "string",
-                "connect.name": "io.debezium.data.Json",
-            }),
-            ScalarType::List(_t) => unimplemented!("list types"),
-            ScalarType::Record { .. } => unimplemented!("record types"),
-        };
-        if typ.nullable {
-            field_type = json!(["null", field_type]);
-        }
-        fields.push(json!({
-            "name": name,
-            "type": field_type,
-        }));
-    }
+    let fields = build_row_schema_fields(columns);
 
     let mut schema_fields = Vec::new();
     schema_fields.push(json!({
@@ -960,6 +916,79 @@ fn build_schema(columns: &[(ColumnName, ColumnType)], include_transaction: bool)
     Schema::parse(&schema).expect("valid schema constructed")
 }
 
+/// Builds a schema for a single record named `name` with the given columns,
+/// as for the keys and values of upsert sinks.
+fn build_row_schema(name: &str, columns: &[(ColumnName, ColumnType)]) -> Schema {
+    let schema = json!({
+        "type": "record",
+        "name": name,
+        "fields": build_row_schema_fields(columns),
+    });
+    Schema::parse(&schema).expect("valid schema constructed")
+}
+
+/// Builds the JSON representation of the Avro record fields for the given
+/// columns.
+fn build_row_schema_fields(columns: &[(ColumnName, ColumnType)]) -> Vec<serde_json::Value> {
+    let mut fields = Vec::new();
+    for (name, typ) in columns.iter() {
+        let mut field_type = match &typ.scalar_type {
+            ScalarType::Bool => json!("boolean"),
+            ScalarType::Int16 | ScalarType::Int32 => json!("int"),
+            ScalarType::Int64 => json!("long"),
+            ScalarType::Float32 => json!("float"),
+            ScalarType::Float64 => json!("double"),
+            ScalarType::Decimal(p, s) => json!({
+                "type": "bytes",
+                "logicalType": "decimal",
+                "precision": p,
+                "scale": s,
+            }),
+            ScalarType::Date => json!({
+                "type": "int",
+                "logicalType": "date",
+            }),
+            ScalarType::Time => json!({
+                "type": "long",
+                "logicalType": "time-micros",
+            }),
+            ScalarType::Timestamp | ScalarType::TimestampTz => json!({
+                "type": "long",
+                "connect.name": "io.debezium.time.MicroTimestamp",
+                "logicalType": "timestamp-micros"
+            }),
+            ScalarType::Interval => json!({
+                "type": "fixed",
+                "size": 12,
+                "logicalType": "duration"
+            }),
+            ScalarType::Bytes => json!("bytes"),
+            ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+                json!("string")
+            }
+            ScalarType::Jsonb => json!({
+                "type": "string",
+                "connect.name": "io.debezium.data.Json",
+            }),
+            ScalarType::Uuid => json!({
+                "type": "string",
+                "logicalType": "uuid",
+            }),
+            ScalarType::List(_t) => unimplemented!("list types"),
+            ScalarType::Array(_t) => unimplemented!("array types"),
+            ScalarType::Record { .. } => unimplemented!("record types"),
+        };
+        if typ.nullable {
+            field_type = json!(["null", field_type]);
+        }
+        fields.push(json!({
+            "name": name,
+            "type": field_type,
+        }));
+    }
+    fields
+}
+
 pub fn get_debezium_transaction_schema() -> &'static Schema {
     &DEBEZIUM_TRANSACTION_SCHEMA
 }
@@ -1001,6 +1030,37 @@ fn encode_avro_header(buf: &mut Vec<u8>, schema_id: i32) {
         .expect("writing to vec cannot fail");
 }
 
+/// Returns the columns of `desc`, inventing names for columns that don't have
+/// a name and deduplicating names that appear more than once.
+pub(crate) fn column_names_and_types(desc: RelationDesc) -> Vec<(ColumnName, ColumnType)> {
+    // Invent names for columns that don't have a name.
+    let mut columns: Vec<_> = desc
+        .into_iter()
+        .enumerate()
+        .map(|(i, (name, ty))| match name {
+            None => (ColumnName::from(format!("column{}", i + 1)), ty),
+            Some(name) => (name, ty),
+        })
+        .collect();
+
+    // Deduplicate names.
+    let mut seen = HashSet::new();
+    for (name, _ty) in &mut columns {
+        let stem_len = name.as_str().len();
+        let mut i = 1;
+        while seen.contains(name) {
+            name.as_mut_str().truncate(stem_len);
+            if name.as_str().ends_with(|c: char| c.is_ascii_digit()) {
+                name.as_mut_str().push('_');
+            }
+            name.as_mut_str().push_str(&i.to_string());
+            i += 1;
+        }
+        seen.insert(name);
+    }
+    columns
+}
+
 /// Manages encoding of Avro-encoded bytes.
 pub struct Encoder {
     columns: Vec<(ColumnName, ColumnType)>,
@@ -1018,32 +1078,7 @@ impl fmt::Debug for Encoder {
 
 impl Encoder {
     pub fn new(desc: RelationDesc, include_transaction: bool) -> Self {
-        // Invent names for columns that don't have a name.
-        let mut columns: Vec<_> = desc
-            .into_iter()
-            .enumerate()
-            .map(|(i, (name, ty))| match name {
-                None => (ColumnName::from(format!("column{}", i + 1)), ty),
-                Some(name) => (name, ty),
-            })
-            .collect();
-
-        // Deduplicate names.
-        let mut seen = HashSet::new();
-        for (name, _ty) in &mut columns {
-            let stem_len = name.as_str().len();
-            let mut i = 1;
-            while seen.contains(name) {
-                name.as_mut_str().truncate(stem_len);
-                if name.as_str().ends_with(|c: char| c.is_ascii_digit()) {
-                    name.as_mut_str().push('_');
-                }
-                name.as_mut_str().push_str(&i.to_string());
-                i += 1;
-            }
-            seen.insert(name);
-        }
-
+        let columns = column_names_and_types(desc);
         let writer_schema = build_schema(&columns, include_transaction);
         Encoder {
             columns,
@@ -1142,62 +1177,140 @@ impl Encoder {
     }
 
     fn row_to_avro(&self, row: Vec<Datum>) -> Value {
-        let fields = self
-            .columns
-            .iter()
-            .zip_eq(row)
-            .map(|((name, typ), datum)| {
-                let name = name.as_str().to_owned();
-                if typ.nullable && datum.is_null() {
-                    return (name, Value::Union(0, Box::new(Value::Null)));
+        row_to_avro(&self.columns, row)
+    }
+}
+
+/// Manages encoding of Avro-encoded bytes for upsert sinks.
+///
+/// Each update is encoded as a key, which is a record of the columns of the
+/// row at `key_indices`, and a value, which is a record of all of the columns
+/// of the row. Deletions are encoded as a key without a value, which Kafka
+/// treats as a tombstone.
+pub struct UpsertEncoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+    key_columns: Vec<(ColumnName, ColumnType)>,
+    key_indices: Vec<usize>,
+    key_schema: Schema,
+    value_schema: Schema,
+}
+
+impl fmt::Debug for UpsertEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UpsertEncoder")
+            .field("key_schema", &self.key_schema)
+            .field("value_schema", &self.value_schema)
+            .finish()
+    }
+}
+
+impl UpsertEncoder {
+    pub fn new(desc: RelationDesc, key_indices: Vec<usize>) -> Self {
+        let columns = column_names_and_types(desc);
+        let key_columns: Vec<_> = key_indices.iter().map(|i| columns[*i].clone()).collect();
+        let key_schema = build_row_schema("key", &key_columns);
+        let value_schema = build_row_schema("row", &columns);
+        UpsertEncoder {
+            columns,
+            key_columns,
+            key_indices,
+            key_schema,
+            value_schema,
+        }
+    }
+
+    pub fn key_schema(&self) -> &Schema {
+        &self.key_schema
+    }
+
+    pub fn value_schema(&self) -> &Schema {
+        &self.value_schema
+    }
+
+    /// Encodes the key columns of `row`.
+    pub fn encode_key_unchecked(&self, schema_id: i32, row: &Row) -> Vec<u8> {
+        let datums = row.unpack();
+        let key = self.key_indices.iter().map(|i| datums[*i]).collect();
+        let mut buf = Vec::new();
+        encode_avro_header(&mut buf, schema_id);
+        let avro = row_to_avro(&self.key_columns, key);
+        debug_assert!(avro.validate(self.key_schema.top_node()));
+        avro::encode_unchecked(&avro, &self.key_schema, &mut buf);
+        buf
+    }
+
+    /// Encodes all columns of `row`.
+    pub fn encode_value_unchecked(&self, schema_id: i32, row: &Row) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_avro_header(&mut buf, schema_id);
+        let avro = row_to_avro(&self.columns, row.unpack());
+        debug_assert!(avro.validate(self.value_schema.top_node()));
+        avro::encode_unchecked(&avro, &self.value_schema, &mut buf);
+        buf
+    }
+}
+
+fn row_to_avro(columns: &[(ColumnName, ColumnType)], row: Vec<Datum>) -> Value {
+    let fields = columns
+        .iter()
+        .zip_eq(row)
+        .map(|((name, typ), datum)| {
+            let name = name.as_str().to_owned();
+            if typ.nullable && datum.is_null() {
+                return (name, Value::Union(0, Box::new(Value::Null)));
+            }
+            let mut val = match &typ.scalar_type {
+                ScalarType::Bool => Value::Boolean(datum.unwrap_bool()),
+                ScalarType::Int16 => Value::Int(i32::from(datum.unwrap_int16())),
+                ScalarType::Int32 => Value::Int(datum.unwrap_int32()),
+                ScalarType::Int64 => Value::Long(datum.unwrap_int64()),
+                ScalarType::Float32 => Value::Float(datum.unwrap_float32()),
+                ScalarType::Float64 => Value::Double(datum.unwrap_float64()),
+                ScalarType::Decimal(p, s) => Value::Decimal(DecimalValue {
+                    unscaled: datum.unwrap_decimal().as_i128().to_be_bytes().to_vec(),
+                    precision: (*p).into(),
+                    scale: (*s).into(),
+                }),
+                ScalarType::Date => Value::Date(datum.unwrap_date()),
+                ScalarType::Time => Value::Long({
+                    let time = datum.unwrap_time();
+                    (time.num_seconds_from_midnight() * 1_000_000) as i64
+                        + (time.nanosecond() as i64) / 1_000
+                }),
+                ScalarType::Timestamp => Value::Timestamp(datum.unwrap_timestamp()),
+                ScalarType::TimestampTz => Value::Timestamp(datum.unwrap_timestamptz().naive_utc()),
+                // This feature isn't actually supported by the Avro Java
+                // client (https://issues.apache.org/jira/browse/AVRO-2123),
+                // so no one is likely to be using it, so we're just using
+                // our own very convenient format.
+                ScalarType::Interval => Value::Fixed(20, {
+                    let iv = datum.unwrap_interval();
+                    let mut buf = Vec::with_capacity(24);
+                    buf.extend(&iv.months.to_le_bytes());
+                    buf.extend(&iv.duration.to_le_bytes());
+                    debug_assert_eq!(buf.len(), 20);
+                    buf
+                }),
+                ScalarType::Bytes => Value::Bytes(Vec::from(datum.unwrap_bytes())),
+                ScalarType::String | ScalarType::VarChar { .. } => {
+                    Value::String(datum.unwrap_str().to_owned())
                 }
-                let mut val = match &typ.scalar_type {
-                    ScalarType::Bool => Value::Boolean(datum.unwrap_bool()),
-                    ScalarType::Int32 => Value::Int(datum.unwrap_int32()),
-                    ScalarType::Int64 => Value::Long(datum.unwrap_int64()),
-                    ScalarType::Float32 => Value::Float(datum.unwrap_float32()),
-                    ScalarType::Float64 => Value::Double(datum.unwrap_float64()),
-                    ScalarType::Decimal(p, s) => Value::Decimal(DecimalValue {
-                        unscaled: datum.unwrap_decimal().as_i128().to_be_bytes().to_vec(),
-                        precision: (*p).into(),
-                        scale: (*s).into(),
-                    }),
-                    ScalarType::Date => Value::Date(datum.unwrap_date()),
-                    ScalarType::Time => Value::Long({
-                        let time = datum.unwrap_time();
-                        (time.num_seconds_from_midnight() * 1_000_000) as i64
-                            + (time.nanosecond() as i64) / 1_000
-                    }),
-                    ScalarType::Timestamp => Value::Timestamp(datum.unwrap_timestamp()),
-                    ScalarType::TimestampTz => {
-                        Value::Timestamp(datum.unwrap_timestamptz().naive_utc())
-                    }
-                    // This feature isn't actually supported by the Avro Java
-                    // client (https://issues.apache.org/jira/browse/AVRO-2123),
-                    // so no one is likely to be using it, so we're just using
-                    // our own very convenient format.
-                    ScalarType::Interval => Value::Fixed(20, {
-                        let iv = datum.unwrap_interval();
-                        let mut buf = Vec::with_capacity(24);
-                        buf.extend(&iv.months.to_le_bytes());
-                        buf.extend(&iv.duration.to_le_bytes());
-                        debug_assert_eq!(buf.len(), 20);
-                        buf
-                    }),
-                    ScalarType::Bytes => Value::Bytes(Vec::from(datum.unwrap_bytes())),
-                    ScalarType::String => Value::String(datum.unwrap_str().to_owned()),
-                    ScalarType::Jsonb => Value::Json(JsonbRef::from_datum(datum).to_serde_json()),
-                    ScalarType::List(_t) => unimplemented!("list types"),
-                    ScalarType::Record { .. } => unimplemented!("record types"),
-                };
-                if typ.nullable {
-                    val = Value::Union(1, Box::new(val));
+                ScalarType::Char { length } => {
+                    Value::String(char::pad(datum.unwrap_str(), *length))
                 }
-                (name, val)
-            })
-            .collect();
-        Value::Record(fields)
-    }
+                ScalarType::Jsonb => Value::Json(JsonbRef::from_datum(datum).to_serde_json()),
+                ScalarType::Uuid => Value::Uuid(datum.unwrap_uuid()),
+                ScalarType::List(_t) => unimplemented!("list types"),
+                ScalarType::Array(_t) => unimplemented!("array types"),
+                ScalarType::Record { .. } => unimplemented!("record types"),
+            };
+            if typ.nullable {
+                val = Value::Union(1, Box::new(val));
+            }
+            (name, val)
+        })
+        .collect();
+    Value::Record(fields)
 }
 
 struct SchemaCache {
@@ -1303,6 +1416,7 @@ mod tests {
         let valid_pairings = vec![
             (ScalarType::Bool, Datum::True, Value::Boolean(true)),
             (ScalarType::Bool, Datum::False, Value::Boolean(false)),
+            (ScalarType::Int16, Datum::Int16(1), Value::Int(1)),
             (ScalarType::Int32, Datum::Int32(1), Value::Int(1)),
             (ScalarType::Int64, Datum::Int64(1), Value::Long(1)),
             (
@@ -1357,4 +1471,34 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_upsert_encoder() -> Result<()> {
+        let desc = RelationDesc::empty()
+            .with_nonnull_column("a", ScalarType::Int32)
+            .with_column("b", ColumnType::new(ScalarType::String).nullable(true));
+        let encoder = UpsertEncoder::new(desc, vec![0]);
+        let row = Row::pack(&[Datum::Int32(1), Datum::String("one")]);
+
+        let key = encoder.encode_key_unchecked(7, &row);
+        assert_eq!(&key[..5], &[0, 0, 0, 0, 7]);
+        assert_eq!(
+            avro::from_avro_datum(encoder.key_schema(), &mut &key[5..])?,
+            Value::Record(vec![("a".into(), Value::Int(1))])
+        );
+
+        let value = encoder.encode_value_unchecked(8, &row);
+        assert_eq!(&value[..5], &[0, 0, 0, 0, 8]);
+        assert_eq!(
+            avro::from_avro_datum(encoder.value_schema(), &mut &value[5..])?,
+            Value::Record(vec![
+                ("a".into(), Value::Int(1)),
+                (
+                    "b".into(),
+                    Value::Union(1, Box::new(Value::String("one".into())))
+                ),
+            ])
+        );
+        Ok(())
+    }
 }
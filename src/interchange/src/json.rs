@@ -0,0 +1,293 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! JSON sink encoding.
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::value::RawValue;
+
+use repr::adt::char;
+use repr::adt::jsonb::JsonbRef;
+use repr::{strconv, ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+
+use crate::avro::{self, DiffPair};
+
+/// Manages encoding of JSON-encoded bytes.
+///
+/// Each row is encoded as a JSON object that maps column names to values.
+/// Booleans and numbers are encoded as JSON booleans and numbers, `jsonb`
+/// values are embedded as-is, lists and records are encoded as JSON arrays and
+/// objects, and all other values are encoded as JSON strings containing their
+/// text representation. Numbers are written with the same digits as their text
+/// representation, so decimals do not lose precision. Floating-point values
+/// that JSON cannot represent, like `NaN`, are encoded as strings.
+///
+/// Both SQL `NULL` and the `jsonb` value `null` are encoded as JSON `null`, and
+/// so cannot be distinguished in the output.
+#[derive(Debug)]
+pub struct Encoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+    value_indices: Vec<usize>,
+}
+
+impl Encoder {
+    pub fn new(desc: RelationDesc) -> Encoder {
+        let columns = avro::column_names_and_types(desc);
+        let value_indices = (0..columns.len()).collect();
+        Encoder {
+            columns,
+            value_indices,
+        }
+    }
+
+    /// Encodes an update as a Debezium-style object with `before` and `after`
+    /// fields, either of which may be null.
+    pub fn encode_diff_pair(&self, diff_pair: DiffPair<&Row>) -> Vec<u8> {
+        #[derive(serde::Serialize)]
+        struct JsonDiffPair<'a> {
+            before: Option<JsonRow<'a>>,
+            after: Option<JsonRow<'a>>,
+        }
+
+        let diff_pair = JsonDiffPair {
+            before: diff_pair
+                .before
+                .map(|row| self.json_row(row, &self.value_indices)),
+            after: diff_pair
+                .after
+                .map(|row| self.json_row(row, &self.value_indices)),
+        };
+        serde_json::to_vec(&diff_pair).expect("JSON encoding cannot fail")
+    }
+
+    /// Encodes all columns of `row`.
+    pub fn encode_row(&self, row: &Row) -> Vec<u8> {
+        serde_json::to_vec(&self.json_row(row, &self.value_indices))
+            .expect("JSON encoding cannot fail")
+    }
+
+    /// Encodes an update to `row` at `timestamp` with multiplicity `diff` as
+    /// an object with `timestamp`, `diff`, and `row` fields.
+    pub fn encode_update(&self, row: &Row, timestamp: u64, diff: i64) -> Vec<u8> {
+        #[derive(serde::Serialize)]
+        struct JsonUpdate<'a> {
+            timestamp: u64,
+            diff: i64,
+            row: JsonRow<'a>,
+        }
+
+        let update = JsonUpdate {
+            timestamp,
+            diff,
+            row: self.json_row(row, &self.value_indices),
+        };
+        serde_json::to_vec(&update).expect("JSON encoding cannot fail")
+    }
+
+    /// Encodes the columns of `row` at `key_indices`, for use as the key of
+    /// an upsert message.
+    pub fn encode_key(&self, row: &Row, key_indices: &[usize]) -> Vec<u8> {
+        serde_json::to_vec(&self.json_row(row, key_indices)).expect("JSON encoding cannot fail")
+    }
+
+    fn json_row<'a>(&'a self, row: &'a Row, indices: &'a [usize]) -> JsonRow<'a> {
+        JsonRow {
+            columns: &self.columns,
+            datums: row.unpack(),
+            indices,
+        }
+    }
+}
+
+/// A projection of a row that serializes as a JSON object.
+struct JsonRow<'a> {
+    columns: &'a [(ColumnName, ColumnType)],
+    datums: Vec<Datum<'a>>,
+    indices: &'a [usize],
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.indices.len()))?;
+        for &i in self.indices {
+            let (name, typ) = &self.columns[i];
+            map.serialize_entry(
+                name.as_str(),
+                &JsonDatum {
+                    datum: self.datums[i],
+                    typ: &typ.scalar_type,
+                },
+            )?;
+        }
+        map.end()
+    }
+}
+
+/// A datum of a known type that serializes as a JSON value.
+struct JsonDatum<'a> {
+    datum: Datum<'a>,
+    typ: &'a ScalarType,
+}
+
+impl Serialize for JsonDatum<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf = String::new();
+        match (self.datum, self.typ) {
+            (Datum::Null, _) => return serializer.serialize_none(),
+            (datum, ScalarType::Jsonb) => {
+                return JsonbRef::from_datum(datum)
+                    .to_serde_json()
+                    .serialize(serializer)
+            }
+            (Datum::True, _) => return serializer.serialize_bool(true),
+            (Datum::False, _) => return serializer.serialize_bool(false),
+            (Datum::Int16(i), _) => return serializer.serialize_i16(i),
+            (Datum::Int32(i), _) => return serializer.serialize_i32(i),
+            (Datum::Int64(i), _) => return serializer.serialize_i64(i),
+            (Datum::Float32(f), _) => {
+                strconv::format_float32(&mut buf, *f);
+                return serialize_number(serializer, &buf);
+            }
+            (Datum::Float64(f), _) => {
+                strconv::format_float64(&mut buf, *f);
+                return serialize_number(serializer, &buf);
+            }
+            (Datum::Decimal(d), ScalarType::Decimal(_, scale)) => {
+                strconv::format_decimal(&mut buf, &d.with_scale(*scale));
+                return serialize_number(serializer, &buf);
+            }
+            (Datum::String(s), ScalarType::Char { length }) => {
+                return serializer.serialize_str(&char::pad(s, *length))
+            }
+            (Datum::String(s), _) => return serializer.serialize_str(s),
+            (Datum::List(list), ScalarType::List(typ))
+            | (Datum::List(list), ScalarType::Array(typ)) => {
+                return serializer
+                    .collect_seq(list.iter().map(|datum| JsonDatum { datum, typ: &**typ }))
+            }
+            (Datum::List(list), ScalarType::Record { fields }) => {
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (datum, (name, typ)) in list.iter().zip(fields) {
+                    map.serialize_entry(name.as_str(), &JsonDatum { datum, typ })?;
+                }
+                return map.end();
+            }
+            (Datum::Date(d), _) => strconv::format_date(&mut buf, d),
+            (Datum::Time(t), _) => strconv::format_time(&mut buf, t),
+            (Datum::Timestamp(ts), _) => strconv::format_timestamp(&mut buf, ts),
+            (Datum::TimestampTz(ts), _) => strconv::format_timestamptz(&mut buf, ts),
+            (Datum::Interval(iv), _) => strconv::format_interval(&mut buf, iv),
+            (Datum::Bytes(b), _) => strconv::format_bytes(&mut buf, b),
+            (Datum::Uuid(u), _) => strconv::format_uuid(&mut buf, u),
+            (datum, typ) => unreachable!("datum {:?} does not match type {}", datum, typ),
+        };
+        serializer.serialize_str(&buf)
+    }
+}
+
+/// Serializes the text representation of a number as a JSON number, or as a
+/// JSON string if JSON cannot represent the number.
+///
+/// The text is embedded verbatim, rather than converted to an `f64`, which
+/// would round decimals with more than 17 significant digits.
+fn serialize_number<S>(serializer: S, text: &str) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match RawValue::from_string(text.to_owned()) {
+        Ok(number) => number.serialize(serializer),
+        Err(_) => serializer.serialize_str(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use repr::adt::decimal::Significand;
+    use repr::adt::jsonb::Jsonb;
+    use repr::{ColumnType, Datum, RelationDesc, RelationType, Row, ScalarType};
+
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let desc = RelationDesc::new(
+            RelationType::new(vec![
+                ColumnType::new(ScalarType::Int32),
+                ColumnType::new(ScalarType::String).nullable(true),
+                ColumnType::new(ScalarType::Decimal(10, 2)),
+                ColumnType::new(ScalarType::Float64),
+                ColumnType::new(ScalarType::Date),
+                ColumnType::new(ScalarType::Jsonb),
+            ]),
+            vec![Some("a"), Some("b"), None, Some("f"), Some("d"), Some("j")],
+        );
+        let encoder = Encoder::new(desc);
+
+        let jsonb = r#"{"x": [1, "y"]}"#.parse::<Jsonb>().unwrap().into_row();
+        let row = Row::pack(&[
+            Datum::Int32(1),
+            Datum::Null,
+            Datum::Decimal(Significand::new(1234)),
+            Datum::Float64(f64::NAN.into()),
+            Datum::Date(chrono::NaiveDate::from_ymd(2020, 1, 2)),
+            jsonb.unpack_first(),
+        ]);
+        let other = Row::pack(&[
+            Datum::Int32(2),
+            Datum::String("hello"),
+            Datum::Decimal(Significand::new(-5)),
+            Datum::Float64(1.5.into()),
+            Datum::Date(chrono::NaiveDate::from_ymd(2020, 1, 3)),
+            Datum::JsonNull,
+        ]);
+
+        assert_eq!(
+            String::from_utf8(encoder.encode_row(&row)).unwrap(),
+            r#"{"a":1,"b":null,"column3":12.34,"f":"NaN","d":"2020-01-02","j":{"x":[1.0,"y"]}}"#,
+        );
+        assert_eq!(
+            String::from_utf8(encoder.encode_diff_pair(DiffPair {
+                before: Some(&other),
+                after: None,
+            }))
+            .unwrap(),
+            r#"{"before":{"a":2,"b":"hello","column3":-0.05,"f":1.5,"d":"2020-01-03","j":null},"after":null}"#,
+        );
+        assert_eq!(
+            String::from_utf8(encoder.encode_key(&other, &[1, 0])).unwrap(),
+            r#"{"b":"hello","a":2}"#,
+        );
+        assert_eq!(
+            String::from_utf8(encoder.encode_update(&other, 7, -1)).unwrap(),
+            r#"{"timestamp":7,"diff":-1,"row":{"a":2,"b":"hello","column3":-0.05,"f":1.5,"d":"2020-01-03","j":null}}"#,
+        );
+    }
+
+    #[test]
+    fn test_encode_exact_decimal() {
+        let desc = RelationDesc::new(
+            RelationType::new(vec![ColumnType::new(ScalarType::Decimal(38, 2))]),
+            vec![Some("d")],
+        );
+        let encoder = Encoder::new(desc);
+        let row = Row::pack(&[Datum::Decimal(Significand::new(
+            12345678901234567890123456789,
+        ))]);
+        assert_eq!(
+            String::from_utf8(encoder.encode_row(&row)).unwrap(),
+            r#"{"d":123456789012345678901234567.89}"#,
+        );
+    }
+}
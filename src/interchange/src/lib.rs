@@ -12,5 +12,7 @@
 #![deny(missing_debug_implementations)]
 
 pub mod avro;
+pub mod csv;
 mod error;
+pub mod json;
 pub mod protobuf;
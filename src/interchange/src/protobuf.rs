@@ -7,11 +7,15 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-//! Protobuf source connector
+//! Protobuf source and sink connectors
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
 
 use failure::{bail, format_err, ResultExt};
 use num_traits::ToPrimitive;
 use ordered_float::OrderedFloat;
+use protobuf::CodedOutputStream;
 use serde::de::Deserialize;
 use serde_protobuf::de::Deserializer;
 use serde_protobuf::descriptor::{
@@ -23,6 +27,7 @@ use serde_value::Value as SerdeValue;
 use repr::adt::decimal::Significand;
 use repr::{ColumnType, Datum, DatumList, RelationDesc, RelationType, Row, RowPacker, ScalarType};
 
+use crate::avro::DiffPair;
 use crate::error::Result;
 
 pub mod test_util;
@@ -194,6 +199,280 @@ impl Decoder {
     }
 }
 
+/// The type of a protobuf field that a column is encoded into.
+#[derive(Debug)]
+enum EncodedFieldType {
+    Bool,
+    Int32,
+    SInt32,
+    SFixed32,
+    Int64,
+    SInt64,
+    SFixed64,
+    UInt32,
+    Fixed32,
+    UInt64,
+    Fixed64,
+    Float,
+    Double,
+    String,
+    Bytes,
+    /// An enum, whose values are encoded from their names.
+    Enum(HashMap<String, i32>),
+}
+
+impl EncodedFieldType {
+    fn from_field(field: &FieldDescriptor, descriptors: &Descriptors) -> Result<EncodedFieldType> {
+        match field.field_label() {
+            FieldLabel::Required => bail!("Required field {} not supported", field.name()),
+            FieldLabel::Repeated => bail!(
+                "Repeated field {} not supported in protobuf sinks",
+                field.name()
+            ),
+            FieldLabel::Optional => (),
+        }
+        Ok(match field.field_type(descriptors) {
+            FieldType::Bool => EncodedFieldType::Bool,
+            FieldType::Int32 => EncodedFieldType::Int32,
+            FieldType::SInt32 => EncodedFieldType::SInt32,
+            FieldType::SFixed32 => EncodedFieldType::SFixed32,
+            FieldType::Int64 => EncodedFieldType::Int64,
+            FieldType::SInt64 => EncodedFieldType::SInt64,
+            FieldType::SFixed64 => EncodedFieldType::SFixed64,
+            FieldType::UInt32 => EncodedFieldType::UInt32,
+            FieldType::Fixed32 => EncodedFieldType::Fixed32,
+            FieldType::UInt64 => EncodedFieldType::UInt64,
+            FieldType::Fixed64 => EncodedFieldType::Fixed64,
+            FieldType::Float => EncodedFieldType::Float,
+            FieldType::Double => EncodedFieldType::Double,
+            FieldType::String => EncodedFieldType::String,
+            FieldType::Bytes => EncodedFieldType::Bytes,
+            FieldType::Enum(e) => EncodedFieldType::Enum(
+                e.values()
+                    .iter()
+                    .map(|v| (v.name().to_owned(), v.number()))
+                    .collect(),
+            ),
+            FieldType::Message(m) => bail!(
+                "Nested message field {} of type {} not supported in protobuf sinks",
+                field.name(),
+                m.name()
+            ),
+            FieldType::Group => bail!("Unions are currently not supported"),
+            FieldType::UnresolvedMessage(m) => bail!("Unresolved message {} not supported", m),
+            FieldType::UnresolvedEnum(e) => bail!("Unresolved enum {} not supported", e),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            EncodedFieldType::Bool => "bool",
+            EncodedFieldType::Int32 => "int32",
+            EncodedFieldType::SInt32 => "sint32",
+            EncodedFieldType::SFixed32 => "sfixed32",
+            EncodedFieldType::Int64 => "int64",
+            EncodedFieldType::SInt64 => "sint64",
+            EncodedFieldType::SFixed64 => "sfixed64",
+            EncodedFieldType::UInt32 => "uint32",
+            EncodedFieldType::Fixed32 => "fixed32",
+            EncodedFieldType::UInt64 => "uint64",
+            EncodedFieldType::Fixed64 => "fixed64",
+            EncodedFieldType::Float => "float",
+            EncodedFieldType::Double => "double",
+            EncodedFieldType::String => "string",
+            EncodedFieldType::Bytes => "bytes",
+            EncodedFieldType::Enum(_) => "enum",
+        }
+    }
+
+    /// Reports whether a column of type `scalar_type` can be encoded into a
+    /// field of this type. The compatible types are those that
+    /// [`validate_descriptors`] would assign to a field of this type.
+    fn accepts(&self, scalar_type: &ScalarType) -> bool {
+        match (self, scalar_type) {
+            (EncodedFieldType::Bool, ScalarType::Bool)
+            | (EncodedFieldType::Int32, ScalarType::Int32)
+            | (EncodedFieldType::SInt32, ScalarType::Int32)
+            | (EncodedFieldType::SFixed32, ScalarType::Int32)
+            | (EncodedFieldType::Int64, ScalarType::Int64)
+            | (EncodedFieldType::SInt64, ScalarType::Int64)
+            | (EncodedFieldType::SFixed64, ScalarType::Int64)
+            | (EncodedFieldType::UInt32, ScalarType::Decimal(_, 0))
+            | (EncodedFieldType::Fixed32, ScalarType::Decimal(_, 0))
+            | (EncodedFieldType::UInt64, ScalarType::Decimal(_, 0))
+            | (EncodedFieldType::Fixed64, ScalarType::Decimal(_, 0))
+            | (EncodedFieldType::Float, ScalarType::Float32)
+            | (EncodedFieldType::Double, ScalarType::Float64)
+            | (EncodedFieldType::String, ScalarType::String)
+            | (EncodedFieldType::String, ScalarType::VarChar { .. })
+            | (EncodedFieldType::Bytes, ScalarType::Bytes)
+            | (EncodedFieldType::Enum(_), ScalarType::String) => true,
+            _ => false,
+        }
+    }
+}
+
+/// A protobuf field that a column is encoded into.
+#[derive(Debug)]
+struct EncodedField {
+    name: String,
+    number: u32,
+    typ: EncodedFieldType,
+}
+
+/// Manages required metadata to write protobuf.
+///
+/// Each update is written as an envelope message that holds the encoded row
+/// in its `before` field, for retractions, or in its `after` field, for
+/// insertions, as if described by:
+///
+/// ```protobuf
+/// message Envelope {
+///     Row before = 1;
+///     Row after = 2;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Encoder {
+    fields: Vec<EncodedField>,
+}
+
+impl Encoder {
+    /// Builds an encoder for rows described by `desc`.
+    ///
+    /// Each column of `desc` must share its name with an optional, scalar
+    /// field of the message `message_name` whose type is compatible with the
+    /// column's type. Fields of the message without a corresponding column are
+    /// left unset.
+    pub fn new(
+        desc: &RelationDesc,
+        message_name: &str,
+        descriptors: &Descriptors,
+    ) -> Result<Encoder> {
+        let proto_name = proto_message_name(message_name);
+        let message = descriptors.message_by_name(&proto_name).ok_or_else(|| {
+            format_err!("Message {:?} not found in file descriptor set", proto_name)
+        })?;
+        let mut fields = vec![];
+        for (name, column_type) in desc.iter() {
+            let name = match name {
+                Some(name) => name.as_str(),
+                None => bail!("protobuf sinks require all columns to be named"),
+            };
+            let field = message
+                .fields()
+                .iter()
+                .find(|f| f.name() == name)
+                .ok_or_else(|| {
+                    format_err!(
+                        "column {} has no corresponding field in message {}",
+                        name,
+                        proto_name
+                    )
+                })?;
+            let typ = EncodedFieldType::from_field(field, descriptors)?;
+            if !typ.accepts(&column_type.scalar_type) {
+                bail!(
+                    "column {} of type {} cannot be encoded into field {} of type {}",
+                    name,
+                    column_type.scalar_type,
+                    field.name(),
+                    typ.name()
+                );
+            }
+            fields.push(EncodedField {
+                name: name.to_owned(),
+                number: u32::try_from(field.number())?,
+                typ,
+            });
+        }
+        Ok(Encoder { fields })
+    }
+
+    /// Encodes an update into an envelope message.
+    pub fn encode(&self, diff_pair: DiffPair<&Row>) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        {
+            let mut os = CodedOutputStream::vec(&mut buf);
+            if let Some(before) = diff_pair.before {
+                os.write_bytes(1, &self.encode_row(before)?)?;
+            }
+            if let Some(after) = diff_pair.after {
+                os.write_bytes(2, &self.encode_row(after)?)?;
+            }
+            os.flush()?;
+        }
+        Ok(buf)
+    }
+
+    fn encode_row(&self, row: &Row) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        {
+            let mut os = CodedOutputStream::vec(&mut buf);
+            for (field, datum) in self.fields.iter().zip(row.iter()) {
+                // Null columns are encoded as unset fields.
+                if datum.is_null() {
+                    continue;
+                }
+                let n = field.number;
+                match &field.typ {
+                    EncodedFieldType::Bool => os.write_bool(n, datum.unwrap_bool())?,
+                    EncodedFieldType::Int32 => os.write_int32(n, datum.unwrap_int32())?,
+                    EncodedFieldType::SInt32 => os.write_sint32(n, datum.unwrap_int32())?,
+                    EncodedFieldType::SFixed32 => os.write_sfixed32(n, datum.unwrap_int32())?,
+                    EncodedFieldType::Int64 => os.write_int64(n, datum.unwrap_int64())?,
+                    EncodedFieldType::SInt64 => os.write_sint64(n, datum.unwrap_int64())?,
+                    EncodedFieldType::SFixed64 => os.write_sfixed64(n, datum.unwrap_int64())?,
+                    EncodedFieldType::UInt32 => {
+                        os.write_uint32(n, unsigned_from_datum(datum, field)?)?
+                    }
+                    EncodedFieldType::Fixed32 => {
+                        os.write_fixed32(n, unsigned_from_datum(datum, field)?)?
+                    }
+                    EncodedFieldType::UInt64 => {
+                        os.write_uint64(n, unsigned_from_datum(datum, field)?)?
+                    }
+                    EncodedFieldType::Fixed64 => {
+                        os.write_fixed64(n, unsigned_from_datum(datum, field)?)?
+                    }
+                    EncodedFieldType::Float => os.write_float(n, datum.unwrap_float32())?,
+                    EncodedFieldType::Double => os.write_double(n, datum.unwrap_float64())?,
+                    EncodedFieldType::String => os.write_string(n, datum.unwrap_str())?,
+                    EncodedFieldType::Bytes => os.write_bytes(n, datum.unwrap_bytes())?,
+                    EncodedFieldType::Enum(values) => {
+                        let value = datum.unwrap_str();
+                        match values.get(value) {
+                            Some(number) => os.write_enum(n, *number)?,
+                            None => {
+                                bail!("{:?} is not a value of enum field {}", value, field.name)
+                            }
+                        }
+                    }
+                }
+            }
+            os.flush()?;
+        }
+        Ok(buf)
+    }
+}
+
+/// Converts a decimal datum with no fractional digits into an unsigned
+/// integer, as encoded into unsigned protobuf fields.
+fn unsigned_from_datum<T>(datum: Datum, field: &EncodedField) -> Result<T>
+where
+    T: TryFrom<i128>,
+{
+    let value = datum.unwrap_decimal().as_i128();
+    T::try_from(value).map_err(|_| {
+        format_err!(
+            "value {} out of range for {} field {}",
+            value,
+            field.typ.name(),
+            field.name
+        )
+    })
+}
+
 fn extract_row_into(
     deserialized_message: SerdeValue,
     descriptors: &Descriptors,
@@ -457,14 +736,16 @@ mod tests {
     };
     use failure::{bail, Error};
     use protobuf::descriptor::{FileDescriptorProto, FileDescriptorSet};
-    use protobuf::{Message, RepeatedField};
+    use protobuf::{CodedInputStream, Message, RepeatedField};
     use serde_protobuf::descriptor::{
         Descriptors, FieldDescriptor, FieldLabel, FieldType, InternalFieldType, MessageDescriptor,
     };
 
     use ordered_float::OrderedFloat;
     use repr::adt::decimal::Significand;
-    use repr::{Datum, DatumList, RelationDesc, ScalarType};
+    use repr::{Datum, DatumList, RelationDesc, Row, ScalarType};
+
+    use crate::avro::DiffPair;
 
     fn sanity_check_relation(
         relation: &RelationDesc,
@@ -588,7 +869,7 @@ mod tests {
         Ok(())
     }
 
-    fn get_decoder(message_name: &str) -> super::Decoder {
+    fn get_descriptors() -> Descriptors {
         let mut repeated_field = RepeatedField::<FileDescriptorProto>::new();
         let file_descriptor_proto = file_descriptor_proto().clone();
         repeated_field.push(file_descriptor_proto);
@@ -596,7 +877,11 @@ mod tests {
         let mut file_descriptor_set: FileDescriptorSet = FileDescriptorSet::new();
         file_descriptor_set.set_file(repeated_field);
 
-        let descriptors = Descriptors::from_proto(&file_descriptor_set);
+        Descriptors::from_proto(&file_descriptor_set)
+    }
+
+    fn get_decoder(message_name: &str) -> super::Decoder {
+        let descriptors = get_descriptors();
         let relation = super::validate_descriptors(message_name, &descriptors)
             .expect("Failed to parse descriptor");
 
@@ -861,4 +1146,97 @@ mod tests {
             assert_eq!(d, Datum::List(DatumList::empty()));
         }
     }
+
+    #[test]
+    fn test_encode() {
+        let descriptors = get_descriptors();
+        let desc = super::validate_descriptors(".TestRecord", &descriptors)
+            .expect("Failed to parse descriptor");
+        let encoder = super::Encoder::new(&desc, ".TestRecord", &descriptors)
+            .expect("Failed to build encoder");
+
+        let row = Row::pack(vec![
+            Datum::Int32(1),
+            Datum::String("one"),
+            Datum::Null,
+            Datum::String("BLUE"),
+            Datum::Decimal(Significand::new(5)),
+            Datum::Decimal(Significand::new(55)),
+            Datum::Float32(OrderedFloat::from(5.456)),
+            Datum::Float64(OrderedFloat::from(99.99)),
+        ]);
+
+        // Insertions are written to the `after` field of the envelope, and
+        // retractions to the `before` field.
+        for (diff_pair, expected_field) in vec![
+            (
+                DiffPair {
+                    before: None,
+                    after: Some(&row),
+                },
+                2,
+            ),
+            (
+                DiffPair {
+                    before: Some(&row),
+                    after: None,
+                },
+                1,
+            ),
+        ] {
+            let bytes = encoder.encode(diff_pair).expect("encoding failed");
+            let mut is = CodedInputStream::from_bytes(&bytes);
+            let (field, _) = is.read_tag_unpack().expect("reading tag failed");
+            assert_eq!(field, expected_field);
+            let message = is.read_bytes().expect("reading message failed");
+            assert!(is.eof().unwrap());
+
+            let decoded = get_decoder(".TestRecord")
+                .decode(&message)
+                .expect("deserialize protobuf into a row")
+                .unwrap();
+            let datums = decoded.iter().collect::<Vec<_>>();
+
+            // Null columns are left unset, and so decode as the default value.
+            let expected = vec![
+                Datum::Int32(1),
+                Datum::String("one"),
+                Datum::Int64(0),
+                Datum::String("BLUE"),
+                Datum::Decimal(Significand::new(5)),
+                Datum::Decimal(Significand::new(55)),
+                Datum::Float32(OrderedFloat::from(5.456)),
+                Datum::Float64(OrderedFloat::from(99.99)),
+            ];
+            assert_eq!(datums, expected);
+        }
+    }
+
+    #[test]
+    fn test_encode_validation() {
+        let descriptors = get_descriptors();
+
+        let desc = RelationDesc::empty().with_nonnull_column("int_field", ScalarType::Int32);
+        assert!(super::Encoder::new(&desc, ".TestRecord", &descriptors).is_ok());
+
+        let desc = RelationDesc::empty().with_nonnull_column("int_field", ScalarType::Int64);
+        assert!(super::Encoder::new(&desc, ".TestRecord", &descriptors).is_err());
+
+        let desc = RelationDesc::empty().with_nonnull_column("no_such_field", ScalarType::Int32);
+        assert!(super::Encoder::new(&desc, ".TestRecord", &descriptors).is_err());
+
+        let desc = RelationDesc::empty().with_nonnull_column("int_field", ScalarType::Jsonb);
+        assert!(super::Encoder::new(&desc, ".TestRepeatedRecord", &descriptors).is_err());
+
+        let desc =
+            RelationDesc::empty().with_nonnull_column("uint_field", ScalarType::Decimal(10, 0));
+        let encoder = super::Encoder::new(&desc, ".TestRecord", &descriptors).unwrap();
+        let row = Row::pack(vec![Datum::Decimal(Significand::new(-1))]);
+        assert!(encoder
+            .encode(DiffPair {
+                before: None,
+                after: Some(&row),
+            })
+            .is_err());
+    }
 }
@@ -142,6 +142,18 @@ fn run() -> Result<(), failure::Error> {
         "PATH",
     );
     opts.optopt("", "tls-key", "private key for TLS connections", "PATH");
+    opts.optopt(
+        "",
+        "auth",
+        "how clients must authenticate: trust, password, or scram-sha-256 (default trust)",
+        "METHOD",
+    );
+    opts.optopt(
+        "",
+        "auth-users-file",
+        "text file whose lines are USER:PASSWORD or USER:SCRAM-VERIFIER pairs",
+        "PATH",
+    );
 
     // Storage options.
     opts.optopt(
@@ -253,6 +265,29 @@ fn run() -> Result<(), failure::Error> {
             key: key.into(),
         }),
     };
+    let auth = match (
+        popts.opt_str("auth").as_deref(),
+        popts.opt_str("auth-users-file"),
+    ) {
+        (None, None) | (Some("trust"), None) => None,
+        (None, Some(_)) | (Some("trust"), Some(_)) => {
+            bail!("--auth-users-file requires --auth=password or --auth=scram-sha-256");
+        }
+        (Some(_), None) => bail!("--auth requires --auth-users-file"),
+        (Some(method), Some(users_file)) => {
+            let method: pgwire::AuthMethod = method.parse()?;
+            if method == pgwire::AuthMethod::Password && tls.is_none() {
+                bail!(
+                    "--auth=password requires TLS, as passwords are sent in cleartext\n\
+                     hint: Specify --tls-cert and --tls-key, or use --auth=scram-sha-256."
+                );
+            }
+            Some(materialized::AuthConfig {
+                method,
+                users_file: users_file.into(),
+            })
+        }
+    };
 
     // Configure storage.
     let data_directory = popts.opt_get_default("data-directory", PathBuf::from("mzdata"))?;
@@ -323,6 +358,7 @@ environment:{}",
         timestamp_frequency,
         listen_addr,
         tls,
+        auth,
         data_directory: Some(data_directory),
         symbiosis_url,
     })?;
@@ -67,6 +67,10 @@ pub struct Server {
     cmdq_tx: UnboundedSender<coord::Command>,
     /// When this server started
     start_time: Instant,
+    /// Whether to serve the catalog at `/internal/catalog`. The HTTP server
+    /// does not authenticate clients, so the catalog is not served when SQL
+    /// clients must authenticate.
+    serve_catalog: bool,
 }
 
 impl Server {
@@ -75,6 +79,7 @@ impl Server {
         cmdq_tx: UnboundedSender<coord::Command>,
         start_time: Instant,
         worker_count: &str,
+        serve_catalog: bool,
     ) -> Server {
         // just set this so it shows up in metrics
         WORKER_COUNT.with_label_values(&[worker_count]).set(1);
@@ -82,6 +87,7 @@ impl Server {
             tls,
             cmdq_tx,
             start_time,
+            serve_catalog,
         }
     }
 
@@ -117,12 +123,13 @@ impl Server {
         let svc = service::service_fn(move |req: Request<Body>| {
             let cmdq_tx = (self.cmdq_tx).clone();
             let start_time = self.start_time;
+            let serve_catalog = self.serve_catalog;
             async move {
                 match (req.method(), req.uri().path()) {
                     (&Method::GET, "/") => handle_home(req).await,
                     (&Method::GET, "/metrics") => handle_prometheus(req, start_time).await,
                     (&Method::GET, "/status") => handle_status(req, start_time).await,
-                    (&Method::GET, "/internal/catalog") => {
+                    (&Method::GET, "/internal/catalog") if serve_catalog => {
                         handle_internal_catalog(req, cmdq_tx).await
                     }
                     _ => handle_unknown(req).await,
@@ -116,6 +116,9 @@ pub struct Config {
     pub listen_addr: Option<SocketAddr>,
     /// TLS encryption configuration.
     pub tls: Option<TlsConfig>,
+    /// Client authentication configuration. If absent, clients are trusted
+    /// without authentication.
+    pub auth: Option<AuthConfig>,
 
     // === Storage options. ===
     /// The directory in which `materialized` should store its own metadata.
@@ -151,6 +154,25 @@ impl TlsConfig {
     }
 }
 
+/// Configures client authentication for pgwire connections.
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    /// The method that clients must use to authenticate.
+    pub method: pgwire::AuthMethod,
+    /// The path to the file that lists the users permitted to connect and
+    /// their credentials.
+    pub users_file: PathBuf,
+}
+
+impl AuthConfig {
+    fn load(&self) -> Result<pgwire::AuthConfig, failure::Error> {
+        Ok(pgwire::AuthConfig {
+            method: self.method,
+            users: pgwire::UserStore::load(&self.users_file)?,
+        })
+    }
+}
+
 /// Start a `materialized` server.
 pub fn serve(mut config: Config) -> Result<Server, failure::Error> {
     let start_time = Instant::now();
@@ -182,6 +204,12 @@ pub fn serve(mut config: Config) -> Result<Server, failure::Error> {
         Some(tls_config) => Some(tls_config.acceptor()?),
     };
 
+    // Load the user store, if authentication is enabled.
+    let auth = match &config.auth {
+        None => None,
+        Some(auth_config) => Some(auth_config.load()?),
+    };
+
     // Initialize network listener.
     let listen_addr = config.listen_addr.unwrap_or_else(|| {
         SocketAddr::new(
@@ -224,13 +252,15 @@ pub fn serve(mut config: Config) -> Result<Server, failure::Error> {
             // addition to switchboard traffic until draining starts.
             if is_primary {
                 let mut mux = Mux::new();
+                let serve_catalog = auth.is_none();
                 mux.add_handler(switchboard.clone());
-                mux.add_handler(pgwire::Server::new(tls.clone(), cmdq_tx.clone()));
+                mux.add_handler(pgwire::Server::new(tls.clone(), auth, cmdq_tx.clone()));
                 mux.add_handler(http::Server::new(
                     tls,
                     cmdq_tx,
                     start_time,
                     &num_timely_workers.to_string(),
+                    serve_catalog,
                 ));
                 mux.serve(incoming.take_until_if(drain_tripwire)).await;
             }
@@ -11,6 +11,8 @@
 
 use std::error::Error;
 use std::fs::File;
+use std::io::{Read, Write};
+use std::net::TcpStream;
 use std::path::Path;
 use std::thread;
 use std::time::Duration;
@@ -21,6 +23,7 @@ use futures::stream::{self, StreamExt, TryStreamExt};
 use openssl::ssl::{SslConnector, SslConnectorBuilder, SslMethod, SslVerifyMode};
 use postgres::config::SslMode;
 use postgres::error::SqlState;
+use postgres::SimpleQueryMessage;
 use postgres_openssl::MakeTlsConnector;
 use tokio::runtime::Runtime;
 
@@ -349,6 +352,279 @@ fn test_persistence() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_table_persistence() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let data_dir = tempfile::tempdir()?;
+    let config = util::Config::default().data_directory(data_dir.path().to_owned());
+
+    // Reads of tables are not guaranteed to observe prior writes, so poll
+    // until the expected contents appear.
+    fn await_contents(
+        client: &mut postgres::Client,
+        expected: &[(i32, &str)],
+    ) -> Result<(), Box<dyn Error>> {
+        let mut contents = vec![];
+        for _ in 0..100 {
+            contents = match client.query("SELECT a, b FROM t ORDER BY a", &[]) {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(|row| (row.get(0), row.get(1)))
+                    .collect::<Vec<(i32, String)>>(),
+                Err(_) => vec![],
+            };
+            if contents
+                .iter()
+                .map(|(a, b)| (*a, b.as_str()))
+                .eq(expected.iter().cloned())
+            {
+                return Ok(());
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        Err(format!("table contents {:?} do not match {:?}", contents, expected).into())
+    }
+
+    {
+        let (_server, mut client) = util::start_server(config.clone())?;
+        client.batch_execute(
+            "CREATE TABLE t (a int NOT NULL, b text NOT NULL); \
+             INSERT INTO t VALUES (1, 'one'), (2, 'two'); \
+             INSERT INTO t (b, a) VALUES ('three', 3); \
+             CREATE TABLE dropped (a int); \
+             INSERT INTO dropped VALUES (1); \
+             DROP TABLE dropped;",
+        )?;
+        await_contents(&mut client, &[(1, "one"), (2, "two"), (3, "three")])?;
+    }
+
+    {
+        let (_server, mut client) = util::start_server(config.clone())?;
+        await_contents(&mut client, &[(1, "one"), (2, "two"), (3, "three")])?;
+        client.batch_execute("INSERT INTO t VALUES (4, 'four')")?;
+        await_contents(
+            &mut client,
+            &[(1, "one"), (2, "two"), (3, "three"), (4, "four")],
+        )?;
+    }
+
+    // Restart once more to ensure that updates written after the table's log
+    // was consolidated at boot are also recovered.
+    {
+        let (_server, mut client) = util::start_server(config)?;
+        await_contents(
+            &mut client,
+            &[(1, "one"), (2, "two"), (3, "three"), (4, "four")],
+        )?;
+    }
+
+    Ok(())
+}
+
+// Tests that concurrent writes to a table, which are recorded in batches, all
+// survive a restart.
+#[test]
+fn test_concurrent_table_persistence() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let data_dir = tempfile::tempdir()?;
+    let config = util::Config::default().data_directory(data_dir.path().to_owned());
+
+    {
+        let (server, mut client) = util::start_server(config.clone())?;
+        client.batch_execute("CREATE TABLE t (a int)")?;
+        let mut threads = vec![];
+        for i in 0..4 {
+            let mut client = server.connect()?;
+            threads.push(thread::spawn(move || -> Result<(), postgres::Error> {
+                for j in 0..10 {
+                    client.batch_execute(&format!("INSERT INTO t VALUES ({})", i * 10 + j))?;
+                }
+                Ok(())
+            }));
+        }
+        for thread in threads {
+            thread.join().unwrap()?;
+        }
+    }
+
+    // Reads of tables are not guaranteed to observe prior writes, so poll
+    // until the expected contents appear.
+    let (_server, mut client) = util::start_server(config)?;
+    let mut count: i64 = 0;
+    for _ in 0..100 {
+        count = client.query_one("SELECT count(*) FROM t", &[])?.get(0);
+        if count == 40 {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    Err(format!("table has {} rows, expected 40", count).into())
+}
+
+// Tests that values of every type that a table can contain survive a restart
+// unchanged.
+#[test]
+fn test_table_persistence_types() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let data_dir = tempfile::tempdir()?;
+    let config = util::Config::default().data_directory(data_dir.path().to_owned());
+
+    // Reads of tables are not guaranteed to observe prior writes, so poll
+    // until the row appears. The simple query protocol returns the text
+    // encoding of each value.
+    let select = |client: &mut postgres::Client| -> Result<Vec<String>, Box<dyn Error>> {
+        for _ in 0..100 {
+            for message in client.simple_query("SELECT * FROM t")? {
+                if let SimpleQueryMessage::Row(row) = message {
+                    return Ok((0..row.len())
+                        .map(|i| row.get(i).unwrap_or("NULL").to_owned())
+                        .collect());
+                }
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        Err("table is empty".into())
+    };
+
+    let expected = {
+        let (_server, mut client) = util::start_server(config.clone())?;
+        client.batch_execute(
+            "CREATE TABLE t (
+                 a bool, b smallint, c int, d bigint, e real, f double precision,
+                 g numeric(38, 10), h date, i time, j timestamp, k timestamptz,
+                 l interval, m bytea, n text, o uuid, p jsonb, q int
+             );
+             INSERT INTO t VALUES (
+                 true, -2, 3, -4000000000, 1.5, 'NaN', 1234567890123456789.0123456789,
+                 '0001-01-01', '23:59:59.999999', '2020-07-01 12:34:56.789',
+                 '2020-07-01 12:34:56.789+05', '1 year 2 days 3 hours', '\\xdeadbeef',
+                 'ünïcödé', 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11',
+                 '{\"a\": [1, null, {\"b\": \"c\"}]}', NULL
+             );",
+        )?;
+        select(&mut client)?
+    };
+
+    let (_server, mut client) = util::start_server(config)?;
+    assert_eq!(select(&mut client)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_rename_persistence() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let data_dir = tempfile::tempdir()?;
+    let config = util::Config::default().data_directory(data_dir.path().to_owned());
+
+    {
+        let (_server, mut client) = util::start_server(config.clone())?;
+        client.batch_execute(
+            "CREATE VIEW a AS SELECT 1 AS x; \
+             CREATE VIEW b AS SELECT a.x FROM a; \
+             CREATE INDEX b_idx ON b (x); \
+             ALTER VIEW a RENAME TO a2; \
+             ALTER INDEX b_idx RENAME TO b_x_idx;",
+        )?;
+    }
+
+    {
+        let (_server, mut client) = util::start_server(config)?;
+        assert_eq!(
+            client
+                .query("SHOW VIEWS", &[])?
+                .into_iter()
+                .map(|row| row.get(0))
+                .collect::<Vec<String>>(),
+            &["a2", "b"]
+        );
+        let create_sql: String = client
+            .query_one("SHOW CREATE VIEW b", &[])?
+            .get("Create View");
+        assert_eq!(
+            create_sql,
+            r#"CREATE VIEW "materialize"."public"."b" AS SELECT "a"."x" FROM "materialize"."public"."a2" AS "a""#
+        );
+        assert_eq!(
+            client
+                .query("SHOW INDEXES FROM b", &[])?
+                .into_iter()
+                .map(|row| row.get("Key_name"))
+                .collect::<Vec<String>>(),
+            &["materialize.public.b_x_idx"]
+        );
+        assert_eq!(
+            client.query_one("SELECT x FROM b", &[])?.get::<_, i32>(0),
+            1
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_copy_from() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let (_server, mut client) = util::start_server(util::Config::default())?;
+    client.batch_execute("CREATE TABLE t (a int NOT NULL, b text, c float8)")?;
+
+    let mut writer = client.copy_in("COPY t FROM STDIN")?;
+    writer.write_all(b"1\tone\t1.5\n2\t\\N\t-2\n3\ttab\\there\t\\N\n\\.\n")?;
+    assert_eq!(writer.finish()?, 3);
+
+    let mut writer = client.copy_in("COPY t (b, a) FROM STDIN WITH (FORMAT csv)")?;
+    writer.write_all(b"\"four, \"\"quoted\"\"\",4\n\"\",5\n,6\n")?;
+    assert_eq!(writer.finish()?, 3);
+
+    // A COPY that fails leaves the table unchanged, even if some of its rows
+    // were valid.
+    let mut writer = client.copy_in("COPY t FROM STDIN")?;
+    writer.write_all(b"7\tseven\t7\n8\teight\t8\textra\n")?;
+    let err = writer.finish().unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::BAD_COPY_FILE_FORMAT));
+
+    let mut writer = client.copy_in("COPY t FROM STDIN")?;
+    writer.write_all(b"9\tnine\t9\nten\tten\t10\n")?;
+    let err = writer.finish().unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::INVALID_TEXT_REPRESENTATION));
+
+    let mut writer = client.copy_in("COPY t (b) FROM STDIN")?;
+    writer.write_all(b"eleven\n")?;
+    let err = writer.finish().unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::NOT_NULL_VIOLATION));
+    assert!(err
+        .to_string()
+        .contains("null value in column \"a\" violates not-null constraint"));
+
+    let expected = vec![
+        (1, Some("one".to_owned()), Some(1.5)),
+        (2, None, Some(-2.0)),
+        (3, Some("tab\there".to_owned()), None),
+        (4, Some("four, \"quoted\"".to_owned()), None),
+        (5, Some("".to_owned()), None),
+        (6, None, None),
+    ];
+    // Reads of tables are not guaranteed to observe prior writes, so poll
+    // until the expected contents appear.
+    let mut contents = vec![];
+    for _ in 0..100 {
+        contents = client
+            .query("SELECT a, b, c FROM t ORDER BY a", &[])?
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1), row.get(2)))
+            .collect::<Vec<(i32, Option<String>, Option<f64>)>>();
+        if contents == expected {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    Err(format!("table contents {:?} do not match {:?}", contents, expected).into())
+}
+
 #[test]
 fn test_tls() -> Result<(), Box<dyn Error>> {
     fn make_tls<F>(configure: F) -> Result<MakeTlsConnector, Box<dyn Error>>
@@ -465,6 +741,188 @@ fn test_tls() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_auth() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let users_path = Path::join(temp_dir.path(), "users");
+    let cert_path = Path::join(temp_dir.path(), "server.crt");
+    let key_path = Path::join(temp_dir.path(), "server.key");
+    util::generate_certs(&cert_path, &key_path)?;
+    let mut users_file = File::create(&users_path)?;
+    writeln!(users_file, "# test users")?;
+    writeln!(users_file, "materialize:hunter2")?;
+    writeln!(
+        users_file,
+        "other:{}",
+        pgwire::ScramVerifier::generate("letmein")?
+    )?;
+    drop(users_file);
+
+    let make_tls = || -> Result<MakeTlsConnector, Box<dyn Error>> {
+        let mut connector_builder = SslConnector::builder(SslMethod::tls())?;
+        connector_builder.set_verify(SslVerifyMode::NONE);
+        Ok(MakeTlsConnector::new(connector_builder.build()))
+    };
+
+    let assert_sqlstate =
+        |res: Result<postgres::Client, postgres::Error>, code: &SqlState| match res {
+            Ok(_) => panic!("expected connection to fail"),
+            Err(e) => assert_eq!(e.code(), Some(code), "unexpected error: {}", e),
+        };
+
+    // Test SCRAM-SHA-256 authentication, which does not require TLS.
+    {
+        let config =
+            util::Config::default().enable_auth(pgwire::AuthMethod::ScramSha256, &users_path);
+        let server = util::start_server_unconnected(config)?;
+
+        let mut client = server
+            .pg_config()
+            .user("materialize")
+            .password("hunter2")
+            .connect(postgres::NoTls)?;
+        assert_eq!(client.query_one("SELECT 1", &[])?.get::<_, i32>(0), 1);
+
+        // Authenticated users must also name a role.
+        assert_sqlstate(
+            server
+                .pg_config()
+                .user("other")
+                .password("letmein")
+                .connect(postgres::NoTls),
+            &SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+        );
+        client.batch_execute("CREATE ROLE other")?;
+        server
+            .pg_config()
+            .user("other")
+            .password("letmein")
+            .connect(postgres::NoTls)?;
+
+        assert_sqlstate(
+            server
+                .pg_config()
+                .user("materialize")
+                .password("hunter3")
+                .connect(postgres::NoTls),
+            &SqlState::INVALID_PASSWORD,
+        );
+        assert_sqlstate(
+            server
+                .pg_config()
+                .user("nobody")
+                .password("hunter2")
+                .connect(postgres::NoTls),
+            &SqlState::INVALID_PASSWORD,
+        );
+        assert!(server.connect().is_err());
+
+        // The HTTP server does not authenticate clients, so it does not serve
+        // the catalog.
+        let mut stream = TcpStream::connect(server.http_addr())?;
+        stream.write_all(
+            b"GET /internal/catalog HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        )?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        assert!(
+            response.starts_with("HTTP/1.1 403"),
+            "unexpected response: {}",
+            response
+        );
+    }
+
+    // Test cleartext password authentication, which is only permitted over
+    // TLS.
+    {
+        let config = util::Config::default()
+            .enable_tls(cert_path, key_path)
+            .enable_auth(pgwire::AuthMethod::Password, &users_path);
+        let server = util::start_server_unconnected(config)?;
+
+        let mut client = server
+            .pg_config()
+            .user("materialize")
+            .ssl_mode(SslMode::Require)
+            .password("hunter2")
+            .connect(make_tls()?)?;
+        assert_eq!(client.query_one("SELECT 1", &[])?.get::<_, i32>(0), 1);
+
+        assert_sqlstate(
+            server
+                .pg_config()
+                .user("materialize")
+                .ssl_mode(SslMode::Require)
+                .password("hunter3")
+                .connect(make_tls()?),
+            &SqlState::INVALID_PASSWORD,
+        );
+        assert_sqlstate(
+            server
+                .pg_config()
+                .user("materialize")
+                .ssl_mode(SslMode::Disable)
+                .password("hunter2")
+                .connect(make_tls()?),
+            &SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_timezone() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let (_server, mut client) = util::start_server(util::Config::default())?;
+
+    // The session time zone only affects the text encoding of timestamps with
+    // time zones, so use the simple query protocol, which always uses it.
+    fn simple_query_one(
+        client: &mut postgres::Client,
+        query: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        match client.simple_query(query)?.into_iter().next() {
+            Some(SimpleQueryMessage::Row(row)) => Ok(row.get(0).unwrap().to_owned()),
+            _ => panic!("query {} did not return a row", query),
+        }
+    }
+
+    let query = "SELECT '2020-07-01 12:00:00+00'::timestamptz";
+    assert_eq!(
+        simple_query_one(&mut client, query)?,
+        "2020-07-01 12:00:00+00"
+    );
+
+    client.batch_execute("SET TIME ZONE 'America/New_York'")?;
+    assert_eq!(
+        simple_query_one(&mut client, "SHOW TimeZone")?,
+        "America/New_York"
+    );
+    assert_eq!(
+        simple_query_one(&mut client, query)?,
+        "2020-07-01 08:00:00-04"
+    );
+
+    client.batch_execute("SET TIME ZONE 'Asia/Kolkata'")?;
+    assert_eq!(
+        simple_query_one(&mut client, query)?,
+        "2020-07-01 17:30:00+05:30"
+    );
+
+    // Numeric offsets follow the POSIX convention, as in PostgreSQL, so
+    // positive offsets are west of UTC.
+    client.batch_execute("SET TIME ZONE '+05:30'")?;
+    assert_eq!(simple_query_one(&mut client, "SHOW TimeZone")?, "+05:30");
+    assert_eq!(
+        simple_query_one(&mut client, query)?,
+        "2020-07-01 06:30:00-05:30"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_record_types() -> Result<(), Box<dyn Error>> {
     ore::test::init_logging();
@@ -488,3 +946,26 @@ fn test_record_types() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_array_types() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let (_server, mut client) = util::start_server(util::Config::default())?;
+
+    let row = client.query_one("SELECT ARRAY[1, NULL, 3]", &[])?;
+    assert_eq!(row.columns()[0].type_(), &postgres::types::Type::INT4_ARRAY);
+    let array: Vec<Option<i32>> = row.get(0);
+    assert_eq!(array, vec![Some(1), None, Some(3)]);
+
+    let row = client.query_one("SELECT ARRAY[]::text[]", &[])?;
+    assert_eq!(row.columns()[0].type_(), &postgres::types::Type::TEXT_ARRAY);
+    let array: Vec<String> = row.get(0);
+    assert!(array.is_empty());
+
+    let row = client.query_one("SELECT $1::bigint[]", &[&vec![1_i64, 2]])?;
+    let array: Vec<i64> = row.get(0);
+    assert_eq!(array, vec![1, 2]);
+
+    Ok(())
+}
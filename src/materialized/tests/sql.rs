@@ -202,6 +202,53 @@ fn test_tail() -> Result<(), Box<dyn Error>> {
         drop(tail_reader);
     }
 
+    // Now tail with progress messages. Progress messages should only ever
+    // advance, and a new update should eventually be followed by a progress
+    // message beyond its timestamp.
+    let cancel_token = client.cancel_token();
+    let q = "TAIL dynamic_csv WITH (SNAPSHOT = false, PROGRESS = true)";
+    let mut tail_reader = client.copy_out(q)?.split(b'\n');
+
+    append(b"City 4,ST,00004\n")?;
+    let mut progress_ts = 0;
+    let mut update_ts = None;
+    loop {
+        let next = tail_reader.next().unwrap()?;
+        let ts = extract_ts(&next)?;
+        if next.starts_with(b"Progress: ") {
+            assert!(ts > progress_ts);
+            progress_ts = ts;
+            if update_ts.map_or(false, |update_ts| ts > update_ts) {
+                break;
+            }
+        } else {
+            assert!(next.starts_with(&b"City 4\tST\t00004\t4\tDiff: 1 at "[..]));
+            assert!(update_ts.is_none());
+            assert!(ts >= progress_ts);
+            update_ts = Some(ts);
+        }
+    }
+
+    cancel_token.cancel_query(postgres::NoTls)?;
+    assert!(tail_reader.next().is_none());
+    drop(tail_reader);
+
+    // Now tail a query over the source, which should only report the updates
+    // to the query's result.
+    let cancel_token = client.cancel_token();
+    let q = "TAIL (SELECT column1, column3 FROM dynamic_csv WHERE column3 <> '00005') \
+             WITH (SNAPSHOT = false)";
+    let mut tail_reader = client.copy_out(q)?.split(b'\n');
+
+    append(b"City 5,ST,00005\n")?;
+    append(b"City 6,ST,00006\n")?;
+    let next = tail_reader.next().unwrap()?;
+    assert!(next.starts_with(&b"City 6\t00006\tDiff: 1 at "[..]));
+
+    cancel_token.cancel_query(postgres::NoTls)?;
+    assert!(tail_reader.next().is_none());
+    drop(tail_reader);
+
     // Check that writing to the tailed file after the view and source are
     // dropped doesn't cause a crash (#1361).
     client.execute("DROP SOURCE dynamic_csv", &[])?;
@@ -355,3 +402,164 @@ fn test_temporary_views() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+// Tests that concurrent UPDATE statements against the same table do not
+// retract the same rows twice, which would lose updates.
+#[test]
+fn test_concurrent_updates() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let (server, mut client) = util::start_server(util::Config::default())?;
+    client.batch_execute("CREATE TABLE t (a int); INSERT INTO t VALUES (0);")?;
+
+    let mut threads = vec![];
+    for _ in 0..4 {
+        let mut client = server.connect()?;
+        threads.push(thread::spawn(move || -> Result<(), postgres::Error> {
+            for _ in 0..10 {
+                client.execute("UPDATE t SET a = a + 1", &[])?;
+            }
+            Ok(())
+        }));
+    }
+    for thread in threads {
+        thread.join().unwrap()?;
+    }
+
+    // Reads of tables are not guaranteed to observe prior writes, so poll
+    // until the expected contents appear.
+    let mut rows = vec![];
+    for _ in 0..100 {
+        rows = client
+            .query("SELECT a FROM t", &[])?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect::<Vec<i32>>();
+        if rows == [40] {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    Err(format!("table contents {:?} do not match [40]", rows).into())
+}
+
+// Tests that roles without superuser privileges can only access the items
+// that they own or have been granted privileges on, and that privileges
+// survive restarts.
+#[test]
+fn test_privileges() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let data_dir = tempfile::tempdir()?;
+    let config = util::Config::default().data_directory(data_dir.path().to_owned());
+
+    let assert_denied = |res: Result<u64, postgres::Error>, message: &str| match res {
+        Ok(_) => panic!("expected statement to fail"),
+        Err(e) => assert!(e.to_string().contains(message), "unexpected error: {}", e),
+    };
+
+    {
+        let (server, mut client) = util::start_server(config.clone())?;
+        client.batch_execute(
+            "CREATE ROLE analyst; \
+             CREATE ROLE loader; \
+             CREATE TABLE t (a int); \
+             INSERT INTO t VALUES (1); \
+             CREATE VIEW v AS SELECT a + 1 AS b FROM t; \
+             CREATE VIEW u AS SELECT a FROM t; \
+             GRANT SELECT ON v TO analyst; \
+             GRANT INSERT ON TABLE t TO loader;",
+        )?;
+
+        let mut analyst = server
+            .pg_config()
+            .user("analyst")
+            .connect(postgres::NoTls)?;
+        let b: i32 = analyst.query_one("SELECT b FROM v", &[])?.get(0);
+        assert_eq!(b, 2);
+        assert_denied(analyst.execute("SELECT * FROM t", &[]), "permission denied");
+        assert_denied(
+            analyst.execute("INSERT INTO t VALUES (2)", &[]),
+            "permission denied",
+        );
+        assert_denied(analyst.execute("DROP VIEW v", &[]), "must be owner");
+        assert_denied(
+            analyst.execute("DROP TABLE t CASCADE", &[]),
+            "must be owner",
+        );
+        assert_denied(
+            analyst.execute("GRANT SELECT ON t TO analyst", &[]),
+            "must be owner",
+        );
+        assert_denied(
+            analyst.execute("CREATE DATABASE d", &[]),
+            "permission denied to create database",
+        );
+        assert_denied(
+            analyst.execute("CREATE ROLE sneaky SUPERUSER", &[]),
+            "permission denied to create role",
+        );
+        assert_denied(
+            analyst.execute("CREATE SOURCE f FROM FILE '/etc/passwd' FORMAT TEXT", &[]),
+            "permission denied to create file source",
+        );
+        assert_denied(
+            analyst.execute("SHOW CREATE VIEW u", &[]),
+            "permission denied",
+        );
+        assert_denied(
+            analyst.execute("EXPLAIN SELECT * FROM t", &[]),
+            "permission denied",
+        );
+        analyst.query("SHOW CREATE VIEW v", &[])?;
+
+        // Analysts can build on the views that they have been granted access
+        // to, and they own what they build.
+        analyst.batch_execute("CREATE VIEW w AS SELECT b * 2 AS c FROM v")?;
+        let c: i32 = analyst.query_one("SELECT c FROM w", &[])?.get(0);
+        assert_eq!(c, 4);
+        assert_denied(
+            client.execute("DROP ROLE analyst", &[]),
+            "owns catalog items",
+        );
+        analyst.batch_execute("DROP VIEW w")?;
+
+        let mut loader = server.pg_config().user("loader").connect(postgres::NoTls)?;
+        loader.execute("INSERT INTO t VALUES (2)", &[])?;
+        assert_denied(loader.execute("SELECT * FROM t", &[]), "permission denied");
+        assert_denied(loader.execute("DELETE FROM t", &[]), "permission denied");
+    }
+
+    {
+        let (server, mut client) = util::start_server(config)?;
+        let mut analyst = server
+            .pg_config()
+            .user("analyst")
+            .connect(postgres::NoTls)?;
+        let rows: Vec<i32> = analyst
+            .query("SELECT b FROM v ORDER BY b", &[])?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect();
+        assert_eq!(rows, &[2, 3]);
+
+        client.batch_execute("REVOKE ALL ON v FROM analyst")?;
+        assert_denied(analyst.execute("SELECT b FROM v", &[]), "permission denied");
+
+        client.batch_execute("DROP ROLE analyst, loader")?;
+        assert_denied(
+            client.execute("GRANT SELECT ON v TO analyst", &[]),
+            "unknown role 'analyst'",
+        );
+
+        // Without authentication, users without a role act as the default
+        // role.
+        let mut analyst = server
+            .pg_config()
+            .user("analyst")
+            .connect(postgres::NoTls)?;
+        analyst.query("SELECT * FROM t", &[])?;
+    }
+
+    Ok(())
+}
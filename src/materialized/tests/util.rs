@@ -26,6 +26,7 @@ pub struct Config {
     data_directory: Option<PathBuf>,
     logging_granularity: Option<Duration>,
     tls: Option<materialized::TlsConfig>,
+    auth: Option<materialized::AuthConfig>,
 }
 
 impl Default for Config {
@@ -34,6 +35,7 @@ impl Default for Config {
             data_directory: None,
             logging_granularity: Some(Duration::from_millis(10)),
             tls: None,
+            auth: None,
         }
     }
 }
@@ -60,10 +62,30 @@ impl Config {
         });
         self
     }
+
+    pub fn enable_auth(
+        mut self,
+        method: pgwire::AuthMethod,
+        users_file: impl Into<PathBuf>,
+    ) -> Self {
+        self.auth = Some(materialized::AuthConfig {
+            method,
+            users_file: users_file.into(),
+        });
+        self
+    }
 }
 
 pub fn start_server(config: Config) -> Result<(Server, postgres::Client), Box<dyn Error>> {
-    let server = Server(materialized::serve(materialized::Config {
+    let server = start_server_unconnected(config)?;
+    let client = server.connect()?;
+    Ok((server, client))
+}
+
+/// Like [`start_server`], but does not connect a client, e.g. because
+/// connecting requires credentials.
+pub fn start_server_unconnected(config: Config) -> Result<Server, Box<dyn Error>> {
+    Ok(Server(materialized::serve(materialized::Config {
         logging_granularity: config.logging_granularity,
         timestamp_frequency: Duration::from_millis(10),
         logical_compaction_window: None,
@@ -74,9 +96,8 @@ pub fn start_server(config: Config) -> Result<(Server, postgres::Client), Box<dy
         symbiosis_url: None,
         listen_addr: None,
         tls: config.tls,
-    })?);
-    let client = server.connect()?;
-    Ok((server, client))
+        auth: config.auth,
+    })?))
 }
 
 pub struct Server(materialized::Server);
@@ -102,6 +123,10 @@ impl Server {
         config
     }
 
+    pub fn http_addr(&self) -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), self.0.local_addr().port())
+    }
+
     pub fn connect(&self) -> Result<postgres::Client, Box<dyn Error>> {
         Ok(self.pg_config().connect(postgres::NoTls)?)
     }
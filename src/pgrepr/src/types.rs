@@ -13,8 +13,12 @@ use repr::ScalarType;
 /// The type of a [`Value`](crate::Value).
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Type {
+    /// A variable-length multidimensional array of values.
+    Array(Box<Type>),
     /// A boolean value.
     Bool,
+    /// A fixed-length, blank-padded string.
+    BpChar,
     /// A byte array, i.e., a variable-length binary string.
     Bytea,
     /// A date.
@@ -23,6 +27,8 @@ pub enum Type {
     Float4,
     /// An 8-byte floating point number.
     Float8,
+    /// A 2-byte signed integer.
+    Int2,
     /// A 4-byte signed integer.
     Int4,
     /// An 8-byte signed integer.
@@ -39,12 +45,16 @@ pub enum Type {
     Record(Vec<Type>),
     /// A variable-length string.
     Text,
+    /// A variable-length string with an optional length limit.
+    VarChar,
     /// A time of day without a day.
     Time,
     /// A date and time, without a timezone.
     Timestamp,
     /// A date and time, with a timezone.
     TimestampTz,
+    /// A universally unique identifier.
+    Uuid,
 }
 
 lazy_static! {
@@ -63,20 +73,48 @@ lazy_static! {
 impl Type {
     pub(crate) fn inner(&self) -> &'static postgres_types::Type {
         match self {
+            Type::Array(t) => match &**t {
+                Type::Bool => &postgres_types::Type::BOOL_ARRAY,
+                Type::BpChar => &postgres_types::Type::BPCHAR_ARRAY,
+                Type::Bytea => &postgres_types::Type::BYTEA_ARRAY,
+                Type::Date => &postgres_types::Type::DATE_ARRAY,
+                Type::Float4 => &postgres_types::Type::FLOAT4_ARRAY,
+                Type::Float8 => &postgres_types::Type::FLOAT8_ARRAY,
+                Type::Int2 => &postgres_types::Type::INT2_ARRAY,
+                Type::Int4 => &postgres_types::Type::INT4_ARRAY,
+                Type::Int8 => &postgres_types::Type::INT8_ARRAY,
+                Type::Interval => &postgres_types::Type::INTERVAL_ARRAY,
+                Type::Jsonb => &postgres_types::Type::JSONB_ARRAY,
+                Type::Numeric => &postgres_types::Type::NUMERIC_ARRAY,
+                Type::Text => &postgres_types::Type::TEXT_ARRAY,
+                Type::VarChar => &postgres_types::Type::VARCHAR_ARRAY,
+                Type::Time => &postgres_types::Type::TIME_ARRAY,
+                Type::Timestamp => &postgres_types::Type::TIMESTAMP_ARRAY,
+                Type::TimestampTz => &postgres_types::Type::TIMESTAMPTZ_ARRAY,
+                Type::Uuid => &postgres_types::Type::UUID_ARRAY,
+                Type::Record(_) => &postgres_types::Type::RECORD_ARRAY,
+                Type::Array(_) | Type::List(_) => {
+                    unreachable!("arrays of arrays and lists are not supported")
+                }
+            },
             Type::Bool => &postgres_types::Type::BOOL,
+            Type::BpChar => &postgres_types::Type::BPCHAR,
             Type::Bytea => &postgres_types::Type::BYTEA,
             Type::Date => &postgres_types::Type::DATE,
             Type::Float4 => &postgres_types::Type::FLOAT4,
             Type::Float8 => &postgres_types::Type::FLOAT8,
+            Type::Int2 => &postgres_types::Type::INT2,
             Type::Int4 => &postgres_types::Type::INT4,
             Type::Int8 => &postgres_types::Type::INT8,
             Type::Interval => &postgres_types::Type::INTERVAL,
             Type::Jsonb => &postgres_types::Type::JSONB,
             Type::Numeric => &postgres_types::Type::NUMERIC,
             Type::Text => &postgres_types::Type::TEXT,
+            Type::VarChar => &postgres_types::Type::VARCHAR,
             Type::Time => &postgres_types::Type::TIME,
             Type::Timestamp => &postgres_types::Type::TIMESTAMP,
             Type::TimestampTz => &postgres_types::Type::TIMESTAMPTZ,
+            Type::Uuid => &postgres_types::Type::UUID,
             Type::List(_) => &LIST,
             Type::Record(_) => &postgres_types::Type::RECORD,
         }
@@ -98,20 +136,25 @@ impl Type {
     /// type, or -1 if the type has a variable-length representation.
     pub fn typlen(&self) -> i16 {
         match self {
+            Type::Array(_) => -1,
             Type::Bool => 1,
+            Type::BpChar => -1,
             Type::Bytea => -1,
             Type::Date => 4,
             Type::Float4 => 4,
             Type::Float8 => 8,
+            Type::Int2 => 2,
             Type::Int4 => 4,
             Type::Int8 => 8,
             Type::Interval => 16,
             Type::Jsonb => -1,
             Type::Numeric => -1,
             Type::Text => -1,
+            Type::VarChar => -1,
             Type::Time => 4,
             Type::Timestamp => 8,
             Type::TimestampTz => 8,
+            Type::Uuid => 16,
             Type::List(_) => -1,
             Type::Record(_) => -1,
         }
@@ -121,7 +164,9 @@ impl Type {
 impl From<&ScalarType> for Type {
     fn from(typ: &ScalarType) -> Type {
         match typ {
+            ScalarType::Array(t) => Type::Array(Box::new(From::from(&**t))),
             ScalarType::Bool => Type::Bool,
+            ScalarType::Int16 => Type::Int2,
             ScalarType::Int32 => Type::Int4,
             ScalarType::Int64 => Type::Int8,
             ScalarType::Float32 => Type::Float4,
@@ -134,7 +179,10 @@ impl From<&ScalarType> for Type {
             ScalarType::Interval => Type::Interval,
             ScalarType::Bytes => Type::Bytea,
             ScalarType::String => Type::Text,
+            ScalarType::Char { .. } => Type::BpChar,
+            ScalarType::VarChar { .. } => Type::VarChar,
             ScalarType::Jsonb => Type::Jsonb,
+            ScalarType::Uuid => Type::Uuid,
             ScalarType::List(t) => Type::List(Box::new(From::from(&**t))),
             ScalarType::Record { fields } => {
                 Type::Record(fields.iter().map(|(_name, ty)| Type::from(ty)).collect())
@@ -11,11 +11,15 @@ use std::error::Error;
 use std::fmt;
 use std::str;
 
+use byteorder::{NetworkEndian, ReadBytesExt};
 use bytes::{BufMut, BytesMut};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use postgres_types::{FromSql, IsNull, ToSql, Type as PgType};
+use uuid::Uuid;
 
 use ore::fmt::FormatBuffer;
+use repr::adt::char;
+use repr::adt::datetime::Timezone;
 use repr::adt::decimal::MAX_DECIMAL_PRECISION;
 use repr::adt::jsonb::JsonbRef;
 use repr::strconv::{self, Nestable};
@@ -31,6 +35,8 @@ pub mod record;
 /// A PostgreSQL datum.
 #[derive(Debug)]
 pub enum Value {
+    /// A variable-length, one-dimensional array of values.
+    Array(Vec<Option<Value>>),
     /// A boolean value.
     Bool(bool),
     /// A byte array, i.e., a variable-length binary string.
@@ -41,6 +47,8 @@ pub enum Value {
     Float4(f32),
     /// An 8-byte floating point number.
     Float8(f64),
+    /// A 2-byte signed integer.
+    Int2(i16),
     /// A 4-byte signed integer.
     Int4(i32),
     /// An 8-byte signed integer.
@@ -63,6 +71,8 @@ pub enum Value {
     Interval(Interval),
     /// A variable-length string.
     Text(String),
+    /// A universally unique identifier.
+    Uuid(Uuid),
 }
 
 impl Value {
@@ -75,6 +85,7 @@ impl Value {
             (Datum::Null, _) => None,
             (Datum::True, ScalarType::Bool) => Some(Value::Bool(true)),
             (Datum::False, ScalarType::Bool) => Some(Value::Bool(false)),
+            (Datum::Int16(i), ScalarType::Int16) => Some(Value::Int2(i)),
             (Datum::Int32(i), ScalarType::Int32) => Some(Value::Int4(i)),
             (Datum::Int64(i), ScalarType::Int64) => Some(Value::Int8(i)),
             (Datum::Float32(f), ScalarType::Float32) => Some(Value::Float4(*f)),
@@ -89,6 +100,11 @@ impl Value {
             }
             (Datum::Bytes(b), ScalarType::Bytes) => Some(Value::Bytea(b.to_vec())),
             (Datum::String(s), ScalarType::String) => Some(Value::Text(s.to_owned())),
+            (Datum::String(s), ScalarType::Char { length }) => {
+                Some(Value::Text(char::pad(s, *length)))
+            }
+            (Datum::String(s), ScalarType::VarChar { .. }) => Some(Value::Text(s.to_owned())),
+            (Datum::Uuid(u), ScalarType::Uuid) => Some(Value::Uuid(u)),
             (_, ScalarType::Jsonb) => {
                 Some(Value::Jsonb(Jsonb(JsonbRef::from_datum(datum).to_owned())))
             }
@@ -97,6 +113,11 @@ impl Value {
                     .map(|elem| Value::from_datum(elem, elem_type))
                     .collect(),
             )),
+            (Datum::List(list), ScalarType::Array(elem_type)) => Some(Value::Array(
+                list.iter()
+                    .map(|elem| Value::from_datum(elem, elem_type))
+                    .collect(),
+            )),
             (Datum::List(record), ScalarType::Record { fields, .. }) => Some(Value::Record(
                 record
                     .iter()
@@ -119,6 +140,7 @@ impl Value {
         match self {
             Value::Bool(true) => (Datum::True, ScalarType::Bool),
             Value::Bool(false) => (Datum::False, ScalarType::Bool),
+            Value::Int2(i) => (Datum::Int16(i), ScalarType::Int16),
             Value::Int4(i) => (Datum::Int32(i), ScalarType::Int32),
             Value::Int8(i) => (Datum::Int64(i), ScalarType::Int64),
             Value::Float4(f) => (Datum::Float32(f.into()), ScalarType::Float32),
@@ -133,7 +155,18 @@ impl Value {
                 ScalarType::Decimal(MAX_DECIMAL_PRECISION, d.0.scale()),
             ),
             Value::Bytea(b) => (Datum::Bytes(buf.push_bytes(b)), ScalarType::Bytes),
-            Value::Text(s) => (Datum::String(buf.push_string(s)), ScalarType::String),
+            Value::Text(s) => match typ {
+                Type::BpChar => (
+                    Datum::String(buf.push_string(char::strip_padding(&s).to_owned())),
+                    ScalarType::Char { length: None },
+                ),
+                Type::VarChar => (
+                    Datum::String(buf.push_string(s)),
+                    ScalarType::VarChar { length: None },
+                ),
+                _ => (Datum::String(buf.push_string(s)), ScalarType::String),
+            },
+            Value::Uuid(u) => (Datum::Uuid(u), ScalarType::Uuid),
             Value::Jsonb(js) => (
                 buf.push_row(js.0.into_row()).unpack_first(),
                 ScalarType::Jsonb,
@@ -154,6 +187,22 @@ impl Value {
                     ScalarType::List(Box::new(elem_type)),
                 )
             }
+            Value::Array(elems) => {
+                let elem_pg_type = match typ {
+                    Type::Array(t) => &*t,
+                    _ => panic!("Value::Array should have type Type::Array. Found {:?}", typ),
+                };
+                let (_, elem_type) = null_datum(&elem_pg_type);
+                let mut packer = RowPacker::new();
+                packer.push_list(elems.into_iter().map(|elem| match elem {
+                    Some(elem) => elem.into_datum(buf, &elem_pg_type).0,
+                    None => Datum::Null,
+                }));
+                (
+                    buf.push_row(packer.finish()).unpack_first(),
+                    ScalarType::Array(Box::new(elem_type)),
+                )
+            }
             Value::Record(_) => {
                 // This situation is handled gracefully by Value::decode; if we
                 // wind up here it's a programming error.
@@ -163,10 +212,13 @@ impl Value {
     }
 
     /// Serializes this value to `buf` in the specified `format`.
-    pub fn encode(&self, ty: &Type, format: Format, buf: &mut BytesMut) {
+    ///
+    /// Timestamps with time zones that are serialized in the text format are
+    /// presented in the time zone `tz`.
+    pub fn encode(&self, ty: &Type, format: Format, tz: &Timezone, buf: &mut BytesMut) {
         match format {
             Format::Text => {
-                self.encode_text(buf);
+                self.encode_text_in(buf, tz);
             }
             Format::Binary => self.encode_binary(ty, buf),
         }
@@ -175,26 +227,38 @@ impl Value {
     /// Serializes this value to `buf` using the [text encoding
     /// format](Format::Text).
     pub fn encode_text<F>(&self, buf: &mut F) -> Nestable
+    where
+        F: FormatBuffer,
+    {
+        self.encode_text_in(buf, &Timezone::default())
+    }
+
+    /// Like [`Value::encode_text`], but presents timestamps with time zones in
+    /// the time zone `tz` rather than in UTC.
+    pub fn encode_text_in<F>(&self, buf: &mut F, tz: &Timezone) -> Nestable
     where
         F: FormatBuffer,
     {
         match self {
+            Value::Array(elems) => encode_list(buf, elems, tz),
             Value::Bool(b) => strconv::format_bool(buf, *b),
             Value::Bytea(b) => strconv::format_bytes(buf, b),
             Value::Date(d) => strconv::format_date(buf, *d),
             Value::Time(t) => strconv::format_time(buf, *t),
             Value::Timestamp(ts) => strconv::format_timestamp(buf, *ts),
-            Value::TimestampTz(ts) => strconv::format_timestamptz(buf, *ts),
+            Value::TimestampTz(ts) => strconv::format_timestamptz_in(buf, *ts, tz),
             Value::Interval(iv) => strconv::format_interval(buf, iv.0),
+            Value::Int2(i) => strconv::format_int16(buf, *i),
             Value::Int4(i) => strconv::format_int32(buf, *i),
             Value::Int8(i) => strconv::format_int64(buf, *i),
             Value::Float4(f) => strconv::format_float32(buf, *f),
             Value::Float8(f) => strconv::format_float64(buf, *f),
             Value::Numeric(n) => strconv::format_decimal(buf, &n.0),
             Value::Text(s) => strconv::format_string(buf, s),
+            Value::Uuid(u) => strconv::format_uuid(buf, *u),
             Value::Jsonb(js) => strconv::format_jsonb(buf, js.0.as_ref()),
-            Value::List(elems) => encode_list(buf, elems),
-            Value::Record(elems) => encode_record(buf, elems),
+            Value::List(elems) => encode_list(buf, elems, tz),
+            Value::Record(elems) => encode_record(buf, elems, tz),
         }
     }
 
@@ -202,6 +266,29 @@ impl Value {
     /// format](Format::Binary).
     pub fn encode_binary(&self, ty: &Type, buf: &mut BytesMut) {
         let is_null = match self {
+            Value::Array(elems) => {
+                let elem_type = match ty {
+                    Type::Array(elem_type) => elem_type,
+                    _ => unreachable!(),
+                };
+                // Arrays are always one-dimensional, except for the empty
+                // array, which PostgreSQL represents as having zero
+                // dimensions.
+                let ndims = if elems.is_empty() { 0 } else { 1 };
+                let has_nulls = elems.iter().any(|e| e.is_none());
+                buf.put_i32(ndims);
+                buf.put_i32(has_nulls as i32);
+                buf.put_u32(elem_type.oid());
+                if ndims > 0 {
+                    buf.put_i32(elems.len() as i32);
+                    // Lower bound.
+                    buf.put_i32(1);
+                }
+                for elem in elems {
+                    encode_binary_element(buf, elem_type, elem);
+                }
+                Ok(postgres_types::IsNull::No)
+            }
             Value::Bool(b) => b.to_sql(&PgType::BOOL, buf),
             Value::Bytea(b) => b.to_sql(&PgType::BYTEA, buf),
             Value::Date(d) => d.to_sql(&PgType::DATE, buf),
@@ -209,12 +296,14 @@ impl Value {
             Value::Timestamp(ts) => ts.to_sql(&PgType::TIMESTAMP, buf),
             Value::TimestampTz(ts) => ts.to_sql(&PgType::TIMESTAMPTZ, buf),
             Value::Interval(iv) => iv.to_sql(&PgType::INTERVAL, buf),
+            Value::Int2(i) => i.to_sql(&PgType::INT2, buf),
             Value::Int4(i) => i.to_sql(&PgType::INT4, buf),
             Value::Int8(i) => i.to_sql(&PgType::INT8, buf),
             Value::Float4(f) => f.to_sql(&PgType::FLOAT4, buf),
             Value::Float8(f) => f.to_sql(&PgType::FLOAT8, buf),
             Value::Numeric(n) => n.to_sql(&PgType::NUMERIC, buf),
             Value::Text(s) => s.to_sql(&PgType::TEXT, buf),
+            Value::Uuid(u) => u.to_sql(&PgType::UUID, buf),
             Value::Jsonb(js) => js.to_sql(&PgType::JSONB, buf),
             Value::List(_) => {
                 // for now just use text encoding
@@ -229,17 +318,7 @@ impl Value {
                 };
                 for (f, ty) in fields.iter().zip(field_types) {
                     buf.put_u32(ty.oid());
-                    match f {
-                        None => buf.put_i32(-1),
-                        Some(f) => {
-                            let base = buf.len();
-                            buf.put_i32(0);
-                            f.encode_binary(ty, buf);
-                            let len = buf.len() - base - 4;
-                            let len = (len as u32).to_be_bytes();
-                            buf[base..base + 4].copy_from_slice(&len);
-                        }
-                    }
+                    encode_binary_element(buf, ty, f);
                 }
                 Ok(postgres_types::IsNull::No)
             }
@@ -268,8 +347,10 @@ impl Value {
     pub fn decode_text(ty: &Type, raw: &[u8]) -> Result<Value, Box<dyn Error + Sync + Send>> {
         let raw = str::from_utf8(raw)?;
         Ok(match ty {
+            Type::Array(elem_type) => Value::Array(decode_list(&elem_type, raw)?),
             Type::Bool => Value::Bool(strconv::parse_bool(raw)?),
             Type::Bytea => Value::Bytea(strconv::parse_bytes(raw)?),
+            Type::Int2 => Value::Int2(strconv::parse_int16(raw)?),
             Type::Int4 => Value::Int4(strconv::parse_int32(raw)?),
             Type::Int8 => Value::Int8(strconv::parse_int64(raw)?),
             Type::Float4 => Value::Float4(strconv::parse_float32(raw)?),
@@ -279,9 +360,10 @@ impl Value {
             Type::Timestamp => Value::Timestamp(strconv::parse_timestamp(raw)?),
             Type::TimestampTz => Value::TimestampTz(strconv::parse_timestamptz(raw)?),
             Type::Interval => Value::Interval(Interval(strconv::parse_interval(raw)?)),
-            Type::Text => Value::Text(raw.to_owned()),
+            Type::Text | Type::BpChar | Type::VarChar => Value::Text(raw.to_owned()),
             Type::Numeric => Value::Numeric(Numeric(strconv::parse_decimal(raw)?)),
             Type::Jsonb => Value::Jsonb(Jsonb(strconv::parse_jsonb(raw)?)),
+            Type::Uuid => Value::Uuid(strconv::parse_uuid(raw)?),
             Type::List(elem_type) => Value::List(decode_list(&elem_type, raw)?),
             Type::Record(_) => {
                 return Err(Box::new(DecodeError::new(
@@ -295,20 +377,25 @@ impl Value {
     /// format](Format::Binary).
     pub fn decode_binary(ty: &Type, raw: &[u8]) -> Result<Value, Box<dyn Error + Sync + Send>> {
         match ty {
+            Type::Array(elem_type) => decode_binary_array(elem_type, raw).map(Value::Array),
             Type::Bool => bool::from_sql(ty.inner(), raw).map(Value::Bool),
             Type::Bytea => Vec::<u8>::from_sql(ty.inner(), raw).map(Value::Bytea),
             Type::Date => chrono::NaiveDate::from_sql(ty.inner(), raw).map(Value::Date),
             Type::Float4 => f32::from_sql(ty.inner(), raw).map(Value::Float4),
             Type::Float8 => f64::from_sql(ty.inner(), raw).map(Value::Float8),
+            Type::Int2 => i16::from_sql(ty.inner(), raw).map(Value::Int2),
             Type::Int4 => i32::from_sql(ty.inner(), raw).map(Value::Int4),
             Type::Int8 => i64::from_sql(ty.inner(), raw).map(Value::Int8),
             Type::Interval => Interval::from_sql(ty.inner(), raw).map(Value::Interval),
             Type::Jsonb => Jsonb::from_sql(ty.inner(), raw).map(Value::Jsonb),
             Type::Numeric => Numeric::from_sql(ty.inner(), raw).map(Value::Numeric),
-            Type::Text => String::from_sql(ty.inner(), raw).map(Value::Text),
+            Type::Text | Type::BpChar | Type::VarChar => {
+                String::from_sql(ty.inner(), raw).map(Value::Text)
+            }
             Type::Time => NaiveTime::from_sql(ty.inner(), raw).map(Value::Time),
             Type::Timestamp => NaiveDateTime::from_sql(ty.inner(), raw).map(Value::Timestamp),
             Type::TimestampTz => DateTime::<Utc>::from_sql(ty.inner(), raw).map(Value::TimestampTz),
+            Type::Uuid => Uuid::from_sql(ty.inner(), raw).map(Value::Uuid),
             Type::List(_) => {
                 // just using the text encoding for now
                 Value::decode_text(ty, raw)
@@ -340,13 +427,13 @@ impl fmt::Display for DecodeError {
 
 impl Error for DecodeError {}
 
-fn encode_list<F>(buf: &mut F, elems: &[Option<Value>]) -> Nestable
+fn encode_list<F>(buf: &mut F, elems: &[Option<Value>], tz: &Timezone) -> Nestable
 where
     F: FormatBuffer,
 {
     strconv::format_list(buf, elems, |buf, elem| match elem {
         None => buf.write_null(),
-        Some(elem) => elem.encode_text(buf.nonnull_buffer()),
+        Some(elem) => elem.encode_text_in(buf.nonnull_buffer(), tz),
     })
 }
 
@@ -361,33 +448,93 @@ fn decode_list(
     )?)
 }
 
-fn encode_record<F>(buf: &mut F, elems: &[Option<Value>]) -> Nestable
+/// Writes a length-prefixed element of a binary-encoded array or record, or
+/// a length of -1 if the element is null.
+fn encode_binary_element(buf: &mut BytesMut, ty: &Type, elem: &Option<Value>) {
+    match elem {
+        None => buf.put_i32(-1),
+        Some(elem) => {
+            let base = buf.len();
+            buf.put_i32(0);
+            elem.encode_binary(ty, buf);
+            let len = buf.len() - base - 4;
+            let len = (len as u32).to_be_bytes();
+            buf[base..base + 4].copy_from_slice(&len);
+        }
+    }
+}
+
+fn decode_binary_array(
+    elem_type: &Type,
+    mut raw: &[u8],
+) -> Result<Vec<Option<Value>>, Box<dyn Error + Sync + Send>> {
+    let ndims = raw.read_i32::<NetworkEndian>()?;
+    let _has_nulls = raw.read_i32::<NetworkEndian>()?;
+    let _elem_oid = raw.read_u32::<NetworkEndian>()?;
+    match ndims {
+        0 => return Ok(vec![]),
+        1 => (),
+        _ => {
+            return Err(Box::new(DecodeError::new(
+                "input of multidimensional arrays is not implemented",
+            )))
+        }
+    }
+    let len = raw.read_i32::<NetworkEndian>()?;
+    let _lower_bound = raw.read_i32::<NetworkEndian>()?;
+    let mut elems = vec![];
+    for _ in 0..len {
+        let elem_len = raw.read_i32::<NetworkEndian>()?;
+        if elem_len < 0 {
+            elems.push(None);
+            continue;
+        }
+        let elem_len = elem_len as usize;
+        if raw.len() < elem_len {
+            return Err(Box::new(DecodeError::new("invalid array element length")));
+        }
+        let (elem_raw, rest) = raw.split_at(elem_len);
+        elems.push(Some(Value::decode_binary(elem_type, elem_raw)?));
+        raw = rest;
+    }
+    Ok(elems)
+}
+
+fn encode_record<F>(buf: &mut F, elems: &[Option<Value>], tz: &Timezone) -> Nestable
 where
     F: FormatBuffer,
 {
     strconv::format_record(buf, elems, |buf, elem| match elem {
         None => buf.write_null(),
-        Some(elem) => elem.encode_text(buf.nonnull_buffer()),
+        Some(elem) => elem.encode_text_in(buf.nonnull_buffer(), tz),
     })
 }
 
 /// Constructs a null datum of the specified type.
 pub fn null_datum(ty: &Type) -> (Datum<'static>, ScalarType) {
     let ty = match ty {
+        Type::Array(t) => {
+            let (_, elem_type) = null_datum(t);
+            ScalarType::Array(Box::new(elem_type))
+        }
         Type::Bool => ScalarType::Bool,
+        Type::BpChar => ScalarType::Char { length: None },
         Type::Bytea => ScalarType::Bytes,
         Type::Date => ScalarType::Date,
         Type::Float4 => ScalarType::Float32,
         Type::Float8 => ScalarType::Float64,
+        Type::Int2 => ScalarType::Int16,
         Type::Int4 => ScalarType::Int32,
         Type::Int8 => ScalarType::Int64,
         Type::Interval => ScalarType::Interval,
         Type::Jsonb => ScalarType::Jsonb,
         Type::Numeric => ScalarType::Decimal(MAX_DECIMAL_PRECISION, 0),
         Type::Text => ScalarType::String,
+        Type::VarChar => ScalarType::VarChar { length: None },
         Type::Time => ScalarType::Time,
         Type::Timestamp => ScalarType::Timestamp,
         Type::TimestampTz => ScalarType::TimestampTz,
+        Type::Uuid => ScalarType::Uuid,
         Type::List(t) => {
             let (_, elem_type) = null_datum(t);
             ScalarType::List(Box::new(elem_type))
@@ -0,0 +1,527 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Client authentication.
+//!
+//! Users and their credentials are read from a user store file. Each
+//! non-empty line of the file that does not begin with `#` has the form
+//! `USER:SECRET`, where `SECRET` is either a SCRAM-SHA-256 verifier, in the
+//! same format that PostgreSQL stores in `pg_authid.rolpassword`, or a
+//! plaintext password. Plaintext passwords are converted to verifiers when the
+//! file is loaded, so the server never holds onto them.
+//!
+//! See [RFC 5802] and [RFC 7677] for the specification of SCRAM-SHA-256, and
+//! the "SASL Authentication" section of the PostgreSQL protocol documentation
+//! for how it is embedded in pgwire.
+//!
+//! [RFC 5802]: https://tools.ietf.org/html/rfc5802
+//! [RFC 7677]: https://tools.ietf.org/html/rfc7677
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::{self, FromStr};
+
+use failure::{bail, format_err, ResultExt};
+use openssl::base64;
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::pkcs5;
+use openssl::pkey::PKey;
+use openssl::rand;
+use openssl::sha;
+use openssl::sign::Signer;
+
+/// The name of the SCRAM-SHA-256 SASL mechanism.
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+/// The number of PBKDF2 iterations used for newly generated verifiers. This
+/// matches PostgreSQL's default.
+const SCRAM_ITERATIONS: u32 = 4096;
+
+/// The length, in bytes, of the salt used for newly generated verifiers.
+const SCRAM_SALT_LEN: usize = 16;
+
+/// The length, in bytes, of the random server nonce, before encoding.
+const SCRAM_NONCE_LEN: usize = 18;
+
+/// The method by which clients must prove their identity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthMethod {
+    /// The client sends its password in cleartext. Permitted only on
+    /// TLS-encrypted connections.
+    Password,
+    /// The client proves that it knows its password via a SCRAM-SHA-256
+    /// exchange, without sending the password itself.
+    ScramSha256,
+}
+
+impl FromStr for AuthMethod {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<AuthMethod, failure::Error> {
+        match s {
+            "password" => Ok(AuthMethod::Password),
+            "scram-sha-256" => Ok(AuthMethod::ScramSha256),
+            _ => bail!(
+                "unknown authentication method {}; valid methods are password and scram-sha-256",
+                s
+            ),
+        }
+    }
+}
+
+impl fmt::Display for AuthMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuthMethod::Password => f.write_str("password"),
+            AuthMethod::ScramSha256 => f.write_str("scram-sha-256"),
+        }
+    }
+}
+
+/// Configures client authentication for pgwire connections.
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    /// The method that clients must use to authenticate.
+    pub method: AuthMethod,
+    /// The users that are permitted to connect.
+    pub users: UserStore,
+}
+
+/// The set of users permitted to connect, and their credentials.
+#[derive(Debug, Clone)]
+pub struct UserStore {
+    users: HashMap<String, ScramVerifier>,
+    /// A random secret from which the salts of mock verifiers are derived.
+    mock_nonce: Vec<u8>,
+}
+
+impl UserStore {
+    /// Loads a user store from the file at `path`.
+    pub fn load(path: &Path) -> Result<UserStore, failure::Error> {
+        let contents = fs::read_to_string(path)
+            .with_context(|e| format!("reading user store {}: {}", path.display(), e))?;
+        let users = UserStore::parse(&contents)
+            .with_context(|e| format!("parsing user store {}: {}", path.display(), e))?;
+        Ok(users)
+    }
+
+    /// Parses a user store from the contents of a user store file.
+    pub fn parse(contents: &str) -> Result<UserStore, failure::Error> {
+        let mut users = HashMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (user, secret) = match line.find(':') {
+                Some(pos) => (&line[..pos], &line[pos + 1..]),
+                None => bail!("line {}: expected USER:SECRET", i + 1),
+            };
+            if user.is_empty() {
+                bail!("line {}: user name must not be empty", i + 1);
+            }
+            let verifier = if secret.starts_with("SCRAM-SHA-256$") {
+                secret
+                    .parse::<ScramVerifier>()
+                    .with_context(|e| format!("line {}: {}", i + 1, e))?
+            } else {
+                ScramVerifier::generate(secret)?
+            };
+            if users.insert(user.to_owned(), verifier).is_some() {
+                bail!("line {}: user {} specified more than once", i + 1, user);
+            }
+        }
+        let mut mock_nonce = vec![0; SCRAM_SALT_LEN];
+        rand::rand_bytes(&mut mock_nonce)?;
+        Ok(UserStore { users, mock_nonce })
+    }
+
+    /// Returns the verifier for the named user, if the user exists.
+    pub fn get(&self, user: &str) -> Option<&ScramVerifier> {
+        self.users.get(user)
+    }
+
+    /// Generates a verifier for the named user, which does not exist, that no
+    /// proof or password will match.
+    ///
+    /// Running an exchange against a mock verifier avoids revealing to the
+    /// client whether the user exists. As in PostgreSQL, the salt is derived
+    /// from the user name and a secret chosen when the store was loaded, so
+    /// that, like the salt of a real user, it is the same on every attempt.
+    pub fn mock(&self, user: &str) -> Result<ScramVerifier, ErrorStack> {
+        let mut hasher = sha::Sha256::new();
+        hasher.update(user.as_bytes());
+        hasher.update(&self.mock_nonce);
+        let salt = hasher.finish()[..SCRAM_SALT_LEN].to_vec();
+        ScramVerifier::mock(salt)
+    }
+}
+
+/// The information the server stores to authenticate a user via
+/// SCRAM-SHA-256.
+///
+/// The verifier is derived from the user's password, but the password cannot
+/// feasibly be recovered from it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ScramVerifier {
+    iterations: u32,
+    salt: Vec<u8>,
+    stored_key: Vec<u8>,
+    server_key: Vec<u8>,
+}
+
+impl ScramVerifier {
+    /// Generates a verifier for `password` with a random salt.
+    pub fn generate(password: &str) -> Result<ScramVerifier, ErrorStack> {
+        let mut salt = vec![0; SCRAM_SALT_LEN];
+        rand::rand_bytes(&mut salt)?;
+        ScramVerifier::with_salt(password, salt, SCRAM_ITERATIONS)
+    }
+
+    fn with_salt(
+        password: &str,
+        salt: Vec<u8>,
+        iterations: u32,
+    ) -> Result<ScramVerifier, ErrorStack> {
+        let salted_password = salted_password(password, &salt, iterations)?;
+        Ok(ScramVerifier {
+            iterations,
+            salt,
+            stored_key: sha::sha256(&hmac(&salted_password, b"Client Key")?).to_vec(),
+            server_key: hmac(&salted_password, b"Server Key")?,
+        })
+    }
+
+    /// Generates a verifier with the specified salt that no proof or password
+    /// will match. See [`UserStore::mock`].
+    fn mock(salt: Vec<u8>) -> Result<ScramVerifier, ErrorStack> {
+        let mut stored_key = vec![0; 32];
+        let mut server_key = vec![0; 32];
+        rand::rand_bytes(&mut stored_key)?;
+        rand::rand_bytes(&mut server_key)?;
+        Ok(ScramVerifier {
+            iterations: SCRAM_ITERATIONS,
+            salt,
+            stored_key,
+            server_key,
+        })
+    }
+
+    /// Reports whether `password` is the password from which this verifier
+    /// was derived.
+    pub fn verify_password(&self, password: &str) -> Result<bool, ErrorStack> {
+        let other = ScramVerifier::with_salt(password, self.salt.clone(), self.iterations)?;
+        Ok(memcmp::eq(&self.stored_key, &other.stored_key)
+            && memcmp::eq(&self.server_key, &other.server_key))
+    }
+}
+
+impl FromStr for ScramVerifier {
+    type Err = failure::Error;
+
+    /// Parses a verifier of the form
+    /// `SCRAM-SHA-256$ITERATIONS:SALT$STOREDKEY:SERVERKEY`, where the salt and
+    /// keys are base64 encoded.
+    fn from_str(s: &str) -> Result<ScramVerifier, failure::Error> {
+        let malformed = || format_err!("malformed SCRAM-SHA-256 verifier");
+        let parts: Vec<_> = s.split('$').collect();
+        if parts.len() != 3 || parts[0] != SCRAM_SHA_256 {
+            return Err(malformed());
+        }
+        let mut params = parts[1].splitn(2, ':');
+        let mut keys = parts[2].splitn(2, ':');
+        let decode = |part: Option<&str>| {
+            part.and_then(|p| base64::decode_block(p).ok())
+                .ok_or_else(malformed)
+        };
+        let iterations = params
+            .next()
+            .and_then(|i| i.parse().ok())
+            .filter(|i| *i > 0)
+            .ok_or_else(malformed)?;
+        let salt = decode(params.next())?;
+        let stored_key = decode(keys.next())?;
+        let server_key = decode(keys.next())?;
+        if stored_key.len() != 32 || server_key.len() != 32 {
+            return Err(malformed());
+        }
+        Ok(ScramVerifier {
+            iterations,
+            salt,
+            stored_key,
+            server_key,
+        })
+    }
+}
+
+impl fmt::Display for ScramVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}${}:{}${}:{}",
+            SCRAM_SHA_256,
+            self.iterations,
+            base64::encode_block(&self.salt),
+            base64::encode_block(&self.stored_key),
+            base64::encode_block(&self.server_key),
+        )
+    }
+}
+
+impl fmt::Debug for ScramVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Don't leak the keys into logs.
+        f.write_str("ScramVerifier { .. }")
+    }
+}
+
+/// The server side of an in-progress SCRAM-SHA-256 exchange.
+#[derive(Debug)]
+pub struct ScramExchange {
+    verifier: ScramVerifier,
+    gs2_header: String,
+    client_first_bare: String,
+    server_first: String,
+    nonce: String,
+}
+
+impl ScramExchange {
+    /// Starts an exchange in response to the client's first message, returning
+    /// the exchange and the server's first message.
+    pub fn start(
+        verifier: ScramVerifier,
+        client_first: &[u8],
+    ) -> Result<(ScramExchange, String), failure::Error> {
+        let mut server_nonce = vec![0; SCRAM_NONCE_LEN];
+        rand::rand_bytes(&mut server_nonce)?;
+        ScramExchange::start_with_nonce(
+            verifier,
+            client_first,
+            &base64::encode_block(&server_nonce),
+        )
+    }
+
+    fn start_with_nonce(
+        verifier: ScramVerifier,
+        client_first: &[u8],
+        server_nonce: &str,
+    ) -> Result<(ScramExchange, String), failure::Error> {
+        let client_first = str::from_utf8(client_first)
+            .map_err(|_| format_err!("invalid UTF-8 in SCRAM message"))?;
+
+        // The message begins with the GS2 header, which indicates whether
+        // the client wants channel binding and names an optional authorization
+        // identity.
+        let mut parts = client_first.splitn(3, ',');
+        let (cbind_flag, authzid, client_first_bare) =
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(f), Some(a), Some(b)) => (f, a, b),
+                _ => bail!("malformed SCRAM message"),
+            };
+        match cbind_flag {
+            "n" | "y" => (),
+            _ if cbind_flag.starts_with("p=") => bail!("SCRAM channel binding is not supported"),
+            _ => bail!("malformed SCRAM message"),
+        }
+        if !authzid.is_empty() {
+            bail!("SCRAM authorization identities are not supported");
+        }
+
+        // The user name in the message is ignored in favor of the one in the
+        // startup message, as PostgreSQL does.
+        let mut attrs = client_first_bare.split(',');
+        match attrs.next() {
+            Some(a) if a.starts_with("n=") => (),
+            Some(a) if a.starts_with("m=") => bail!("unsupported SCRAM extension"),
+            _ => bail!("malformed SCRAM message"),
+        }
+        let client_nonce = match attrs.next() {
+            Some(a) if a.starts_with("r=") && a.len() > 2 => &a[2..],
+            _ => bail!("malformed SCRAM message"),
+        };
+        if !client_nonce
+            .chars()
+            .all(|c| c.is_ascii_graphic() && c != ',')
+        {
+            bail!("malformed SCRAM nonce");
+        }
+
+        let nonce = format!("{}{}", client_nonce, server_nonce);
+        let server_first = format!(
+            "r={},s={},i={}",
+            nonce,
+            base64::encode_block(&verifier.salt),
+            verifier.iterations,
+        );
+        let exchange = ScramExchange {
+            verifier,
+            gs2_header: format!("{},{},", cbind_flag, authzid),
+            client_first_bare: client_first_bare.to_owned(),
+            server_first: server_first.clone(),
+            nonce,
+        };
+        Ok((exchange, server_first))
+    }
+
+    /// Finishes the exchange in response to the client's final message.
+    ///
+    /// If the client's proof is valid, returns the server's final message,
+    /// which proves to the client that the server, too, knows the verifier.
+    /// If the proof is invalid, returns `None`.
+    pub fn finish(self, client_final: &[u8]) -> Result<Option<String>, failure::Error> {
+        let client_final = str::from_utf8(client_final)
+            .map_err(|_| format_err!("invalid UTF-8 in SCRAM message"))?;
+
+        let (without_proof, proof) = match client_final.rfind(",p=") {
+            Some(pos) => (&client_final[..pos], &client_final[pos + 3..]),
+            None => bail!("malformed SCRAM message"),
+        };
+        let mut attrs = without_proof.split(',');
+        match attrs.next() {
+            Some(a) if a.starts_with("c=") => {
+                if base64::decode_block(&a[2..]).ok().as_deref() != Some(self.gs2_header.as_bytes())
+                {
+                    bail!("SCRAM channel binding check failed");
+                }
+            }
+            _ => bail!("malformed SCRAM message"),
+        }
+        match attrs.next() {
+            Some(a) if a.starts_with("r=") => {
+                if a[2..] != self.nonce {
+                    bail!("SCRAM nonce does not match");
+                }
+            }
+            _ => bail!("malformed SCRAM message"),
+        }
+        let proof =
+            base64::decode_block(proof).map_err(|_| format_err!("malformed SCRAM proof"))?;
+        if proof.len() != self.verifier.stored_key.len() {
+            bail!("malformed SCRAM proof");
+        }
+
+        let auth_message = format!(
+            "{},{},{}",
+            self.client_first_bare, self.server_first, without_proof
+        );
+        let client_signature = hmac(&self.verifier.stored_key, auth_message.as_bytes())?;
+        let client_key: Vec<_> = proof
+            .iter()
+            .zip(&client_signature)
+            .map(|(p, s)| p ^ s)
+            .collect();
+        if !memcmp::eq(&sha::sha256(&client_key), &self.verifier.stored_key) {
+            return Ok(None);
+        }
+        let server_signature = hmac(&self.verifier.server_key, auth_message.as_bytes())?;
+        Ok(Some(format!(
+            "v={}",
+            base64::encode_block(&server_signature)
+        )))
+    }
+}
+
+fn salted_password(password: &str, salt: &[u8], iterations: u32) -> Result<Vec<u8>, ErrorStack> {
+    let mut out = vec![0; 32];
+    pkcs5::pbkdf2_hmac(
+        password.as_bytes(),
+        salt,
+        iterations as usize,
+        MessageDigest::sha256(),
+        &mut out,
+    )?;
+    Ok(out)
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(data)?;
+    signer.sign_to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example exchange from RFC 7677, section 3.
+    const PASSWORD: &str = "pencil";
+    const SALT: &str = "W22ZaJ0SNY7soEsUEjb6gQ==";
+    const CLIENT_FIRST: &str = "n,,n=user,r=rOprNGfwEbeRWgbNEkqO";
+    const SERVER_NONCE: &str = "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+    const SERVER_FIRST: &str =
+        "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    const CLIENT_FINAL: &str = "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+                                p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const SERVER_FINAL: &str = "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    fn rfc_verifier() -> ScramVerifier {
+        ScramVerifier::with_salt(PASSWORD, base64::decode_block(SALT).unwrap(), 4096).unwrap()
+    }
+
+    #[test]
+    fn test_scram_exchange() -> Result<(), failure::Error> {
+        let (exchange, server_first) =
+            ScramExchange::start_with_nonce(rfc_verifier(), CLIENT_FIRST.as_bytes(), SERVER_NONCE)?;
+        assert_eq!(server_first, SERVER_FIRST);
+        assert_eq!(
+            exchange.finish(CLIENT_FINAL.as_bytes())?.as_deref(),
+            Some(SERVER_FINAL)
+        );
+
+        let (exchange, _) =
+            ScramExchange::start_with_nonce(rfc_verifier(), CLIENT_FIRST.as_bytes(), SERVER_NONCE)?;
+        let bad_proof = CLIENT_FINAL.replace("p=dH", "p=eH");
+        assert_eq!(exchange.finish(bad_proof.as_bytes())?, None);
+
+        let (exchange, _) =
+            ScramExchange::start_with_nonce(rfc_verifier(), CLIENT_FIRST.as_bytes(), "other")?;
+        assert!(exchange.finish(CLIENT_FINAL.as_bytes()).is_err());
+
+        assert!(ScramExchange::start(rfc_verifier(), b"p=tls-server-end-point,,n=,r=abc").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verifier() -> Result<(), failure::Error> {
+        let verifier = rfc_verifier();
+        assert!(verifier.verify_password(PASSWORD)?);
+        assert!(!verifier.verify_password("pencils")?);
+        assert_eq!(verifier.to_string().parse::<ScramVerifier>()?, verifier);
+        assert!("SCRAM-SHA-256$4096:c2FsdA==$abc"
+            .parse::<ScramVerifier>()
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_store() -> Result<(), failure::Error> {
+        let store = UserStore::parse(&format!(
+            "# comment\n\nalice:hunter2\nbob:{}\n",
+            rfc_verifier()
+        ))?;
+        assert!(store.get("alice").unwrap().verify_password("hunter2")?);
+        assert_eq!(store.get("bob"), Some(&rfc_verifier()));
+        assert_eq!(store.get("carol"), None);
+
+        // Mock verifiers for the same user share a salt, so that an attacker
+        // cannot distinguish them from real users by their salts.
+        let mock = store.mock("carol")?;
+        assert!(!mock.verify_password("")?);
+        assert_eq!(mock.salt, store.mock("carol")?.salt);
+        assert_ne!(mock.salt, store.mock("dave")?.salt);
+        assert!(UserStore::parse("alice").is_err());
+        assert!(UserStore::parse("alice:a\nalice:b").is_err());
+        Ok(())
+    }
+}
@@ -28,6 +28,7 @@ use tokio_util::codec::{Decoder, Encoder};
 
 use ore::cast::CastFrom;
 use ore::netio;
+use repr::adt::datetime::Timezone;
 
 use crate::message::{
     BackendMessage, FrontendMessage, FrontendStartupMessage, TransactionStatus, VERSION_CANCEL,
@@ -81,6 +82,7 @@ impl fmt::Display for CodecError {
 pub struct Codec {
     decode_state: DecodeState,
     encode_state: Vec<(pgrepr::Type, pgrepr::Format)>,
+    timezone: Timezone,
 }
 
 impl Codec {
@@ -89,11 +91,19 @@ impl Codec {
         Codec {
             decode_state: DecodeState::Head,
             encode_state: vec![],
+            timezone: Timezone::default(),
         }
     }
 
-    pub fn set_encode_state(&mut self, encode_state: Vec<(pgrepr::Type, pgrepr::Format)>) {
+    /// Sets the types and formats of the fields of subsequent data rows, and
+    /// the time zone in which timestamps with time zones are presented.
+    pub fn set_encode_state(
+        &mut self,
+        encode_state: Vec<(pgrepr::Type, pgrepr::Format)>,
+        timezone: Timezone,
+    ) {
         self.encode_state = encode_state;
+        self.timezone = timezone;
     }
 
     fn encode_error_notice_response(
@@ -134,7 +144,11 @@ impl Encoder<BackendMessage> for Codec {
     fn encode(&mut self, msg: BackendMessage, dst: &mut BytesMut) -> Result<(), io::Error> {
         // Write type byte.
         let byte = match msg {
-            BackendMessage::AuthenticationOk => b'R',
+            BackendMessage::AuthenticationOk
+            | BackendMessage::AuthenticationCleartextPassword
+            | BackendMessage::AuthenticationSASL(_)
+            | BackendMessage::AuthenticationSASLContinue(_)
+            | BackendMessage::AuthenticationSASLFinal(_) => b'R',
             BackendMessage::RowDescription(_) => b'T',
             BackendMessage::DataRow(_) => b'D',
             BackendMessage::CommandComplete { .. } => b'C',
@@ -150,6 +164,7 @@ impl Encoder<BackendMessage> for Codec {
             BackendMessage::CloseComplete => b'3',
             BackendMessage::NoticeResponse { .. } => b'N',
             BackendMessage::ErrorResponse { .. } => b'E',
+            BackendMessage::CopyInResponse { .. } => b'G',
             BackendMessage::CopyOutResponse { .. } => b'H',
             BackendMessage::CopyData(_) => b'd',
             BackendMessage::CopyDone => b'c',
@@ -162,7 +177,11 @@ impl Encoder<BackendMessage> for Codec {
 
         // Write message contents.
         match msg {
-            BackendMessage::CopyOutResponse {
+            BackendMessage::CopyInResponse {
+                overall_format,
+                column_formats,
+            }
+            | BackendMessage::CopyOutResponse {
                 overall_format,
                 column_formats,
             } => {
@@ -179,6 +198,24 @@ impl Encoder<BackendMessage> for Codec {
             BackendMessage::AuthenticationOk => {
                 dst.put_u32(0);
             }
+            BackendMessage::AuthenticationCleartextPassword => {
+                dst.put_u32(3);
+            }
+            BackendMessage::AuthenticationSASL(mechanisms) => {
+                dst.put_u32(10);
+                for mechanism in mechanisms {
+                    dst.put_string(mechanism);
+                }
+                dst.put_u8(b'\0');
+            }
+            BackendMessage::AuthenticationSASLContinue(data) => {
+                dst.put_u32(11);
+                dst.put_slice(&data);
+            }
+            BackendMessage::AuthenticationSASLFinal(data) => {
+                dst.put_u32(12);
+                dst.put_slice(&data);
+            }
             BackendMessage::RowDescription(fields) => {
                 dst.put_u16(fields.len() as u16);
                 for f in &fields {
@@ -198,7 +235,7 @@ impl Encoder<BackendMessage> for Codec {
                     if let Some(f) = f {
                         let base = dst.len();
                         dst.put_u32(0);
-                        f.encode(ty, *format, dst);
+                        f.encode(ty, *format, &self.timezone, dst);
                         let len = dst.len() - base - 4;
                         let len = (len as u32).to_be_bytes();
                         dst[base..base + 4].copy_from_slice(&len);
@@ -383,6 +420,14 @@ impl Decoder for Codec {
                         b'S' => decode_sync(buf)?,
                         b'C' => decode_close(buf)?,
 
+                        // Copy-in flow.
+                        b'd' => decode_copy_data(buf)?,
+                        b'c' => decode_copy_done(buf)?,
+                        b'f' => decode_copy_fail(buf)?,
+
+                        // Authentication flow.
+                        b'p' => decode_password(buf)?,
+
                         // Termination.
                         b'X' => decode_terminate(buf)?,
 
@@ -528,6 +573,45 @@ fn decode_sync(mut _buf: Cursor) -> Result<FrontendMessage, io::Error> {
     Ok(FrontendMessage::Sync)
 }
 
+fn decode_copy_data(buf: Cursor) -> Result<FrontendMessage, io::Error> {
+    Ok(FrontendMessage::CopyData(buf.buf.to_vec()))
+}
+
+fn decode_copy_done(mut _buf: Cursor) -> Result<FrontendMessage, io::Error> {
+    // Nothing more to decode.
+    Ok(FrontendMessage::CopyDone)
+}
+
+fn decode_copy_fail(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
+    Ok(FrontendMessage::CopyFail(buf.read_cstr()?.to_string()))
+}
+
+fn decode_password(buf: Cursor) -> Result<FrontendMessage, io::Error> {
+    Ok(FrontendMessage::Password(buf.buf.to_vec()))
+}
+
+/// Decodes the contents of a [`FrontendMessage::Password`] sent in response to
+/// a request for a cleartext password.
+pub fn decode_cleartext_password(data: &[u8]) -> Result<&str, io::Error> {
+    Cursor::new(data).read_cstr()
+}
+
+/// Decodes the contents of a [`FrontendMessage::Password`] sent as the initial
+/// response of a SASL exchange, returning the name of the selected mechanism
+/// and the mechanism-specific initial response, if any.
+pub fn decode_sasl_initial_response(data: &[u8]) -> Result<(&str, Option<&[u8]>), io::Error> {
+    let mut buf = Cursor::new(data);
+    let mechanism = buf.read_cstr()?;
+    let len = buf.read_i32()?;
+    if len == -1 {
+        Ok((mechanism, None))
+    } else if len < 0 || len as usize != buf.buf.len() {
+        Err(input_err("invalid length for SASL initial response"))
+    } else {
+        Ok((mechanism, Some(buf.buf)))
+    }
+}
+
 /// Decodes data within pgwire messages.
 ///
 /// The API provided is very similar to [`bytes::Buf`], but operations return
@@ -0,0 +1,449 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of the data streamed by clients during `COPY FROM STDIN`.
+//!
+//! See "[COPY: File Formats][1]" in the PostgreSQL reference for the
+//! specification of the text and CSV formats.
+//!
+//! [1]: https://www.postgresql.org/docs/11/sql-copy.html#id-1.9.3.55.9
+
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+
+use failure::{bail, format_err};
+use postgres::error::SqlState;
+
+use repr::adt::char;
+use repr::{strconv, Datum, RelationDesc, Row, RowArena, ScalarType};
+use sql::ast::CopyFormat;
+
+/// An error in the data that a client streams during `COPY FROM STDIN`.
+#[derive(Debug)]
+pub enum CopyError {
+    /// The data is malformed, or a record has the wrong number of fields.
+    Format(String),
+    /// A field is not a valid value of its column's type.
+    InvalidValue(String),
+    /// A record has no value for a column that is not nullable.
+    NotNull(String),
+}
+
+impl CopyError {
+    /// Returns the SQLSTATE code with which PostgreSQL reports the error.
+    pub fn code(&self) -> SqlState {
+        match self {
+            CopyError::Format(_) => SqlState::BAD_COPY_FILE_FORMAT,
+            CopyError::InvalidValue(_) => SqlState::INVALID_TEXT_REPRESENTATION,
+            CopyError::NotNull(_) => SqlState::NOT_NULL_VIOLATION,
+        }
+    }
+}
+
+impl fmt::Display for CopyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CopyError::Format(message)
+            | CopyError::InvalidValue(message)
+            | CopyError::NotNull(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Error for CopyError {}
+
+/// Incrementally decodes the data that a client streams in a series of
+/// `CopyData` messages into rows destined for the table described by `desc`.
+///
+/// The fields of each record supply the columns of the table listed in
+/// `columns`, in order. Columns that are not listed are filled with `NULL`.
+pub struct CopyDecoder<'a> {
+    format: CopyFormat,
+    columns: &'a [usize],
+    desc: &'a RelationDesc,
+    /// Data that has been received but not yet decoded, as it does not end
+    /// with a complete record.
+    buf: Vec<u8>,
+    /// Whether the end-of-data marker has been decoded, after which any
+    /// further data is ignored.
+    done: bool,
+}
+
+impl<'a> CopyDecoder<'a> {
+    pub fn new(format: CopyFormat, columns: &'a [usize], desc: &'a RelationDesc) -> Self {
+        CopyDecoder {
+            format,
+            columns,
+            desc,
+            buf: vec![],
+            done: false,
+        }
+    }
+
+    /// Decodes the complete records in `data`, including any record that
+    /// began in data passed to a previous call. An incomplete record at the
+    /// end of `data` is retained until the next call.
+    pub fn decode(&mut self, data: &[u8]) -> Result<Vec<Row>, CopyError> {
+        if self.done {
+            return Ok(vec![]);
+        }
+        self.buf.extend(data);
+        let end = match self.format {
+            CopyFormat::Text => self.buf.iter().rposition(|b| *b == b'\n'),
+            CopyFormat::Csv => last_csv_record_end(&self.buf),
+        };
+        match end {
+            Some(end) => {
+                let rest = self.buf.split_off(end + 1);
+                let data = mem::replace(&mut self.buf, rest);
+                self.decode_records(&data)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Decodes the data that remains once the client has finished sending
+    /// data.
+    pub fn finish(&mut self) -> Result<Vec<Row>, CopyError> {
+        if self.done {
+            return Ok(vec![]);
+        }
+        let data = mem::take(&mut self.buf);
+        self.decode_records(&data)
+    }
+
+    fn decode_records(&mut self, data: &[u8]) -> Result<Vec<Row>, CopyError> {
+        let (records, done) = match self.format {
+            CopyFormat::Text => decode_text(data),
+            CopyFormat::Csv => decode_csv(data),
+        }
+        .map_err(|e| CopyError::Format(e.to_string()))?;
+        self.done = done;
+        decode_rows(records, self.columns, self.desc)
+    }
+}
+
+/// Returns the position of the newline that ends the last complete CSV
+/// record in `data`, if any.
+fn last_csv_record_end(data: &[u8]) -> Option<usize> {
+    let mut end = None;
+    let mut in_quotes = false;
+    for (i, b) in data.iter().enumerate() {
+        match b {
+            // Doubled double quotes inside a quoted field toggle the state
+            // twice, and so leave it unchanged.
+            b'"' => in_quotes = !in_quotes,
+            b'\n' if !in_quotes => end = Some(i),
+            _ => (),
+        }
+    }
+    end
+}
+
+/// Converts records of fields into rows destined for the table described by
+/// `desc`.
+fn decode_rows(
+    records: Vec<Vec<Option<String>>>,
+    columns: &[usize],
+    desc: &RelationDesc,
+) -> Result<Vec<Row>, CopyError> {
+    let column_name = |column: usize| {
+        desc.get_unambiguous_name(column)
+            .map(|n| n.as_str())
+            .unwrap_or("?column?")
+    };
+    let column_types = &desc.typ().column_types;
+    let mut rows = Vec::with_capacity(records.len());
+    for record in records {
+        if record.len() > columns.len() {
+            return Err(CopyError::Format(
+                "extra data after last expected column".into(),
+            ));
+        } else if record.len() < columns.len() {
+            return Err(CopyError::Format(format!(
+                "missing data for column \"{}\"",
+                column_name(columns[record.len()])
+            )));
+        }
+        let arena = RowArena::new();
+        let mut datums = vec![Datum::Null; column_types.len()];
+        for (field, &column) in record.iter().zip(columns) {
+            if let Some(field) = field {
+                datums[column] = decode_datum(field, &column_types[column].scalar_type, &arena)
+                    .map_err(|e| CopyError::InvalidValue(e.to_string()))?;
+            }
+        }
+        for (column, (datum, typ)) in datums.iter().zip(column_types).enumerate() {
+            if datum.is_null() && !typ.nullable {
+                return Err(CopyError::NotNull(format!(
+                    "null value in column \"{}\" violates not-null constraint",
+                    column_name(column)
+                )));
+            }
+        }
+        rows.push(Row::pack(&datums));
+    }
+    Ok(rows)
+}
+
+/// Parses a single field into a datum of type `typ`.
+fn decode_datum<'a>(
+    field: &'a str,
+    typ: &ScalarType,
+    arena: &'a RowArena,
+) -> Result<Datum<'a>, failure::Error> {
+    Ok(match typ {
+        ScalarType::Bool => Datum::from(strconv::parse_bool(field)?),
+        ScalarType::Int16 => Datum::from(strconv::parse_int16(field)?),
+        ScalarType::Int32 => Datum::from(strconv::parse_int32(field)?),
+        ScalarType::Int64 => Datum::from(strconv::parse_int64(field)?),
+        ScalarType::Float32 => Datum::from(strconv::parse_float32(field)?),
+        ScalarType::Float64 => Datum::from(strconv::parse_float64(field)?),
+        ScalarType::Decimal(_, scale) => {
+            let d = strconv::parse_decimal(field)?;
+            let significand = d
+                .rescale(*scale)
+                .ok_or_else(|| format_err!("decimal {} out of range for scale {}", d, scale))?;
+            Datum::from(significand)
+        }
+        ScalarType::Date => Datum::Date(strconv::parse_date(field)?),
+        ScalarType::Time => Datum::Time(strconv::parse_time(field)?),
+        ScalarType::Timestamp => Datum::Timestamp(strconv::parse_timestamp(field)?),
+        ScalarType::TimestampTz => Datum::TimestampTz(strconv::parse_timestamptz(field)?),
+        ScalarType::Interval => Datum::Interval(strconv::parse_interval(field)?),
+        ScalarType::Bytes => Datum::Bytes(arena.push_bytes(strconv::parse_bytes(field)?)),
+        ScalarType::String => Datum::String(field),
+        ScalarType::Char { length } => {
+            let field = match length {
+                Some(length) => char::truncate(field, *length, true)
+                    .ok_or_else(|| format_err!("value too long for type character({})", length))?,
+                None => field,
+            };
+            Datum::String(char::strip_padding(field))
+        }
+        ScalarType::VarChar { length } => match length {
+            Some(length) => {
+                Datum::String(char::truncate(field, *length, true).ok_or_else(|| {
+                    format_err!("value too long for type character varying({})", length)
+                })?)
+            }
+            None => Datum::String(field),
+        },
+        ScalarType::Uuid => Datum::Uuid(strconv::parse_uuid(field)?),
+        ScalarType::Jsonb => arena
+            .push_row(strconv::parse_jsonb(field)?.into_row())
+            .unpack_first(),
+        ScalarType::List(_) | ScalarType::Array(_) | ScalarType::Record { .. } => {
+            bail!("COPY FROM STDIN does not support columns of type {}", typ)
+        }
+    })
+}
+
+/// Splits data in PostgreSQL's text format into records of fields.
+///
+/// Records are separated by newlines and fields by tabs. The sequence `\N`
+/// represents `NULL`, and other backslash sequences are unescaped. A line
+/// containing only `\.` marks the end of the data. Returns whether the end of
+/// the data was marked.
+fn decode_text(data: &[u8]) -> Result<(Vec<Vec<Option<String>>>, bool), failure::Error> {
+    let mut records = vec![];
+    let mut lines: Vec<_> = data.split(|b| *b == b'\n').collect();
+    if lines.last() == Some(&&b""[..]) {
+        lines.pop();
+    }
+    for line in lines {
+        let line = if line.ends_with(b"\r") {
+            &line[..line.len() - 1]
+        } else {
+            line
+        };
+        if line == b"\\." {
+            return Ok((records, true));
+        }
+        let mut record = vec![];
+        let mut start = 0;
+        let mut i = 0;
+        while i <= line.len() {
+            match line.get(i) {
+                Some(b'\\') => i = cmp::min(i + 2, line.len()),
+                Some(b'\t') | None => {
+                    record.push(unescape_text_field(&line[start..i])?);
+                    i += 1;
+                    start = i;
+                }
+                Some(_) => i += 1,
+            }
+        }
+        records.push(record);
+    }
+    Ok((records, false))
+}
+
+fn unescape_text_field(field: &[u8]) -> Result<Option<String>, failure::Error> {
+    if field == b"\\N" {
+        return Ok(None);
+    }
+    let mut out = Vec::with_capacity(field.len());
+    let mut bytes = field.iter().copied().peekable();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'b') => out.push(b'\x08'),
+            Some(b'f') => out.push(b'\x0c'),
+            Some(b'n') => out.push(b'\n'),
+            Some(b'r') => out.push(b'\r'),
+            Some(b't') => out.push(b'\t'),
+            Some(b'v') => out.push(b'\x0b'),
+            Some(b'x') if bytes.peek().map_or(false, u8::is_ascii_hexdigit) => {
+                let mut n = 0;
+                for _ in 0..2 {
+                    match bytes.peek().and_then(|b| char::from(*b).to_digit(16)) {
+                        Some(d) => {
+                            n = n * 16 + d;
+                            bytes.next();
+                        }
+                        None => break,
+                    }
+                }
+                out.push(n as u8);
+            }
+            Some(b @ b'0'..=b'7') => {
+                let mut n = u32::from(b - b'0');
+                for _ in 0..2 {
+                    match bytes.peek() {
+                        Some(b @ b'0'..=b'7') => {
+                            n = n * 8 + u32::from(b - b'0');
+                            bytes.next();
+                        }
+                        _ => break,
+                    }
+                }
+                out.push(n as u8);
+            }
+            Some(b) => out.push(b),
+            None => bail!("unterminated escape sequence in COPY data"),
+        }
+    }
+    String::from_utf8(out)
+        .map_err(|_| format_err!("invalid UTF-8 in COPY data"))
+        .map(Some)
+}
+
+/// Splits CSV data into records of fields.
+///
+/// Fields are separated by commas and may be quoted with double quotes, in
+/// which case they may contain commas, newlines, and doubled double quotes.
+/// An unquoted empty field represents `NULL`, while a quoted empty field
+/// represents the empty string. A line containing only `\.` marks the end of
+/// the data. Returns whether the end of the data was marked.
+fn decode_csv(data: &[u8]) -> Result<(Vec<Vec<Option<String>>>, bool), failure::Error> {
+    fn finish_field(field: &mut Vec<u8>, quoted: bool) -> Result<Option<String>, failure::Error> {
+        let field = mem::take(field);
+        if field.is_empty() && !quoted {
+            Ok(None)
+        } else {
+            String::from_utf8(field)
+                .map(Some)
+                .map_err(|_| format_err!("invalid UTF-8 in COPY data"))
+        }
+    }
+
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = vec![];
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut bytes = data.iter().copied().peekable();
+    loop {
+        let b = bytes.next();
+        if in_quotes {
+            match b {
+                Some(b'"') if bytes.peek() == Some(&b'"') => {
+                    bytes.next();
+                    field.push(b'"');
+                }
+                Some(b'"') => in_quotes = false,
+                Some(b) => field.push(b),
+                None => bail!("unterminated CSV quoted field"),
+            }
+            continue;
+        }
+        match b {
+            Some(b'"') => {
+                in_quotes = true;
+                quoted = true;
+            }
+            Some(b',') => {
+                record.push(finish_field(&mut field, quoted)?);
+                quoted = false;
+            }
+            Some(b'\r') if bytes.peek() == Some(&b'\n') => (),
+            Some(b'\n') | None => {
+                if b.is_none() && record.is_empty() && field.is_empty() && !quoted {
+                    // Trailing newline or empty input.
+                    break;
+                }
+                if record.is_empty() && !quoted && field == b"\\." {
+                    return Ok((records, true));
+                }
+                record.push(finish_field(&mut field, quoted)?);
+                records.push(mem::take(&mut record));
+                quoted = false;
+                if b.is_none() {
+                    break;
+                }
+            }
+            Some(b) => field.push(b),
+        }
+    }
+    Ok((records, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use repr::ColumnType;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_split_records() -> Result<(), failure::Error> {
+        let desc = RelationDesc::empty()
+            .with_column("a", ColumnType::new(ScalarType::String))
+            .with_column("b", ColumnType::new(ScalarType::String));
+        let columns = [0, 1];
+        let row = |a, b| Row::pack(&[Datum::String(a), Datum::String(b)]);
+
+        let mut decoder = CopyDecoder::new(CopyFormat::Text, &columns, &desc);
+        assert!(decoder.decode(b"one\tt")?.is_empty());
+        assert_eq!(
+            decoder.decode(b"wo\nthree\tfour\nfi")?,
+            vec![row("one", "two"), row("three", "four")]
+        );
+        assert_eq!(
+            decoder.decode(b"ve\tsix\n\\.\nseven\teight\n")?,
+            vec![row("five", "six")]
+        );
+        assert!(decoder.finish()?.is_empty());
+
+        let mut decoder = CopyDecoder::new(CopyFormat::Csv, &columns, &desc);
+        assert!(decoder.decode(b"one,\"line\n")?.is_empty());
+        assert_eq!(
+            decoder.decode(b"break\"\nthree,four")?,
+            vec![row("one", "line\nbreak")]
+        );
+        assert_eq!(decoder.finish()?, vec![row("three", "four")]);
+
+        Ok(())
+    }
+}
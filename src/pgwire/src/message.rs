@@ -11,7 +11,8 @@ use bytes::BytesMut;
 use postgres::error::SqlState;
 
 use coord::session::TransactionStatus as CoordTransactionStatus;
-use dataflow_types::Update;
+use dataflow_types::{Timestamp, Update};
+use repr::adt::datetime::Timezone;
 use repr::{ColumnName, RelationDesc, RelationType, ScalarType};
 
 // Pgwire protocol versions are represented as 32-bit integers, where the
@@ -208,6 +209,28 @@ pub enum FrontendMessage {
     // This command is part of the extended query flow.
     ClosePortal { name: String },
 
+    /// Send data as part of a `COPY FROM STDIN` operation.
+    ///
+    /// This command is part of the copy-in flow.
+    CopyData(Vec<u8>),
+
+    /// Signal the end of data in a `COPY FROM STDIN` operation.
+    ///
+    /// This command is part of the copy-in flow.
+    CopyDone,
+
+    /// Abort a `COPY FROM STDIN` operation with the specified error message.
+    ///
+    /// This command is part of the copy-in flow.
+    CopyFail(String),
+
+    /// Respond to an authentication request.
+    ///
+    /// The contents of the message depend on the type of the request: a
+    /// password, or the initial or subsequent response in a SASL exchange.
+    /// The contents are therefore left undecoded until the request is known.
+    Password(Vec<u8>),
+
     /// Terminate a connection.
     Terminate,
 }
@@ -225,6 +248,10 @@ impl FrontendMessage {
             FrontendMessage::Sync => "sync",
             FrontendMessage::CloseStatement { .. } => "close_statement",
             FrontendMessage::ClosePortal { .. } => "close_portal",
+            FrontendMessage::CopyData(_) => "copy_data",
+            FrontendMessage::CopyDone => "copy_done",
+            FrontendMessage::CopyFail(_) => "copy_fail",
+            FrontendMessage::Password(_) => "password",
             FrontendMessage::Terminate => "terminate",
         }
     }
@@ -236,6 +263,10 @@ impl FrontendMessage {
 #[derive(Debug)]
 pub enum BackendMessage {
     AuthenticationOk,
+    AuthenticationCleartextPassword,
+    AuthenticationSASL(Vec<&'static str>),
+    AuthenticationSASLContinue(Vec<u8>),
+    AuthenticationSASLFinal(Vec<u8>),
     CommandComplete {
         tag: String,
     },
@@ -267,6 +298,10 @@ pub enum BackendMessage {
         message: String,
         detail: Option<String>,
     },
+    CopyInResponse {
+        overall_format: pgrepr::Format,
+        column_formats: Vec<pgrepr::Format>,
+    },
     CopyOutResponse {
         overall_format: pgrepr::Format,
         column_formats: Vec<pgrepr::Format>,
@@ -316,7 +351,7 @@ pub struct FieldDescription {
     pub format: pgrepr::Format,
 }
 
-pub fn encode_update(update: Update, typ: &RelationType) -> Vec<u8> {
+pub fn encode_update(update: Update, typ: &RelationType, tz: &Timezone) -> Vec<u8> {
     let mut out = Vec::new();
     let mut buf = BytesMut::new();
     for field in pgrepr::values_from_row(update.row, typ) {
@@ -324,7 +359,7 @@ pub fn encode_update(update: Update, typ: &RelationType) -> Vec<u8> {
             None => out.extend(b"\\N"),
             Some(field) => {
                 buf.clear();
-                field.encode_text(&mut buf);
+                field.encode_text_in(&mut buf, tz);
                 for b in &buf {
                     match b {
                         b'\\' => out.extend(b"\\\\"),
@@ -342,6 +377,10 @@ pub fn encode_update(update: Update, typ: &RelationType) -> Vec<u8> {
     out
 }
 
+pub fn encode_progress(timestamp: Timestamp) -> Vec<u8> {
+    format!("Progress: {}\n", timestamp).into_bytes()
+}
+
 pub fn row_description_from_desc(desc: &RelationDesc) -> Vec<FieldDescription> {
     desc.iter()
         .map(|(name, typ)| {
@@ -361,6 +400,15 @@ pub fn row_description_from_desc(desc: &RelationDesc) -> Vec<FieldDescription> {
                     ScalarType::Decimal(precision, scale) => {
                         ((i32::from(*precision) << 16) | i32::from(*scale)) + 4
                     }
+                    // Length-constrained string types store their length plus
+                    // four, for historical reasons (the size of the varlena
+                    // header).
+                    ScalarType::Char {
+                        length: Some(length),
+                    }
+                    | ScalarType::VarChar {
+                        length: Some(length),
+                    } => *length as i32 + 4,
                     _ => -1,
                 },
                 format: pgrepr::Format::Text,
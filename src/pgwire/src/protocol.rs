@@ -25,12 +25,15 @@ use tokio_util::codec::Framed;
 
 use coord::session::Session;
 use coord::{ExecuteResponse, StartupMessage};
-use dataflow_types::{PeekResponse, Update};
+use dataflow_types::{PeekResponse, TailResponse};
+use expr::GlobalId;
 use ore::future::OreSinkExt;
 use repr::{Datum, RelationDesc, Row, RowArena};
-use sql::ast::Statement;
+use sql::ast::{CopyFormat, Statement};
 
-use crate::codec::Codec;
+use crate::auth::{AuthConfig, AuthMethod, ScramExchange, ScramVerifier, SCRAM_SHA_256};
+use crate::codec::{self, Codec};
+use crate::copy;
 use crate::message::{
     self, BackendMessage, ErrorSeverity, FrontendMessage, NoticeSeverity, VERSIONS, VERSION_3,
 };
@@ -87,6 +90,8 @@ pub struct StateMachine<'a, A> {
     pub conn: &'a mut sink::Buffer<Framed<A, Codec>, BackendMessage>,
     pub conn_id: u32,
     pub secret_key: u32,
+    pub encrypted: bool,
+    pub auth: Option<&'a AuthConfig>,
     pub cmdq_tx: futures::channel::mpsc::UnboundedSender<coord::Command>,
 }
 
@@ -157,6 +162,18 @@ where
             Some(FrontendMessage::ClosePortal { name }) => self.close_portal(session, name).await?,
             Some(FrontendMessage::Flush) => self.flush(session).await?,
             Some(FrontendMessage::Sync) => self.sync(session).await?,
+            // Per the protocol, copy-in messages received outside of a copy-in
+            // operation are ignored.
+            Some(FrontendMessage::CopyData(_))
+            | Some(FrontendMessage::CopyDone)
+            | Some(FrontendMessage::CopyFail(_)) => State::Ready(session),
+            Some(FrontendMessage::Password(_)) => {
+                self.fatal(
+                    SqlState::PROTOCOL_VIOLATION,
+                    "unexpected password message after authentication",
+                )
+                .await?
+            }
             Some(FrontendMessage::Terminate) => State::Done,
             None => State::Done,
         };
@@ -195,13 +212,34 @@ where
                 .await;
         }
 
+        if let Some(auth) = self.auth {
+            let user = match params.iter().find(|(name, _)| name == "user") {
+                Some((_, user)) => user.clone(),
+                None => {
+                    return self
+                        .fatal(
+                            SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+                            "no user specified in startup message",
+                        )
+                        .await;
+                }
+            };
+            if !self.authenticate(auth, &user).await? {
+                return Ok(State::Done);
+            }
+        }
+
         for (name, value) in params {
             let _ = session.set(&name, &value);
         }
 
         let (tx, rx) = futures::channel::oneshot::channel();
         self.cmdq_tx
-            .send(coord::Command::Startup { session, tx })
+            .send(coord::Command::Startup {
+                session,
+                authenticated: self.auth.is_some(),
+                tx,
+            })
             .await?;
         let (notices, session) = match rx.await? {
             coord::Response {
@@ -230,12 +268,16 @@ where
                     .collect();
                 (notices, session)
             }
+            // The coordinator only rejects sessions whose authenticated user
+            // has no role.
             coord::Response {
-                result: Err(err),
-                session,
+                result: Err(err), ..
             } => {
                 return self
-                    .error(session, SqlState::INTERNAL_ERROR, err.to_string())
+                    .fatal(
+                        SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+                        err.to_string(),
+                    )
                     .await;
             }
         };
@@ -257,6 +299,140 @@ where
         self.flush(session).await
     }
 
+    /// Authenticates `user` using the method configured in `auth`.
+    ///
+    /// Returns whether the user successfully authenticated. If not, any error
+    /// has already been reported to the client, and the connection should be
+    /// closed.
+    async fn authenticate(&mut self, auth: &AuthConfig, user: &str) -> Result<bool, comm::Error> {
+        let verifier = match auth.users.get(user) {
+            Some(verifier) => verifier.clone(),
+            // Carry on with a verifier that cannot match, so that the client
+            // can't determine whether the user exists.
+            None => match auth.users.mock(user) {
+                Ok(verifier) => verifier,
+                Err(err) => return self.auth_fatal(SqlState::INTERNAL_ERROR, err).await,
+            },
+        };
+        match auth.method {
+            AuthMethod::Password => self.authenticate_password(verifier, user).await,
+            AuthMethod::ScramSha256 => self.authenticate_scram(verifier, user).await,
+        }
+    }
+
+    async fn authenticate_password(
+        &mut self,
+        verifier: ScramVerifier,
+        user: &str,
+    ) -> Result<bool, comm::Error> {
+        if !self.encrypted {
+            return self
+                .auth_fatal(
+                    SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+                    "password authentication requires a TLS-encrypted connection",
+                )
+                .await;
+        }
+        self.conn
+            .send(BackendMessage::AuthenticationCleartextPassword)
+            .await?;
+        let data = match self.recv_password().await? {
+            Some(data) => data,
+            None => return Ok(false),
+        };
+        let res = match codec::decode_cleartext_password(&data) {
+            Ok(password) => verifier.verify_password(password),
+            Err(err) => return self.auth_fatal(SqlState::PROTOCOL_VIOLATION, err).await,
+        };
+        match res {
+            Ok(true) => Ok(true),
+            Ok(false) => self.auth_failed(user).await,
+            Err(err) => self.auth_fatal(SqlState::INTERNAL_ERROR, err).await,
+        }
+    }
+
+    async fn authenticate_scram(
+        &mut self,
+        verifier: ScramVerifier,
+        user: &str,
+    ) -> Result<bool, comm::Error> {
+        self.conn
+            .send(BackendMessage::AuthenticationSASL(vec![SCRAM_SHA_256]))
+            .await?;
+        let data = match self.recv_password().await? {
+            Some(data) => data,
+            None => return Ok(false),
+        };
+        let res = match codec::decode_sasl_initial_response(&data) {
+            Ok((SCRAM_SHA_256, Some(client_first))) => ScramExchange::start(verifier, client_first),
+            Ok((SCRAM_SHA_256, None)) => {
+                return self
+                    .auth_fatal(SqlState::PROTOCOL_VIOLATION, "missing SCRAM message")
+                    .await
+            }
+            Ok((mechanism, _)) => {
+                let message = format!("unsupported SASL mechanism {}", mechanism);
+                return self.auth_fatal(SqlState::PROTOCOL_VIOLATION, message).await;
+            }
+            Err(err) => return self.auth_fatal(SqlState::PROTOCOL_VIOLATION, err).await,
+        };
+        let (exchange, server_first) = match res {
+            Ok(res) => res,
+            Err(err) => return self.auth_fatal(SqlState::PROTOCOL_VIOLATION, err).await,
+        };
+
+        self.conn
+            .send(BackendMessage::AuthenticationSASLContinue(
+                server_first.into_bytes(),
+            ))
+            .await?;
+        let data = match self.recv_password().await? {
+            Some(data) => data,
+            None => return Ok(false),
+        };
+        match exchange.finish(&data) {
+            Ok(Some(server_final)) => {
+                self.send(BackendMessage::AuthenticationSASLFinal(
+                    server_final.into_bytes(),
+                ))
+                .await?;
+                Ok(true)
+            }
+            Ok(None) => self.auth_failed(user).await,
+            Err(err) => self.auth_fatal(SqlState::PROTOCOL_VIOLATION, err).await,
+        }
+    }
+
+    /// Receives the client's response to an authentication request.
+    ///
+    /// Returns `None` if the client disconnected or sent some other message,
+    /// in which case the connection should be closed.
+    async fn recv_password(&mut self) -> Result<Option<Vec<u8>>, comm::Error> {
+        match self.recv().await? {
+            Some(FrontendMessage::Password(data)) => Ok(Some(data)),
+            Some(message) => {
+                let message = format!("expected password response, got {}", message.name());
+                self.fatal(SqlState::PROTOCOL_VIOLATION, message).await?;
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn auth_failed(&mut self, user: &str) -> Result<bool, comm::Error> {
+        let message = format!("password authentication failed for user \"{}\"", user);
+        self.auth_fatal(SqlState::INVALID_PASSWORD, message).await
+    }
+
+    async fn auth_fatal(
+        &mut self,
+        code: SqlState,
+        message: impl ToString,
+    ) -> Result<bool, comm::Error> {
+        self.fatal(code, message.to_string()).await?;
+        Ok(false)
+    }
+
     async fn one_query(&mut self, session: Session, stmt: Statement) -> Result<State, comm::Error> {
         let stmt_name = String::from("");
         let portal_name = String::from("");
@@ -690,6 +866,12 @@ where
         }
 
         match response {
+            ExecuteResponse::CopyFrom {
+                id,
+                columns,
+                format,
+                desc,
+            } => self.copy_from(session, id, columns, format, desc).await,
             ExecuteResponse::CreatedDatabase { existed } => {
                 created!(existed, SqlState::DUPLICATE_DATABASE, "database")
             }
@@ -711,6 +893,8 @@ where
             ExecuteResponse::CreatedView { existed } => {
                 created!(existed, SqlState::DUPLICATE_OBJECT, "view")
             }
+            ExecuteResponse::AlteredObject(o) => command_complete!("ALTER {}", o),
+            ExecuteResponse::CreatedRole => command_complete!("CREATE ROLE"),
             ExecuteResponse::Deleted(n) => command_complete!("DELETE {}", n),
             ExecuteResponse::DroppedDatabase => command_complete!("DROP DATABASE"),
             ExecuteResponse::DroppedSchema => command_complete!("DROP SCHEMA"),
@@ -719,10 +903,13 @@ where
             ExecuteResponse::DroppedSink => command_complete!("DROP SINK"),
             ExecuteResponse::DroppedTable => command_complete!("DROP TABLE"),
             ExecuteResponse::DroppedView => command_complete!("DROP VIEW"),
+            ExecuteResponse::DroppedRole => command_complete!("DROP ROLE"),
             ExecuteResponse::EmptyQuery => {
                 self.send(BackendMessage::EmptyQueryResponse).await?;
                 Ok(State::Ready(session))
             }
+            ExecuteResponse::GrantedPrivilege => command_complete!("GRANT"),
+            ExecuteResponse::RevokedPrivilege => command_complete!("REVOKE"),
             ExecuteResponse::Inserted(n) => {
                 // "On successful completion, an INSERT command returns a
                 // command tag of the form `INSERT <oid> <count>`."
@@ -832,6 +1019,7 @@ where
                 .map(|ty| pgrepr::Type::from(&ty.scalar_type))
                 .zip(portal.result_formats.iter().copied())
                 .collect(),
+            session.timezone(),
         );
 
         let mut row_count = 0u32;
@@ -869,7 +1057,7 @@ where
         &mut self,
         session: Session,
         row_desc: RelationDesc,
-        mut rx: comm::mpsc::Receiver<Vec<Update>>,
+        mut rx: comm::mpsc::Receiver<TailResponse>,
     ) -> Result<State, comm::Error> {
         let typ = row_desc.typ();
         let column_formats = iter::repeat(pgrepr::Format::Text)
@@ -885,16 +1073,25 @@ where
         loop {
             match time::timeout(Duration::from_secs(1), rx.next()).await {
                 Ok(None) => break,
-                Ok(Some(updates)) => {
-                    let updates = updates?;
-                    count += updates.len();
-                    for update in updates {
-                        self.send(BackendMessage::CopyData(message::encode_update(
-                            update, typ,
+                Ok(Some(response)) => match response? {
+                    TailResponse::Rows(updates) => {
+                        count += updates.len();
+                        for update in updates {
+                            self.send(BackendMessage::CopyData(message::encode_update(
+                                update,
+                                typ,
+                                &session.timezone(),
+                            )))
+                            .await?;
+                        }
+                    }
+                    TailResponse::Progress(timestamp) => {
+                        self.send(BackendMessage::CopyData(message::encode_progress(
+                            timestamp,
                         )))
                         .await?;
                     }
-                }
+                },
                 Err(time::Elapsed { .. }) => {
                     // It's been a while since we've had any data to send, and
                     // the client may have disconnected. Send a data message
@@ -926,6 +1123,96 @@ where
         Ok(State::Ready(session))
     }
 
+    async fn copy_from(
+        &mut self,
+        session: Session,
+        id: GlobalId,
+        columns: Vec<usize>,
+        format: CopyFormat,
+        desc: RelationDesc,
+    ) -> Result<State, comm::Error> {
+        let column_formats = iter::repeat(pgrepr::Format::Text)
+            .take(columns.len())
+            .collect();
+        self.send(BackendMessage::CopyInResponse {
+            overall_format: pgrepr::Format::Text,
+            column_formats,
+        })
+        .await?;
+        self.conn.flush().await?;
+
+        // The rows are staged until the client finishes sending data, and
+        // then inserted in a single write, so that a COPY that fails partway
+        // through leaves the table unchanged.
+        let mut decoder = copy::CopyDecoder::new(format, &columns, &desc);
+        let mut rows = vec![];
+        loop {
+            let res = match self.recv().await? {
+                Some(FrontendMessage::CopyData(buf)) => decoder.decode(&buf),
+                Some(FrontendMessage::CopyDone) => break,
+                Some(FrontendMessage::CopyFail(err)) => {
+                    return self
+                        .error(
+                            session,
+                            SqlState::QUERY_CANCELED,
+                            format!("COPY from stdin failed: {}", err),
+                        )
+                        .await
+                }
+                // Per the protocol, Flush and Sync messages are ignored
+                // during a copy-in operation.
+                Some(FrontendMessage::Flush) | Some(FrontendMessage::Sync) => continue,
+                Some(_) => {
+                    return self
+                        .error(
+                            session,
+                            SqlState::PROTOCOL_VIOLATION,
+                            "unexpected message type during COPY from stdin",
+                        )
+                        .await
+                }
+                None => return Ok(State::Done),
+            };
+            match res {
+                Ok(decoded) => rows.extend(decoded),
+                Err(err) => return self.error(session, err.code(), err.to_string()).await,
+            }
+        }
+        match decoder.finish() {
+            Ok(decoded) => rows.extend(decoded),
+            Err(err) => return self.error(session, err.code(), err.to_string()).await,
+        }
+
+        let count = rows.len();
+        let (tx, rx) = futures::channel::oneshot::channel();
+        self.cmdq_tx
+            .send(coord::Command::CopyRows {
+                id,
+                rows,
+                session,
+                tx,
+            })
+            .await?;
+        let session = match rx.await? {
+            coord::Response {
+                result: Ok(()),
+                session,
+            } => session,
+            coord::Response {
+                result: Err(err),
+                session,
+            } => {
+                return self
+                    .error(session, SqlState::INTERNAL_ERROR, err.to_string())
+                    .await
+            }
+        };
+
+        let tag = format!("COPY {}", count);
+        self.send(BackendMessage::CommandComplete { tag }).await?;
+        Ok(State::Ready(session))
+    }
+
     async fn recv(&mut self) -> Result<Option<FrontendMessage>, comm::Error> {
         let message = self.conn.try_next().await?;
         match &message {
@@ -20,6 +20,7 @@ use tokio_util::codec::Framed;
 
 use coord::session::Session;
 
+use crate::auth::AuthConfig;
 use crate::codec::{self, Codec, ACCEPT_SSL_ENCRYPTION, REJECT_ENCRYPTION};
 use crate::id_alloc::{IdAllocator, IdExhaustionError};
 use crate::message::FrontendStartupMessage;
@@ -30,18 +31,21 @@ pub struct Server {
     id_alloc: IdAllocator,
     secrets: SecretManager,
     tls: Option<SslAcceptor>,
+    auth: Option<AuthConfig>,
     cmdq_tx: futures::channel::mpsc::UnboundedSender<coord::Command>,
 }
 
 impl Server {
     pub fn new(
         tls: Option<SslAcceptor>,
+        auth: Option<AuthConfig>,
         cmdq_tx: futures::channel::mpsc::UnboundedSender<coord::Command>,
     ) -> Server {
         Server {
             id_alloc: IdAllocator::new(1, 1 << 16),
             secrets: SecretManager::new(),
             tls,
+            auth,
             cmdq_tx,
         }
     }
@@ -62,13 +66,25 @@ impl Server {
                     };
                     self.secrets.generate(conn_id);
 
+                    let encrypted = match conn {
+                        Conn::Unencrypted(_) => false,
+                        Conn::Ssl(_) => true,
+                    };
                     let mut machine = StateMachine {
                         conn: &mut Framed::new(conn, Codec::new()).buffer(32),
                         conn_id,
                         secret_key: self.secrets.get(conn_id).unwrap(),
+                        encrypted,
+                        auth: self.auth.as_ref(),
                         cmdq_tx: self.cmdq_tx.clone(),
                     };
-                    let res = machine.start(Session::new(conn_id), version, params).await;
+                    let user = params
+                        .iter()
+                        .find(|(name, _)| name == "user")
+                        .map(|(_, user)| user.clone())
+                        .unwrap_or_else(|| "materialize".into());
+                    let session = Session::new(conn_id, user);
+                    let res = machine.start(session, version, params).await;
 
                     // Clean up state tied to this specific connection.
                     self.cmdq_tx
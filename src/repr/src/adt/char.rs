@@ -0,0 +1,69 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Length-constrained character string types.
+//!
+//! Values of the blank-padded `char(n)` type are stored without their trailing
+//! spaces, which PostgreSQL considers insignificant, so that they can be
+//! compared, hashed, and grouped exactly as ordinary strings are. The padding
+//! is restored by [`pad`] only when a value is presented to a client.
+
+/// Truncates `s` to at most `length` characters.
+///
+/// If `fail_on_len` is set, as it is for assignment casts, discarding any
+/// character other than a trailing space is not permitted, and `None` is
+/// returned instead.
+pub fn truncate(s: &str, length: usize, fail_on_len: bool) -> Option<&str> {
+    match s.char_indices().nth(length) {
+        None => Some(s),
+        Some((i, _)) if fail_on_len && s[i..].chars().any(|c| c != ' ') => None,
+        Some((i, _)) => Some(&s[..i]),
+    }
+}
+
+/// Strips the insignificant trailing spaces from the blank-padded string `s`.
+pub fn strip_padding(s: &str) -> &str {
+    s.trim_end_matches(' ')
+}
+
+/// Pads `s` with spaces to `length` characters, if a length is specified.
+pub fn pad(s: &str, length: Option<usize>) -> String {
+    let mut out = s.to_owned();
+    if let Some(length) = length {
+        let n = s.chars().count();
+        if n < length {
+            out.extend(std::iter::repeat(' ').take(length - n));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("abc", 5, true), Some("abc"));
+        assert_eq!(truncate("abcdef", 3, false), Some("abc"));
+        assert_eq!(truncate("abc   ", 3, true), Some("abc"));
+        assert_eq!(truncate("你好世界", 2, false), Some("你好"));
+        assert_eq!(truncate("abc d", 3, true), None);
+    }
+
+    #[test]
+    fn test_padding() {
+        assert_eq!(strip_padding("ab  "), "ab");
+        assert_eq!(strip_padding(" ab"), " ab");
+        assert_eq!(pad("ab", Some(4)), "ab  ");
+        assert_eq!(pad("你好", Some(3)), "你好 ");
+        assert_eq!(pad("abc", Some(2)), "abc");
+        assert_eq!(pad("ab", None), "ab");
+    }
+}
@@ -11,12 +11,15 @@
 
 #![allow(missing_docs)]
 
+use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::convert::TryInto;
 use std::fmt;
 use std::str::FromStr;
 
-use chrono::{NaiveDate, NaiveTime};
+use chrono::offset::{LocalResult, Offset, TimeZone};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::{Tz, TZ_VARIANTS};
 use serde::{Deserialize, Serialize};
 
 use crate::adt::interval::Interval;
@@ -1707,6 +1710,150 @@ pub(crate) fn parse_timezone_offset_second(value: &str) -> Result<i64, String> {
     Ok(build_timezone_offset_second(&toks, value)?)
 }
 
+/// A time zone, which is either a named zone from the IANA time zone database
+/// or a fixed offset from UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Timezone {
+    /// A fixed offset, in seconds east of UTC.
+    FixedOffset(i32),
+    /// A named zone, whose offset from UTC may vary over time.
+    Tz(#[serde(with = "tz_serde")] Tz),
+}
+
+impl Timezone {
+    /// Returns the offset from UTC that is in effect in this zone at the
+    /// instant `utc`.
+    pub fn offset_at(&self, utc: &NaiveDateTime) -> FixedOffset {
+        match self {
+            Timezone::FixedOffset(secs) => FixedOffset::east(*secs),
+            Timezone::Tz(tz) => tz.offset_from_utc_datetime(utc).fix(),
+        }
+    }
+
+    /// Converts the instant `ts` to the local time in this zone.
+    pub fn to_local(&self, ts: &DateTime<Utc>) -> NaiveDateTime {
+        let offset = self.offset_at(&ts.naive_utc());
+        ts.naive_utc() + Duration::seconds(offset.local_minus_utc().into())
+    }
+
+    /// Interprets `ts` as a local time in this zone and converts it to an
+    /// instant.
+    ///
+    /// As in PostgreSQL, a local time that occurs twice, because clocks were
+    /// set back, is resolved to the later of the two instants, while a local
+    /// time that never occurs, because clocks were set forward, is interpreted
+    /// with the offset that was in effect before the transition.
+    pub fn to_utc(&self, ts: &NaiveDateTime) -> DateTime<Utc> {
+        let utc = match self {
+            Timezone::FixedOffset(secs) => *ts - Duration::seconds((*secs).into()),
+            Timezone::Tz(tz) => match tz.from_local_datetime(ts) {
+                LocalResult::Single(dt) => dt.naive_utc(),
+                LocalResult::Ambiguous(_, latest) => latest.naive_utc(),
+                LocalResult::None => {
+                    let offset = self.offset_at(&(*ts - Duration::days(1)));
+                    *ts - Duration::seconds(offset.local_minus_utc().into())
+                }
+            },
+        };
+        DateTime::from_utc(utc, Utc)
+    }
+}
+
+impl Default for Timezone {
+    fn default() -> Timezone {
+        Timezone::Tz(Tz::UTC)
+    }
+}
+
+impl fmt::Display for Timezone {
+    /// Formats named zones by name and fixed offsets in the POSIX convention
+    /// in which they were specified, so `+05:30` is five and a half hours
+    /// west of UTC.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Timezone::FixedOffset(secs) => {
+                let sign = if *secs > 0 { '-' } else { '+' };
+                let secs = secs.abs();
+                write!(f, "{}{:02}:{:02}", sign, secs / 3600, secs / 60 % 60)?;
+                if secs % 60 != 0 {
+                    write!(f, ":{:02}", secs % 60)?;
+                }
+                Ok(())
+            }
+            Timezone::Tz(tz) => f.write_str(tz.name()),
+        }
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = String;
+
+    /// Parses the name of a zone in the IANA time zone database, like
+    /// `America/New_York`, or a numeric offset from UTC, like `+05:30`.
+    ///
+    /// Zone names are matched case insensitively. As in PostgreSQL, numeric
+    /// offsets follow the POSIX convention, in which positive offsets are
+    /// west of UTC, so `+05:30` is five and a half hours behind UTC.
+    fn from_str(s: &str) -> Result<Timezone, String> {
+        if let Ok(tz) = s.parse() {
+            return Ok(Timezone::Tz(tz));
+        }
+        if let Some(tz) = TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().eq_ignore_ascii_case(s))
+        {
+            return Ok(Timezone::Tz(*tz));
+        }
+        parse_timezone_offset_second(s)
+            .ok()
+            .and_then(|secs| FixedOffset::west_opt(secs.try_into().ok()?))
+            .map(|offset| Timezone::FixedOffset(offset.local_minus_utc()))
+            .ok_or_else(|| format!("time zone \"{}\" not recognized", s))
+    }
+}
+
+// Fixed offsets sort before named zones. Named zones are ordered by name,
+// rather than by their position in the time zone database, so that the
+// ordering is stable across versions of the database.
+impl PartialOrd for Timezone {
+    fn partial_cmp(&self, other: &Timezone) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Timezone {
+    fn cmp(&self, other: &Timezone) -> Ordering {
+        match (self, other) {
+            (Timezone::FixedOffset(a), Timezone::FixedOffset(b)) => a.cmp(b),
+            (Timezone::FixedOffset(_), Timezone::Tz(_)) => Ordering::Less,
+            (Timezone::Tz(_), Timezone::FixedOffset(_)) => Ordering::Greater,
+            (Timezone::Tz(a), Timezone::Tz(b)) => a.name().cmp(b.name()),
+        }
+    }
+}
+
+/// Serializes named zones by name.
+mod tz_serde {
+    use chrono_tz::Tz;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(tz: &Tz, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(tz.name())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Tz, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(D::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -3439,4 +3586,48 @@ fn test_parseddatetime_compute_interval() {
             )
         }
     }
+
+    #[test]
+    fn test_timezone() {
+        let ny: Timezone = "america/new_york".parse().unwrap();
+        assert_eq!(ny.to_string(), "America/New_York");
+        assert_eq!("uTc".parse::<Timezone>(), Ok(Timezone::default()));
+        assert_eq!(
+            "+05:30".parse::<Timezone>(),
+            Ok(Timezone::FixedOffset(-(5 * 60 * 60 + 30 * 60)))
+        );
+        assert_eq!("+05:30".parse::<Timezone>().unwrap().to_string(), "+05:30");
+        assert_eq!("-2".parse::<Timezone>().unwrap().to_string(), "-02:00");
+        assert_eq!(
+            "etc/gmt-5".parse::<Timezone>().unwrap().to_string(),
+            "Etc/GMT-5"
+        );
+        assert!("Mars/Olympus_Mons".parse::<Timezone>().is_err());
+
+        let local = |s| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+        let utc = |s| DateTime::<Utc>::from_utc(local(s), Utc);
+
+        // Standard and daylight time.
+        assert_eq!(
+            ny.to_local(&utc("2020-01-01 12:00:00")),
+            local("2020-01-01 07:00:00")
+        );
+        assert_eq!(
+            ny.to_local(&utc("2020-07-01 12:00:00")),
+            local("2020-07-01 08:00:00")
+        );
+        assert_eq!(
+            ny.to_utc(&local("2020-07-01 08:00:00")),
+            utc("2020-07-01 12:00:00")
+        );
+        // Ambiguous and skipped local times.
+        assert_eq!(
+            ny.to_utc(&local("2020-11-01 01:30:00")),
+            utc("2020-11-01 06:30:00")
+        );
+        assert_eq!(
+            ny.to_utc(&local("2020-03-08 02:30:00")),
+            utc("2020-03-08 07:30:00")
+        );
+    }
 }
@@ -47,7 +47,7 @@
 //! [bigdecimal]: https://crates.io/crates/bigdecimal
 //! [fixed-point arithmetic]: https://en.wikipedia.org/wiki/Fixed-point_arithmetic
 
-use std::cmp::PartialEq;
+use std::cmp::{Ordering, PartialEq};
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::iter::Sum;
@@ -358,6 +358,24 @@ impl Decimal {
             scale: self.scale,
         }
     }
+
+    /// Returns the significand of this decimal when it is expressed with
+    /// `scale` digits after the decimal point, rounding half away from zero if
+    /// digits must be dropped.
+    ///
+    /// Returns `None` if the rescaled significand does not fit in an `i128`.
+    pub fn rescale(&self, scale: u8) -> Option<Significand> {
+        let significand = match self.scale.cmp(&scale) {
+            Ordering::Less => self
+                .significand
+                .checked_mul(10_i128.checked_pow(u32::from(scale - self.scale))?)?,
+            Ordering::Equal => self.significand,
+            Ordering::Greater => {
+                rounding_downscale(self.significand, usize::from(self.scale - scale))
+            }
+        };
+        Some(Significand::new(significand))
+    }
 }
 
 impl FromStr for Decimal {
@@ -558,6 +576,16 @@ mod tests {
         assert_eq!(d("55.5555").round(-3), d("0.0000"));
     }
 
+    #[test]
+    fn test_rescale() {
+        assert_eq!(d("1.25").rescale(4), Some(Significand::new(12500)));
+        assert_eq!(d("1.25").rescale(2), Some(Significand::new(125)));
+        assert_eq!(d("1.25").rescale(1), Some(Significand::new(13)));
+        assert_eq!(d("-1.25").rescale(1), Some(Significand::new(-13)));
+        assert_eq!(d("1.24").rescale(1), Some(Significand::new(12)));
+        assert_eq!(d("10").rescale(38), None);
+    }
+
     #[test]
     fn test_parse_decimal() {
         assert_eq!(d("123.45"), Significand::new(12345).with_scale(2));
@@ -16,6 +16,7 @@ use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
+use uuid::Uuid;
 
 use crate::adt::decimal::Significand;
 use crate::adt::interval::Interval;
@@ -147,6 +148,10 @@ pub struct DatumDict<'a> {
     data: &'a [u8],
 }
 
+/// The tag that precedes each serialized datum.
+///
+/// The tags are part of the serialized form of rows, which is persisted, so
+/// new tags must be added at the end, and existing tags must not be reordered.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tag {
     Null,
@@ -168,6 +173,8 @@ enum Tag {
     Dict,
     JsonNull,
     Dummy,
+    Int16,
+    Uuid,
 }
 
 // --------------------------------------------------------------------------------
@@ -234,6 +241,10 @@ unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
         Tag::Null => Datum::Null,
         Tag::False => Datum::False,
         Tag::True => Datum::True,
+        Tag::Int16 => {
+            let i = read_copy::<i16>(data, offset);
+            Datum::Int16(i)
+        }
         Tag::Int32 => {
             let i = read_copy::<i32>(data, offset);
             Datum::Int32(i)
@@ -283,6 +294,10 @@ unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
             let string = read_untagged_string(data, offset);
             Datum::String(string)
         }
+        Tag::Uuid => {
+            let bytes = read_copy::<uuid::Bytes>(data, offset);
+            Datum::Uuid(Uuid::from_bytes(bytes))
+        }
         Tag::List => {
             let bytes = read_untagged_bytes(data, offset);
             Datum::List(DatumList { data: bytes })
@@ -324,6 +339,10 @@ fn push_datum(data: &mut Vec<u8>, datum: Datum) {
         Datum::Null => data.push(Tag::Null as u8),
         Datum::False => data.push(Tag::False as u8),
         Datum::True => data.push(Tag::True as u8),
+        Datum::Int16(i) => {
+            data.push(Tag::Int16 as u8);
+            push_copy!(data, i, i16);
+        }
         Datum::Int32(i) => {
             data.push(Tag::Int32 as u8);
             push_copy!(data, i, i32);
@@ -373,6 +392,10 @@ fn push_datum(data: &mut Vec<u8>, datum: Datum) {
             data.push(Tag::String as u8);
             push_untagged_string(data, string);
         }
+        Datum::Uuid(u) => {
+            data.push(Tag::Uuid as u8);
+            push_copy!(data, *u.as_bytes(), uuid::Bytes);
+        }
         Datum::List(list) => {
             data.push(Tag::List as u8);
             push_untagged_bytes(data, &list.data);
@@ -394,6 +417,7 @@ pub fn datum_size(datum: &Datum) -> usize {
         Datum::Null => 1,
         Datum::False => 1,
         Datum::True => 1,
+        Datum::Int16(_) => 1 + size_of::<i16>(),
         Datum::Int32(_) => 1 + size_of::<i32>(),
         Datum::Int64(_) => 1 + size_of::<i64>(),
         Datum::Float32(_) => 1 + size_of::<u32>(),
@@ -406,6 +430,7 @@ pub fn datum_size(datum: &Datum) -> usize {
         Datum::Decimal(_) => 1 + size_of::<Significand>(),
         Datum::Bytes(bytes) => 1 + size_of::<usize>() + bytes.len(),
         Datum::String(string) => 1 + size_of::<usize>() + string.as_bytes().len(),
+        Datum::Uuid(_) => 1 + size_of::<uuid::Bytes>(),
         Datum::List(list) => 1 + size_of::<usize>() + list.data.len(),
         Datum::Dict(dict) => 1 + size_of::<usize>() + dict.data.len(),
         Datum::JsonNull => 1,
@@ -922,6 +947,32 @@ impl Default for RowArena {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_tag_discriminants() {
+        // Changing these values breaks rows that have already been persisted.
+        assert_eq!(Tag::Null as u8, 0);
+        assert_eq!(Tag::False as u8, 1);
+        assert_eq!(Tag::True as u8, 2);
+        assert_eq!(Tag::Int32 as u8, 3);
+        assert_eq!(Tag::Int64 as u8, 4);
+        assert_eq!(Tag::Float32 as u8, 5);
+        assert_eq!(Tag::Float64 as u8, 6);
+        assert_eq!(Tag::Decimal as u8, 7);
+        assert_eq!(Tag::Date as u8, 8);
+        assert_eq!(Tag::Time as u8, 9);
+        assert_eq!(Tag::Timestamp as u8, 10);
+        assert_eq!(Tag::TimestampTz as u8, 11);
+        assert_eq!(Tag::Interval as u8, 12);
+        assert_eq!(Tag::Bytes as u8, 13);
+        assert_eq!(Tag::String as u8, 14);
+        assert_eq!(Tag::List as u8, 15);
+        assert_eq!(Tag::Dict as u8, 16);
+        assert_eq!(Tag::JsonNull as u8, 17);
+        assert_eq!(Tag::Dummy as u8, 18);
+        assert_eq!(Tag::Int16 as u8, 19);
+        assert_eq!(Tag::Uuid as u8, 20);
+    }
+
     #[test]
     fn test_assumptions() {
         assert_eq!(size_of::<Tag>(), 1);
@@ -975,6 +1026,7 @@ mod tests {
             Datum::Null,
             Datum::False,
             Datum::True,
+            Datum::Int16(-42),
             Datum::Int32(-42),
             Datum::Int64(-2_147_483_648 - 42),
             Datum::Float32(OrderedFloat::from(-42.12)),
@@ -996,6 +1048,10 @@ mod tests {
             Datum::Bytes(&[0, 2, 1, 255]),
             Datum::String(""),
             Datum::String("العَرَبِيَّة"),
+            Datum::Uuid(Uuid::from_bytes([
+                0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+                0xe0, 0xc8,
+            ])),
         ]);
     }
 
@@ -1064,6 +1120,7 @@ mod tests {
         let values_of_interest = vec![
             Datum::Null,
             Datum::False,
+            Datum::Int16(0),
             Datum::Int32(0),
             Datum::Int64(0),
             Datum::Float32(OrderedFloat(0.0)),
@@ -1075,6 +1132,7 @@ mod tests {
             Datum::Interval(Interval::default()),
             Datum::Bytes(&[]),
             Datum::String(""),
+            Datum::Uuid(Uuid::nil()),
             Datum::JsonNull,
         ];
         for value in values_of_interest {
@@ -14,6 +14,7 @@ use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::adt::decimal::Significand;
 use crate::adt::interval::Interval;
@@ -28,6 +29,8 @@ pub enum Datum<'a> {
     False,
     /// The `true` boolean value.
     True,
+    /// A 16-bit signed integer.
+    Int16(i16),
     /// A 32-bit signed integer.
     Int32(i32),
     /// A 64-bit signed integer.
@@ -53,6 +56,8 @@ pub enum Datum<'a> {
     Bytes(&'a [u8]),
     /// A sequence of Unicode codepoints encoded as UTF-8.
     String(&'a str),
+    /// A universally unique identifier.
+    Uuid(Uuid),
     /// A sequence of `Datum`s.
     List(DatumList<'a>),
     /// A mapping from string keys to `Datum`s.
@@ -103,6 +108,18 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the 16-bit integer value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Int16`].
+    pub fn unwrap_int16(&self) -> i16 {
+        match self {
+            Datum::Int16(i) => *i,
+            _ => panic!("Datum::unwrap_int16 called on {:?}", self),
+        }
+    }
+
     /// Unwraps the 32-bit integer value within this datum.
     ///
     /// # Panics
@@ -261,6 +278,18 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the uuid value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Uuid`].
+    pub fn unwrap_uuid(&self) -> Uuid {
+        match self {
+            Datum::Uuid(u) => *u,
+            _ => panic!("Datum::unwrap_uuid called on {:?}", self),
+        }
+    }
+
     /// Unwraps the list value within this datum.
     ///
     /// # Panics
@@ -313,6 +342,8 @@ impl<'a> Datum<'a> {
                     (Datum::False, _) => false,
                     (Datum::True, ScalarType::Bool) => true,
                     (Datum::True, _) => false,
+                    (Datum::Int16(_), ScalarType::Int16) => true,
+                    (Datum::Int16(_), _) => false,
                     (Datum::Int32(_), ScalarType::Int32) => true,
                     (Datum::Int32(_), _) => false,
                     (Datum::Int64(_), ScalarType::Int64) => true,
@@ -335,11 +366,18 @@ impl<'a> Datum<'a> {
                     (Datum::Decimal(_), _) => false,
                     (Datum::Bytes(_), ScalarType::Bytes) => true,
                     (Datum::Bytes(_), _) => false,
-                    (Datum::String(_), ScalarType::String) => true,
+                    (Datum::String(_), ScalarType::String)
+                    | (Datum::String(_), ScalarType::Char { .. })
+                    | (Datum::String(_), ScalarType::VarChar { .. }) => true,
                     (Datum::String(_), _) => false,
+                    (Datum::Uuid(_), ScalarType::Uuid) => true,
+                    (Datum::Uuid(_), _) => false,
                     (Datum::List(list), ScalarType::List(t)) => list
                         .iter()
                         .all(|e| e.is_null() || is_instance_of_scalar(e, t)),
+                    (Datum::List(list), ScalarType::Array(t)) => list
+                        .iter()
+                        .all(|e| e.is_null() || is_instance_of_scalar(e, t)),
                     (Datum::List(list), ScalarType::Record { fields }) => list
                         .iter()
                         .zip_eq(fields)
@@ -369,6 +407,12 @@ impl From<bool> for Datum<'static> {
     }
 }
 
+impl From<i16> for Datum<'static> {
+    fn from(i: i16) -> Datum<'static> {
+        Datum::Int16(i)
+    }
+}
+
 impl From<i32> for Datum<'static> {
     fn from(i: i32) -> Datum<'static> {
         Datum::Int32(i)
@@ -448,6 +492,12 @@ impl<'a> From<&'a [u8]> for Datum<'a> {
     }
 }
 
+impl<'a> From<Uuid> for Datum<'a> {
+    fn from(u: Uuid) -> Datum<'a> {
+        Datum::Uuid(u)
+    }
+}
+
 impl<'a> From<NaiveDate> for Datum<'a> {
     fn from(d: NaiveDate) -> Datum<'a> {
         Datum::Date(d)
@@ -505,6 +555,7 @@ impl fmt::Display for Datum<'_> {
             Datum::Null => f.write_str("null"),
             Datum::True => f.write_str("true"),
             Datum::False => f.write_str("false"),
+            Datum::Int16(num) => write!(f, "{}", num),
             Datum::Int32(num) => write!(f, "{}", num),
             Datum::Int64(num) => write!(f, "{}", num),
             Datum::Float32(num) => write!(f, "{}", num),
@@ -533,6 +584,7 @@ impl fmt::Display for Datum<'_> {
                 }
                 f.write_str("\"")
             }
+            Datum::Uuid(u) => write!(f, "{}", u),
             Datum::List(list) => {
                 f.write_str("[")?;
                 write_delimited(f, ", ", list, |f, e| write!(f, "{}", e))?;
@@ -557,6 +609,8 @@ impl fmt::Display for Datum<'_> {
 pub enum ScalarType {
     /// The type of [`Datum::True`] and [`Datum::False`].
     Bool,
+    /// The type of [`Datum::Int16`].
+    Int16,
     /// The type of [`Datum::Int32`].
     Int32,
     /// The type of [`Datum::Int64`].
@@ -589,6 +643,23 @@ pub enum ScalarType {
     Bytes,
     /// The type of [`Datum::String`].
     String,
+    /// A blank-padded string of a fixed length, represented as a
+    /// [`Datum::String`].
+    ///
+    /// Trailing spaces are insignificant in values of this type, and so are
+    /// not stored. See the [`adt::char`](crate::adt::char) module for details.
+    Char {
+        /// The number of characters in the string, if constrained.
+        length: Option<usize>,
+    },
+    /// A string with an optional maximum length, represented as a
+    /// [`Datum::String`].
+    VarChar {
+        /// The maximum number of characters in the string, if constrained.
+        length: Option<usize>,
+    },
+    /// The type of [`Datum::Uuid`].
+    Uuid,
     /// The type of a datum that may represent any valid JSON value.
     ///
     /// Valid datum variants for this type are:
@@ -606,6 +677,12 @@ pub enum ScalarType {
     /// Elements within the list are of the specified type. List elements may
     /// always be [`Datum::Null`].
     List(Box<ScalarType>),
+    /// A one-dimensional SQL array, represented as a [`Datum::List`].
+    ///
+    /// Unlike lists, arrays follow PostgreSQL semantics: they are indexed
+    /// starting from one, and are transmitted to clients using the PostgreSQL
+    /// array types. Elements may always be [`Datum::Null`].
+    Array(Box<ScalarType>),
     /// An ordered and named sequence of datums.
     Record {
         /// The names and types of the fields of the record, in order from left
@@ -634,6 +711,8 @@ impl<'a> ScalarType {
         match self {
             ScalarType::Record { .. } => ScalarType::Record { fields: vec![] },
             ScalarType::Decimal(..) => ScalarType::Decimal(0, 0),
+            ScalarType::Char { .. } => ScalarType::Char { length: None },
+            ScalarType::VarChar { .. } => ScalarType::VarChar { length: None },
             _ => self.clone(),
         }
     }
@@ -647,8 +726,11 @@ impl PartialEq for ScalarType {
         use ScalarType::*;
         match (self, other) {
             (Decimal(_, s1), Decimal(_, s2)) => s1 == s2,
+            (Char { length: l1 }, Char { length: l2 }) => l1 == l2,
+            (VarChar { length: l1 }, VarChar { length: l2 }) => l1 == l2,
 
             (Bool, Bool)
+            | (Int16, Int16)
             | (Int32, Int32)
             | (Int64, Int64)
             | (Float32, Float32)
@@ -660,12 +742,15 @@ impl PartialEq for ScalarType {
             | (Interval, Interval)
             | (Bytes, Bytes)
             | (String, String)
+            | (Uuid, Uuid)
             | (Jsonb, Jsonb) => true,
 
             (List(a), List(b)) => a.eq(b),
+            (Array(a), Array(b)) => a.eq(b),
             (Record { fields: fields_a }, Record { fields: fields_b }) => fields_a.eq(fields_b),
 
             (Bool, _)
+            | (Int16, _)
             | (Int32, _)
             | (Int64, _)
             | (Float32, _)
@@ -678,8 +763,12 @@ impl PartialEq for ScalarType {
             | (Interval, _)
             | (Bytes, _)
             | (String, _)
+            | (Char { .. }, _)
+            | (VarChar { .. }, _)
+            | (Uuid, _)
             | (Jsonb, _)
             | (List(_), _)
+            | (Array(_), _)
             | (Record { .. }, _) => false,
         }
     }
@@ -716,6 +805,20 @@ impl Hash for ScalarType {
                 state.write_u8(15);
                 fields.hash(state);
             }
+            Uuid => state.write_u8(16),
+            Array(t) => {
+                state.write_u8(17);
+                t.hash(state);
+            }
+            Int16 => state.write_u8(18),
+            Char { length } => {
+                state.write_u8(19);
+                length.hash(state);
+            }
+            VarChar { length } => {
+                state.write_u8(20);
+                length.hash(state);
+            }
         }
     }
 }
@@ -730,6 +833,7 @@ impl fmt::Display for ScalarType {
         use ScalarType::*;
         match self {
             Bool => f.write_str("bool"),
+            Int16 => f.write_str("i16"),
             Int32 => f.write_str("i32"),
             Int64 => f.write_str("i64"),
             Float32 => f.write_str("f32"),
@@ -742,8 +846,14 @@ impl fmt::Display for ScalarType {
             Interval => f.write_str("interval"),
             Bytes => f.write_str("bytes"),
             String => f.write_str("string"),
+            Char { length: None } => f.write_str("char"),
+            Char { length: Some(n) } => write!(f, "char({})", n),
+            VarChar { length: None } => f.write_str("varchar"),
+            VarChar { length: Some(n) } => write!(f, "varchar({})", n),
+            Uuid => f.write_str("uuid"),
             Jsonb => f.write_str("jsonb"),
-            List(t) => write!(f, "{}[]", t),
+            List(t) => write!(f, "{} list", t),
+            Array(t) => write!(f, "{}[]", t),
             Record { fields } => {
                 f.write_str("record(")?;
                 write_delimited(f, ", ", fields, |f, (n, t)| write!(f, "{}: {}", n, t))?;
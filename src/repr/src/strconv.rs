@@ -31,8 +31,9 @@ use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelik
 use serde::{Deserialize, Serialize};
 
 use ore::fmt::FormatBuffer;
+use uuid::Uuid;
 
-use crate::adt::datetime::{self, DateTimeField, ParsedDateTime};
+use crate::adt::datetime::{self, DateTimeField, ParsedDateTime, Timezone};
 use crate::adt::decimal::Decimal;
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
@@ -79,6 +80,22 @@ where
     Nestable::Yes
 }
 
+/// Parses an [`i16`] from `s`.
+pub fn parse_int16(s: &str) -> Result<i16, ParseError> {
+    s.trim()
+        .parse()
+        .map_err(|e| ParseError::new("int2", s).with_details(e))
+}
+
+/// Writes an [`i16`] to `buf`.
+pub fn format_int16<F>(buf: &mut F, i: i16) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", i);
+    Nestable::Yes
+}
+
 /// Parses an [`i32`] from `s`.
 ///
 /// Valid values are whatever the [`FromStr`] implementation on `i32` accepts,
@@ -304,8 +321,30 @@ pub fn format_timestamptz<F>(buf: &mut F, ts: DateTime<Utc>) -> Nestable
 where
     F: FormatBuffer,
 {
-    write!(buf, "{}", ts.format("%Y-%m-%d %H:%M:%S+00"));
-    format_nanos(buf, ts.timestamp_subsec_nanos());
+    format_timestamptz_in(buf, ts, &Timezone::default())
+}
+
+/// Writes a [`DateTime<Utc>`] timestamp to `buf` as a local time in the time
+/// zone `tz`, followed by the zone's offset from UTC at that instant.
+pub fn format_timestamptz_in<F>(buf: &mut F, ts: DateTime<Utc>, tz: &Timezone) -> Nestable
+where
+    F: FormatBuffer,
+{
+    let local = tz.to_local(&ts);
+    write!(buf, "{}", local.format("%Y-%m-%d %H:%M:%S"));
+    format_nanos(buf, local.timestamp_subsec_nanos());
+    // As in PostgreSQL, the minutes and seconds of the offset are omitted
+    // when they are zero.
+    let offset = tz.offset_at(&ts.naive_utc()).local_minus_utc();
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.abs();
+    write!(buf, "{}{:02}", sign, offset / 3600);
+    if offset % 3600 != 0 {
+        write!(buf, ":{:02}", offset / 60 % 60);
+    }
+    if offset % 60 != 0 {
+        write!(buf, ":{:02}", offset % 60);
+    }
     // NOTE(benesch): this may be overly conservative. Perhaps timestamptzs
     // never have special characters.
     Nestable::MayNeedEscaping
@@ -423,6 +462,20 @@ where
     Nestable::Yes
 }
 
+pub fn parse_uuid(s: &str) -> Result<Uuid, ParseError> {
+    s.trim()
+        .parse()
+        .map_err(|e| ParseError::new("uuid", s).with_details(e))
+}
+
+pub fn format_uuid<F>(buf: &mut F, uuid: Uuid) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", uuid);
+    Nestable::Yes
+}
+
 pub fn parse_jsonb(s: &str) -> Result<Jsonb, ParseError> {
     s.trim()
         .parse()
@@ -9,7 +9,7 @@
 
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 
-use repr::adt::datetime::DateTimeField;
+use repr::adt::datetime::{DateTimeField, Timezone};
 use repr::adt::interval::Interval;
 use repr::strconv;
 
@@ -236,6 +236,22 @@ fn test_parse_timestamptz_errors() {
     }
 }
 
+#[test]
+fn test_format_timestamptz() {
+    let ts = Utc.ymd(2020, 7, 1).and_hms_micro(12, 34, 56, 789);
+    run_test_format_timestamptz(ts, "UTC", "2020-07-01 12:34:56.000789+00");
+    run_test_format_timestamptz(ts, "America/New_York", "2020-07-01 08:34:56.000789-04");
+    run_test_format_timestamptz(ts, "Asia/Kolkata", "2020-07-01 18:04:56.000789+05:30");
+    run_test_format_timestamptz(ts, "-02:15", "2020-07-01 10:19:56.000789-02:15");
+
+    fn run_test_format_timestamptz(ts: DateTime<Utc>, tz: &str, expected: &str) {
+        let tz: Timezone = tz.parse().unwrap();
+        let mut buf = String::new();
+        strconv::format_timestamptz_in(&mut buf, ts, &tz);
+        assert_eq!(buf, expected);
+    }
+}
+
 #[test]
 fn test_parse_interval_monthlike() {
     run_test_parse_interval_monthlike(
@@ -124,7 +124,11 @@ pub enum Format {
         n_cols: Option<usize>,
         delimiter: char,
     },
-    Json,
+    Json {
+        /// The top-level fields to extract into typed columns. If empty, each
+        /// message is decoded into a single `jsonb` column.
+        columns: Vec<ColumnDef>,
+    },
     Text,
 }
 
@@ -132,7 +136,13 @@ pub enum Format {
 pub enum Envelope {
     None,
     Debezium,
+    /// `UPSERT [FORMAT <format>]`, where the key of each record is decoded
+    /// from the record's key using the specified format, or the value's format
+    /// if unspecified.
     Upsert(Option<Format>),
+    /// `UPSERT KEY (<column>, ...)`, where the key of each record is formed
+    /// from the named columns of the record's value.
+    UpsertKeyColumns(Vec<Ident>),
 }
 
 impl Default for Envelope {
@@ -158,6 +168,11 @@ impl AstDisplay for Envelope {
                     f.write_node(format);
                 }
             }
+            Self::UpsertKeyColumns(columns) => {
+                f.write_str("UPSERT KEY (");
+                f.write_node(&display::comma_separated(columns));
+                f.write_str(")");
+            }
         }
     }
 }
@@ -190,11 +205,12 @@ impl AstDisplay for Format {
                 n_cols,
                 delimiter,
             } => {
-                f.write_str("CSV WITH ");
+                f.write_str("CSV");
                 if *header_row {
-                    f.write_str("HEADER");
-                } else {
-                    f.write_str(n_cols.unwrap());
+                    f.write_str(" WITH HEADER");
+                } else if let Some(n_cols) = n_cols {
+                    f.write_str(" WITH ");
+                    f.write_str(n_cols);
                     f.write_str(" COLUMNS");
                 }
                 if *delimiter != ',' {
@@ -203,7 +219,14 @@ impl AstDisplay for Format {
                     f.write_str("'");
                 }
             }
-            Self::Json => f.write_str("JSON"),
+            Self::Json { columns } => {
+                f.write_str("JSON");
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+            }
             Self::Text => f.write_str("TEXT"),
         }
     }
@@ -226,6 +249,19 @@ pub enum Connector {
     AvroOcf {
         path: String,
     },
+    /// A table replicated from PostgreSQL via logical replication
+    Postgres {
+        /// The libpq connection string of the upstream database
+        conn: String,
+        /// The name of the publication to subscribe to
+        publication: String,
+        /// The schema of the replicated table
+        namespace: String,
+        /// The name of the replicated table
+        table: String,
+        /// The columns of the replicated table, if known
+        columns: Vec<ColumnDef>,
+    },
 }
 
 impl AstDisplay for Connector {
@@ -254,6 +290,28 @@ impl AstDisplay for Connector {
                 f.write_node(&display::escape_single_quote_string(path));
                 f.write_str("'");
             }
+            Connector::Postgres {
+                conn,
+                publication,
+                namespace,
+                table,
+                columns,
+            } => {
+                f.write_str("POSTGRES CONNECTION '");
+                f.write_node(&display::escape_single_quote_string(conn));
+                f.write_str("' PUBLICATION '");
+                f.write_node(&display::escape_single_quote_string(publication));
+                f.write_str("' NAMESPACE '");
+                f.write_node(&display::escape_single_quote_string(namespace));
+                f.write_str("' TABLE '");
+                f.write_node(&display::escape_single_quote_string(table));
+                f.write_str("'");
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+            }
         }
     }
 }
@@ -86,6 +86,11 @@ pub enum Expr {
         expr: Box<Expr>,
         collation: ObjectName,
     },
+    /// `expr AT TIME ZONE time_zone`
+    AtTimeZone {
+        timestamp: Box<Expr>,
+        time_zone: Box<Expr>,
+    },
     /// COALESCE(<expr>, ...)
     ///
     /// While COALESCE has the same syntax as a function call, its semantics are
@@ -130,6 +135,13 @@ pub enum Expr {
     },
     /// `LIST[<expr>*]`
     List(Vec<Expr>),
+    /// `ARRAY[<expr>*]`
+    Array(Vec<Expr>),
+    /// `<expr>[<expr>]`
+    SubscriptIndex {
+        expr: Box<Expr>,
+        subscript: Box<Expr>,
+    },
 }
 
 impl AstDisplay for Expr {
@@ -249,6 +261,14 @@ impl AstDisplay for Expr {
                 f.write_str(" COLLATE ");
                 f.write_node(&collation);
             }
+            Expr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => {
+                f.write_node(&timestamp);
+                f.write_str(" AT TIME ZONE ");
+                f.write_node(&time_zone);
+            }
             Expr::Coalesce { exprs } => {
                 f.write_str("COALESCE(");
                 f.write_node(&display::comma_separated(&exprs));
@@ -331,6 +351,23 @@ impl AstDisplay for Expr {
                 }
                 f.write_str("]");
             }
+            Expr::Array(exprs) => {
+                let mut exprs = exprs.iter().peekable();
+                f.write_str("ARRAY[");
+                while let Some(expr) = exprs.next() {
+                    f.write_node(expr);
+                    if exprs.peek().is_some() {
+                        f.write_str(", ");
+                    }
+                }
+                f.write_str("]");
+            }
+            Expr::SubscriptIndex { expr, subscript } => {
+                f.write_node(&expr);
+                f.write_str("[");
+                f.write_node(&subscript);
+                f.write_str("]");
+            }
         }
     }
 }
@@ -27,6 +27,8 @@ use crate::ast::{Expr, FunctionArgs, Ident, ObjectName};
 /// including `WITH`, `UNION` / other set operations, and `ORDER BY`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Query {
+    /// Whether the `WITH` clause was `WITH RECURSIVE`
+    pub recursive: bool,
     /// WITH (common table expressions, or CTEs)
     pub ctes: Vec<Cte>,
     /// SELECT or UNION / EXCEPT / INTECEPT
@@ -45,6 +47,9 @@ impl AstDisplay for Query {
     fn fmt(&self, f: &mut AstFormatter) {
         if !self.ctes.is_empty() {
             f.write_str("WITH ");
+            if self.recursive {
+                f.write_str("RECURSIVE ");
+            }
             f.write_node(&display::comma_separated(&self.ctes));
             f.write_str(" ");
         }
@@ -73,6 +78,7 @@ impl_display!(Query);
 impl Query {
     pub fn select(select: Select) -> Query {
         Query {
+            recursive: false,
             ctes: vec![],
             body: SetExpr::Select(Box::new(select)),
             order_by: vec![],
@@ -86,6 +92,7 @@ impl Query {
         mem::replace(
             self,
             Query {
+                recursive: false,
                 ctes: vec![],
                 order_by: vec![],
                 body: SetExpr::Values(Values(vec![])),
@@ -42,13 +42,14 @@ pub enum Statement {
         /// A SQL query that specifies what to insert
         source: Box<Query>,
     },
+    /// `COPY ... FROM STDIN`
     Copy {
         /// TABLE
         table_name: ObjectName,
         /// COLUMNS
         columns: Vec<Ident>,
-        /// VALUES a vector of values to be copied
-        values: Vec<Option<String>>,
+        /// FORMAT
+        format: CopyFormat,
     },
     /// `UPDATE`
     Update {
@@ -94,6 +95,7 @@ pub enum Statement {
         connector: Connector,
         with_options: Vec<SqlOption>,
         format: Option<Format>,
+        envelope: Option<Envelope>,
         with_snapshot: bool,
         as_of: Option<Expr>,
         if_not_exists: bool,
@@ -136,6 +138,13 @@ pub enum Statement {
         name: ObjectName,
         operation: AlterTableOperation,
     },
+    /// `ALTER <object> [IF EXISTS] <name> RENAME TO <to_item_name>`
+    AlterObjectRename {
+        object_type: ObjectType,
+        if_exists: bool,
+        name: ObjectName,
+        to_item_name: Ident,
+    },
     DropDatabase {
         name: Ident,
         if_exists: bool,
@@ -152,6 +161,28 @@ pub enum Statement {
         /// `RESTRICT` or no drop behavior at all was specified.
         cascade: bool,
     },
+    /// `CREATE ROLE`
+    CreateRole {
+        name: Ident,
+        options: Vec<RoleOption>,
+    },
+    /// `DROP ROLE`
+    DropRole {
+        if_exists: bool,
+        names: Vec<Ident>,
+    },
+    /// `GRANT <privileges> ON <objects> TO <roles>`
+    Grant {
+        privileges: Privileges,
+        objects: Vec<ObjectName>,
+        grantees: Vec<Ident>,
+    },
+    /// `REVOKE <privileges> ON <objects> FROM <roles>`
+    Revoke {
+        privileges: Privileges,
+        objects: Vec<ObjectName>,
+        grantees: Vec<Ident>,
+    },
     /// `SET <variable>`
     ///
     /// Note: this is not a standard SQL statement, but it is supported by at
@@ -239,8 +270,8 @@ pub enum Statement {
     },
     /// `TAIL`
     Tail {
-        name: ObjectName,
-        with_snapshot: bool,
+        relation: TailRelation,
+        options: Vec<SqlOption>,
         as_of: Option<Expr>,
     },
     /// `EXPLAIN ...`
@@ -279,30 +310,21 @@ impl AstDisplay for Statement {
             Statement::Copy {
                 table_name,
                 columns,
-                values,
+                format,
             } => {
                 f.write_str("COPY ");
                 f.write_node(&table_name);
                 if !columns.is_empty() {
-                    f.write_str("(");
+                    f.write_str(" (");
                     f.write_node(&display::comma_separated(columns));
                     f.write_str(")");
                 }
-                f.write_str(" FROM stdin; ");
-                if !values.is_empty() {
-                    f.write_str("\n");
-                    let mut delim = "";
-                    for v in values {
-                        f.write_str(delim);
-                        delim = "\t";
-                        if let Some(v) = v {
-                            f.write_str(v);
-                        } else {
-                            f.write_str("\\N");
-                        }
-                    }
-                }
-                f.write_str("\n\\.");
+                f.write_str(" FROM STDIN");
+                if *format != CopyFormat::Text {
+                    f.write_str(" WITH (FORMAT ");
+                    f.write_node(format);
+                    f.write_str(")");
+                }
             }
             Statement::Update {
                 table_name,
@@ -398,6 +420,7 @@ impl AstDisplay for Statement {
                 connector,
                 with_options,
                 format,
+                envelope,
                 with_snapshot,
                 as_of,
                 if_not_exists,
@@ -420,6 +443,10 @@ impl AstDisplay for Statement {
                     f.write_str(" FORMAT ");
                     f.write_node(format);
                 }
+                if let Some(envelope) = envelope {
+                    f.write_str(" ENVELOPE ");
+                    f.write_node(envelope);
+                }
                 if *with_snapshot {
                     f.write_str(" WITH SNAPSHOT");
                 } else {
@@ -533,6 +560,22 @@ impl AstDisplay for Statement {
                 f.write_str(" ");
                 f.write_node(operation);
             }
+            Statement::AlterObjectRename {
+                object_type,
+                if_exists,
+                name,
+                to_item_name,
+            } => {
+                f.write_str("ALTER ");
+                f.write_node(object_type);
+                f.write_str(" ");
+                if *if_exists {
+                    f.write_str("IF EXISTS ");
+                }
+                f.write_node(name);
+                f.write_str(" RENAME TO ");
+                f.write_node(to_item_name);
+            }
             Statement::DropDatabase { name, if_exists } => {
                 f.write_str("DROP DATABASE ");
                 if *if_exists {
@@ -557,6 +600,45 @@ impl AstDisplay for Statement {
                     f.write_str(" CASCADE");
                 }
             }
+            Statement::CreateRole { name, options } => {
+                f.write_str("CREATE ROLE ");
+                f.write_node(name);
+                if !options.is_empty() {
+                    f.write_str(" WITH ");
+                    f.write_node(&display::separated(options, " "));
+                }
+            }
+            Statement::DropRole { if_exists, names } => {
+                f.write_str("DROP ROLE ");
+                if *if_exists {
+                    f.write_str("IF EXISTS ");
+                }
+                f.write_node(&display::comma_separated(names));
+            }
+            Statement::Grant {
+                privileges,
+                objects,
+                grantees,
+            } => {
+                f.write_str("GRANT ");
+                f.write_node(privileges);
+                f.write_str(" ON ");
+                f.write_node(&display::comma_separated(objects));
+                f.write_str(" TO ");
+                f.write_node(&display::comma_separated(grantees));
+            }
+            Statement::Revoke {
+                privileges,
+                objects,
+                grantees,
+            } => {
+                f.write_str("REVOKE ");
+                f.write_node(privileges);
+                f.write_str(" ON ");
+                f.write_node(&display::comma_separated(objects));
+                f.write_str(" FROM ");
+                f.write_node(&display::comma_separated(grantees));
+            }
             Statement::SetVariable {
                 local,
                 variable,
@@ -696,17 +778,17 @@ impl AstDisplay for Statement {
                 }
             }
             Statement::Tail {
-                name,
-                with_snapshot,
+                relation,
+                options,
                 as_of,
             } => {
                 f.write_str("TAIL ");
-                f.write_node(&name);
+                f.write_node(relation);
 
-                if *with_snapshot {
-                    f.write_str(" WITH SNAPSHOT");
-                } else {
-                    f.write_str(" WITHOUT SNAPSHOT");
+                if !options.is_empty() {
+                    f.write_str(" WITH (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
                 }
                 if let Some(as_of) = as_of {
                     f.write_str(" AS OF ");
@@ -755,6 +837,65 @@ impl AstDisplay for ObjectType {
 }
 impl_display!(ObjectType);
 
+/// An option in a [Statement::CreateRole].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoleOption {
+    /// `SUPERUSER`
+    SuperUser,
+    /// `NOSUPERUSER`
+    NoSuperUser,
+}
+
+impl AstDisplay for RoleOption {
+    fn fmt(&self, f: &mut AstFormatter) {
+        f.write_str(match self {
+            RoleOption::SuperUser => "SUPERUSER",
+            RoleOption::NoSuperUser => "NOSUPERUSER",
+        })
+    }
+}
+impl_display!(RoleOption);
+
+/// The privileges named in a [Statement::Grant] or [Statement::Revoke].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Privileges {
+    /// `ALL [PRIVILEGES]`
+    All,
+    /// An explicit list of privileges.
+    List(Vec<Privilege>),
+}
+
+impl AstDisplay for Privileges {
+    fn fmt(&self, f: &mut AstFormatter) {
+        match self {
+            Privileges::All => f.write_str("ALL PRIVILEGES"),
+            Privileges::List(privileges) => f.write_node(&display::comma_separated(privileges)),
+        }
+    }
+}
+impl_display!(Privileges);
+
+/// A privilege that can be granted on a table-like object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Privilege {
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+impl AstDisplay for Privilege {
+    fn fmt(&self, f: &mut AstFormatter) {
+        f.write_str(match self {
+            Privilege::Select => "SELECT",
+            Privilege::Insert => "INSERT",
+            Privilege::Update => "UPDATE",
+            Privilege::Delete => "DELETE",
+        })
+    }
+}
+impl_display!(Privilege);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ShowStatementFilter {
     Like(String),
@@ -885,6 +1026,25 @@ impl AstDisplay for Assignment {
 }
 impl_display!(Assignment);
 
+/// The format of the data in a [Statement::Copy].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CopyFormat {
+    /// PostgreSQL's tab-separated text format.
+    Text,
+    /// Comma-separated values.
+    Csv,
+}
+
+impl AstDisplay for CopyFormat {
+    fn fmt(&self, f: &mut AstFormatter) {
+        match self {
+            CopyFormat::Text => f.write_str("text"),
+            CopyFormat::Csv => f.write_str("csv"),
+        }
+    }
+}
+impl_display!(CopyFormat);
+
 /// Specifies what [Statement::Explain] is actually explaining
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ExplainStage {
@@ -913,6 +1073,26 @@ pub enum Explainee {
     Query(Query),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TailRelation {
+    Name(ObjectName),
+    Query(Query),
+}
+
+impl AstDisplay for TailRelation {
+    fn fmt(&self, f: &mut AstFormatter) {
+        match self {
+            TailRelation::Name(name) => f.write_node(name),
+            TailRelation::Query(query) => {
+                f.write_str("(");
+                f.write_node(query);
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display!(TailRelation);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExplainOptions {
     pub typed: bool,
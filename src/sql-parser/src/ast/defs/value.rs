@@ -226,6 +226,8 @@ pub enum DataType {
     Bytea,
     /// List
     List(Box<DataType>),
+    /// Array
+    Array(Box<DataType>),
     /// Binary JSON
     Jsonb,
 }
@@ -289,6 +291,10 @@ impl AstDisplay for DataType {
                 f.write_node(&ty);
                 f.write_str(" list");
             }
+            DataType::Array(ty) => {
+                f.write_node(&ty);
+                f.write_str("[]");
+            }
             DataType::Jsonb => f.write_str("jsonb"),
         }
     }
@@ -125,6 +125,7 @@ define_keywords!(
     CONDITION,
     CONFLUENT,
     CONNECT,
+    CONNECTION,
     CONSISTENCY,
     CONSTRAINT,
     CONTAINS,
@@ -299,6 +300,7 @@ define_keywords!(
     MONTH,
     MONTHS,
     MULTISET,
+    NAMESPACE,
     NATURAL,
     NCHAR,
     NCLOB,
@@ -307,6 +309,7 @@ define_keywords!(
     NO,
     NONE,
     NORMALIZE,
+    NOSUPERUSER,
     NOT,
     NTH_VALUE,
     NTILE,
@@ -341,6 +344,7 @@ define_keywords!(
     PERIOD,
     PLAN,
     PORTION,
+    POSTGRES,
     POSITION,
     POSITION_REGEX,
     POWER,
@@ -349,8 +353,10 @@ define_keywords!(
     PRECISION,
     PREPARE,
     PRIMARY,
+    PRIVILEGES,
     PROCEDURE,
     PROTOBUF,
+    PUBLICATION,
     QUARTER,
     RANGE,
     RANK,
@@ -376,6 +382,7 @@ define_keywords!(
     REGR_SXY,
     REGR_SYY,
     RELEASE,
+    RENAME,
     REPEATABLE,
     REPLACE,
     RESTRICT,
@@ -384,6 +391,7 @@ define_keywords!(
     RETURNS,
     REVOKE,
     RIGHT,
+    ROLE,
     ROLLBACK,
     ROLLUP,
     ROW,
@@ -432,6 +440,7 @@ define_keywords!(
     SUBSTRING_REGEX,
     SUCCEEDS,
     SUM,
+    SUPERUSER,
     SYMMETRIC,
     SYSTEM,
     SYSTEM_TIME,
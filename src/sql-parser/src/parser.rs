@@ -141,8 +141,10 @@ enum Precedence {
     JsonOp,
     Plus,
     Times,
+    AtTimeZone,
     UnaryOp,
     DoubleColon,
+    Subscript,
 }
 
 impl Parser {
@@ -217,6 +219,8 @@ impl Parser {
                     "ROLLBACK" => Ok(self.parse_rollback()?),
                     "TAIL" => Ok(self.parse_tail()?),
                     "EXPLAIN" => Ok(self.parse_explain()?),
+                    "GRANT" => Ok(self.parse_grant()?),
+                    "REVOKE" => Ok(self.parse_revoke()?),
                     _ => parser_err!(
                         self,
                         self.peek_prev_range(),
@@ -306,6 +310,7 @@ impl Parser {
                     self.prev_token();
                     Ok(Expr::Value(self.parse_value()?))
                 }
+                "ARRAY" if self.peek_token() == Some(Token::LBracket) => self.parse_array(),
                 "LIST" => self.parse_list(),
                 "CASE" => self.parse_case_expr(),
                 "CAST" => self.parse_cast_expr(),
@@ -868,11 +873,20 @@ impl Parser {
                         )
                     }
                 }
+                "AT" => {
+                    self.expect_keywords(&["TIME", "ZONE"])?;
+                    Ok(Expr::AtTimeZone {
+                        timestamp: Box::new(expr),
+                        time_zone: Box::new(self.parse_subexpr(Precedence::AtTimeZone)?),
+                    })
+                }
                 // Can only happen if `get_next_precedence` got out of sync with this function
                 _ => panic!("No infix parser for token {:?}", tok),
             }
         } else if Token::DoubleColon == tok {
             self.parse_pg_cast(expr)
+        } else if Token::LBracket == tok {
+            self.parse_subscript(expr)
         } else {
             // Can only happen if `get_next_precedence` got out of sync with this function
             panic!("No infix parser for token {:?}", tok)
@@ -949,6 +963,18 @@ impl Parser {
                 Token::Word(k) if k.keyword == "IN" => Precedence::Like,
                 Token::Word(k) if k.keyword == "BETWEEN" => Precedence::Like,
                 Token::Word(k) if k.keyword == "LIKE" => Precedence::Like,
+                Token::Word(k) if k.keyword == "AT" => {
+                    // AT is only an infix operator when it begins the
+                    // `AT TIME ZONE` operator.
+                    match (&self.peek_nth_token(1), &self.peek_nth_token(2)) {
+                        (Some(Token::Word(k1)), Some(Token::Word(k2)))
+                            if k1.keyword == "TIME" && k2.keyword == "ZONE" =>
+                        {
+                            Precedence::AtTimeZone
+                        }
+                        _ => Precedence::Zero,
+                    }
+                }
                 Token::Eq | Token::Lt | Token::LtEq | Token::Neq | Token::Gt | Token::GtEq => {
                     Precedence::Cmp
                 }
@@ -968,6 +994,7 @@ impl Parser {
                 Token::Plus | Token::Minus => Precedence::Plus,
                 Token::Mult | Token::Div | Token::Mod => Precedence::Times,
                 Token::DoubleColon => Precedence::DoubleColon,
+                Token::LBracket => Precedence::Subscript,
                 _ => Precedence::Zero,
             }
         } else {
@@ -1011,12 +1038,6 @@ impl Parser {
         }
     }
 
-    /// Return the first unprocessed token, possibly whitespace.
-    fn next_token_no_skip(&mut self) -> Option<&Token> {
-        self.index += 1;
-        self.tokens.get(self.index - 1).map(|(token, _range)| token)
-    }
-
     /// Push back the last one non-whitespace token. Must be called after
     /// `next_token()`, otherwise might panic. OK to call after
     /// `next_token()` indicates an EOF.
@@ -1270,10 +1291,12 @@ impl Parser {
         } else if self.parse_keyword("INDEX") {
             self.prev_token();
             self.parse_create_index()
+        } else if self.parse_keyword("ROLE") {
+            self.parse_create_role()
         } else {
             self.expected(
                 self.peek_range(),
-                "DATABASE, SCHEMA, [MATERIALIZED] VIEW, SOURCE, SINK, or INDEX after CREATE",
+                "DATABASE, SCHEMA, [MATERIALIZED] VIEW, SOURCE, SINK, INDEX, or ROLE after CREATE",
                 self.peek_token(),
             )
         }
@@ -1288,6 +1311,21 @@ impl Parser {
         })
     }
 
+    fn parse_create_role(&mut self) -> Result<Statement, ParserError> {
+        let name = self.parse_identifier()?;
+        let _ = self.parse_keyword("WITH");
+        let mut options = vec![];
+        loop {
+            match self.parse_one_of_keywords(&["SUPERUSER", "NOSUPERUSER"]) {
+                Some("SUPERUSER") => options.push(RoleOption::SuperUser),
+                Some("NOSUPERUSER") => options.push(RoleOption::NoSuperUser),
+                Some(_) => unreachable!(),
+                None => break,
+            }
+        }
+        Ok(Statement::CreateRole { name, options })
+    }
+
     fn parse_create_schema(&mut self) -> Result<Statement, ParserError> {
         let if_not_exists = self.parse_if_not_exists()?;
         let name = self.parse_object_name()?;
@@ -1314,15 +1352,22 @@ impl Parser {
             let regex = self.parse_literal_string()?;
             Format::Regex(regex)
         } else if self.parse_keyword("CSV") {
-            self.expect_keyword("WITH")?;
-            let (header_row, n_cols) =
+            // The WITH clause is optional, but a bare WITH might instead
+            // introduce a later clause, like CREATE SINK's WITH SNAPSHOT.
+            let (header_row, n_cols) = if self.parse_keyword("WITH") {
                 if self.parse_keyword("HEADER") || self.parse_keyword("HEADERS") {
                     (true, None)
-                } else {
+                } else if let Some(Token::Number(_)) = self.peek_token() {
                     let n_cols = self.parse_literal_uint()? as usize;
                     self.expect_keyword("COLUMNS")?;
                     (false, Some(n_cols))
-                };
+                } else {
+                    self.prev_token();
+                    (false, None)
+                }
+            } else {
+                (false, None)
+            };
             let delimiter = if self.parse_keywords(vec!["DELIMITED", "BY"]) {
                 let s = self.parse_literal_string()?;
                 match s.len() {
@@ -1340,7 +1385,14 @@ impl Parser {
                 delimiter,
             }
         } else if self.parse_keyword("JSON") {
-            Format::Json
+            let columns = if self.consume_token(&Token::LParen) {
+                let columns = self.parse_comma_separated(Parser::parse_json_column)?;
+                self.expect_token(&Token::RParen)?;
+                columns
+            } else {
+                vec![]
+            };
+            Format::Json { columns }
         } else if self.parse_keyword("TEXT") {
             Format::Text
         } else if self.parse_keyword("BYTES") {
@@ -1355,6 +1407,19 @@ impl Parser {
         Ok(format)
     }
 
+    /// Parses a `<name> <data type>` pair in the column list of a
+    /// `FORMAT JSON` clause.
+    fn parse_json_column(&mut self) -> Result<ColumnDef, ParserError> {
+        let name = self.parse_identifier()?;
+        let data_type = self.parse_data_type()?;
+        Ok(ColumnDef {
+            name,
+            data_type,
+            collation: None,
+            options: vec![],
+        })
+    }
+
     fn parse_avro_schema(&mut self) -> Result<AvroSchema, ParserError> {
         let avro_schema = if self.parse_keywords(vec!["CONFLUENT", "SCHEMA", "REGISTRY"]) {
             let url = self.parse_literal_string()?;
@@ -1418,12 +1483,17 @@ impl Parser {
         } else if self.parse_keyword("DEBEZIUM") {
             Envelope::Debezium
         } else if self.parse_keyword("UPSERT") {
-            let format = if self.parse_keyword("FORMAT") {
-                Some(self.parse_format()?)
+            if self.parse_keyword("KEY") {
+                let columns = self.parse_parenthesized_column_list(Mandatory)?;
+                Envelope::UpsertKeyColumns(columns)
             } else {
-                None
-            };
-            Envelope::Upsert(format)
+                let format = if self.parse_keyword("FORMAT") {
+                    Some(self.parse_format()?)
+                } else {
+                    None
+                };
+                Envelope::Upsert(format)
+            }
         } else {
             return self.expected(
                 self.peek_range(),
@@ -1486,6 +1556,11 @@ impl Parser {
         } else {
             None
         };
+        let envelope = if self.parse_keyword("ENVELOPE") {
+            Some(self.parse_envelope()?)
+        } else {
+            None
+        };
         let with_snapshot = if self.parse_keyword("WITH") {
             self.expect_keyword("SNAPSHOT")?;
             true
@@ -1504,6 +1579,7 @@ impl Parser {
             connector,
             with_options,
             format,
+            envelope,
             with_snapshot,
             as_of,
             if_not_exists,
@@ -1511,7 +1587,7 @@ impl Parser {
     }
 
     fn parse_connector(&mut self) -> Result<Connector, ParserError> {
-        match self.expect_one_of_keywords(&["FILE", "KAFKA", "KINESIS", "AVRO"])? {
+        match self.expect_one_of_keywords(&["FILE", "KAFKA", "KINESIS", "AVRO", "POSTGRES"])? {
             "FILE" => {
                 let path = self.parse_literal_string()?;
                 Ok(Connector::File { path })
@@ -1533,6 +1609,34 @@ impl Parser {
                 let path = self.parse_literal_string()?;
                 Ok(Connector::AvroOcf { path })
             }
+            "POSTGRES" => {
+                self.expect_keyword("CONNECTION")?;
+                let conn = self.parse_literal_string()?;
+                self.expect_keyword("PUBLICATION")?;
+                let publication = self.parse_literal_string()?;
+                let namespace = if self.parse_keyword("NAMESPACE") {
+                    self.parse_literal_string()?
+                } else {
+                    "public".into()
+                };
+                self.expect_keyword("TABLE")?;
+                let table = self.parse_literal_string()?;
+                let (columns, constraints) = self.parse_columns()?;
+                if !constraints.is_empty() {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_range(),
+                        "Cannot specify constraints in Postgres table definition"
+                    );
+                }
+                Ok(Connector::Postgres {
+                    conn,
+                    publication,
+                    namespace,
+                    table,
+                    columns,
+                })
+            }
             _ => unreachable!(),
         }
     }
@@ -1633,7 +1737,7 @@ impl Parser {
 
     fn parse_drop(&mut self) -> Result<Statement, ParserError> {
         let object_type = match self.parse_one_of_keywords(&[
-            "DATABASE", "SCHEMA", "TABLE", "VIEW", "SOURCE", "SINK", "INDEX",
+            "DATABASE", "SCHEMA", "TABLE", "VIEW", "SOURCE", "SINK", "INDEX", "ROLE",
         ]) {
             Some("DATABASE") => {
                 return Ok(Statement::DropDatabase {
@@ -1641,6 +1745,12 @@ impl Parser {
                     name: self.parse_identifier()?,
                 });
             }
+            Some("ROLE") => {
+                return Ok(Statement::DropRole {
+                    if_exists: self.parse_if_exists()?,
+                    names: self.parse_comma_separated(Parser::parse_identifier)?,
+                });
+            }
             Some("SCHEMA") => ObjectType::Schema,
             Some("TABLE") => ObjectType::Table,
             Some("VIEW") => ObjectType::View,
@@ -1650,7 +1760,7 @@ impl Parser {
             _ => {
                 return self.expected(
                     self.peek_range(),
-                    "DATABASE, SCHEMA, TABLE, VIEW, SOURCE, SINK, or INDEX after DROP",
+                    "DATABASE, SCHEMA, TABLE, VIEW, SOURCE, SINK, INDEX, or ROLE after DROP",
                     self.peek_token(),
                 )
             }
@@ -1677,6 +1787,51 @@ impl Parser {
         })
     }
 
+    fn parse_grant(&mut self) -> Result<Statement, ParserError> {
+        let privileges = self.parse_privileges()?;
+        self.expect_keyword("ON")?;
+        let _ = self.parse_keyword("TABLE");
+        let objects = self.parse_comma_separated(Parser::parse_object_name)?;
+        self.expect_keyword("TO")?;
+        let grantees = self.parse_comma_separated(Parser::parse_identifier)?;
+        Ok(Statement::Grant {
+            privileges,
+            objects,
+            grantees,
+        })
+    }
+
+    fn parse_revoke(&mut self) -> Result<Statement, ParserError> {
+        let privileges = self.parse_privileges()?;
+        self.expect_keyword("ON")?;
+        let _ = self.parse_keyword("TABLE");
+        let objects = self.parse_comma_separated(Parser::parse_object_name)?;
+        self.expect_keyword("FROM")?;
+        let grantees = self.parse_comma_separated(Parser::parse_identifier)?;
+        Ok(Statement::Revoke {
+            privileges,
+            objects,
+            grantees,
+        })
+    }
+
+    fn parse_privileges(&mut self) -> Result<Privileges, ParserError> {
+        if self.parse_keyword("ALL") {
+            let _ = self.parse_keyword("PRIVILEGES");
+            return Ok(Privileges::All);
+        }
+        let privileges = self.parse_comma_separated(|parser| {
+            match parser.expect_one_of_keywords(&["SELECT", "INSERT", "UPDATE", "DELETE"])? {
+                "SELECT" => Ok(Privilege::Select),
+                "INSERT" => Ok(Privilege::Insert),
+                "UPDATE" => Ok(Privilege::Update),
+                "DELETE" => Ok(Privilege::Delete),
+                _ => unreachable!(),
+            }
+        })?;
+        Ok(Privileges::List(privileges))
+    }
+
     fn parse_create_table(&mut self) -> Result<Statement, ParserError> {
         let if_not_exists = self.parse_if_not_exists()?;
         let table_name = self.parse_object_name()?;
@@ -1856,9 +2011,42 @@ impl Parser {
     }
 
     fn parse_alter(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword("TABLE")?;
-        let _ = self.parse_keyword("ONLY");
-        let table_name = self.parse_object_name()?;
+        let object_type =
+            match self.parse_one_of_keywords(&["TABLE", "VIEW", "SOURCE", "SINK", "INDEX"]) {
+                Some("TABLE") => ObjectType::Table,
+                Some("VIEW") => ObjectType::View,
+                Some("SOURCE") => ObjectType::Source,
+                Some("SINK") => ObjectType::Sink,
+                Some("INDEX") => ObjectType::Index,
+                _ => {
+                    return self.expected(
+                        self.peek_range(),
+                        "TABLE, VIEW, SOURCE, SINK, or INDEX after ALTER",
+                        self.peek_token(),
+                    )
+                }
+            };
+
+        let if_exists = self.parse_if_exists()?;
+        if object_type == ObjectType::Table {
+            let _ = self.parse_keyword("ONLY");
+        }
+        let name = self.parse_object_name()?;
+
+        if self.parse_keyword("RENAME") {
+            self.expect_keyword("TO")?;
+            let to_item_name = self.parse_identifier()?;
+            return Ok(Statement::AlterObjectRename {
+                object_type,
+                if_exists,
+                name,
+                to_item_name,
+            });
+        } else if object_type != ObjectType::Table || if_exists {
+            return self.expected(self.peek_range(), "RENAME", self.peek_token());
+        }
+
+        let table_name = name;
         let operation = if self.parse_keyword("ADD") {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 AlterTableOperation::AddConstraint(constraint)
@@ -1872,7 +2060,7 @@ impl Parser {
         } else {
             return self.expected(
                 self.peek_range(),
-                "ADD after ALTER TABLE",
+                "ADD or RENAME after ALTER TABLE",
                 self.peek_token(),
             );
         };
@@ -1887,57 +2075,31 @@ impl Parser {
         let table_name = self.parse_object_name()?;
         let columns = self.parse_parenthesized_column_list(Optional)?;
         self.expect_keywords(&["FROM", "STDIN"])?;
-        self.expect_token(&Token::SemiColon)?;
-        let values = self.parse_tsv()?;
+        let has_options = if self.parse_keyword("WITH") {
+            self.expect_token(&Token::LParen)?;
+            true
+        } else {
+            self.consume_token(&Token::LParen)
+        };
+        let format = if has_options {
+            self.expect_keyword("FORMAT")?;
+            let format = match self.expect_one_of_keywords(&["TEXT", "CSV"])? {
+                "TEXT" => CopyFormat::Text,
+                "CSV" => CopyFormat::Csv,
+                _ => unreachable!(),
+            };
+            self.expect_token(&Token::RParen)?;
+            format
+        } else {
+            CopyFormat::Text
+        };
         Ok(Statement::Copy {
             table_name,
             columns,
-            values,
+            format,
         })
     }
 
-    /// Parse a tab separated values in
-    /// COPY payload
-    fn parse_tsv(&mut self) -> Result<Vec<Option<String>>, ParserError> {
-        let values = self.parse_tab_value()?;
-        Ok(values)
-    }
-
-    fn parse_tab_value(&mut self) -> Result<Vec<Option<String>>, ParserError> {
-        let mut values = vec![];
-        let mut content = String::from("");
-        while let Some(t) = self.next_token_no_skip() {
-            match t {
-                Token::Whitespace(Whitespace::Tab) => {
-                    values.push(Some(content.to_string()));
-                    content.clear();
-                }
-                Token::Whitespace(Whitespace::Newline) => {
-                    values.push(Some(content.to_string()));
-                    content.clear();
-                }
-                Token::Backslash => {
-                    if self.consume_token(&Token::Period) {
-                        return Ok(values);
-                    }
-                    if let Some(token) = self.next_token() {
-                        if let Token::Word(Word { value: v, .. }) = token {
-                            if v == "N" {
-                                values.push(None);
-                            }
-                        }
-                    } else {
-                        continue;
-                    }
-                }
-                _ => {
-                    content.push_str(&t.to_string());
-                }
-            }
-        }
-        Ok(values)
-    }
-
     /// Parse a literal value (numbers, strings, date/time, booleans)
     fn parse_value(&mut self) -> Result<Value, ParserError> {
         match self.next_token() {
@@ -1992,6 +2154,30 @@ impl Parser {
         Ok(Expr::List(exprs))
     }
 
+    /// Parse the elements of an `ARRAY[...]` expression, assuming the `ARRAY`
+    /// keyword has already been consumed.
+    fn parse_array(&mut self) -> Result<Expr, ParserError> {
+        self.expect_token(&Token::LBracket)?;
+        let exprs = if self.peek_token() == Some(Token::RBracket) {
+            vec![]
+        } else {
+            self.parse_comma_separated(Parser::parse_expr)?
+        };
+        self.expect_token(&Token::RBracket)?;
+        Ok(Expr::Array(exprs))
+    }
+
+    /// Parse an array subscript like `expr[1]`, assuming the opening bracket
+    /// has already been consumed.
+    fn parse_subscript(&mut self, expr: Expr) -> Result<Expr, ParserError> {
+        let subscript = self.parse_expr()?;
+        self.expect_token(&Token::RBracket)?;
+        Ok(Expr::SubscriptIndex {
+            expr: Box::new(expr),
+            subscript: Box::new(subscript),
+        })
+    }
+
     fn parse_number_value(&mut self) -> Result<Value, ParserError> {
         match self.parse_value()? {
             v @ Value::Number(_) => Ok(v),
@@ -2028,13 +2214,13 @@ impl Parser {
         let mut data_type = match self.next_token() {
             Some(Token::Word(k)) => match k.keyword.as_ref() {
                 "BOOL" | "BOOLEAN" => DataType::Boolean,
-                "FLOAT" | "FLOAT4" => DataType::Float(self.parse_optional_precision()?),
-                "REAL" => DataType::Real,
+                "FLOAT" => DataType::Float(self.parse_optional_precision()?),
+                "REAL" | "FLOAT4" => DataType::Real,
                 "DOUBLE" | "FLOAT8" => {
                     let _ = self.parse_keyword("PRECISION");
                     DataType::Double
                 }
-                "SMALLINT" => DataType::SmallInt,
+                "SMALLINT" | "INT2" => DataType::SmallInt,
                 "INT" | "INTEGER" | "INT4" => DataType::Int,
                 "INT8" | "BIGINT" => DataType::BigInt,
                 "VARCHAR" => DataType::Varchar(self.parse_optional_precision()?),
@@ -2093,6 +2279,11 @@ impl Parser {
                     self.next_token();
                     data_type = DataType::List(Box::new(data_type));
                 }
+                Some(Token::LBracket) if self.peek_nth_token(1) == Some(Token::RBracket) => {
+                    self.next_token();
+                    self.next_token();
+                    data_type = DataType::Array(Box::new(data_type));
+                }
                 _ => break,
             }
         }
@@ -2249,11 +2440,11 @@ impl Parser {
     /// expect the initial keyword to be already consumed
     fn parse_query(&mut self) -> Result<Query, ParserError> {
         self.check_descent(|parser| {
-            let ctes = if parser.parse_keyword("WITH") {
-                // TODO: optional RECURSIVE
-                parser.parse_comma_separated(Parser::parse_cte)?
+            let (recursive, ctes) = if parser.parse_keyword("WITH") {
+                let recursive = parser.parse_keyword("RECURSIVE");
+                (recursive, parser.parse_comma_separated(Parser::parse_cte)?)
             } else {
-                vec![]
+                (false, vec![])
             };
 
             let body = parser.parse_query_body(Precedence::Zero)?;
@@ -2283,6 +2474,7 @@ impl Parser {
             };
 
             Ok(Query {
+                recursive,
                 ctes,
                 body,
                 limit,
@@ -2678,15 +2870,29 @@ impl Parser {
     /// A table name or a parenthesized subquery, followed by optional `[AS] alias`
     fn parse_table_factor(&mut self) -> Result<TableFactor, ParserError> {
         if self.parse_keyword("LATERAL") {
-            // LATERAL must always be followed by a subquery.
+            // LATERAL must always be followed by a subquery or a table
+            // function.
+            if self.consume_token(&Token::LParen) {
+                return self.parse_derived_table_factor(Lateral);
+            }
+            // Table functions can always refer to the tables that precede
+            // them, so LATERAL is implied and need not be recorded.
+            let name = self.parse_object_name()?;
             if !self.consume_token(&Token::LParen) {
                 self.expected(
                     self.peek_range(),
-                    "subquery after LATERAL",
+                    "subquery or table function after LATERAL",
                     self.peek_token(),
                 )?;
             }
-            return self.parse_derived_table_factor(Lateral);
+            let args = self.parse_optional_args()?;
+            let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
+            return Ok(TableFactor::Table {
+                name,
+                alias,
+                args: Some(args),
+                with_hints: vec![],
+            });
         }
 
         if self.consume_token(&Token::LParen) {
@@ -2982,7 +3188,7 @@ impl Parser {
                 TransactionMode::AccessMode(TransactionAccessMode::ReadOnly)
             } else if self.parse_keywords(vec!["READ", "WRITE"]) {
                 TransactionMode::AccessMode(TransactionAccessMode::ReadWrite)
-            } else if required || self.peek_token().is_some() {
+            } else if required || !matches!(self.peek_token(), None | Some(Token::SemiColon)) {
                 self.expected(self.peek_range(), "transaction mode", self.peek_token())?
             } else {
                 break;
@@ -3021,23 +3227,34 @@ impl Parser {
     }
 
     fn parse_tail(&mut self) -> Result<Statement, ParserError> {
-        let name = self.parse_object_name()?;
+        let relation = if self.consume_token(&Token::LParen) {
+            let query = self.parse_query()?;
+            self.expect_token(&Token::RParen)?;
+            TailRelation::Query(query)
+        } else {
+            TailRelation::Name(self.parse_object_name()?)
+        };
 
-        let with_snapshot = if self.parse_keyword("WITH") {
-            self.expect_keyword("SNAPSHOT")?;
-            true
+        // The `WITH SNAPSHOT` and `WITHOUT SNAPSHOT` forms predate `WITH`
+        // options, and are equivalent to setting the `snapshot` option.
+        let snapshot_option = |snapshot| {
+            vec![SqlOption {
+                name: Ident::new("snapshot"),
+                value: Value::Boolean(snapshot),
+            }]
+        };
+        let options = if self.parse_keywords(vec!["WITH", "SNAPSHOT"]) {
+            snapshot_option(true)
         } else if self.parse_keyword("WITHOUT") {
             self.expect_keyword("SNAPSHOT")?;
-            false
+            snapshot_option(false)
         } else {
-            // If neither WITH nor WITHOUT SNAPSHOT is provided,
-            // default to WITH SNAPSHOT.
-            true
+            self.parse_with_options()?
         };
         let as_of = self.parse_optional_as_of()?;
         Ok(Statement::Tail {
-            name,
-            with_snapshot,
+            relation,
+            options,
             as_of,
         })
     }
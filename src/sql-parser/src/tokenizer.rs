@@ -113,7 +113,7 @@ pub enum Token {
     DoubleColon,
     /// SemiColon `;` used as separator for COPY and payload
     SemiColon,
-    /// Backslash `\` used in terminating the COPY payload with `\.`
+    /// Backslash `\`
     Backslash,
     /// Left bracket `[`
     LBracket,
@@ -209,7 +209,7 @@ fn test_basic_visitor() -> Result<(), Box<dyn Error>> {
         ALTER TABLE i01 ADD CONSTRAINT i02 UNIQUE (i03);
         DROP TABLE j01;
         DROP VIEW k01;
-        COPY l01 (l02) FROM stdin;
+        COPY l01 (l02) FROM STDIN;
         START TRANSACTION READ ONLY;
         SET TRANSACTION ISOLATION LEVEL READ COMMITTED;
         COMMIT;
@@ -128,6 +128,12 @@ pub trait Catalog: fmt::Debug {
     ///
     /// Panics if `id` does not specify a valid item.
     fn get_item_by_id(&self, id: &GlobalId) -> &dyn CatalogItem;
+
+    /// Resolves the named role.
+    ///
+    /// If `role_name` exists in the catalog, it returns `Ok`; otherwise it
+    /// returns an error.
+    fn resolve_role(&self, role_name: &str) -> Result<(), failure::Error>;
 }
 
 /// An item in a [`Catalog`].
@@ -252,4 +258,8 @@ impl Catalog for DummyCatalog {
     fn get_item_by_id(&self, _: &GlobalId) -> &dyn CatalogItem {
         unimplemented!();
     }
+
+    fn resolve_role(&self, _: &str) -> Result<(), failure::Error> {
+        unimplemented!();
+    }
 }
@@ -12,6 +12,7 @@
 #![deny(missing_debug_implementations)]
 
 mod kafka_util;
+mod postgres_util;
 
 pub use sql_parser::ast;
 pub mod catalog;
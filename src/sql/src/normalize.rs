@@ -16,8 +16,8 @@ use repr::ColumnName;
 use sql_parser::ast::display::AstDisplay;
 use sql_parser::ast::visit_mut::{self, VisitMut};
 use sql_parser::ast::{
-    Function, FunctionArgs, Ident, IfExistsBehavior, ObjectName, SqlOption, Statement, TableFactor,
-    Value,
+    Expr, Function, FunctionArgs, Ident, IfExistsBehavior, ObjectName, SqlOption, Statement,
+    TableAlias, TableFactor, Value,
 };
 
 use crate::names::{DatabaseSpecifier, FullName, PartialName};
@@ -192,12 +192,24 @@ pub fn create_statement(
             *materialized = false;
         }
 
+        Statement::CreateTable {
+            name,
+            columns: _,
+            constraints: _,
+            with_options: _,
+            if_not_exists,
+        } => {
+            *name = allocate_name(name)?;
+            *if_not_exists = false;
+        }
+
         Statement::CreateSink {
             name,
             from,
             connector: _,
             with_options: _,
             format: _,
+            envelope: _,
             with_snapshot: _,
             as_of: _,
             if_not_exists,
@@ -257,6 +269,110 @@ pub fn create_statement(
     Ok(stmt.to_ast_string_stable())
 }
 
+/// Rewrites a `CREATE` statement that was produced by [`create_statement`] to
+/// reflect that the item named `from` has been renamed to `to`.
+///
+/// The statement may describe either the renamed item itself or an item that
+/// depends upon the renamed item. References to the renamed item in the
+/// query of a view are given an alias of the item's old name, so that
+/// qualified column references in the query continue to resolve. Column
+/// references that are qualified with the schema of the renamed item, like
+/// `public.foo.a`, would not resolve against the alias, and so are rewritten
+/// to refer to the alias instead.
+pub fn create_statement_rename(
+    create_sql: &str,
+    from: &FullName,
+    to: &FullName,
+) -> Result<String, failure::Error> {
+    struct QueryRenamer<'a> {
+        from: &'a ObjectName,
+        to: &'a ObjectName,
+        from_item: &'a str,
+    }
+
+    impl<'a, 'ast> VisitMut<'ast> for QueryRenamer<'a> {
+        fn visit_table_factor_mut(&mut self, table_factor: &'ast mut TableFactor) {
+            if let TableFactor::Table {
+                name,
+                args: None,
+                alias,
+                ..
+            } = table_factor
+            {
+                if name == self.from {
+                    *name = self.to.clone();
+                    if alias.is_none() {
+                        *alias = Some(TableAlias {
+                            name: Ident::new(self.from_item),
+                            columns: vec![],
+                            strict: false,
+                        });
+                    }
+                }
+            }
+            visit_mut::visit_table_factor_mut(self, table_factor);
+        }
+
+        fn visit_expr_mut(&mut self, expr: &'ast mut Expr) {
+            match expr {
+                Expr::Identifier(names) if names.len() > 2 => {
+                    let (column, qualifier) = names.split_last().unwrap();
+                    if self.from.0.ends_with(qualifier) {
+                        *names = vec![Ident::new(self.from_item), column.clone()];
+                    }
+                }
+                Expr::QualifiedWildcard(qualifier) if qualifier.len() > 1 => {
+                    if self.from.0.ends_with(qualifier) {
+                        *qualifier = vec![Ident::new(self.from_item)];
+                    }
+                }
+                _ => (),
+            }
+            visit_mut::visit_expr_mut(self, expr);
+        }
+    }
+
+    let from_name = unresolve(from.clone());
+    let to_name = unresolve(to.clone());
+    let rename = |name: &mut ObjectName| {
+        if *name == from_name {
+            *name = to_name.clone();
+        }
+    };
+
+    let mut stmt = crate::parse::parse(create_sql.into())?.into_element();
+    match &mut stmt {
+        Statement::CreateSource { name, .. } | Statement::CreateTable { name, .. } => rename(name),
+        Statement::CreateSink { name, from, .. } => {
+            rename(name);
+            rename(from);
+        }
+        Statement::CreateView { name, query, .. } => {
+            rename(name);
+            let mut renamer = QueryRenamer {
+                from: &from_name,
+                to: &to_name,
+                from_item: &from.item,
+            };
+            renamer.visit_query_mut(query);
+        }
+        Statement::CreateIndex { name, on_name, .. } => {
+            // Indexes live in the same schema as the item they index.
+            if let Some(name) = name {
+                let mut index_name = on_name.clone();
+                index_name.0.pop();
+                index_name.0.push(name.clone());
+                if index_name == from_name {
+                    *name = Ident::new(to.item.clone());
+                }
+            }
+            rename(on_name);
+        }
+        _ => bail!("cannot rename catalog item described by {}", create_sql),
+    }
+    Ok(stmt.to_ast_string_stable())
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
@@ -285,4 +401,67 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn renamed_create() -> Result<(), Box<dyn Error>> {
+        let name = |item: &str| FullName {
+            database: DatabaseSpecifier::Name("materialize".into()),
+            schema: "public".into(),
+            item: item.into(),
+        };
+        let from = name("foo");
+        let to = name("bar");
+
+        // The renamed item itself.
+        assert_eq!(
+            r#"CREATE VIEW "materialize"."public"."bar" AS SELECT 1 AS "a""#,
+            create_statement_rename(
+                r#"CREATE VIEW "materialize"."public"."foo" AS SELECT 1 AS "a""#,
+                &from,
+                &to,
+            )?,
+        );
+
+        // A view that depends upon the renamed item retains the old name as
+        // an alias, but explicit aliases are left alone.
+        assert_eq!(
+            r#"CREATE VIEW "materialize"."public"."v" AS SELECT "foo"."a" FROM "materialize"."public"."bar" AS "foo", "materialize"."public"."bar" AS "x", "materialize"."public"."baz""#,
+            create_statement_rename(
+                r#"CREATE VIEW "materialize"."public"."v" AS SELECT "foo"."a" FROM "materialize"."public"."foo", "materialize"."public"."foo" AS "x", "materialize"."public"."baz""#,
+                &from,
+                &to,
+            )?,
+        );
+
+        // Column references qualified with the renamed item's schema refer to
+        // the alias instead.
+        assert_eq!(
+            r#"CREATE VIEW "materialize"."public"."v" AS SELECT "foo"."a", "foo"."b", "foo".*, "public"."baz"."c" FROM "materialize"."public"."bar" AS "foo", "materialize"."public"."baz""#,
+            create_statement_rename(
+                r#"CREATE VIEW "materialize"."public"."v" AS SELECT "materialize"."public"."foo"."a", "public"."foo"."b", "materialize"."public"."foo".*, "public"."baz"."c" FROM "materialize"."public"."foo", "materialize"."public"."baz""#,
+                &from,
+                &to,
+            )?,
+        );
+
+        // An index on the renamed item, and a renamed index.
+        assert_eq!(
+            r#"CREATE INDEX "foo_primary_idx" ON "materialize"."public"."bar" ("a")"#,
+            create_statement_rename(
+                r#"CREATE INDEX "foo_primary_idx" ON "materialize"."public"."foo" ("a")"#,
+                &from,
+                &to,
+            )?,
+        );
+        assert_eq!(
+            r#"CREATE INDEX "bar" ON "materialize"."public"."t" ("a")"#,
+            create_statement_rename(
+                r#"CREATE INDEX "foo" ON "materialize"."public"."t" ("a")"#,
+                &from,
+                &to,
+            )?,
+        );
+
+        Ok(())
+    }
 }
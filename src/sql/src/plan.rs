@@ -33,7 +33,7 @@ use ::expr::{GlobalId, RowSetFinishing};
 use dataflow_types::{PeekWhen, SinkConnectorBuilder, SourceConnector, Timestamp};
 use repr::{ColumnName, RelationDesc, Row, ScalarType};
 
-use crate::ast::{ExplainOptions, ExplainStage, ObjectType, Statement};
+use crate::ast::{CopyFormat, ExplainOptions, ExplainStage, ObjectType, Privilege, Statement};
 use crate::catalog::Catalog;
 use crate::names::{DatabaseSpecifier, FullName};
 
@@ -80,7 +80,7 @@ pub enum Plan {
     },
     CreateTable {
         name: FullName,
-        desc: RelationDesc,
+        table: Table,
         if_not_exists: bool,
     },
     CreateView {
@@ -108,6 +108,31 @@ pub enum Plan {
         items: Vec<GlobalId>,
         ty: ObjectType,
     },
+    AlterNoop {
+        object_type: ObjectType,
+    },
+    AlterItemRename {
+        id: GlobalId,
+        to_name: String,
+        object_type: ObjectType,
+    },
+    CreateRole {
+        name: String,
+        superuser: bool,
+    },
+    DropRoles {
+        names: Vec<String>,
+    },
+    GrantPrivileges {
+        privileges: Vec<Privilege>,
+        ids: Vec<GlobalId>,
+        roles: Vec<String>,
+    },
+    RevokePrivileges {
+        privileges: Vec<Privilege>,
+        ids: Vec<GlobalId>,
+        roles: Vec<String>,
+    },
     EmptyQuery,
     ShowAllVariables,
     ShowVariable(String),
@@ -125,11 +150,17 @@ pub enum Plan {
         materialize: bool,
     },
     Tail {
-        id: GlobalId,
+        from: TailFrom,
         with_snapshot: bool,
         ts: Option<Timestamp>,
+        emit_progress: bool,
     },
     SendRows(Vec<Row>),
+    ShowCreate {
+        /// The item whose `CREATE` statement is shown.
+        id: GlobalId,
+        row: Row,
+    },
     ExplainPlan {
         raw_plan: RelationExpr,
         decorrelated_plan: ::expr::RelationExpr,
@@ -137,6 +168,20 @@ pub enum Plan {
         stage: ExplainStage,
         options: ExplainOptions,
     },
+    Insert {
+        id: GlobalId,
+        values: ::expr::RelationExpr,
+    },
+    CopyFrom {
+        id: GlobalId,
+        columns: Vec<usize>,
+        format: CopyFormat,
+    },
+    ReadThenWrite {
+        id: GlobalId,
+        selection: ::expr::RelationExpr,
+        kind: MutationKind,
+    },
     SendDiffs {
         id: GlobalId,
         updates: Vec<(Row, isize)>,
@@ -151,6 +196,12 @@ pub enum Plan {
     },
 }
 
+#[derive(Clone, Debug)]
+pub struct Table {
+    pub create_sql: String,
+    pub desc: RelationDesc,
+}
+
 #[derive(Clone, Debug)]
 pub struct Source {
     pub create_sql: String,
@@ -180,6 +231,19 @@ pub struct Index {
     pub keys: Vec<::expr::ScalarExpr>,
 }
 
+/// The relation whose updates a `TAIL` reports.
+#[derive(Clone, Debug)]
+pub enum TailFrom {
+    /// An existing source or view.
+    Id(GlobalId),
+    /// A query, which is maintained by a transient dataflow for as long as the
+    /// `TAIL` runs.
+    Query {
+        expr: ::expr::RelationExpr,
+        desc: RelationDesc,
+    },
+}
+
 #[derive(Debug)]
 pub enum MutationKind {
     Insert,
@@ -89,8 +89,17 @@ impl RelationExpr {
         let mut id_gen = expr::IdGen::default();
         transform_expr::split_subquery_predicates(&mut self);
         transform_expr::try_simplify_quantified_comparisons(&mut self);
+        transform_expr::rebind_local_ids(&mut self, &mut id_gen);
+        self.applied_to_unit(&mut id_gen)
+    }
+
+    /// Return a `expr::RelationExpr` which evaluates `self` exactly once.
+    ///
+    /// This is only appropriate for `self` that is not correlated with any
+    /// outer relation.
+    fn applied_to_unit(self, id_gen: &mut expr::IdGen) -> expr::RelationExpr {
         expr::RelationExpr::constant(vec![vec![]], RelationType::new(vec![]))
-            .let_in(&mut id_gen, |id_gen, get_outer| {
+            .let_in(id_gen, |id_gen, get_outer| {
                 self.applied_to(id_gen, get_outer, &ColumnMap::empty())
             })
     }
@@ -135,6 +144,25 @@ impl RelationExpr {
                 // Get statements are only to external sources, and are not correlated with `get_outer`.
                 get_outer.product(SR::Get { id, typ })
             }
+            Let { id, value, body } => {
+                // The value of a CTE is not correlated with `get_outer`, and so can be
+                // bound once, independent of the rows of `get_outer`.
+                let value = value.applied_to_unit(id_gen);
+                let body = body.applied_to(id_gen, get_outer, col_map);
+                SR::Let {
+                    id,
+                    value: Box::new(value),
+                    body: Box::new(body),
+                }
+            }
+            Iterate { id, body } => {
+                // Recursive CTEs are likewise not correlated with `get_outer`.
+                let body = body.applied_to_unit(id_gen);
+                get_outer.product(SR::Iterate {
+                    id,
+                    body: Box::new(body),
+                })
+            }
             Project { input, outputs } => {
                 // Projections should be applied to the decorrelated `inner`, and to its columns,
                 // which means rebasing `outputs` to start `get_outer.arity()` columns later.
@@ -214,6 +242,61 @@ impl RelationExpr {
                 right,
                 on,
                 kind,
+                lateral: true,
+            } => {
+                // The right expression of a lateral join is correlated with the left
+                // expression, and so it is decorrelated exactly as a subquery would be,
+                // evaluating it once for each distinct assignment of values to the
+                // columns of the left expression on which it depends.
+                //
+                // Only inner and left outer joins can be lateral.
+                assert!(kind == JoinKind::Inner || kind == JoinKind::LeftOuter);
+                let oa = get_outer.arity();
+                let left = left.applied_to(id_gen, get_outer, col_map);
+                let la = left.arity() - oa;
+                left.let_in(id_gen, |id_gen, get_left| {
+                    let mut product = branch(
+                        id_gen,
+                        get_left.clone(),
+                        col_map,
+                        *right,
+                        |id_gen, right, get_inner, col_map| {
+                            right.applied_to(id_gen, get_inner, col_map)
+                        },
+                    );
+                    let old_arity = product.arity();
+                    let on = on.applied_to(id_gen, col_map, &mut product);
+                    let mut join = product.filter(vec![on]);
+                    let new_arity = join.arity();
+                    if old_arity != new_arity {
+                        // this means we added some columns to handle subqueries, and now we need to get rid of them
+                        join = join.project((0..old_arity).collect());
+                    }
+                    if kind == JoinKind::LeftOuter {
+                        let jt = join.typ();
+                        join.let_in(id_gen, |id_gen, get_join| {
+                            let left_outer = get_left.anti_lookup(
+                                id_gen,
+                                get_join.clone(),
+                                jt.column_types
+                                    .into_iter()
+                                    .skip(oa + la)
+                                    .map(|typ| (Datum::Null, typ.nullable(true)))
+                                    .collect(),
+                            );
+                            get_join.union(left_outer)
+                        })
+                    } else {
+                        join
+                    }
+                })
+            }
+            Join {
+                left,
+                right,
+                on,
+                kind,
+                lateral: false,
             } => {
                 // Both join expressions should be decorrelated, and then joined by their
                 // leading columns to form only those pairs corresponding to the same row
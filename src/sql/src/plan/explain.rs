@@ -140,7 +140,7 @@ impl RelationExpr {
                     | Negate { .. }
                     | Threshold { .. }
                     | Distinct { .. } => false,
-                    Join { .. } | Union { .. } => true,
+                    Join { .. } | Union { .. } | Let { .. } | Iterate { .. } => true,
                     Constant { .. } | Get { .. } => unreachable!(), // these don't have children
                 },
             };
@@ -155,6 +155,8 @@ impl RelationExpr {
             match &node.expr {
                 Constant { .. }
                 | Get { .. }
+                | Let { .. }
+                | Iterate { .. }
                 | Project { .. }
                 | Distinct { .. }
                 | Negate { .. }
@@ -211,9 +213,7 @@ impl RelationExpr {
                     write!(pretty, "Constant {}", Separated(" ", rows.clone())).unwrap();
                 }
                 Get { id, .. } => match id {
-                    Id::Local(_) => {
-                        unimplemented!("sql::RelationExpr::Get can't contain LocalId yet")
-                    }
+                    Id::Local(local_id) => write!(pretty, "Get {}", local_id).unwrap(),
                     Id::Global(_) => write!(
                         pretty,
                         "Get {} ({})",
@@ -224,6 +224,17 @@ impl RelationExpr {
                     )
                     .unwrap(),
                 },
+                Let { id, value, body } => write!(
+                    pretty,
+                    "Let {} = %{} in %{}",
+                    id,
+                    expr_chain(value),
+                    expr_chain(body)
+                )
+                .unwrap(),
+                Iterate { id, body } => {
+                    write!(pretty, "Iterate {} = %{}", id, expr_chain(body)).unwrap()
+                }
                 Project { outputs, .. } => {
                     write!(pretty, "Project {}", Bracketed("(", ")", Indices(outputs))).unwrap()
                 }
@@ -272,11 +283,13 @@ impl RelationExpr {
                     right,
                     on,
                     kind,
+                    lateral,
                 } => {
                     write!(
                         pretty,
-                        "{}Join %{} %{} on {}",
+                        "{}{}Join %{} %{} on {}",
                         kind,
+                        if *lateral { "Lateral" } else { "" },
                         expr_chain(left),
                         expr_chain(right),
                         on.fmt_with(&mut subqueries),
@@ -27,6 +27,7 @@ use crate::plan::Params;
 // these happen to be unchanged at the moment, but there might be additions later
 pub use expr::{
     AggregateFunc, BinaryFunc, ColumnOrder, NullaryFunc, TableFunc, UnaryFunc, VariadicFunc,
+    WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunc,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -40,12 +41,18 @@ pub enum RelationExpr {
         id: expr::Id,
         typ: RelationType,
     },
-    // only needed for CTEs
-    // Let {
-    //     name: Name,
-    //     value: Box<RelationExpr>,
-    //     body: Box<RelationExpr>,
-    // },
+    /// Unlike expr::RelationExpr, `value` must not be correlated with any
+    /// outer relation, as it is only needed for CTEs.
+    Let {
+        id: expr::LocalId,
+        value: Box<RelationExpr>,
+        body: Box<RelationExpr>,
+    },
+    /// Like `Let`, `body` must not be correlated with any outer relation.
+    Iterate {
+        id: expr::LocalId,
+        body: Box<RelationExpr>,
+    },
     Project {
         input: Box<RelationExpr>,
         outputs: Vec<usize>,
@@ -70,6 +77,9 @@ pub enum RelationExpr {
         right: Box<RelationExpr>,
         on: ScalarExpr,
         kind: JoinKind,
+        /// If set, `right` may refer to the columns of `left`, which appear to
+        /// it as the innermost outer relation, as with `LATERAL`.
+        lateral: bool,
     },
     /// Unlike expr::RelationExpr, when `key` is empty AND `input` is empty this returns
     /// a single row with the aggregates evaluated over empty groups, rather than returning zero
@@ -180,6 +190,7 @@ pub enum CoercibleScalarExpr {
     LiteralNull,
     LiteralString(String),
     LiteralList(Vec<CoercibleScalarExpr>),
+    LiteralArray(Vec<CoercibleScalarExpr>),
     LiteralRecord(Vec<CoercibleScalarExpr>),
 }
 
@@ -220,6 +231,21 @@ impl ScalarTypeable for CoercibleScalarExpr {
     ) -> Self::Type {
         match self {
             CoercibleScalarExpr::Coerced(expr) => Some(expr.typ(outers, inner, params)),
+            // An array literal whose elements are not all of unknown type has
+            // a type even before it is coerced, so that it can participate in
+            // function selection and homogeneous type inference like any
+            // other array.
+            CoercibleScalarExpr::LiteralArray(exprs) => {
+                let types: Vec<_> = exprs
+                    .iter()
+                    .map(|e| e.typ(outers, inner, params).map(|t| t.scalar_type))
+                    .collect();
+                if types.iter().all(|t| t.is_none()) {
+                    return None;
+                }
+                let elem_type = typeconv::guess_best_common_type(&types)?;
+                Some(ColumnType::new(ScalarType::Array(Box::new(elem_type))))
+            }
             _ => None,
         }
     }
@@ -277,6 +303,9 @@ impl RelationExpr {
         match self {
             RelationExpr::Constant { typ, .. } => typ.clone(),
             RelationExpr::Get { typ, .. } => typ.clone(),
+            RelationExpr::Let { body, .. } | RelationExpr::Iterate { body, .. } => {
+                body.typ(outers, params)
+            }
             RelationExpr::Project { input, outputs } => {
                 let input_typ = input.typ(outers, params);
                 RelationType::new(
@@ -307,15 +336,27 @@ impl RelationExpr {
                 input.typ(outers, params)
             }
             RelationExpr::Join {
-                left, right, kind, ..
+                left,
+                right,
+                kind,
+                lateral,
+                ..
             } => {
                 let left_nullable = *kind == JoinKind::RightOuter || *kind == JoinKind::FullOuter;
                 let right_nullable = *kind == JoinKind::LeftOuter || *kind == JoinKind::FullOuter;
-                let lt = left.typ(outers, params).column_types.into_iter().map(|t| {
+                let left_typ = left.typ(outers, params);
+                let right_typ = if *lateral {
+                    let mut outers = outers.to_vec();
+                    outers.push(left_typ.clone());
+                    right.typ(&outers, params)
+                } else {
+                    right.typ(outers, params)
+                };
+                let lt = left_typ.column_types.into_iter().map(|t| {
                     let nullable = t.nullable || left_nullable;
                     t.nullable(nullable)
                 });
-                let rt = right.typ(outers, params).column_types.into_iter().map(|t| {
+                let rt = right_typ.column_types.into_iter().map(|t| {
                     let nullable = t.nullable || right_nullable;
                     t.nullable(nullable)
                 });
@@ -362,6 +403,7 @@ impl RelationExpr {
         match self {
             RelationExpr::Constant { typ, .. } => typ.column_types.len(),
             RelationExpr::Get { typ, .. } => typ.column_types.len(),
+            RelationExpr::Let { body, .. } | RelationExpr::Iterate { body, .. } => body.arity(),
             RelationExpr::Project { outputs, .. } => outputs.len(),
             RelationExpr::Map { input, scalars } => input.arity() + scalars.len(),
             RelationExpr::FlatMap { input, func, .. } => input.arity() + func.output_arity(),
@@ -429,6 +471,7 @@ impl RelationExpr {
                 right: Box::new(right),
                 on: ScalarExpr::literal_true(),
                 kind: JoinKind::Inner,
+                lateral: false,
             }
         }
     }
@@ -515,6 +558,13 @@ impl RelationExpr {
     {
         match self {
             RelationExpr::Constant { .. } | RelationExpr::Get { .. } => (),
+            RelationExpr::Let { value, body, .. } => {
+                f(value);
+                f(body);
+            }
+            RelationExpr::Iterate { body, .. } => {
+                f(body);
+            }
             RelationExpr::Project { input, .. } => {
                 f(input);
             }
@@ -567,6 +617,13 @@ impl RelationExpr {
     {
         match self {
             RelationExpr::Constant { .. } | RelationExpr::Get { .. } => (),
+            RelationExpr::Let { value, body, .. } => {
+                f(value);
+                f(body);
+            }
+            RelationExpr::Iterate { body, .. } => {
+                f(body);
+            }
             RelationExpr::Project { input, .. } => {
                 f(input);
             }
@@ -614,37 +671,57 @@ impl RelationExpr {
     where
         F: FnMut(usize, &mut ColumnRef),
     {
-        self.visit_mut(&mut |e| match e {
-            RelationExpr::Join { on, .. } => on.visit_columns(depth, f),
-            RelationExpr::Map { scalars, .. } => {
+        match self {
+            RelationExpr::Join {
+                left,
+                right,
+                on,
+                lateral,
+                ..
+            } => {
+                left.visit_columns(depth, f);
+                // The right input of a lateral join sees the left input as an
+                // additional level of nesting.
+                right.visit_columns(if *lateral { depth + 1 } else { depth }, f);
+                on.visit_columns(depth, f);
+            }
+            RelationExpr::Map { input, scalars } => {
+                input.visit_columns(depth, f);
                 for scalar in scalars {
                     scalar.visit_columns(depth, f);
                 }
             }
-            RelationExpr::FlatMap { exprs, .. } => {
+            RelationExpr::FlatMap { input, exprs, .. } => {
+                input.visit_columns(depth, f);
                 for expr in exprs {
                     expr.visit_columns(depth, f);
                 }
             }
-            RelationExpr::Filter { predicates, .. } => {
+            RelationExpr::Filter { input, predicates } => {
+                input.visit_columns(depth, f);
                 for predicate in predicates {
                     predicate.visit_columns(depth, f);
                 }
             }
-            RelationExpr::Reduce { aggregates, .. } => {
+            RelationExpr::Reduce {
+                input, aggregates, ..
+            } => {
+                input.visit_columns(depth, f);
                 for aggregate in aggregates {
                     aggregate.visit_columns(depth, f);
                 }
             }
             RelationExpr::Constant { .. }
             | RelationExpr::Get { .. }
+            | RelationExpr::Let { .. }
+            | RelationExpr::Iterate { .. }
             | RelationExpr::Project { .. }
             | RelationExpr::Distinct { .. }
             | RelationExpr::TopK { .. }
             | RelationExpr::Negate { .. }
             | RelationExpr::Threshold { .. }
-            | RelationExpr::Union { .. } => (),
-        })
+            | RelationExpr::Union { .. } => self.visit1_mut(|e| e.visit_columns(depth, f)),
+        }
     }
 
     /// Replaces any parameter references in the expression with the
@@ -674,6 +751,8 @@ impl RelationExpr {
             }
             RelationExpr::Constant { .. }
             | RelationExpr::Get { .. }
+            | RelationExpr::Let { .. }
+            | RelationExpr::Iterate { .. }
             | RelationExpr::Project { .. }
             | RelationExpr::Distinct { .. }
             | RelationExpr::TopK { .. }
@@ -24,7 +24,7 @@ use sql_parser::ast::{BinaryOperator, Expr, UnaryOperator};
 
 use super::expr::{
     AggregateFunc, BinaryFunc, CoercibleScalarExpr, NullaryFunc, ScalarExpr, TableFunc, UnaryFunc,
-    VariadicFunc,
+    VariadicFunc, WindowFunc,
 };
 use super::query::{self, ExprContext, QueryLifetime};
 use super::typeconv::{self, rescale_decimal, CastTo, CoerceTo};
@@ -39,6 +39,7 @@ use crate::unsupported;
 /// [typcategory]:
 /// https://www.postgresql.org/docs/9.6/catalog-pg-type.html#CATALOG-TYPCATEGORY-TABLE
 pub enum TypeCategory {
+    Array,
     Bool,
     DateTime,
     Numeric,
@@ -62,8 +63,11 @@ impl TypeCategory {
     /// ```
     fn from_type(typ: &ScalarType) -> Self {
         match typ {
+            ScalarType::Array(_) => Self::Array,
             ScalarType::Bool => Self::Bool,
-            ScalarType::Bytes | ScalarType::Jsonb | ScalarType::List(_) => Self::UserDefined,
+            ScalarType::Bytes | ScalarType::Jsonb | ScalarType::List(_) | ScalarType::Uuid => {
+                Self::UserDefined
+            }
             ScalarType::Date
             | ScalarType::Time
             | ScalarType::Timestamp
@@ -71,10 +75,13 @@ impl TypeCategory {
             ScalarType::Decimal(..)
             | ScalarType::Float32
             | ScalarType::Float64
+            | ScalarType::Int16
             | ScalarType::Int32
             | ScalarType::Int64 => Self::Numeric,
             ScalarType::Interval => Self::Timespan,
-            ScalarType::String => Self::String,
+            ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+                Self::String
+            }
             ScalarType::Record { .. } => Self::Pseudo,
         }
     }
@@ -82,7 +89,9 @@ impl TypeCategory {
     fn from_param(param: &ParamType) -> Self {
         match param {
             ParamType::Plain(t) => Self::from_type(t),
-            ParamType::Any | ParamType::StringAny | ParamType::JsonbAny => Self::Pseudo,
+            ParamType::Any | ParamType::ArrayAny | ParamType::StringAny | ParamType::JsonbAny => {
+                Self::Pseudo
+            }
         }
     }
 
@@ -100,7 +109,7 @@ impl TypeCategory {
             Self::Numeric => Some(ScalarType::Float64),
             Self::String => Some(ScalarType::String),
             Self::Timespan => Some(ScalarType::Interval),
-            Self::Pseudo | Self::UserDefined => None,
+            Self::Array | Self::Pseudo | Self::UserDefined => None,
         }
     }
 }
@@ -251,6 +260,8 @@ pub enum ParamType {
     Plain(ScalarType),
     /// A psuedotype permitting any type.
     Any,
+    /// A pseudotype permitting any array type.
+    ArrayAny,
     /// A pseudotype permitting any type, but requires it to be cast to a `ScalarType::String`.
     StringAny,
     /// A pseudotype permitting any type, but requires it to be cast to a
@@ -263,6 +274,7 @@ impl ParamType {
     fn accepts_type_directly(&self, t: &ScalarType) -> bool {
         match (self, t) {
             (ParamType::Plain(s), o) => *s == o.desaturate(),
+            (ParamType::ArrayAny, o) => matches!(o, ScalarType::Array(_)),
             (ParamType::Any, _) | (ParamType::StringAny, _) | (ParamType::JsonbAny, _) => true,
         }
     }
@@ -271,6 +283,7 @@ impl ParamType {
     fn accepts_type_implicitly(&self, from_type: &ScalarType) -> bool {
         let cast_to = match self {
             ParamType::Plain(s) => CastTo::Implicit(s.clone()),
+            ParamType::ArrayAny => return matches!(from_type, ScalarType::Array(_)),
             ParamType::Any | ParamType::JsonbAny | ParamType::StringAny => return true,
         };
 
@@ -281,6 +294,7 @@ impl ParamType {
     fn accepts_cat(&self, c: &TypeCategory) -> bool {
         match (self, c) {
             (ParamType::Plain(_), c) => TypeCategory::from_param(&self) == *c,
+            (ParamType::ArrayAny, c) => *c == TypeCategory::Array,
             (ParamType::Any, _) | (ParamType::StringAny, _) | (ParamType::JsonbAny, _) => true,
         }
     }
@@ -310,7 +324,10 @@ impl PartialEq<ScalarType> for ParamType {
         match (self, other) {
             (ParamType::Plain(s), o) => *s == o.desaturate(),
             // Pseudotypes do not equal concrete types.
-            (ParamType::Any, _) | (ParamType::StringAny, _) | (ParamType::JsonbAny, _) => false,
+            (ParamType::Any, _)
+            | (ParamType::ArrayAny, _)
+            | (ParamType::StringAny, _)
+            | (ParamType::JsonbAny, _) => false,
         }
     }
 }
@@ -624,7 +641,7 @@ impl<'a> ArgImplementationMatcher<'a> {
         use ScalarType::*;
         let coerce_to = match typ {
             ParamType::Plain(s) => CoerceTo::Plain(s.clone()),
-            ParamType::Any => CoerceTo::Plain(String),
+            ParamType::Any | ParamType::ArrayAny => CoerceTo::Plain(String),
             ParamType::JsonbAny => CoerceTo::JsonbAny,
             ParamType::StringAny => CoerceTo::Plain(String),
         };
@@ -632,8 +649,22 @@ impl<'a> ArgImplementationMatcher<'a> {
         let arg_type = self.ecx.scalar_type(&arg);
         let cast_to = match typ {
             ParamType::Plain(Decimal(..)) if matches!(arg_type, Decimal(..)) => return Ok(arg),
+            // Blank-padded and length-constrained strings are represented
+            // exactly as strings are, so they need no conversion.
+            ParamType::Plain(String) if matches!(arg_type, Char { .. } | VarChar { .. }) => {
+                return Ok(arg)
+            }
+            ParamType::Plain(Char { .. }) if matches!(arg_type, Char { .. }) => return Ok(arg),
             ParamType::Plain(s) => CastTo::Implicit(s.clone()),
             ParamType::Any => return Ok(arg),
+            ParamType::ArrayAny => match arg_type {
+                Array(_) => return Ok(arg),
+                _ => bail!(
+                    "{} requires an array argument, not {}",
+                    self.ident,
+                    arg_type
+                ),
+            },
             ParamType::JsonbAny => CastTo::JsonbAny,
             ParamType::StringAny => CastTo::Explicit(String),
         };
@@ -686,6 +717,7 @@ lazy_static! {
         use ScalarType::*;
         impls! {
             "abs" => {
+                params!(Int16) => UnaryFunc::AbsInt16,
                 params!(Int32) => UnaryFunc::AbsInt32,
                 params!(Int64) => UnaryFunc::AbsInt64,
                 params!(Decimal(0, 0)) => UnaryFunc::AbsDecimal,
@@ -742,7 +774,17 @@ lazy_static! {
             },
             "date_trunc" => {
                 params!(String, Timestamp) => BinaryFunc::DateTruncTimestamp,
-                params!(String, TimestampTz) => BinaryFunc::DateTruncTimestampTz
+                params!(String, TimestampTz) => BinaryFunc::DateTruncTimestampTz,
+                params!(String, TimestampTz, String) => variadic_op(|_ecx, mut exprs| {
+                    // Truncate the local time in the specified time zone,
+                    // then interpret the result as a local time in that zone.
+                    let tz = exprs.pop().unwrap();
+                    let ts = exprs.pop().unwrap();
+                    let units = exprs.pop().unwrap();
+                    let local = tz.clone().call_binary(ts, BinaryFunc::TimezoneTimestampTz);
+                    let trunc = units.call_binary(local, BinaryFunc::DateTruncTimestamp);
+                    Ok(tz.call_binary(trunc, BinaryFunc::TimezoneTimestamp))
+                })
             },
             "floor" => {
                 params!(Float32) => UnaryFunc::FloorFloat32,
@@ -761,6 +803,12 @@ lazy_static! {
                 params!(Float32) => identity_op(),
                 params!(Float64) => identity_op(),
                 params!(Decimal(0, 0)) => identity_op(),
+                params!(Int16) => unary_op(|ecx, e| {
+                      super::typeconv::plan_cast(
+                          "internal.avg_promotion", ecx, e,
+                          CastTo::Explicit(ScalarType::Decimal(5, 0)),
+                      )
+                }),
                 params!(Int32) => unary_op(|ecx, e| {
                       super::typeconv::plan_cast(
                           "internal.avg_promotion", ecx, e,
@@ -850,6 +898,10 @@ lazy_static! {
                     Ok(e.call_unary(UnaryFunc::SqrtDec(s)))
                 })
             },
+            "timezone" => {
+                params!(String, Timestamp) => BinaryFunc::TimezoneTimestamp,
+                params!(String, TimestampTz) => BinaryFunc::TimezoneTimestampTz
+            },
             "to_char" => {
                 params!(Timestamp, String) => BinaryFunc::ToCharTimestamp,
                 params!(TimestampTz, String) => BinaryFunc::ToCharTimestampTz
@@ -930,6 +982,7 @@ lazy_static! {
         let mut m = impls! {
             // ARITHMETIC
             Plus => {
+                params!(Int16, Int16) => AddInt16,
                 params!(Int32, Int32) => AddInt32,
                 params!(Int64, Int64) => AddInt64,
                 params!(Float32, Float32) => AddFloat32,
@@ -963,6 +1016,7 @@ lazy_static! {
                 }
             },
             Minus => {
+                params!(Int16, Int16) => SubInt16,
                 params!(Int32, Int32) => SubInt32,
                 params!(Int64, Int64) => SubInt64,
                 params!(Float32, Float32) => SubFloat32,
@@ -986,6 +1040,7 @@ lazy_static! {
                 // Array(Int64) and Array(String)
             },
             Multiply => {
+                params!(Int16, Int16) => MulInt16,
                 params!(Int32, Int32) => MulInt32,
                 params!(Int64, Int64) => MulInt64,
                 params!(Float32, Float32) => MulFloat32,
@@ -1001,6 +1056,7 @@ lazy_static! {
                 })
             },
             Divide => {
+                params!(Int16, Int16) => DivInt16,
                 params!(Int32, Int32) => DivInt32,
                 params!(Int64, Int64) => DivInt64,
                 params!(Float32, Float32) => DivFloat32,
@@ -1020,6 +1076,7 @@ lazy_static! {
                 })
             },
             Modulus => {
+                params!(Int16, Int16) => ModInt16,
                 params!(Int32, Int32) => ModInt32,
                 params!(Int64, Int64) => ModInt64,
                 params!(Float32, Float32) => ModFloat32,
@@ -1164,6 +1221,7 @@ lazy_static! {
         ] {
             insert_impl!(m, op,
                 params!(Bool, Bool) => func.clone(),
+                params!(Int16, Int16) => func.clone(),
                 params!(Int32, Int32) => func.clone(),
                 params!(Int64, Int64) => func.clone(),
                 params!(Float32, Float32) => func.clone(),
@@ -1175,6 +1233,7 @@ lazy_static! {
                 params!(Interval, Interval) => func.clone(),
                 params!(Bytes, Bytes) => func.clone(),
                 params!(String, String) => func.clone(),
+                params!(Char { length: None }, Char { length: None }) => func.clone(),
                 params!(Jsonb, Jsonb) => func.clone()
             );
         }
@@ -1255,6 +1314,7 @@ lazy_static! {
             },
 
             Minus => {
+                params!(Int16) => UnaryFunc::NegInt16,
                 params!(Int32) => UnaryFunc::NegInt32,
                 params!(Int64) => UnaryFunc::NegInt64,
                 params!(Float32) => UnaryFunc::NegFloat32,
@@ -1373,6 +1433,19 @@ lazy_static! {
                     })
                 })
             },
+            "unnest" => {
+                params!(ArrayAny) => unary_op(move |ecx, array| {
+                    let el_typ = match ecx.scalar_type(&array) {
+                        ScalarType::Array(el_typ) => *el_typ,
+                        _ => unreachable!("unnest requires an array argument"),
+                    };
+                    Ok(TableFuncPlan {
+                        func: TableFunc::UnnestArray { el_typ },
+                        exprs: vec![array],
+                        column_names: vec![Some("unnest".into())],
+                    })
+                })
+            },
             "regexp_extract" => {
                 params!(String, String) => binary_op(move |_ecx, regex, haystack| {
                     let regex = match regex.into_literal_string() {
@@ -1437,6 +1510,14 @@ lazy_static! {
         use ParamType::*;
         use ScalarType::*;
         impls! {
+            "array_agg" => {
+                params!(Any) => unary_op(|ecx, e| {
+                    if let ScalarType::Array(_) | ScalarType::List(_) = ecx.scalar_type(&e) {
+                        unsupported!("array_agg on arrays");
+                    }
+                    Ok((e, AggregateFunc::ArrayAgg))
+                })
+            },
             "count" => {
                 params!() => nullary_op(|_ecx| {
                     // We have to return *some* expr, even though `CountAll`'s
@@ -1457,6 +1538,7 @@ lazy_static! {
                 params!(Any) => AggregateFunc::Any
             },
             "max" => {
+                params!(Int16) => AggregateFunc::MaxInt16,
                 params!(Int32) => AggregateFunc::MaxInt32,
                 params!(Int64) => AggregateFunc::MaxInt64,
                 params!(Float32) => AggregateFunc::MaxFloat32,
@@ -1469,6 +1551,7 @@ lazy_static! {
                 params!(TimestampTz) => AggregateFunc::MaxTimestampTz
             },
             "min" => {
+                params!(Int16) => AggregateFunc::MinInt16,
                 params!(Int32) => AggregateFunc::MinInt32,
                 params!(Int64) => AggregateFunc::MinInt64,
                 params!(Float32) => AggregateFunc::MinFloat32,
@@ -1484,6 +1567,11 @@ lazy_static! {
                 params!(JsonbAny) => AggregateFunc::JsonbAgg
             },
             "sum" => {
+                params!(Int16) => unary_op(|_ecx, e| {
+                    // Widen the sum of 16-bit integers to avoid
+                    // overflowing the narrow input type.
+                    Ok((e.call_unary(UnaryFunc::CastInt16ToInt32), AggregateFunc::SumInt32))
+                }),
                 params!(Int32) => AggregateFunc::SumInt32,
                 params!(Int64) => AggregateFunc::SumInt64,
                 params!(Float32) => AggregateFunc::SumFloat32,
@@ -1524,3 +1612,118 @@ pub fn select_aggregate_func(
 
     ArgImplementationMatcher::select_implementation(ident, func_err_string, ecx, impls, cexprs)
 }
+
+pub struct WindowFuncPlan {
+    pub func: WindowFunc,
+    pub exprs: Vec<ScalarExpr>,
+}
+
+lazy_static! {
+    /// Correlates a built-in window function name to its implementations.
+    ///
+    /// Aggregate functions may also be used as window functions; they are
+    /// planned via [`select_aggregate_func`] instead.
+    static ref BUILTIN_WINDOW_IMPLS: HashMap<&'static str, Vec<FuncImpl<WindowFuncPlan>>> = {
+        use ParamType::*;
+        use ScalarType::*;
+        impls! {
+            "dense_rank" => {
+                params!() => plan_ranking(WindowFunc::DenseRank)
+            },
+            "first_value" => {
+                params!(Any) => unary_op(|_ecx, e| {
+                    Ok(WindowFuncPlan { func: WindowFunc::FirstValue, exprs: vec![e] })
+                })
+            },
+            "lag" => {
+                params!(Any) => plan_lag_lead(true),
+                params!(Any, Int32) => plan_lag_lead(true),
+                params!(Any, Int32, Any) => plan_lag_lead(true)
+            },
+            "last_value" => {
+                params!(Any) => unary_op(|_ecx, e| {
+                    Ok(WindowFuncPlan { func: WindowFunc::LastValue, exprs: vec![e] })
+                })
+            },
+            "lead" => {
+                params!(Any) => plan_lag_lead(false),
+                params!(Any, Int32) => plan_lag_lead(false),
+                params!(Any, Int32, Any) => plan_lag_lead(false)
+            },
+            "rank" => {
+                params!() => plan_ranking(WindowFunc::Rank)
+            },
+            "row_number" => {
+                params!() => plan_ranking(WindowFunc::RowNumber)
+            }
+        }
+    };
+}
+
+fn plan_ranking(func: WindowFunc) -> Operation<WindowFuncPlan> {
+    nullary_op(move |_ecx| {
+        Ok(WindowFuncPlan {
+            func: func.clone(),
+            exprs: vec![],
+        })
+    })
+}
+
+/// Plans `lag` or `lead`, filling in the default offset of one and the default
+/// default value of `NULL`, so that the window function always receives
+/// exactly three arguments.
+fn plan_lag_lead(lag: bool) -> Operation<WindowFuncPlan> {
+    variadic_op(move |ecx, mut exprs| {
+        let value_type = ecx.scalar_type(&exprs[0]);
+        if exprs.len() < 2 {
+            exprs.push(ScalarExpr::literal(
+                Datum::Int32(1),
+                ColumnType::new(ScalarType::Int32),
+            ));
+        }
+        let default = if exprs.len() < 3 {
+            ScalarExpr::literal_null(value_type)
+        } else {
+            match exprs.pop().unwrap() {
+                ScalarExpr::Literal(row, _) if row.unpack_first().is_null() => {
+                    ScalarExpr::literal_null(value_type)
+                }
+                default => typeconv::plan_cast(
+                    if lag { "lag" } else { "lead" },
+                    ecx,
+                    default,
+                    CastTo::Implicit(value_type),
+                )?,
+            }
+        };
+        exprs.push(default);
+        Ok(WindowFuncPlan {
+            func: WindowFunc::LagLead { lag },
+            exprs,
+        })
+    })
+}
+
+pub fn is_window_func(ident: &str) -> bool {
+    BUILTIN_WINDOW_IMPLS.get(ident).is_some()
+}
+
+/// Plans a built-in window function that is not also an aggregate function.
+pub fn select_window_func(
+    ecx: &ExprContext,
+    ident: &str,
+    args: &[Expr],
+) -> Result<WindowFuncPlan, failure::Error> {
+    let impls = match BUILTIN_WINDOW_IMPLS.get(ident) {
+        Some(i) => i,
+        None => unsupported!(ident),
+    };
+
+    let mut cexprs = Vec::new();
+    for arg in args {
+        let cexpr = query::plan_expr(ecx, arg)?;
+        cexprs.push(cexpr);
+    }
+
+    ArgImplementationMatcher::select_implementation(ident, func_err_string, ecx, impls, cexprs)
+}
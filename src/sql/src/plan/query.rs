@@ -20,7 +20,7 @@
 //! To deal with this, whenever we see a SQL GROUP BY we look ahead for aggregates and precompute them in the `RelationExpr::Reduce`. When we reach the same aggregates during normal planning later on, we look them up in an `ExprContext` to find the precomputed versions.
 
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryInto;
 use std::iter;
 use std::rc::Rc;
@@ -28,22 +28,25 @@ use std::rc::Rc;
 use failure::{bail, ensure, format_err, ResultExt};
 use sql_parser::ast::visit::{self, Visit};
 use sql_parser::ast::{
-    BinaryOperator, DataType, Expr, Function, FunctionArgs, Ident, JoinConstraint, JoinOperator,
-    ObjectName, Query, Select, SelectItem, SetExpr, SetOperator, ShowStatementFilter, TableAlias,
-    TableFactor, TableWithJoins, Value, Values,
+    Assignment, BinaryOperator, ColumnDef, ColumnOption, Cte, DataType, Expr, Function,
+    FunctionArgs, Ident, JoinConstraint, JoinOperator, ObjectName, Query, Select, SelectItem,
+    SetExpr, SetOperator, ShowStatementFilter, Statement, TableAlias, TableFactor, TableWithJoins,
+    Value, Values,
 };
 
-use ::expr::{Id, RowSetFinishing};
+use ::expr::{GlobalId, Id, IdGen, LocalId, RowSetFinishing};
 use dataflow_types::Timestamp;
 use repr::adt::decimal::{Decimal, MAX_DECIMAL_PRECISION};
 use repr::{
     strconv, ColumnName, ColumnType, Datum, RelationDesc, RelationType, RowArena, ScalarType,
 };
 
+use crate::catalog::{CatalogItem, CatalogItemType};
 use crate::names::PartialName;
 use crate::plan::expr::{
     AggregateExpr, AggregateFunc, BinaryFunc, CoercibleScalarExpr, ColumnOrder, ColumnRef,
-    JoinKind, RelationExpr, ScalarExpr, ScalarTypeable, UnaryFunc, VariadicFunc,
+    JoinKind, RelationExpr, ScalarExpr, ScalarTypeable, TableFunc, UnaryFunc, VariadicFunc,
+    WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunc,
 };
 use crate::plan::func;
 use crate::plan::scope::{Scope, ScopeItem, ScopeItemName};
@@ -179,6 +182,7 @@ pub fn eval_as_of<'a>(
         ScalarType::Decimal(_, _) => {
             bail!("decimal with fractional component is not a valid timestamp")
         }
+        ScalarType::Int16 => evaled.unwrap_int16().try_into()?,
         ScalarType::Int32 => evaled.unwrap_int32().try_into()?,
         ScalarType::Int64 => evaled.unwrap_int64().try_into()?,
         ScalarType::TimestampTz => evaled.unwrap_timestamptz().timestamp_millis().try_into()?,
@@ -187,6 +191,265 @@ pub fn eval_as_of<'a>(
     })
 }
 
+/// Plans the query that produces the rows inserted by an `INSERT` statement.
+///
+/// The columns produced by `source` are matched with the columns of the table
+/// named `table_name`, either positionally or according to `columns`, and cast
+/// to the types of the table's columns. Columns of the table that are not
+/// supplied by `source` are filled with `NULL`.
+///
+/// Returns the ID of the table, the planned query, and the types of the
+/// parameters in the query.
+pub fn plan_insert_query(
+    scx: &StatementContext,
+    table_name: ObjectName,
+    columns: Vec<Ident>,
+    mut source: Query,
+) -> Result<(GlobalId, RelationExpr, Vec<ScalarType>), failure::Error> {
+    let table_name = scx.resolve_item(table_name)?;
+    let table = scx.catalog.get_item(&table_name);
+    let column_defs = match table_column_defs(table) {
+        Some(column_defs) => column_defs,
+        None => bail!(
+            "cannot insert into {} because it is a {}",
+            table_name,
+            table.item_type()
+        ),
+    };
+    let desc = table.desc()?;
+
+    transform_ast::transform_query(&mut source)?;
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let (mut expr, _scope, finishing) = plan_query(&qcx, &source)?;
+    expr.finish(finishing);
+    let typ = qcx.relation_type(&expr);
+
+    // Determine which column of the query, if any, supplies each column of
+    // the table.
+    let mut sources = vec![None; desc.arity()];
+    if columns.is_empty() {
+        if typ.arity() > desc.arity() {
+            bail!("INSERT has more expressions than target columns");
+        }
+        for (i, source) in sources.iter_mut().take(typ.arity()).enumerate() {
+            *source = Some(i);
+        }
+    } else {
+        if typ.arity() > columns.len() {
+            bail!("INSERT has more expressions than target columns");
+        } else if typ.arity() < columns.len() {
+            bail!("INSERT has more target columns than expressions");
+        }
+        for (i, column) in columns.into_iter().enumerate() {
+            let name = normalize::column_name(column);
+            let pos = match desc.iter_names().position(|n| n == Some(&name)) {
+                Some(pos) => pos,
+                None => bail!(
+                    "column \"{}\" of relation \"{}\" does not exist",
+                    name,
+                    table_name
+                ),
+            };
+            if sources[pos].replace(i).is_some() {
+                bail!("column \"{}\" specified more than once", name);
+            }
+        }
+    }
+
+    let exprs = {
+        let scope = Scope::empty(None);
+        let ecx = &ExprContext {
+            qcx: &qcx,
+            name: "INSERT",
+            scope: &scope,
+            relation_type: &typ,
+            allow_aggregates: false,
+            allow_subqueries: false,
+        };
+        let mut exprs = vec![];
+        for ((source, column_type), column_def) in sources
+            .into_iter()
+            .zip(&desc.typ().column_types)
+            .zip(&column_defs)
+        {
+            let scalar_type = column_type.scalar_type.clone();
+            exprs.push(match source {
+                Some(i) => typeconv::plan_cast(
+                    "INSERT",
+                    ecx,
+                    ScalarExpr::Column(ColumnRef {
+                        level: 0,
+                        column: i,
+                    }),
+                    CastTo::Assignment(scalar_type),
+                )?,
+                None if column_def
+                    .options
+                    .iter()
+                    .any(|o| matches!(o.option, ColumnOption::Default(_))) =>
+                {
+                    unsupported!("INSERT into columns with DEFAULT values")
+                }
+                None => ScalarExpr::literal_null(scalar_type),
+            });
+        }
+        exprs
+    };
+    let arity = typ.arity();
+    let expr = expr
+        .map(exprs)
+        .project((arity..arity + desc.arity()).collect());
+
+    let mut param_types = vec![];
+    for (i, (n, typ)) in qcx.unwrap_param_types().into_iter().enumerate() {
+        if n != i + 1 {
+            bail!("unable to infer type for parameter ${}", i + 1);
+        }
+        param_types.push(typ);
+    }
+    Ok((table.id(), expr, param_types))
+}
+
+/// Plans the query that reads the rows affected by an `UPDATE` or `DELETE`
+/// statement.
+///
+/// The returned query produces the rows of the table named `table_name` that
+/// satisfy `selection`. If `assignments` is non-empty, as it is for `UPDATE`,
+/// each row is followed by the updated version of that row, and so the query
+/// has twice as many columns as the table.
+///
+/// Returns the ID of the table, the planned query, and the types of the
+/// parameters in the query.
+pub fn plan_mutation_query(
+    scx: &StatementContext,
+    table_name: ObjectName,
+    assignments: Vec<Assignment>,
+    selection: Option<Expr>,
+) -> Result<(GlobalId, RelationExpr, Vec<ScalarType>), failure::Error> {
+    let verb = if assignments.is_empty() {
+        "delete from"
+    } else {
+        "update"
+    };
+    let table_name = scx.resolve_item(table_name)?;
+    let table = scx.catalog.get_item(&table_name);
+    if table_column_defs(table).is_none() {
+        bail!(
+            "cannot {} {} because it is a {}",
+            verb,
+            table_name,
+            table.item_type()
+        );
+    }
+    let desc = table.desc()?;
+
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let mut expr = RelationExpr::Get {
+        id: Id::Global(table.id()),
+        typ: desc.typ().clone(),
+    };
+    let scope = Scope::from_source(
+        Some(table_name.clone().into()),
+        desc.iter_names().map(|n| n.cloned()),
+        Some(Scope::empty(None)),
+    );
+
+    if let Some(mut selection) = selection {
+        transform_ast::transform_expr(&mut selection)?;
+        let ecx = &ExprContext {
+            qcx: &qcx,
+            name: "WHERE clause",
+            scope: &scope,
+            relation_type: &qcx.relation_type(&expr),
+            allow_aggregates: false,
+            allow_subqueries: true,
+        };
+        let selection = plan_expr(ecx, &selection)?.type_as(ecx, ScalarType::Bool)?;
+        expr = expr.filter(vec![selection]);
+    }
+
+    if !assignments.is_empty() {
+        let mut sets = vec![None; desc.arity()];
+        for Assignment { id, mut value } in assignments {
+            let name = normalize::column_name(id);
+            let pos = match desc.iter_names().position(|n| n == Some(&name)) {
+                Some(pos) => pos,
+                None => bail!(
+                    "column \"{}\" of relation \"{}\" does not exist",
+                    name,
+                    table_name
+                ),
+            };
+            transform_ast::transform_expr(&mut value)?;
+            if sets[pos].replace(value).is_some() {
+                bail!("multiple assignments to same column \"{}\"", name);
+            }
+        }
+
+        let typ = qcx.relation_type(&expr);
+        let ecx = &ExprContext {
+            qcx: &qcx,
+            name: "UPDATE",
+            scope: &scope,
+            relation_type: &typ,
+            allow_aggregates: false,
+            allow_subqueries: true,
+        };
+        let mut exprs = vec![];
+        for (i, (set, column_type)) in sets.into_iter().zip(&typ.column_types).enumerate() {
+            exprs.push(match set {
+                Some(value) => {
+                    let scalar_type = column_type.scalar_type.clone();
+                    let value = plan_expr(ecx, &value)?;
+                    // Coerce literals to the unconstrained version of the
+                    // column's type, so that the assignment cast below checks
+                    // the length of string literals rather than truncating
+                    // them.
+                    let coerce_to = match scalar_type {
+                        ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+                            scalar_type.desaturate()
+                        }
+                        _ => scalar_type.clone(),
+                    };
+                    let value = typeconv::plan_coerce(ecx, value, CoerceTo::Plain(coerce_to))?;
+                    typeconv::plan_cast("UPDATE", ecx, value, CastTo::Assignment(scalar_type))?
+                }
+                None => ScalarExpr::Column(ColumnRef {
+                    level: 0,
+                    column: i,
+                }),
+            });
+        }
+        expr = expr.map(exprs);
+    }
+
+    let mut param_types = vec![];
+    for (i, (n, typ)) in qcx.unwrap_param_types().into_iter().enumerate() {
+        if n != i + 1 {
+            bail!("unable to infer type for parameter ${}", i + 1);
+        }
+        param_types.push(typ);
+    }
+    Ok((table.id(), expr, param_types))
+}
+
+/// Returns the column definitions of `item`, if `item` is a table.
+///
+/// Tables are represented in the catalog as sources, and are distinguished
+/// only by the `CREATE TABLE` statement that defines them.
+pub fn table_column_defs(item: &dyn CatalogItem) -> Option<Vec<ColumnDef>> {
+    if item.item_type() != CatalogItemType::Source {
+        return None;
+    }
+    match crate::parse::parse(item.create_sql().to_owned()) {
+        Ok(stmts) => match stmts.into_iter().next() {
+            Some(Statement::CreateTable { columns, .. }) => Some(columns),
+            _ => None,
+        },
+        Err(_) => None,
+    }
+}
+
 pub fn plan_index_exprs<'a>(
     scx: &'a StatementContext,
     on_desc: &RelationDesc,
@@ -249,9 +512,28 @@ fn plan_query(
     qcx: &QueryContext,
     q: &Query,
 ) -> Result<(RelationExpr, Scope, RowSetFinishing), failure::Error> {
-    if !q.ctes.is_empty() {
-        unsupported!(2617, "CTEs");
+    let mut cte_qcx = qcx.clone();
+    let mut bindings = vec![];
+    let mut names = HashSet::new();
+    for cte in &q.ctes {
+        let name = normalize::ident(cte.alias.name.clone());
+        if !names.insert(name.clone()) {
+            bail!("WITH query name \"{}\" specified more than once", name);
+        }
+        let (value, column_names) = plan_cte(&cte_qcx, q.recursive, &name, cte)?;
+        let id = cte_qcx.allocate_local_id();
+        cte_qcx.ctes.insert(
+            name,
+            CteDesc {
+                id,
+                typ: cte_qcx.relation_type(&value),
+                column_names,
+            },
+        );
+        bindings.push((id, value));
     }
+    let qcx = &cte_qcx;
+
     let limit = match &q.limit {
         None => None,
         Some(Expr::Value(Value::Number(x))) => Some(x.parse()?),
@@ -305,7 +587,124 @@ fn plan_query(
         project: (0..output_typ.column_types.len()).collect(),
         offset,
     };
-    Ok((expr.map(map_exprs), scope, finishing))
+    let mut expr = expr.map(map_exprs);
+    for (id, value) in bindings.into_iter().rev() {
+        expr = RelationExpr::Let {
+            id,
+            value: Box::new(value),
+            body: Box::new(expr),
+        };
+    }
+    Ok((expr, scope, finishing))
+}
+
+/// Plans the definition of the common table expression `cte`, which is bound
+/// to `name`, returning its plan and the names of its columns.
+///
+/// If `recursive` is set and the definition is of the form
+/// `non_recursive_term UNION [ALL] recursive_term`, the recursive term may
+/// refer to the CTE itself, and the plan computes its fixed point.
+fn plan_cte(
+    qcx: &QueryContext,
+    recursive: bool,
+    name: &str,
+    cte: &Cte,
+) -> Result<(RelationExpr, Vec<Option<ColumnName>>), failure::Error> {
+    // The definition is evaluated only once, and so cannot refer to the
+    // columns of any outer query.
+    let mut qcx = qcx.clone();
+    qcx.outer_scope = Scope::empty(None);
+    qcx.outer_relation_types = vec![];
+
+    fn column_names(
+        qcx: &QueryContext,
+        cte: &Cte,
+        scope: &Scope,
+    ) -> Result<Vec<Option<ColumnName>>, failure::Error> {
+        let column_names = scope.column_names().map(|n| n.cloned()).collect();
+        let scope = plan_table_alias(qcx, Some(&cte.alias), None, column_names)?;
+        Ok(scope.column_names().map(|n| n.cloned()).collect())
+    }
+
+    match &cte.query.body {
+        SetExpr::SetOperation {
+            op: SetOperator::Union,
+            all,
+            left,
+            right,
+        } if recursive => {
+            if qcx.in_recursive_cte {
+                unsupported!("nested recursive CTEs");
+            }
+            if !cte.query.ctes.is_empty()
+                || !cte.query.order_by.is_empty()
+                || cte.query.limit.is_some()
+                || cte.query.offset.is_some()
+            {
+                unsupported!("WITH, ORDER BY, LIMIT, or OFFSET in a recursive query");
+            }
+            qcx.in_recursive_cte = true;
+
+            let (left_expr, left_scope) = plan_set_expr(&qcx, left)?;
+            let column_names = column_names(&qcx, cte, &left_scope)?;
+            let left_types = qcx.relation_type(&left_expr).column_types;
+
+            // Make the CTE visible to the recursive term. Its columns take the
+            // types of the non-recursive term, but may become null.
+            let id = qcx.allocate_local_id();
+            qcx.ctes.insert(
+                name.to_owned(),
+                CteDesc {
+                    id,
+                    typ: RelationType::new(
+                        left_types
+                            .iter()
+                            .map(|t| t.clone().nullable(true))
+                            .collect(),
+                    ),
+                    column_names: column_names.clone(),
+                },
+            );
+            let (right_expr, _right_scope) = plan_set_expr(&qcx, right)?;
+
+            let right_types = qcx.relation_type(&right_expr).column_types;
+            if left_types.len() != right_types.len() {
+                bail!(
+                    "each UNION query must have the same number of columns: {} vs {}",
+                    left_types.len(),
+                    right_types.len(),
+                );
+            }
+            for (i, (left_col_type, right_col_type)) in
+                left_types.iter().zip(right_types.iter()).enumerate()
+            {
+                if left_col_type.scalar_type != right_col_type.scalar_type {
+                    bail!(
+                        "recursive query \"{}\" column {} has type {} in non-recursive term \
+                         but type {} overall",
+                        name,
+                        i + 1,
+                        left_col_type.scalar_type,
+                        right_col_type.scalar_type,
+                    );
+                }
+            }
+
+            let mut body = left_expr.union(right_expr);
+            if !*all {
+                body = body.distinct();
+            }
+            let expr = RelationExpr::Iterate {
+                id,
+                body: Box::new(body),
+            };
+            Ok((expr, column_names))
+        }
+        _ => {
+            let (expr, scope) = plan_subquery(&qcx, &cte.query)?;
+            Ok((expr, column_names(&qcx, cte, &scope)?))
+        }
+    }
 }
 
 fn plan_subquery(qcx: &QueryContext, q: &Query) -> Result<(RelationExpr, Scope), failure::Error> {
@@ -503,7 +902,7 @@ fn plan_view_select(
     }
 
     // Step 3. Handle GROUP BY clause.
-    let (group_scope, select_all_mapping) = {
+    let (mut group_scope, select_all_mapping) = {
         // gather group columns
         let ecx = &ExprContext {
             qcx,
@@ -605,7 +1004,26 @@ fn plan_view_select(
         relation_expr = relation_expr.filter(vec![expr]);
     }
 
-    // Step 5. Handle projections.
+    // Step 5. Handle window functions.
+    {
+        let mut window_visitor = WindowFuncVisitor::new();
+        for p in &s.projection {
+            window_visitor.visit_select_item(p);
+        }
+        for sql_function in window_visitor.into_result()? {
+            relation_expr = plan_window_function(qcx, relation_expr, &group_scope, sql_function)?;
+            group_scope.items.push(ScopeItem {
+                names: vec![ScopeItemName {
+                    table_name: None,
+                    column_name: Some(sql_function.name.to_string().into()),
+                }],
+                expr: Some(Expr::Function(sql_function.clone())),
+                nameable: true,
+            });
+        }
+    }
+
+    // Step 6. Handle projections.
     let project_scope = {
         let mut project_exprs = vec![];
         let mut project_key = vec![];
@@ -633,7 +1051,7 @@ fn plan_view_select(
         project_scope
     };
 
-    // Step 6. Handle DISTINCT.
+    // Step 7. Handle DISTINCT.
     if s.distinct {
         relation_expr = relation_expr.distinct();
     }
@@ -690,7 +1108,42 @@ fn plan_table_factor<'a>(
                     allow_aggregates: false,
                     allow_subqueries: true,
                 };
-                plan_table_function(ecx, left, &name, alias.as_ref(), args)
+                if let JoinOperator::CrossJoin = join_operator {
+                    return plan_table_function(ecx, left, &name, alias.as_ref(), args);
+                }
+                // Table functions may always refer to the columns of the
+                // tables that precede them, so for any other join operator
+                // they must be planned as the right input of a lateral join.
+                let lateral_qcx = ecx.derived_query_context();
+                let (identity, identity_scope) = plan_join_identity(&lateral_qcx);
+                let ecx = &ExprContext {
+                    qcx: &lateral_qcx,
+                    name: "FROM table function",
+                    scope: &identity_scope,
+                    relation_type: &qcx.relation_type(&identity),
+                    allow_aggregates: false,
+                    allow_subqueries: true,
+                };
+                let (expr, scope) =
+                    plan_table_function(ecx, identity, &name, alias.as_ref(), args)?;
+                let scope = Scope {
+                    items: scope.items,
+                    outer_scope: Some(Box::new(qcx.outer_scope.clone())),
+                };
+                plan_join_operator(qcx, &join_operator, left, left_scope, expr, scope, true)
+            } else if let Some((cte_name, cte)) = qcx.resolve_cte(name) {
+                let expr = RelationExpr::Get {
+                    id: Id::Local(cte.id),
+                    typ: cte.typ.clone(),
+                };
+                let name = PartialName {
+                    database: None,
+                    schema: None,
+                    item: cte_name,
+                };
+                let scope =
+                    plan_table_alias(qcx, alias.as_ref(), Some(name), cte.column_names.clone())?;
+                plan_join_operator(qcx, &join_operator, left, left_scope, expr, scope, false)
             } else {
                 let name = qcx.scx.resolve_item(name.clone())?;
                 let item = qcx.scx.catalog.get_item(&name);
@@ -700,7 +1153,7 @@ fn plan_table_factor<'a>(
                 };
                 let column_names = item.desc()?.iter_names().map(|n| n.cloned()).collect();
                 let scope = plan_table_alias(qcx, alias.as_ref(), Some(name.into()), column_names)?;
-                plan_join_operator(qcx, &join_operator, left, left_scope, expr, scope)
+                plan_join_operator(qcx, &join_operator, left, left_scope, expr, scope, false)
             }
         }
         TableFactor::Derived {
@@ -708,14 +1161,30 @@ fn plan_table_factor<'a>(
             subquery,
             alias,
         } => {
-            if *lateral {
-                unsupported!(3111, "LATERAL derived tables");
-            }
-            let (expr, scope) = plan_subquery(&qcx, &subquery)?;
+            // The MSSQL-specific APPLY operators are equivalent to lateral
+            // joins.
+            let lateral = *lateral
+                || matches!(
+                    join_operator,
+                    JoinOperator::CrossApply | JoinOperator::OuterApply
+                );
+            let (expr, scope) = if lateral {
+                let ecx = &ExprContext {
+                    qcx,
+                    name: "LATERAL subquery",
+                    scope: &left_scope,
+                    relation_type: &qcx.relation_type(&left),
+                    allow_aggregates: false,
+                    allow_subqueries: true,
+                };
+                plan_subquery(&ecx.derived_query_context(), &subquery)?
+            } else {
+                plan_subquery(&qcx, &subquery)?
+            };
             let table_name = None;
             let column_names = scope.column_names().map(|n| n.cloned()).collect();
             let scope = plan_table_alias(qcx, alias.as_ref(), table_name, column_names)?;
-            plan_join_operator(qcx, &join_operator, left, left_scope, expr, scope)
+            plan_join_operator(qcx, &join_operator, left, left_scope, expr, scope, lateral)
         }
         TableFactor::NestedJoin(table_with_joins) => {
             let (identity, identity_scope) = plan_join_identity(qcx);
@@ -726,7 +1195,7 @@ fn plan_table_factor<'a>(
                 &JoinOperator::CrossJoin,
                 table_with_joins,
             )?;
-            plan_join_operator(qcx, &join_operator, left, left_scope, expr, scope)
+            plan_join_operator(qcx, &join_operator, left, left_scope, expr, scope, false)
         }
     }
 }
@@ -820,6 +1289,7 @@ fn invent_column_name(expr: &Expr) -> Option<ScopeItemName> {
             .map(|n| normalize::column_name(n.clone())),
         Expr::Coalesce { .. } => Some("coalesce".into()),
         Expr::List { .. } => Some("list".into()),
+        Expr::Array { .. } => Some("array".into()),
         Expr::Cast { expr, .. } => return invent_column_name(expr),
         Expr::FieldAccess { field, .. } => Some(normalize::column_name(field.clone())),
         _ => return None,
@@ -941,51 +1411,33 @@ fn plan_join_operator(
     left_scope: Scope,
     right: RelationExpr,
     right_scope: Scope,
+    lateral: bool,
 ) -> Result<(RelationExpr, Scope), failure::Error> {
-    match operator {
-        JoinOperator::Inner(constraint) => plan_join_constraint(
-            qcx,
-            &constraint,
-            left,
-            left_scope,
-            right,
-            right_scope,
-            JoinKind::Inner,
-        ),
-        JoinOperator::LeftOuter(constraint) => plan_join_constraint(
-            qcx,
-            &constraint,
-            left,
-            left_scope,
-            right,
-            right_scope,
-            JoinKind::LeftOuter,
-        ),
-        JoinOperator::RightOuter(constraint) => plan_join_constraint(
-            qcx,
-            &constraint,
-            left,
-            left_scope,
-            right,
-            right_scope,
-            JoinKind::RightOuter,
-        ),
-        JoinOperator::FullOuter(constraint) => plan_join_constraint(
-            qcx,
-            &constraint,
-            left,
-            left_scope,
-            right,
-            right_scope,
-            JoinKind::FullOuter,
-        ),
-        JoinOperator::CrossJoin => Ok((left.product(right), left_scope.product(right_scope))),
-        // The remaining join types are MSSQL-specific. We are unlikely to
-        // ever support them. The standard SQL equivalent is LATERAL, which
-        // we are not capable of even parsing at the moment.
-        JoinOperator::CrossApply => unsupported!("CROSS APPLY"),
-        JoinOperator::OuterApply => unsupported!("OUTER APPLY"),
+    let on_true = JoinConstraint::On(Expr::Value(Value::Boolean(true)));
+    let (constraint, kind) = match operator {
+        JoinOperator::Inner(constraint) => (constraint, JoinKind::Inner),
+        JoinOperator::LeftOuter(constraint) => (constraint, JoinKind::LeftOuter),
+        JoinOperator::RightOuter(constraint) => (constraint, JoinKind::RightOuter),
+        JoinOperator::FullOuter(constraint) => (constraint, JoinKind::FullOuter),
+        JoinOperator::CrossJoin | JoinOperator::CrossApply if !lateral => {
+            return Ok((left.product(right), left_scope.product(right_scope)));
+        }
+        JoinOperator::CrossJoin | JoinOperator::CrossApply => (&on_true, JoinKind::Inner),
+        JoinOperator::OuterApply => (&on_true, JoinKind::LeftOuter),
+    };
+    if lateral && (kind == JoinKind::RightOuter || kind == JoinKind::FullOuter) {
+        bail!("the combining JOIN type must be INNER or LEFT for a LATERAL reference");
     }
+    plan_join_constraint(
+        qcx,
+        constraint,
+        left,
+        left_scope,
+        right,
+        right_scope,
+        kind,
+        lateral,
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -997,6 +1449,7 @@ fn plan_join_constraint<'a>(
     right: RelationExpr,
     right_scope: Scope,
     kind: JoinKind,
+    lateral: bool,
 ) -> Result<(RelationExpr, Scope), failure::Error> {
     let (expr, scope) = match constraint {
         JoinConstraint::On(expr) => {
@@ -1009,7 +1462,7 @@ fn plan_join_constraint<'a>(
                     qcx.relation_type(&left)
                         .column_types
                         .into_iter()
-                        .chain(qcx.relation_type(&right).column_types)
+                        .chain(qcx.join_right_type(&left, &right, lateral).column_types)
                         .collect(),
                 ),
                 allow_aggregates: false,
@@ -1041,6 +1494,7 @@ fn plan_join_constraint<'a>(
                 right: Box::new(right),
                 on,
                 kind,
+                lateral,
             };
             (joined, product_scope)
         }
@@ -1055,6 +1509,7 @@ fn plan_join_constraint<'a>(
             right,
             right_scope,
             kind,
+            lateral,
         )?,
         JoinConstraint::Natural => {
             let mut column_names = vec![];
@@ -1078,6 +1533,7 @@ fn plan_join_constraint<'a>(
                 right,
                 right_scope,
                 kind,
+                lateral,
             )?
         }
     };
@@ -1094,6 +1550,7 @@ fn plan_using_constraint(
     right: RelationExpr,
     right_scope: Scope,
     kind: JoinKind,
+    lateral: bool,
 ) -> Result<(RelationExpr, Scope), failure::Error> {
     let mut join_exprs = vec![];
     let mut map_exprs = vec![];
@@ -1123,7 +1580,7 @@ fn plan_using_constraint(
             ),
         };
         let l_type = &qcx.relation_type(&left).column_types[l];
-        let r_type = &qcx.relation_type(&right).column_types[r];
+        let r_type = &qcx.join_right_type(&left, &right, lateral).column_types[r];
         if l_type.scalar_type != r_type.scalar_type {
             bail!(
                 "{:?} and {:?} are not comparable (in NATURAL/USING join on {})",
@@ -1192,6 +1649,7 @@ fn plan_using_constraint(
                 }
             }),
         kind,
+        lateral,
     }
     .map(map_exprs)
     .project(project_key);
@@ -1235,6 +1693,13 @@ pub fn plan_expr<'a>(
             }
             CoercibleScalarExpr::LiteralList(out)
         }
+        Expr::Array(exprs) => {
+            let mut out = vec![];
+            for e in exprs {
+                out.push(plan_expr(ecx, e)?);
+            }
+            CoercibleScalarExpr::LiteralArray(out)
+        }
         Expr::Row { exprs } => {
             let mut out = vec![];
             for e in exprs {
@@ -1289,6 +1754,25 @@ pub fn plan_expr<'a>(
             }
         }
         Expr::WildcardAccess(expr) => plan_expr(ecx, expr)?,
+        Expr::SubscriptIndex { expr, subscript } => {
+            let expr = plan_expr(ecx, expr)?.type_as_any(ecx)?;
+            let ty = ecx.scalar_type(&expr);
+            if !matches!(ty, ScalarType::Array(_)) {
+                bail!("cannot subscript type {}", ty);
+            }
+            let subscript = typeconv::plan_coerce(
+                ecx,
+                plan_expr(ecx, subscript)?,
+                CoerceTo::Plain(ScalarType::Int64),
+            )?;
+            let subscript = typeconv::plan_cast(
+                "array subscript",
+                ecx,
+                subscript,
+                CastTo::Implicit(ScalarType::Int64),
+            )?;
+            expr.call_binary(subscript, BinaryFunc::ArrayIndex).into()
+        }
 
         // Subqueries.
         Expr::Exists(query) => {
@@ -1322,6 +1806,7 @@ pub fn plan_expr<'a>(
         Expr::Any { .. } => unreachable!("Expr::Any not desugared"),
         Expr::All { .. } => unreachable!("Expr::All not desugared"),
         Expr::Between { .. } => unreachable!("Expr::Between not desugared"),
+        Expr::AtTimeZone { .. } => unreachable!("Expr::AtTimeZone not desugared"),
     })
 }
 
@@ -1381,10 +1866,6 @@ fn plan_aggregate(ecx: &ExprContext, sql_func: &Function) -> Result<AggregateExp
     let name = normalize::function_name(sql_func.name.clone())?;
     assert!(func::is_aggregate_func(&name));
 
-    if sql_func.over.is_some() {
-        unsupported!(213, "window functions");
-    }
-
     // We follow PostgreSQL's rule here for mapping `count(*)` into the
     // generalized function selection framework. The rule is simple: the user
     // must type `count(*)`, but the function selection framework sees an empty
@@ -1501,6 +1982,12 @@ fn plan_function<'a>(
     let name = normalize::function_name(sql_func.name.clone())?;
     let ident = &*name;
 
+    if sql_func.over.is_some() {
+        // Window functions in the SELECT list should already have been caught
+        // by `scope.resolve_expr` in `plan_expr`.
+        bail!("window functions are not allowed in {}", ecx.name);
+    }
+
     if func::is_aggregate_func(&name) {
         if ecx.allow_aggregates {
             // should already have been caught by `scope.resolve_expr` in `plan_expr`
@@ -1518,8 +2005,8 @@ fn plan_function<'a>(
         );
     }
 
-    if sql_func.over.is_some() {
-        unsupported!(213, "window functions");
+    if func::is_window_func(&name) {
+        bail!("window function {} requires an OVER clause", ident);
     }
     if sql_func.filter.is_some() {
         bail!(
@@ -1683,10 +2170,28 @@ pub fn scalar_type_from_sql(data_type: &DataType) -> Result<ScalarType, failure:
     // NOTE this needs to stay in sync with symbiosis::push_column
     Ok(match data_type {
         DataType::Boolean => ScalarType::Bool,
-        DataType::Char(_) | DataType::Varchar(_) | DataType::Text => ScalarType::String,
-        DataType::SmallInt | DataType::Int => ScalarType::Int32,
+        DataType::Char(length) => ScalarType::Char {
+            length: Some(validate_char_length("character", length.unwrap_or(1))?),
+        },
+        DataType::Varchar(length) => ScalarType::VarChar {
+            length: match length {
+                Some(length) => Some(validate_char_length("character varying", *length)?),
+                None => None,
+            },
+        },
+        DataType::Text => ScalarType::String,
+        DataType::SmallInt => ScalarType::Int16,
+        DataType::Int => ScalarType::Int32,
         DataType::BigInt => ScalarType::Int64,
-        DataType::Float(_) | DataType::Real | DataType::Double => ScalarType::Float64,
+        DataType::Real => ScalarType::Float32,
+        DataType::Double => ScalarType::Float64,
+        DataType::Float(precision) => match precision {
+            None => ScalarType::Float64,
+            Some(0) => bail!("precision for type float must be at least 1 bit"),
+            Some(p) if *p <= 24 => ScalarType::Float32,
+            Some(p) if *p <= 53 => ScalarType::Float64,
+            Some(_) => bail!("precision for type float must be less than 54 bits"),
+        },
         DataType::Decimal(precision, scale) => {
             let precision = precision.unwrap_or(MAX_DECIMAL_PRECISION.into());
             let scale = scale.unwrap_or(0);
@@ -1709,17 +2214,38 @@ pub fn scalar_type_from_sql(data_type: &DataType) -> Result<ScalarType, failure:
         DataType::Interval => ScalarType::Interval,
         DataType::Bytea => ScalarType::Bytes,
         DataType::Jsonb => ScalarType::Jsonb,
+        DataType::Uuid => ScalarType::Uuid,
         DataType::List(elem_type) => ScalarType::List(Box::new(scalar_type_from_sql(elem_type)?)),
+        DataType::Array(elem_type) => match scalar_type_from_sql(elem_type)? {
+            ScalarType::Array(_) => unsupported!("multidimensional arrays"),
+            ScalarType::List(_) => unsupported!("arrays of lists"),
+            elem_type => ScalarType::Array(Box::new(elem_type)),
+        },
         other @ DataType::Binary(..)
         | other @ DataType::Blob(_)
         | other @ DataType::Clob(_)
         | other @ DataType::Regclass
         | other @ DataType::TimeTz
-        | other @ DataType::Uuid
         | other @ DataType::Varbinary(_) => bail!("Unexpected SQL type: {:?}", other),
     })
 }
 
+/// The maximum length of a length-constrained string type, as in PostgreSQL.
+const MAX_CHAR_LENGTH: u64 = 10_485_760;
+
+fn validate_char_length(type_name: &str, length: u64) -> Result<usize, failure::Error> {
+    if length == 0 {
+        bail!("length for type {} must be at least 1", type_name);
+    } else if length > MAX_CHAR_LENGTH {
+        bail!(
+            "length for type {} cannot exceed {}",
+            type_name,
+            MAX_CHAR_LENGTH
+        );
+    }
+    Ok(length as usize)
+}
+
 /// This is used to collect aggregates from within an `Expr`.
 /// See the explanation of aggregate handling at the top of the file for more details.
 struct AggregateFuncVisitor<'ast> {
@@ -1757,7 +2283,9 @@ impl<'ast> AggregateFuncVisitor<'ast> {
 impl<'ast> Visit<'ast> for AggregateFuncVisitor<'ast> {
     fn visit_function(&mut self, func: &'ast Function) {
         if let Ok(name) = normalize::function_name(func.name.clone()) {
-            if func::is_aggregate_func(&name) {
+            // Aggregate functions with an OVER clause are window functions,
+            // which are planned after the aggregation.
+            if func::is_aggregate_func(&name) && func.over.is_none() {
                 if self.within_aggregate {
                     self.err = Some(format_err!("nested aggregate functions are not allowed"));
                     return;
@@ -1788,6 +2316,253 @@ impl<'ast> Visit<'ast> for AggregateFuncVisitor<'ast> {
     }
 }
 
+/// This is used to collect window functions from within an `Expr`.
+struct WindowFuncVisitor<'ast> {
+    funcs: Vec<&'ast Function>,
+    within_window: bool,
+    err: Option<failure::Error>,
+}
+
+impl<'ast> WindowFuncVisitor<'ast> {
+    fn new() -> WindowFuncVisitor<'ast> {
+        WindowFuncVisitor {
+            funcs: Vec::new(),
+            within_window: false,
+            err: None,
+        }
+    }
+
+    fn into_result(self) -> Result<Vec<&'ast Function>, failure::Error> {
+        match self.err {
+            Some(err) => Err(err),
+            None => {
+                // dedup window functions while preserving the order, as with
+                // aggregates
+                let mut seen = HashSet::new();
+                Ok(self
+                    .funcs
+                    .into_iter()
+                    .filter(move |func| seen.insert(&**func))
+                    .collect())
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for WindowFuncVisitor<'ast> {
+    fn visit_function(&mut self, func: &'ast Function) {
+        if func.over.is_some() {
+            if self.within_window {
+                self.err = Some(format_err!("window function calls cannot be nested"));
+                return;
+            }
+            self.funcs.push(func);
+            let old_within_window = self.within_window;
+            self.within_window = true;
+            visit::visit_function(self, func);
+            self.within_window = old_within_window;
+            return;
+        }
+        visit::visit_function(self, func);
+    }
+
+    fn visit_query(&mut self, _query: &'ast Query) {
+        // Don't go into subqueries.
+    }
+}
+
+/// Plans a window function call over `input`, whose columns are described by
+/// `scope`.
+///
+/// The result has all the columns of `input`, followed by one column
+/// containing the value of the window function for that row. The window
+/// function is computed by a reduction keyed on the `PARTITION BY`
+/// expressions, whose single aggregate gathers every row of the partition into
+/// a list alongside that row's value. The list is then unnested to recover the
+/// original rows.
+fn plan_window_function(
+    qcx: &QueryContext,
+    input: RelationExpr,
+    scope: &Scope,
+    sql_func: &Function,
+) -> Result<RelationExpr, failure::Error> {
+    let name = normalize::function_name(sql_func.name.clone())?;
+    let window_spec = sql_func.over.as_ref().unwrap();
+    let relation_type = qcx.relation_type(&input);
+    let ecx = &ExprContext {
+        qcx,
+        name: "window function",
+        scope,
+        relation_type: &relation_type,
+        allow_aggregates: true,
+        allow_subqueries: true,
+    };
+
+    let (func, args) = if func::is_aggregate_func(&name) {
+        if sql_func.distinct {
+            unsupported!("DISTINCT in window functions");
+        }
+        let aggregate = plan_aggregate(ecx, sql_func)?;
+        (
+            WindowFunc::Aggregate(Box::new(aggregate.func)),
+            vec![*aggregate.expr],
+        )
+    } else if func::is_window_func(&name) {
+        if sql_func.filter.is_some() {
+            bail!("FILTER is not implemented for non-aggregate window functions");
+        }
+        let args = match &sql_func.args {
+            FunctionArgs::Star => {
+                bail!("* argument is invalid with non-aggregate function {}", name)
+            }
+            FunctionArgs::Args(args) => args,
+        };
+        let plan = func::select_window_func(ecx, &name, args)?;
+        (plan.func, plan.exprs)
+    } else {
+        bail!(
+            "OVER specified, but {} is not a window function nor an aggregate function",
+            name
+        );
+    };
+
+    let mut partition_exprs = vec![];
+    for expr in &window_spec.partition_by {
+        partition_exprs.push(plan_expr(ecx, expr)?.type_as_any(ecx)?);
+    }
+    let mut order_exprs = vec![];
+    let mut order_by = vec![];
+    for (i, obe) in window_spec.order_by.iter().enumerate() {
+        order_exprs.push(plan_expr(ecx, &obe.expr)?.type_as_any(ecx)?);
+        order_by.push(ColumnOrder {
+            column: i,
+            desc: !obe.asc.unwrap_or(true),
+        });
+    }
+    let frame = match &window_spec.window_frame {
+        Some(frame) => plan_window_frame(frame)?,
+        None => WindowFrame::default(),
+    };
+
+    let arity = relation_type.arity();
+    let row = record_create(
+        (0..arity)
+            .map(|column| ScalarExpr::Column(ColumnRef { level: 0, column }))
+            .collect(),
+    );
+    let expr = ScalarExpr::CallVariadic {
+        func: VariadicFunc::RecordCreate {
+            field_names: vec!["row".into(), "order".into(), "args".into()],
+        },
+        exprs: vec![row, record_create(order_exprs), record_create(args)],
+    };
+    let func = AggregateFunc::Window {
+        func,
+        order_by,
+        frame,
+    };
+    let el_typ = match func
+        .output_type(ColumnType::new(ecx.scalar_type(&expr)))
+        .scalar_type
+    {
+        ScalarType::List(el_typ) => *el_typ,
+        _ => unreachable!("window functions produce lists"),
+    };
+
+    // Each partition is reduced to a single list, which is recomputed in full
+    // whenever any row in the partition changes. Without a `PARTITION BY`,
+    // the entire input is one partition, and so is packed into one row and
+    // processed by a single worker.
+    let num_keys = partition_exprs.len();
+    let group_key = (arity..arity + num_keys).collect();
+    let reduced = input.map(partition_exprs).reduce(
+        group_key,
+        vec![AggregateExpr {
+            func,
+            expr: Box::new(expr),
+            distinct: false,
+        }],
+    );
+    let unnested = RelationExpr::FlatMap {
+        input: Box::new(reduced),
+        func: TableFunc::UnnestList { el_typ },
+        exprs: vec![ScalarExpr::Column(ColumnRef {
+            level: 0,
+            column: num_keys,
+        })],
+    };
+
+    // Unpack the original row and the window function's value from each list
+    // element.
+    let el = ScalarExpr::Column(ColumnRef {
+        level: 0,
+        column: num_keys + 1,
+    });
+    let mut exprs: Vec<_> = (0..arity)
+        .map(|i| {
+            el.clone()
+                .call_unary(UnaryFunc::RecordGet(0))
+                .call_unary(UnaryFunc::RecordGet(i))
+        })
+        .collect();
+    exprs.push(el.call_unary(UnaryFunc::RecordGet(1)));
+    let start = num_keys + 2;
+    Ok(unnested
+        .map(exprs)
+        .project((start..start + arity + 1).collect()))
+}
+
+fn plan_window_frame(frame: &sql_parser::ast::WindowFrame) -> Result<WindowFrame, failure::Error> {
+    use sql_parser::ast::{WindowFrameBound as AstBound, WindowFrameUnits as AstUnits};
+
+    let units = match frame.units {
+        AstUnits::Rows => WindowFrameUnits::Rows,
+        AstUnits::Range => WindowFrameUnits::Range,
+        AstUnits::Groups => unsupported!("GROUPS window frames"),
+    };
+    let plan_bound = |bound: &AstBound| match bound {
+        AstBound::CurrentRow => WindowFrameBound::CurrentRow,
+        AstBound::Preceding(None) => WindowFrameBound::UnboundedPreceding,
+        AstBound::Preceding(Some(n)) => WindowFrameBound::OffsetPreceding(*n),
+        AstBound::Following(None) => WindowFrameBound::UnboundedFollowing,
+        AstBound::Following(Some(n)) => WindowFrameBound::OffsetFollowing(*n),
+    };
+    let start_bound = plan_bound(&frame.start_bound);
+    let end_bound = match &frame.end_bound {
+        Some(bound) => plan_bound(bound),
+        None => WindowFrameBound::CurrentRow,
+    };
+    if let WindowFrameBound::UnboundedFollowing = start_bound {
+        bail!("frame start cannot be UNBOUNDED FOLLOWING");
+    }
+    if let WindowFrameBound::UnboundedPreceding = end_bound {
+        bail!("frame end cannot be UNBOUNDED PRECEDING");
+    }
+    let has_offset = |bound: &WindowFrameBound| match bound {
+        WindowFrameBound::OffsetPreceding(_) | WindowFrameBound::OffsetFollowing(_) => true,
+        _ => false,
+    };
+    if units == WindowFrameUnits::Range && (has_offset(&start_bound) || has_offset(&end_bound)) {
+        unsupported!("RANGE window frames with offsets");
+    }
+    Ok(WindowFrame {
+        units,
+        start_bound,
+        end_bound,
+    })
+}
+
+fn record_create(exprs: Vec<ScalarExpr>) -> ScalarExpr {
+    ScalarExpr::CallVariadic {
+        func: VariadicFunc::RecordCreate {
+            field_names: (0..exprs.len())
+                .map(|i| ColumnName::from(format!("f{}", i + 1)))
+                .collect(),
+        },
+        exprs,
+    }
+}
+
 /// Specifies how long a query will live. This impacts whether the query is
 /// allowed to reason about the time at which it is running, e.g., by calling
 /// the `now()` function.
@@ -1799,8 +2574,19 @@ pub enum QueryLifetime {
     Static,
 }
 
+/// A common table expression that is in scope.
+#[derive(Debug, Clone)]
+pub struct CteDesc {
+    /// The identifier to which the CTE is bound.
+    pub id: LocalId,
+    /// The type of the CTE.
+    pub typ: RelationType,
+    /// The names of the columns of the CTE.
+    pub column_names: Vec<Option<ColumnName>>,
+}
+
 /// The state required when planning a `Query`.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct QueryContext<'a> {
     /// The context for the containing `Statement`.
     pub scx: &'a StatementContext<'a>,
@@ -1813,6 +2599,12 @@ pub struct QueryContext<'a> {
     /// The types of the parameters in the query. This is filled in as planning
     /// occurs.
     pub param_types: Rc<RefCell<BTreeMap<usize, ScalarType>>>,
+    /// The common table expressions in scope, by name.
+    pub ctes: HashMap<String, CteDesc>,
+    /// Whether the query is part of the definition of a recursive CTE.
+    pub in_recursive_cte: bool,
+    /// Allocates the identifiers to which CTEs are bound.
+    pub id_gen: Rc<RefCell<IdGen>>,
 }
 
 impl<'a> QueryContext<'a> {
@@ -1823,6 +2615,9 @@ impl<'a> QueryContext<'a> {
             outer_scope: Scope::empty(None),
             outer_relation_types: vec![],
             param_types: Rc::new(RefCell::new(BTreeMap::new())),
+            ctes: HashMap::new(),
+            in_recursive_cte: false,
+            id_gen: Rc::new(RefCell::new(IdGen::default())),
         }
     }
 
@@ -1833,6 +2628,41 @@ impl<'a> QueryContext<'a> {
     fn relation_type(&self, expr: &RelationExpr) -> RelationType {
         expr.typ(&self.outer_relation_types, &self.param_types.borrow())
     }
+
+    /// Computes the type of `right`, the right input of a join with `left`.
+    ///
+    /// If the join is lateral, `right` may refer to the columns of `left`.
+    fn join_right_type(
+        &self,
+        left: &RelationExpr,
+        right: &RelationExpr,
+        lateral: bool,
+    ) -> RelationType {
+        if lateral {
+            let mut outers = self.outer_relation_types.clone();
+            outers.push(self.relation_type(left));
+            right.typ(&outers, &self.param_types.borrow())
+        } else {
+            self.relation_type(right)
+        }
+    }
+
+    fn allocate_local_id(&self) -> LocalId {
+        LocalId::new(self.id_gen.borrow_mut().allocate_id())
+    }
+
+    /// Looks up the CTE to which `name` refers, if any, returning its
+    /// normalized name along with its description.
+    fn resolve_cte(&self, name: &ObjectName) -> Option<(String, &CteDesc)> {
+        match &name.0[..] {
+            [ident] => {
+                let ident = normalize::ident(ident.clone());
+                let cte = self.ctes.get(&ident)?;
+                Some((ident, cte))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// A bundle of unrelated things that we need for planning `Expr`s.
@@ -1888,6 +2718,9 @@ impl<'a> ExprContext<'a> {
                 .cloned()
                 .collect(),
             param_types: self.qcx.param_types.clone(),
+            ctes: self.qcx.ctes.clone(),
+            in_recursive_cte: self.qcx.in_recursive_cte,
+            id_gen: self.qcx.id_gen.clone(),
         }
     }
 }
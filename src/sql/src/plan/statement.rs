@@ -22,25 +22,32 @@ use url::Url;
 
 use dataflow_types::{
     AvroEncoding, AvroOcfSinkConnectorBuilder, Consistency, CsvEncoding, DataEncoding, Envelope,
-    ExternalSourceConnector, FileSourceConnector, KafkaSinkConnectorBuilder, KafkaSourceConnector,
-    KinesisSourceConnector, PeekWhen, ProtobufEncoding, SinkConnectorBuilder, SourceConnector,
+    ExternalSourceConnector, FileSinkConnectorBuilder, FileSinkFormat, FileSourceConnector,
+    JsonEncoding, KafkaSinkConnectorBuilder, KafkaSinkFormatBuilder, KafkaSourceConnector,
+    KinesisSinkConnectorBuilder, KinesisSinkFormat, KinesisSourceConnector, PeekWhen,
+    PostgresSourceConnector, ProtobufEncoding, SinkConnectorBuilder, SinkEnvelope, SourceConnector,
+    UpsertKey,
 };
 use expr::{like_pattern, GlobalId, RowSetFinishing};
-use interchange::avro::{self, DebeziumDeduplicationStrategy, Encoder};
+use interchange::avro::{self, DebeziumDeduplicationStrategy, Encoder, UpsertEncoder};
+use interchange::protobuf;
 use ore::collections::CollectionExt;
 use repr::strconv;
 use repr::{ColumnType, Datum, RelationDesc, RelationType, Row, RowArena, ScalarType};
 use sql_parser::ast::{
-    AvroSchema, Connector, ExplainOptions, ExplainStage, Explainee, Expr, Format, Ident,
-    IfExistsBehavior, ObjectName, ObjectType, Query, SetVariableValue, ShowStatementFilter,
-    SqlOption, Statement, Value,
+    Assignment, AvroSchema, ColumnOption, Connector, CopyFormat, ExplainOptions, ExplainStage,
+    Explainee, Expr, Format, Ident, IfExistsBehavior, ObjectName, ObjectType, Privilege,
+    Privileges, Query, RoleOption, SetVariableValue, ShowStatementFilter, SqlOption, Statement,
+    TableConstraint, TailRelation, Value,
 };
 
 use crate::catalog::{Catalog, CatalogItemType};
 use crate::kafka_util;
 use crate::names::{DatabaseSpecifier, FullName, PartialName};
 use crate::plan::query::QueryLifetime;
-use crate::plan::{query, Index, Params, Plan, PlanContext, Sink, Source, View};
+use crate::plan::{
+    query, Index, MutationKind, Params, Plan, PlanContext, Sink, Source, Table, TailFrom, View,
+};
 use crate::pure::Schema;
 use crate::{normalize, unsupported};
 
@@ -102,14 +109,21 @@ pub fn describe_statement(
     let pcx = &PlanContext::default();
     let scx = &StatementContext { catalog, pcx };
     Ok(match stmt {
-        Statement::CreateDatabase { .. }
+        Statement::Copy { .. }
+        | Statement::CreateDatabase { .. }
         | Statement::CreateSchema { .. }
         | Statement::CreateIndex { .. }
         | Statement::CreateSource { .. }
         | Statement::CreateSink { .. }
+        | Statement::CreateTable { .. }
         | Statement::CreateView { .. }
         | Statement::DropDatabase { .. }
         | Statement::DropObjects { .. }
+        | Statement::CreateRole { .. }
+        | Statement::DropRole { .. }
+        | Statement::Grant { .. }
+        | Statement::Revoke { .. }
+        | Statement::AlterObjectRename { .. }
         | Statement::SetVariable { .. }
         | Statement::StartTransaction { .. }
         | Statement::Rollback { .. }
@@ -214,11 +228,18 @@ pub fn describe_statement(
             }
         }
 
-        Statement::Tail { name, .. } => {
-            let name = scx.resolve_item(name)?;
-            let sql_object = scx.catalog.get_item(&name);
-            (Some(sql_object.desc()?.clone()), vec![])
-        }
+        Statement::Tail { relation, .. } => match relation {
+            TailRelation::Name(name) => {
+                let name = scx.resolve_item(name)?;
+                let sql_object = scx.catalog.get_item(&name);
+                (Some(sql_object.desc()?.clone()), vec![])
+            }
+            TailRelation::Query(query) => {
+                let (_relation_expr, desc, _finishing, param_types) =
+                    query::plan_root_query(scx, query, QueryLifetime::Static)?;
+                (Some(desc), param_types)
+            }
+        },
 
         Statement::Select { query, .. } => {
             // TODO(benesch): ideally we'd save `relation_expr` and `finishing`
@@ -229,10 +250,32 @@ pub fn describe_statement(
                 query::plan_root_query(scx, *query, QueryLifetime::OneShot)?;
             (Some(desc), param_types)
         }
-        Statement::CreateTable { .. } => bail!(
-            "CREATE TABLE statements are not supported. \
-             Try CREATE SOURCE or CREATE [MATERIALIZED] VIEW instead."
-        ),
+        Statement::Insert {
+            table_name,
+            columns,
+            source,
+        } => {
+            let (_id, _expr, param_types) =
+                query::plan_insert_query(scx, table_name, columns, *source)?;
+            (None, param_types)
+        }
+        Statement::Update {
+            table_name,
+            assignments,
+            selection,
+        } => {
+            let (_id, _expr, param_types) =
+                query::plan_mutation_query(scx, table_name, assignments, selection)?;
+            (None, param_types)
+        }
+        Statement::Delete {
+            table_name,
+            selection,
+        } => {
+            let (_id, _expr, param_types) =
+                query::plan_mutation_query(scx, table_name, vec![], selection)?;
+            (None, param_types)
+        }
         _ => unsupported!(format!("{:?}", stmt)),
     })
 }
@@ -246,10 +289,10 @@ pub fn handle_statement(
     let scx = &StatementContext { pcx, catalog };
     match stmt {
         Statement::Tail {
-            name,
-            with_snapshot,
+            relation,
+            options,
             as_of,
-        } => handle_tail(scx, name, with_snapshot, as_of),
+        } => handle_tail(scx, relation, options, as_of, params),
         Statement::StartTransaction { .. } => Ok(Plan::StartTransaction),
         Statement::Commit { .. } => Ok(Plan::CommitTransaction),
         Statement::Rollback { .. } => Ok(Plan::AbortTransaction),
@@ -262,6 +305,7 @@ pub fn handle_statement(
             if_not_exists,
         } => handle_create_schema(scx, name, if_not_exists),
         Statement::CreateSource { .. } => handle_create_source(scx, stmt),
+        Statement::CreateTable { .. } => handle_create_table(scx, stmt),
         Statement::CreateView { .. } => handle_create_view(scx, stmt, params),
         Statement::CreateSink { .. } => handle_create_sink(scx, stmt),
         Statement::CreateIndex { .. } => handle_create_index(scx, stmt),
@@ -272,6 +316,39 @@ pub fn handle_statement(
             names,
             cascade,
         } => handle_drop_objects(scx, object_type, if_exists, names, cascade),
+        Statement::Copy {
+            table_name,
+            columns,
+            format,
+        } => handle_copy(scx, table_name, columns, format),
+        Statement::Insert {
+            table_name,
+            columns,
+            source,
+        } => handle_insert(scx, table_name, columns, *source, params),
+        Statement::Update {
+            table_name,
+            assignments,
+            selection,
+        } => handle_mutation(
+            scx,
+            table_name,
+            assignments,
+            selection,
+            MutationKind::Update,
+            params,
+        ),
+        Statement::Delete {
+            table_name,
+            selection,
+        } => handle_mutation(
+            scx,
+            table_name,
+            vec![],
+            selection,
+            MutationKind::Delete,
+            params,
+        ),
         Statement::Select { query, as_of } => handle_select(scx, *query, as_of, params),
         Statement::SetVariable {
             local,
@@ -308,6 +385,24 @@ pub fn handle_statement(
             explainee,
             options,
         } => handle_explain(scx, stage, explainee, options, params),
+        Statement::CreateRole { name, options } => handle_create_role(scx, name, options),
+        Statement::DropRole { if_exists, names } => handle_drop_role(scx, if_exists, names),
+        Statement::Grant {
+            privileges,
+            objects,
+            grantees,
+        } => handle_grant(scx, privileges, objects, grantees),
+        Statement::Revoke {
+            privileges,
+            objects,
+            grantees,
+        } => handle_revoke(scx, privileges, objects, grantees),
+        Statement::AlterObjectRename {
+            object_type,
+            if_exists,
+            name,
+            to_item_name,
+        } => handle_alter_object_rename(scx, object_type, if_exists, name, to_item_name),
 
         _ => bail!("unsupported SQL statement: {:?}", stmt),
     }
@@ -342,26 +437,61 @@ fn handle_show_variable(_: &StatementContext, variable: Ident) -> Result<Plan, f
 
 fn handle_tail(
     scx: &StatementContext,
-    from: ObjectName,
-    with_snapshot: bool,
+    relation: TailRelation,
+    options: Vec<SqlOption>,
     as_of: Option<sql_parser::ast::Expr>,
+    params: &Params,
 ) -> Result<Plan, failure::Error> {
-    let from = scx.resolve_item(from)?;
-    let entry = scx.catalog.get_item(&from);
     let ts = as_of.map(|e| query::eval_as_of(scx, e)).transpose()?;
 
-    match entry.item_type() {
-        CatalogItemType::Source | CatalogItemType::View => Ok(Plan::Tail {
-            id: entry.id(),
-            ts,
-            with_snapshot,
-        }),
-        CatalogItemType::Index | CatalogItemType::Sink => bail!(
-            "'{}' cannot be tailed because it is a {}",
-            from,
-            entry.item_type(),
-        ),
+    let mut options = normalize::with_options(&options);
+    let with_snapshot = match options.remove("snapshot") {
+        None => true,
+        Some(Value::Boolean(b)) => b,
+        Some(_) => bail!("snapshot must be a boolean"),
+    };
+    let emit_progress = match options.remove("progress") {
+        None => false,
+        Some(Value::Boolean(b)) => b,
+        Some(_) => bail!("progress must be a boolean"),
+    };
+    if let Some(name) = options.keys().next() {
+        bail!("unrecognized option for TAIL: {}", name);
     }
+
+    let from = match relation {
+        TailRelation::Name(name) => {
+            let name = scx.resolve_item(name)?;
+            let entry = scx.catalog.get_item(&name);
+            match entry.item_type() {
+                CatalogItemType::Source | CatalogItemType::View => TailFrom::Id(entry.id()),
+                CatalogItemType::Index | CatalogItemType::Sink => bail!(
+                    "'{}' cannot be tailed because it is a {}",
+                    name,
+                    entry.item_type(),
+                ),
+            }
+        }
+        TailRelation::Query(query) => {
+            // As with views, the query's result is maintained indefinitely,
+            // and so any ORDER BY or LIMIT is folded into the query itself.
+            let (mut expr, desc, finishing, _param_types) =
+                query::plan_root_query(scx, query, QueryLifetime::Static)?;
+            expr.bind_parameters(params);
+            expr.finish(finishing);
+            TailFrom::Query {
+                expr: expr.decorrelate(),
+                desc,
+            }
+        }
+    };
+
+    Ok(Plan::Tail {
+        from,
+        ts,
+        with_snapshot,
+        emit_progress,
+    })
 }
 
 fn finish_show_where(
@@ -601,10 +731,13 @@ fn handle_show_create_view(
     let name = scx.resolve_item(name)?;
     let entry = scx.catalog.get_item(&name);
     if let CatalogItemType::View = entry.item_type() {
-        Ok(Plan::SendRows(vec![Row::pack(&[
-            Datum::String(&name.to_string()),
-            Datum::String(entry.create_sql()),
-        ])]))
+        Ok(Plan::ShowCreate {
+            id: entry.id(),
+            row: Row::pack(&[
+                Datum::String(&name.to_string()),
+                Datum::String(entry.create_sql()),
+            ]),
+        })
     } else {
         bail!("{} is not a view", name);
     }
@@ -617,10 +750,13 @@ fn handle_show_create_source(
     let name = scx.resolve_item(name)?;
     let entry = scx.catalog.get_item(&name);
     if let CatalogItemType::Source = entry.item_type() {
-        Ok(Plan::SendRows(vec![Row::pack(&[
-            Datum::String(&name.to_string()),
-            Datum::String(entry.create_sql()),
-        ])]))
+        Ok(Plan::ShowCreate {
+            id: entry.id(),
+            row: Row::pack(&[
+                Datum::String(&name.to_string()),
+                Datum::String(entry.create_sql()),
+            ]),
+        })
     } else {
         bail!("{} is not a source", name);
     }
@@ -633,10 +769,13 @@ fn handle_show_create_sink(
     let name = scx.resolve_item(name)?;
     let entry = scx.catalog.get_item(&name);
     if let CatalogItemType::Sink = entry.item_type() {
-        Ok(Plan::SendRows(vec![Row::pack(&[
-            Datum::String(&name.to_string()),
-            Datum::String(entry.create_sql()),
-        ])]))
+        Ok(Plan::ShowCreate {
+            id: entry.id(),
+            row: Row::pack(&[
+                Datum::String(&name.to_string()),
+                Datum::String(entry.create_sql()),
+            ]),
+        })
     } else {
         bail!("'{}' is not a sink", name);
     }
@@ -649,10 +788,13 @@ fn handle_show_create_index(
     let name = scx.resolve_item(name)?;
     let entry = scx.catalog.get_item(&name);
     if let CatalogItemType::Index = entry.item_type() {
-        Ok(Plan::SendRows(vec![Row::pack(&[
-            Datum::String(&name.to_string()),
-            Datum::String(entry.create_sql()),
-        ])]))
+        Ok(Plan::ShowCreate {
+            id: entry.id(),
+            row: Row::pack(&[
+                Datum::String(&name.to_string()),
+                Datum::String(entry.create_sql()),
+            ]),
+        })
     } else {
         bail!("'{}' is not an index", name);
     }
@@ -660,13 +802,63 @@ fn handle_show_create_index(
 
 fn kafka_sink_builder(
     format: Option<Format>,
+    envelope: Option<sql_parser::ast::Envelope>,
     with_options: Vec<SqlOption>,
     mut broker: String,
     topic_prefix: String,
+    from_name: &FullName,
     desc: RelationDesc,
     topic_suffix: String,
 ) -> Result<SinkConnectorBuilder, failure::Error> {
-    let schema_registry_url = match format {
+    if !broker.contains(':') {
+        broker += ":9092";
+    }
+    let broker_url = broker.parse()?;
+
+    let mut with_options = normalize::with_options(&with_options);
+    let include_consistency = match with_options.remove("consistency") {
+        Some(Value::Boolean(b)) => b,
+        None => false,
+        Some(_) => bail!("consistency must be a boolean"),
+    };
+    let exactly_once = match with_options.remove("exactly_once") {
+        Some(Value::Boolean(b)) => b,
+        None => false,
+        Some(_) => bail!("exactly_once must be a boolean"),
+    };
+    if exactly_once && !include_consistency {
+        // Each transaction ends with the END consistency message for its last
+        // timestamp, so exactly-once sinks need a consistency topic.
+        bail!("exactly_once requires consistency = true");
+    }
+
+    let envelope = match envelope {
+        None | Some(sql_parser::ast::Envelope::Debezium) => SinkEnvelope::Debezium,
+        Some(sql_parser::ast::Envelope::Upsert(None)) => match desc.typ().keys.first() {
+            Some(key_indices) => SinkEnvelope::Upsert {
+                key_indices: key_indices.clone(),
+            },
+            None => bail!(
+                "upsert envelope for sinks requires a unique key, but {} does not have one; \
+                 specify the key columns explicitly, as in ENVELOPE UPSERT KEY (column, ...)",
+                from_name
+            ),
+        },
+        Some(sql_parser::ast::Envelope::UpsertKeyColumns(columns)) => {
+            let mut key_indices = vec![];
+            for column in columns {
+                let column = normalize::column_name(column);
+                match desc.get_by_name(&column) {
+                    Some((i, _)) => key_indices.push(i),
+                    None => bail!("upsert key column {} does not exist", column),
+                }
+            }
+            SinkEnvelope::Upsert { key_indices }
+        }
+        Some(envelope) => bail!("ENVELOPE {} is not supported for sinks", envelope),
+    };
+
+    let format = match format {
         Some(Format::Avro(AvroSchema::CsrUrl {
             url,
             seed,
@@ -678,25 +870,77 @@ fn kafka_sink_builder(
             if !with_options.is_empty() {
                 unsupported!("CONFLUENT SCHEMA REGISTRY ... WITH options in CREATE SINK");
             }
-            url.parse()?
+            validate_avro_sink_desc(&desc)?;
+            let (key_schema, value_schema) = match &envelope {
+                SinkEnvelope::Debezium => {
+                    let encoder = Encoder::new(desc, include_consistency);
+                    (None, encoder.writer_schema().canonical_form())
+                }
+                SinkEnvelope::Upsert { key_indices } => {
+                    let encoder = UpsertEncoder::new(desc, key_indices.clone());
+                    (
+                        Some(encoder.key_schema().canonical_form()),
+                        encoder.value_schema().canonical_form(),
+                    )
+                }
+            };
+            let consistency_value_schema = if include_consistency {
+                Some(avro::get_debezium_transaction_schema().canonical_form())
+            } else {
+                None
+            };
+            KafkaSinkFormatBuilder::Avro {
+                schema_registry_url: url.parse()?,
+                key_schema,
+                value_schema,
+                consistency_value_schema,
+            }
+        }
+        Some(Format::Protobuf {
+            message_name,
+            schema,
+        }) => {
+            if include_consistency {
+                unsupported!("consistency topics for protobuf sinks");
+            }
+            let descriptors = match schema {
+                sql_parser::ast::Schema::Inline(bytes) => strconv::parse_bytes(&bytes)?,
+                sql_parser::ast::Schema::File(_) => {
+                    unreachable!("File schema should already have been inlined")
+                }
+            };
+            // Validate that the sink's columns can be encoded into the
+            // message.
+            protobuf::Encoder::new(
+                &desc,
+                &message_name,
+                &protobuf::decode_descriptors(&descriptors)?,
+            )?;
+            KafkaSinkFormatBuilder::Protobuf(ProtobufEncoding {
+                descriptors,
+                message_name,
+            })
+        }
+        Some(Format::Json { columns }) => {
+            if !columns.is_empty() {
+                bail!("JSON sinks cannot specify a column list");
+            }
+            if include_consistency {
+                unsupported!("consistency topics for JSON sinks");
+            }
+            KafkaSinkFormatBuilder::Json
         }
         _ => unsupported!("non-confluent schema registry avro sinks"),
     };
 
-    if !broker.contains(':') {
-        broker += ":9092";
+    if let SinkEnvelope::Upsert { .. } = envelope {
+        match format {
+            KafkaSinkFormatBuilder::Json | KafkaSinkFormatBuilder::Avro { .. } => (),
+            KafkaSinkFormatBuilder::Protobuf(_) => {
+                unsupported!("upsert envelope for protobuf sinks")
+            }
+        }
     }
-    let broker_url = broker.parse()?;
-
-    let mut with_options = normalize::with_options(&with_options);
-    let include_consistency = match with_options.remove("consistency") {
-        Some(Value::Boolean(b)) => b,
-        None => false,
-        Some(_) => bail!("consistency must be a boolean"),
-    };
-
-    let encoder = Encoder::new(desc, include_consistency);
-    let value_schema = encoder.writer_schema().canonical_form();
 
     // Use the user supplied value for replication factor, or default to 1
     let replication_factor = match with_options.remove("replication_factor") {
@@ -709,34 +953,48 @@ fn kafka_sink_builder(
         bail!("replication factor for sink topics has to be greater than zero");
     }
 
-    let consistency_value_schema = if include_consistency {
-        Some(avro::get_debezium_transaction_schema().canonical_form())
-    } else {
-        None
-    };
-
     Ok(SinkConnectorBuilder::Kafka(KafkaSinkConnectorBuilder {
         broker_url,
-        schema_registry_url,
-        value_schema,
+        format,
+        envelope,
         topic_prefix,
         topic_suffix,
         replication_factor,
+        exactly_once,
         fuel: 10000,
-        consistency_value_schema,
     }))
 }
 
+/// Returns an error if any column of `desc` has a type that the Avro encoder
+/// cannot yet represent.
+fn validate_avro_sink_desc(desc: &RelationDesc) -> Result<(), failure::Error> {
+    for typ in desc.iter_types() {
+        match typ.scalar_type {
+            ScalarType::Array(_) => unsupported!("array columns in Avro sinks"),
+            ScalarType::List(_) => unsupported!("list columns in Avro sinks"),
+            ScalarType::Record { .. } => unsupported!("record columns in Avro sinks"),
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
 fn avro_ocf_sink_builder(
     format: Option<Format>,
+    envelope: Option<sql_parser::ast::Envelope>,
     with_options: Vec<SqlOption>,
     path: String,
     file_name_suffix: String,
+    desc: &RelationDesc,
 ) -> Result<SinkConnectorBuilder, failure::Error> {
     if format.is_some() {
         bail!("avro ocf sinks cannot specify a format");
     }
 
+    if envelope.is_some() {
+        bail!("avro ocf sinks cannot specify an envelope");
+    }
+
     if !with_options.is_empty() {
         bail!("avro ocf sinks do not support WITH options");
     }
@@ -747,37 +1005,204 @@ fn avro_ocf_sink_builder(
         bail!("avro ocf sink cannot write to a directory");
     }
 
+    validate_avro_sink_desc(desc)?;
+
     Ok(SinkConnectorBuilder::AvroOcf(AvroOcfSinkConnectorBuilder {
         path,
         file_name_suffix,
     }))
 }
 
+fn kinesis_sink_builder(
+    format: Option<Format>,
+    envelope: Option<sql_parser::ast::Envelope>,
+    with_options: Vec<SqlOption>,
+    arn: String,
+    desc: RelationDesc,
+) -> Result<SinkConnectorBuilder, failure::Error> {
+    match envelope {
+        None | Some(sql_parser::ast::Envelope::Debezium) => (),
+        Some(envelope) => bail!("ENVELOPE {} is not supported for Kinesis sinks", envelope),
+    }
+
+    let mut with_options = normalize::with_options(&with_options);
+    let KinesisSourceConnector {
+        stream_name,
+        region,
+        access_key_id,
+        secret_access_key,
+        token,
+    } = kinesis_connector(&arn, &mut with_options)?;
+
+    // Records are partitioned by the named columns, or else by the unique key
+    // of the sink's input, so that all updates to a key are written to the
+    // same shard, in order. Without a key, records are partitioned by all of
+    // their columns.
+    let partition_key_indices = match with_options.remove("partition_key_columns") {
+        Some(Value::String(columns)) => {
+            let mut indices = vec![];
+            for column in columns.split(',') {
+                let column = normalize::column_name(Ident::new(column.trim()));
+                match desc.get_by_name(&column) {
+                    Some((i, _)) => indices.push(i),
+                    None => bail!("partition key column {} does not exist", column),
+                }
+            }
+            indices
+        }
+        Some(_) => bail!("partition_key_columns must be a string"),
+        None => match desc.typ().keys.first() {
+            Some(key_indices) => key_indices.clone(),
+            None => (0..desc.arity()).collect(),
+        },
+    };
+
+    let format = match format {
+        Some(Format::Protobuf {
+            message_name,
+            schema,
+        }) => {
+            let descriptors = match schema {
+                sql_parser::ast::Schema::Inline(bytes) => strconv::parse_bytes(&bytes)?,
+                sql_parser::ast::Schema::File(_) => {
+                    unreachable!("File schema should already have been inlined")
+                }
+            };
+            // Validate that the sink's columns can be encoded into the
+            // message.
+            protobuf::Encoder::new(
+                &desc,
+                &message_name,
+                &protobuf::decode_descriptors(&descriptors)?,
+            )?;
+            KinesisSinkFormat::Protobuf(ProtobufEncoding {
+                descriptors,
+                message_name,
+            })
+        }
+        Some(Format::Json { columns }) => {
+            if !columns.is_empty() {
+                bail!("JSON sinks cannot specify a column list");
+            }
+            KinesisSinkFormat::Json
+        }
+        Some(Format::Avro(_)) => unsupported!("Avro Kinesis sinks"),
+        _ => bail!("Kinesis sinks require FORMAT JSON or FORMAT PROTOBUF"),
+    };
+
+    Ok(SinkConnectorBuilder::Kinesis(KinesisSinkConnectorBuilder {
+        stream_name,
+        region,
+        access_key_id,
+        secret_access_key,
+        token,
+        format,
+        partition_key_indices,
+    }))
+}
+
+fn file_sink_builder(
+    format: Option<Format>,
+    envelope: Option<sql_parser::ast::Envelope>,
+    with_options: Vec<SqlOption>,
+    path: String,
+    desc: &RelationDesc,
+) -> Result<SinkConnectorBuilder, failure::Error> {
+    if envelope.is_some() {
+        bail!("file sinks cannot specify an envelope");
+    }
+
+    let mut with_options = normalize::with_options(&with_options);
+    let consolidated_snapshots = match with_options.remove("consolidated_snapshots") {
+        None => false,
+        Some(Value::Boolean(b)) => b,
+        Some(_) => bail!("consolidated_snapshots must be a boolean"),
+    };
+    if let Some(name) = with_options.keys().next() {
+        bail!("unrecognized option for file sinks: {}", name);
+    }
+
+    let format = match format {
+        Some(Format::Csv {
+            header_row,
+            n_cols,
+            delimiter,
+        }) => {
+            if let Some(n_cols) = n_cols {
+                if n_cols != desc.arity() {
+                    bail!(
+                        "CSV sink specifies {} columns, but the sink's input has {} columns",
+                        n_cols,
+                        desc.arity()
+                    );
+                }
+            }
+            FileSinkFormat::Csv {
+                header_row,
+                delimiter: match delimiter as u32 {
+                    0..=127 => delimiter as u8,
+                    _ => bail!("CSV delimiter must be an ASCII character"),
+                },
+            }
+        }
+        Some(Format::Json { columns }) => {
+            if !columns.is_empty() {
+                bail!("JSON sinks cannot specify a column list");
+            }
+            FileSinkFormat::Json
+        }
+        _ => bail!("file sinks require FORMAT CSV or FORMAT JSON"),
+    };
+
+    let path = PathBuf::from(path);
+
+    if path.is_dir() {
+        bail!("file sink cannot write to a directory");
+    }
+
+    Ok(SinkConnectorBuilder::File(FileSinkConnectorBuilder {
+        path,
+        format,
+        consolidated_snapshots,
+    }))
+}
+
 fn handle_create_sink(scx: &StatementContext, stmt: Statement) -> Result<Plan, failure::Error> {
     let create_sql = normalize::create_statement(scx, stmt.clone())?;
-    let (name, from, connector, with_options, format, with_snapshot, as_of, if_not_exists) =
-        match stmt {
-            Statement::CreateSink {
-                name,
-                from,
-                connector,
-                with_options,
-                format,
-                with_snapshot,
-                as_of,
-                if_not_exists,
-            } => (
-                name,
-                from,
-                connector,
-                with_options,
-                format,
-                with_snapshot,
-                as_of,
-                if_not_exists,
-            ),
-            _ => unreachable!(),
-        };
+    let (
+        name,
+        from,
+        connector,
+        with_options,
+        format,
+        envelope,
+        with_snapshot,
+        as_of,
+        if_not_exists,
+    ) = match stmt {
+        Statement::CreateSink {
+            name,
+            from,
+            connector,
+            with_options,
+            format,
+            envelope,
+            with_snapshot,
+            as_of,
+            if_not_exists,
+        } => (
+            name,
+            from,
+            connector,
+            with_options,
+            format,
+            envelope,
+            with_snapshot,
+            as_of,
+            if_not_exists,
+        ),
+        _ => unreachable!(),
+    };
 
     let name = scx.allocate_name(normalize::object_name(name)?);
     let from = scx.catalog.get_item(&scx.resolve_item(from)?);
@@ -792,17 +1217,26 @@ fn handle_create_sink(scx: &StatementContext, stmt: Statement) -> Result<Plan, f
 
     let as_of = as_of.map(|e| query::eval_as_of(scx, e)).transpose()?;
     let connector_builder = match connector {
-        Connector::File { .. } => unsupported!("file sinks"),
+        Connector::File { path } => {
+            file_sink_builder(format, envelope, with_options, path, from.desc()?)?
+        }
         Connector::Kafka { broker, topic } => kafka_sink_builder(
             format,
+            envelope,
             with_options,
             broker,
             topic,
+            from.name(),
             from.desc()?.clone(),
             suffix,
         )?,
-        Connector::Kinesis { .. } => unsupported!("Kinesis sinks"),
-        Connector::AvroOcf { path } => avro_ocf_sink_builder(format, with_options, path, suffix)?,
+        Connector::Kinesis { arn } => {
+            kinesis_sink_builder(format, envelope, with_options, arn, from.desc()?.clone())?
+        }
+        Connector::AvroOcf { path } => {
+            avro_ocf_sink_builder(format, envelope, with_options, path, suffix, from.desc()?)?
+        }
+        Connector::Postgres { .. } => unsupported!("Postgres sinks"),
     };
 
     Ok(Plan::CreateSink {
@@ -969,6 +1403,88 @@ fn handle_create_schema(
     })
 }
 
+fn handle_create_table(scx: &StatementContext, stmt: Statement) -> Result<Plan, failure::Error> {
+    let create_sql = normalize::create_statement(scx, stmt.clone())?;
+    let (name, columns, constraints, with_options, if_not_exists) = match stmt {
+        Statement::CreateTable {
+            name,
+            columns,
+            constraints,
+            with_options,
+            if_not_exists,
+        } => (name, columns, constraints, with_options, if_not_exists),
+        _ => unreachable!(),
+    };
+    if !with_options.is_empty() {
+        unsupported!("WITH options");
+    }
+
+    let names: Vec<_> = columns
+        .iter()
+        .map(|c| Some(normalize::column_name(c.name.clone())))
+        .collect();
+
+    // Build initial relation type that handles declared data types and NOT
+    // NULL constraints.
+    let mut typ = RelationType::new(
+        columns
+            .iter()
+            .map(|c| {
+                let ty = query::scalar_type_from_sql(&c.data_type)?;
+                let nullable = !c.options.iter().any(|o| o.option == ColumnOption::NotNull);
+                Ok(ColumnType::new(ty).nullable(nullable))
+            })
+            .collect::<Result<Vec<_>, failure::Error>>()?,
+    );
+
+    // Handle column-level UNIQUE and PRIMARY KEY constraints. PRIMARY KEY
+    // implies UNIQUE and NOT NULL.
+    for (index, column) in columns.iter().enumerate() {
+        for option in column.options.iter() {
+            if let ColumnOption::Unique { is_primary } = option.option {
+                typ = typ.with_key(vec![index]);
+                if is_primary {
+                    typ.column_types[index].nullable = false;
+                }
+            }
+        }
+    }
+
+    // Handle table-level UNIQUE and PRIMARY KEY constraints. PRIMARY KEY
+    // implies UNIQUE and NOT NULL.
+    for constraint in constraints {
+        if let TableConstraint::Unique {
+            name: _,
+            columns,
+            is_primary,
+        } = constraint
+        {
+            let mut key = vec![];
+            for column in columns {
+                let name = normalize::column_name(column);
+                match names.iter().position(|n| n.as_ref() == Some(&name)) {
+                    None => bail!("unknown column {} in unique constraint", name),
+                    Some(i) => key.push(i),
+                }
+            }
+            if is_primary {
+                for i in key.iter() {
+                    typ.column_types[*i].nullable = false;
+                }
+            }
+            typ = typ.with_key(key);
+        }
+    }
+
+    let name = scx.allocate_name(normalize::object_name(name)?);
+    let desc = RelationDesc::new(typ, names);
+    Ok(Plan::CreateTable {
+        name,
+        table: Table { create_sql, desc },
+        if_not_exists,
+    })
+}
+
 fn handle_create_view(
     scx: &StatementContext,
     mut stmt: Statement,
@@ -1068,6 +1584,78 @@ fn extract_timestamp_frequency_option(
     }
 }
 
+/// Plans the connection to the Kinesis stream named by `arn`, consuming the
+/// region and credential options from `with_options`.
+fn kinesis_connector(
+    arn: &str,
+    with_options: &mut HashMap<String, Value>,
+) -> Result<KinesisSourceConnector, failure::Error> {
+    let arn: ARN = match arn.parse() {
+        Ok(arn) => arn,
+        Err(e) => bail!("Unable to parse provided ARN: {:#?}", e),
+    };
+    let stream_name = match arn.resource {
+        Resource::Path(path) => {
+            if path.starts_with("stream/") {
+                String::from(&path["stream/".len()..])
+            } else {
+                bail!("Unable to parse stream name from resource path: {}", path);
+            }
+        }
+        _ => unsupported!(format!("AWS Resource type: {:#?}", arn.resource)),
+    };
+
+    let region: Region = match arn.region {
+        Some(region) => match region.parse() {
+            Ok(region) => region,
+            Err(e) => {
+                // Region's fromstr doesn't support parsing custom regions.
+                // If a Kinesis stream's ARN indicates it exists in a custom
+                // region, support it iff a valid endpoint for the stream
+                // is also provided.
+                match with_options.remove("endpoint") {
+                    Some(Value::String(endpoint)) => Region::Custom {
+                        name: region,
+                        endpoint,
+                    },
+                    _ => bail!(
+                        "Unable to parse AWS region: {}. If providing a custom \
+                         region, an `endpoint` option must also be provided",
+                        e
+                    ),
+                }
+            }
+        },
+        None => bail!("Provided ARN does not include an AWS region"),
+    };
+
+    // todo@jldlaughlin: We should support all (?) variants of AWS authentication.
+    // https://github.com/materializeinc/materialize/issues/1991
+    let access_key_id = match with_options.remove("access_key_id") {
+        Some(Value::String(access_key_id)) => Some(access_key_id),
+        Some(_) => bail!("access_key_id must be a string"),
+        _ => None,
+    };
+    let secret_access_key = match with_options.remove("secret_access_key") {
+        Some(Value::String(secret_access_key)) => Some(secret_access_key),
+        Some(_) => bail!("secret_access_key must be a string"),
+        _ => None,
+    };
+    let token = match with_options.remove("token") {
+        Some(Value::String(token)) => Some(token),
+        Some(_) => bail!("token must be a string"),
+        _ => None,
+    };
+
+    Ok(KinesisSourceConnector {
+        stream_name,
+        region,
+        access_key_id,
+        secret_access_key,
+        token,
+    })
+}
+
 fn handle_create_source(scx: &StatementContext, stmt: Statement) -> Result<Plan, failure::Error> {
     match &stmt {
         Statement::CreateSource {
@@ -1181,7 +1769,23 @@ fn handle_create_source(scx: &StatementContext, stmt: Statement) -> Result<Plan,
                             },
                         })
                     }
-                    Format::Json => unsupported!("JSON sources"),
+                    Format::Json { columns } => {
+                        let columns = columns
+                            .iter()
+                            .map(|c| {
+                                let name = normalize::column_name(c.name.clone());
+                                let ty = query::scalar_type_from_sql(&c.data_type)?;
+                                if let ScalarType::List(_)
+                                | ScalarType::Array(_)
+                                | ScalarType::Record { .. } = ty
+                                {
+                                    unsupported!(format!("JSON source columns of type {}", ty));
+                                }
+                                Ok((name, ty))
+                            })
+                            .collect::<Result<Vec<_>, failure::Error>>()?;
+                        DataEncoding::Json(JsonEncoding { columns })
+                    }
                     Format::Text => DataEncoding::Text,
                 })
             };
@@ -1241,70 +1845,10 @@ fn handle_create_source(scx: &StatementContext, stmt: Statement) -> Result<Plan,
                     (connector, encoding)
                 }
                 Connector::Kinesis { arn, .. } => {
-                    let arn: ARN = match arn.parse() {
-                        Ok(arn) => arn,
-                        Err(e) => bail!("Unable to parse provided ARN: {:#?}", e),
-                    };
-                    let stream_name = match arn.resource {
-                        Resource::Path(path) => {
-                            if path.starts_with("stream/") {
-                                String::from(&path["stream/".len()..])
-                            } else {
-                                bail!("Unable to parse stream name from resource path: {}", path);
-                            }
-                        }
-                        _ => unsupported!(format!("AWS Resource type: {:#?}", arn.resource)),
-                    };
-
-                    let region: Region = match arn.region {
-                        Some(region) => match region.parse() {
-                            Ok(region) => region,
-                            Err(e) => {
-                                // Region's fromstr doesn't support parsing custom regions.
-                                // If a Kinesis stream's ARN indicates it exists in a custom
-                                // region, support it iff a valid endpoint for the stream
-                                // is also provided.
-                                match with_options.remove("endpoint") {
-                                    Some(Value::String(endpoint)) => Region::Custom {
-                                        name: region,
-                                        endpoint,
-                                    },
-                                    _ => bail!(
-                                        "Unable to parse AWS region: {}. If providing a custom \
-                                         region, an `endpoint` option must also be provided",
-                                        e
-                                    ),
-                                }
-                            }
-                        },
-                        None => bail!("Provided ARN does not include an AWS region"),
-                    };
-
-                    // todo@jldlaughlin: We should support all (?) variants of AWS authentication.
-                    // https://github.com/materializeinc/materialize/issues/1991
-                    let access_key_id = match with_options.remove("access_key_id") {
-                        Some(Value::String(access_key_id)) => Some(access_key_id),
-                        Some(_) => bail!("access_key_id must be a string"),
-                        _ => None,
-                    };
-                    let secret_access_key = match with_options.remove("secret_access_key") {
-                        Some(Value::String(secret_access_key)) => Some(secret_access_key),
-                        Some(_) => bail!("secret_access_key must be a string"),
-                        _ => None,
-                    };
-                    let token = match with_options.remove("token") {
-                        Some(Value::String(token)) => Some(token),
-                        Some(_) => bail!("token must be a string"),
-                        _ => None,
-                    };
-
-                    let connector = ExternalSourceConnector::Kinesis(KinesisSourceConnector {
-                        stream_name,
-                        region,
-                        access_key_id,
-                        secret_access_key,
-                        token,
-                    });
+                    let connector = ExternalSourceConnector::Kinesis(kinesis_connector(
+                        &arn,
+                        &mut with_options,
+                    )?);
                     let encoding = get_encoding(format)?;
                     (connector, encoding)
                 }
@@ -1361,6 +1905,43 @@ fn handle_create_source(scx: &StatementContext, stmt: Statement) -> Result<Plan,
                     let encoding = DataEncoding::AvroOcf { reader_schema };
                     (connector, encoding)
                 }
+                Connector::Postgres {
+                    conn,
+                    publication,
+                    namespace,
+                    table,
+                    columns,
+                } => {
+                    if format.is_some() {
+                        bail!("Postgres sources cannot specify a format");
+                    }
+                    if *envelope != sql_parser::ast::Envelope::None {
+                        bail!("Postgres sources cannot specify an envelope");
+                    }
+                    if columns.is_empty() {
+                        bail!("Postgres table {}.{} has no columns", namespace, table);
+                    }
+                    ts_frequency = extract_timestamp_frequency_option(&mut with_options)?;
+
+                    let mut desc = RelationDesc::empty();
+                    for column in columns {
+                        let nullable = !column
+                            .options
+                            .iter()
+                            .any(|o| o.option == ColumnOption::NotNull);
+                        let ty = ColumnType::new(query::scalar_type_from_sql(&column.data_type)?)
+                            .nullable(nullable);
+                        desc = desc.with_column(normalize::column_name(column.name.clone()), ty);
+                    }
+
+                    let connector = ExternalSourceConnector::Postgres(PostgresSourceConnector {
+                        conn: conn.clone(),
+                        publication: publication.clone(),
+                        namespace: namespace.clone(),
+                        table: table.clone(),
+                    });
+                    (connector, DataEncoding::Postgres(desc))
+                }
             };
 
             // TODO (materialize#2537): cleanup format validation
@@ -1378,8 +1959,15 @@ fn handle_create_source(scx: &StatementContext, stmt: Statement) -> Result<Plan,
             // in the Upsert envelope's key_format so it can be validated like
             // a schema used to decode records.
 
+            // The key columns of the upsert envelope, if specified, are
+            // resolved below, once the names of the decoded columns are known.
+            let upsert_key_columns = match envelope {
+                sql_parser::ast::Envelope::UpsertKeyColumns(columns) => Some(columns),
+                _ => None,
+            };
+
             // TODO: remove bails as more support for upsert is added.
-            let envelope = match &envelope {
+            let mut envelope = match &envelope {
                 sql_parser::ast::Envelope::None => dataflow_types::Envelope::None,
                 sql_parser::ast::Envelope::Debezium => {
                     let dedup_strat = match with_options.remove("deduplication") {
@@ -1394,6 +1982,20 @@ fn handle_create_source(scx: &StatementContext, stmt: Statement) -> Result<Plan,
                     dataflow_types::Envelope::Debezium(dedup_strat)
                 }
                 sql_parser::ast::Envelope::Upsert(key_format) => match connector {
+                    Connector::Kinesis { .. } => {
+                        // Kinesis records are keyed by their partition key,
+                        // which is always a string.
+                        let key_encoding = if key_format.is_some() {
+                            get_encoding(key_format)?
+                        } else {
+                            DataEncoding::Text
+                        };
+                        match &key_encoding {
+                            DataEncoding::Bytes | DataEncoding::Text => {}
+                            _ => unsupported!("format for Kinesis upsert key"),
+                        }
+                        dataflow_types::Envelope::Upsert(UpsertKey::Encoding(key_encoding))
+                    }
                     Connector::Kafka { .. } => {
                         let mut key_encoding = if key_format.is_some() {
                             get_encoding(key_format)?
@@ -1413,13 +2015,20 @@ fn handle_create_source(scx: &StatementContext, stmt: Statement) -> Result<Plan,
                             DataEncoding::Bytes | DataEncoding::Text => {}
                             _ => unsupported!("format for upsert key"),
                         }
-                        dataflow_types::Envelope::Upsert(key_encoding)
+                        dataflow_types::Envelope::Upsert(UpsertKey::Encoding(key_encoding))
                     }
-                    _ => unsupported!("upsert envelope for non-Kafka sources"),
+                    _ => bail!(
+                        "upsert envelope for {} sources requires a list of key columns, \
+                         as in ENVELOPE UPSERT KEY (column, ...)",
+                        external_connector.name()
+                    ),
                 },
+                // Values are decoded without an envelope, and the key is
+                // projected from the decoded columns.
+                sql_parser::ast::Envelope::UpsertKeyColumns(_) => dataflow_types::Envelope::None,
             };
 
-            if let dataflow_types::Envelope::Upsert(key_encoding) = &envelope {
+            if let dataflow_types::Envelope::Upsert(UpsertKey::Encoding(key_encoding)) = &envelope {
                 match &mut encoding {
                     DataEncoding::Avro(AvroEncoding { key_schema, .. }) => {
                         *key_schema = None;
@@ -1445,6 +2054,25 @@ fn handle_create_source(scx: &StatementContext, stmt: Statement) -> Result<Plan,
 
             desc = maybe_rename_columns(format!("source {}", name), desc, col_names)?;
 
+            if let Some(key_columns) = upsert_key_columns {
+                let mut key_indices = vec![];
+                for column in key_columns {
+                    let column = normalize::column_name(column.clone());
+                    match desc.get_by_name(&column) {
+                        Some((i, _)) => key_indices.push(i),
+                        None => bail!("upsert key column {} does not exist", column),
+                    }
+                }
+                let value_indices = (0..desc.arity())
+                    .filter(|i| !key_indices.contains(i))
+                    .collect();
+                desc = desc.without_keys().with_key(key_indices.clone());
+                envelope = dataflow_types::Envelope::Upsert(UpsertKey::Columns {
+                    key_indices,
+                    value_indices,
+                });
+            }
+
             // TODO(benesch): the available metadata columns should not depend
             // on the format.
             //
@@ -1548,10 +2176,11 @@ fn handle_drop_objects(
 ) -> Result<Plan, failure::Error> {
     match object_type {
         ObjectType::Schema => handle_drop_schema(scx, if_exists, names, cascade),
-        ObjectType::Source | ObjectType::View | ObjectType::Index | ObjectType::Sink => {
-            handle_drop_items(scx, object_type, if_exists, names, cascade)
-        }
-        _ => unsupported!(format!("DROP {}", object_type)),
+        ObjectType::Source
+        | ObjectType::Table
+        | ObjectType::View
+        | ObjectType::Index
+        | ObjectType::Sink => handle_drop_items(scx, object_type, if_exists, names, cascade),
     }
 }
 
@@ -1661,6 +2290,247 @@ fn handle_drop_item(
     Ok(Some(catalog_entry.id()))
 }
 
+fn handle_create_role(
+    _: &StatementContext,
+    name: Ident,
+    options: Vec<RoleOption>,
+) -> Result<Plan, failure::Error> {
+    let mut superuser = None;
+    for option in options {
+        if superuser.is_some() {
+            bail!("conflicting or redundant options");
+        }
+        superuser = Some(match option {
+            RoleOption::SuperUser => true,
+            RoleOption::NoSuperUser => false,
+        });
+    }
+    Ok(Plan::CreateRole {
+        name: normalize::ident(name),
+        superuser: superuser.unwrap_or(false),
+    })
+}
+
+fn handle_drop_role(
+    scx: &StatementContext,
+    if_exists: bool,
+    names: Vec<Ident>,
+) -> Result<Plan, failure::Error> {
+    let mut out = vec![];
+    for name in names {
+        let name = normalize::ident(name);
+        if name == "materialize" {
+            bail!(
+                "cannot drop role {} because it is required by the database system",
+                name
+            );
+        }
+        match scx.catalog.resolve_role(&name) {
+            Ok(()) => out.push(name),
+            Err(_) if if_exists => {
+                // TODO(benesch): generate a notice indicating this
+                // role does not exist.
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(Plan::DropRoles { names: out })
+}
+
+fn handle_alter_object_rename(
+    scx: &StatementContext,
+    object_type: ObjectType,
+    if_exists: bool,
+    name: ObjectName,
+    to_item_name: Ident,
+) -> Result<Plan, failure::Error> {
+    let name = match scx.resolve_item(name) {
+        Ok(name) => name,
+        Err(_) if if_exists => {
+            // TODO(benesch): generate a notice indicating this
+            // item does not exist.
+            return Ok(Plan::AlterNoop { object_type });
+        }
+        Err(err) => return Err(err),
+    };
+    let catalog_entry = scx.catalog.get_item(&name);
+    if catalog_entry.id().is_system() {
+        bail!(
+            "cannot rename item {} because it is required by the database system",
+            name
+        );
+    }
+    if object_type != catalog_entry.item_type() {
+        bail!("{} is not of type {}", name, object_type);
+    }
+    Ok(Plan::AlterItemRename {
+        id: catalog_entry.id(),
+        to_name: normalize::ident(to_item_name),
+        object_type,
+    })
+}
+
+fn handle_grant(
+    scx: &StatementContext,
+    privileges: Privileges,
+    objects: Vec<ObjectName>,
+    grantees: Vec<Ident>,
+) -> Result<Plan, failure::Error> {
+    let (privileges, ids, roles) = plan_privileges(scx, "grant", privileges, objects, grantees)?;
+    Ok(Plan::GrantPrivileges {
+        privileges,
+        ids,
+        roles,
+    })
+}
+
+fn handle_revoke(
+    scx: &StatementContext,
+    privileges: Privileges,
+    objects: Vec<ObjectName>,
+    grantees: Vec<Ident>,
+) -> Result<Plan, failure::Error> {
+    let (privileges, ids, roles) = plan_privileges(scx, "revoke", privileges, objects, grantees)?;
+    Ok(Plan::RevokePrivileges {
+        privileges,
+        ids,
+        roles,
+    })
+}
+
+/// Resolves the privileges, objects, and roles named in a `GRANT` or `REVOKE`
+/// statement.
+///
+/// Privileges can only be granted on sources, tables, and views that are not
+/// owned by the system.
+fn plan_privileges(
+    scx: &StatementContext,
+    action: &str,
+    privileges: Privileges,
+    objects: Vec<ObjectName>,
+    grantees: Vec<Ident>,
+) -> Result<(Vec<Privilege>, Vec<GlobalId>, Vec<String>), failure::Error> {
+    let mut privileges = match privileges {
+        Privileges::All => vec![
+            Privilege::Select,
+            Privilege::Insert,
+            Privilege::Update,
+            Privilege::Delete,
+        ],
+        Privileges::List(privileges) => privileges,
+    };
+    privileges.sort();
+    privileges.dedup();
+
+    let mut ids = vec![];
+    for name in objects {
+        let name = scx.resolve_item(name)?;
+        let item = scx.catalog.get_item(&name);
+        if item.id().is_system() {
+            bail!(
+                "cannot {} privileges on {} because it is required by the database system",
+                action,
+                name
+            );
+        }
+        match item.item_type() {
+            CatalogItemType::Source | CatalogItemType::View => ids.push(item.id()),
+            ty @ CatalogItemType::Sink | ty @ CatalogItemType::Index => bail!(
+                "cannot {} privileges on {} because it is a {}",
+                action,
+                name,
+                ty
+            ),
+        }
+    }
+
+    let mut roles = vec![];
+    for grantee in grantees {
+        let role = normalize::ident(grantee);
+        scx.catalog.resolve_role(&role)?;
+        roles.push(role);
+    }
+
+    Ok((privileges, ids, roles))
+}
+
+fn handle_copy(
+    scx: &StatementContext,
+    table_name: ObjectName,
+    columns: Vec<Ident>,
+    format: CopyFormat,
+) -> Result<Plan, failure::Error> {
+    let table_name = scx.resolve_item(table_name)?;
+    let table = scx.catalog.get_item(&table_name);
+    if query::table_column_defs(table).is_none() {
+        bail!(
+            "cannot copy to {} because it is a {}",
+            table_name,
+            table.item_type()
+        );
+    }
+    let desc = table.desc()?;
+    let columns = if columns.is_empty() {
+        (0..desc.arity()).collect()
+    } else {
+        let mut positions = vec![];
+        for column in columns {
+            let name = normalize::column_name(column);
+            let pos = match desc.iter_names().position(|n| n == Some(&name)) {
+                Some(pos) => pos,
+                None => bail!(
+                    "column \"{}\" of relation \"{}\" does not exist",
+                    name,
+                    table_name
+                ),
+            };
+            if positions.contains(&pos) {
+                bail!("column \"{}\" specified more than once", name);
+            }
+            positions.push(pos);
+        }
+        positions
+    };
+    Ok(Plan::CopyFrom {
+        id: table.id(),
+        columns,
+        format,
+    })
+}
+
+fn handle_insert(
+    scx: &StatementContext,
+    table_name: ObjectName,
+    columns: Vec<Ident>,
+    source: Query,
+    params: &Params,
+) -> Result<Plan, failure::Error> {
+    let (id, mut expr, _param_types) = query::plan_insert_query(scx, table_name, columns, source)?;
+    expr.bind_parameters(&params);
+    Ok(Plan::Insert {
+        id,
+        values: expr.decorrelate(),
+    })
+}
+
+fn handle_mutation(
+    scx: &StatementContext,
+    table_name: ObjectName,
+    assignments: Vec<Assignment>,
+    selection: Option<Expr>,
+    kind: MutationKind,
+    params: &Params,
+) -> Result<Plan, failure::Error> {
+    let (id, mut expr, _param_types) =
+        query::plan_mutation_query(scx, table_name, assignments, selection)?;
+    expr.bind_parameters(&params);
+    Ok(Plan::ReadThenWrite {
+        id,
+        selection: expr.decorrelate(),
+        kind,
+    })
+}
+
 fn handle_select(
     scx: &StatementContext,
     query: Query,
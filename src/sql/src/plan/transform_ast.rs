@@ -256,6 +256,15 @@ impl Desugarer {
             }
         }
 
+        // `$expr AT TIME ZONE $tz` => `timezone($tz, $expr)`
+        if let Expr::AtTimeZone {
+            timestamp,
+            time_zone,
+        } = expr
+        {
+            *expr = Expr::call("timezone", vec![time_zone.take(), timestamp.take()]);
+        }
+
         // `$expr IN ($e1, $e2, ..., $en)`
         // =>
         // `$expr = $e1 OR $expr = $e2 OR ... OR $expr = $en`
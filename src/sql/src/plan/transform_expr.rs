@@ -9,11 +9,12 @@
 
 //! Transformations of SQL IR, before decorrelation.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::mem;
 
 use lazy_static::lazy_static;
 
+use expr::{Id, IdGen, LocalId};
 use repr::{ColumnType, RelationType, ScalarType};
 
 use crate::plan::expr::{
@@ -134,6 +135,66 @@ pub fn split_subquery_predicates(expr: &mut RelationExpr) {
     walk_relation(expr)
 }
 
+/// Rebinds the identifiers bound by `Let` and `Iterate` expressions to
+/// identifiers allocated from `id_gen`.
+///
+/// The planner allocates these identifiers itself, and they must not collide
+/// with those that decorrelation allocates for its own bindings.
+pub fn rebind_local_ids(expr: &mut RelationExpr, id_gen: &mut IdGen) {
+    fn walk_relation(
+        expr: &mut RelationExpr,
+        id_gen: &mut IdGen,
+        ids: &mut HashMap<LocalId, LocalId>,
+    ) {
+        expr.visit_mut(&mut |expr| match expr {
+            RelationExpr::Let { id, .. } | RelationExpr::Iterate { id, .. } => {
+                *id = rebind(*id, id_gen, ids)
+            }
+            RelationExpr::Get {
+                id: Id::Local(id), ..
+            } => *id = rebind(*id, id_gen, ids),
+            RelationExpr::Join { on, .. } => walk_scalar(on, id_gen, ids),
+            RelationExpr::Map { scalars, .. } => {
+                for scalar in scalars {
+                    walk_scalar(scalar, id_gen, ids);
+                }
+            }
+            RelationExpr::FlatMap { exprs, .. } => {
+                for expr in exprs {
+                    walk_scalar(expr, id_gen, ids);
+                }
+            }
+            RelationExpr::Filter { predicates, .. } => {
+                for predicate in predicates {
+                    walk_scalar(predicate, id_gen, ids);
+                }
+            }
+            RelationExpr::Reduce { aggregates, .. } => {
+                for aggregate in aggregates {
+                    walk_scalar(&mut aggregate.expr, id_gen, ids);
+                }
+            }
+            _ => (),
+        })
+    }
+
+    fn walk_scalar(expr: &mut ScalarExpr, id_gen: &mut IdGen, ids: &mut HashMap<LocalId, LocalId>) {
+        expr.visit_mut(&mut |expr| match expr {
+            ScalarExpr::Exists(input) | ScalarExpr::Select(input) => {
+                walk_relation(input, id_gen, ids)
+            }
+            _ => (),
+        })
+    }
+
+    fn rebind(id: LocalId, id_gen: &mut IdGen, ids: &mut HashMap<LocalId, LocalId>) -> LocalId {
+        *ids.entry(id)
+            .or_insert_with(|| LocalId::new(id_gen.allocate_id()))
+    }
+
+    walk_relation(expr, id_gen, &mut HashMap::new())
+}
+
 /// Rewrites quantified comparisons into simpler EXISTS operators.
 ///
 /// Note that this transformation is only valid when the expression is
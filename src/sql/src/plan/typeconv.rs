@@ -22,6 +22,7 @@ use repr::{ColumnName, ColumnType, Datum, ScalarType};
 
 use super::expr::{BinaryFunc, CoercibleScalarExpr, ScalarExpr, UnaryFunc};
 use super::query::ExprContext;
+use crate::unsupported;
 
 /// Describes methods of planning a conversion between [`ScalarType`]s, which
 /// can be invoked with [`CastOp::gen_expr`].
@@ -138,6 +139,40 @@ fn from_jsonb_f64_cast(
     from_f64_to_cast.gen_expr(ecx, e.call_unary(UnaryFunc::CastJsonbToFloat64), cast_to)
 }
 
+// Cast the string-like `e` to the `Char` type `cast_to`, truncating it as
+// necessary.
+fn to_char_cast(
+    _: &ExprContext,
+    e: ScalarExpr,
+    cast_to: CastTo,
+) -> Result<ScalarExpr, failure::Error> {
+    let length = match cast_to.scalar_type() {
+        ScalarType::Char { length } => length,
+        _ => unreachable!(),
+    };
+    Ok(e.call_unary(UnaryFunc::CastStringToChar {
+        length,
+        fail_on_len: matches!(cast_to, CastTo::Assignment(_)),
+    }))
+}
+
+// Cast the string-like `e` to the length-constrained `VarChar` type `cast_to`,
+// truncating it as necessary.
+fn to_varchar_cast(
+    _: &ExprContext,
+    e: ScalarExpr,
+    cast_to: CastTo,
+) -> Result<ScalarExpr, failure::Error> {
+    let length = match cast_to.scalar_type() {
+        ScalarType::VarChar { length } => length,
+        _ => unreachable!(),
+    };
+    Ok(e.call_unary(UnaryFunc::CastStringToVarChar {
+        length,
+        fail_on_len: matches!(cast_to, CastTo::Assignment(_)),
+    }))
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 /// Describes the context of the cast, the target type.
 pub enum CastTo {
@@ -146,6 +181,12 @@ pub enum CastTo {
     Implicit(ScalarType),
     /// Allow either explicit or implicit casts.
     Explicit(ScalarType),
+    /// Allow the casts permitted when storing a value in a table column.
+    ///
+    /// This is presently as permissive as `Explicit`, except that values too
+    /// long for a length-constrained string type are an error rather than
+    /// being truncated.
+    Assignment(ScalarType),
     /// Cast the source to a JSONB element directly, or cast to a compatible
     /// intermediary type (`ScalarType::String`, `ScalarType::Float64`) and then
     /// to a JSONB element.
@@ -155,7 +196,7 @@ pub enum CastTo {
 impl fmt::Display for CastTo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            CastTo::Implicit(t) | CastTo::Explicit(t) => write!(f, "{}", t),
+            CastTo::Implicit(t) | CastTo::Explicit(t) | CastTo::Assignment(t) => write!(f, "{}", t),
             CastTo::JsonbAny => write!(f, "jsonbany"),
         }
     }
@@ -164,7 +205,7 @@ impl fmt::Display for CastTo {
 impl CastTo {
     pub fn scalar_type(&self) -> ScalarType {
         match self {
-            CastTo::Implicit(t) | CastTo::Explicit(t) => t.clone(),
+            CastTo::Implicit(t) | CastTo::Explicit(t) | CastTo::Assignment(t) => t.clone(),
             CastTo::JsonbAny => ScalarType::Jsonb,
         }
     }
@@ -195,8 +236,21 @@ lazy_static! {
             (Bool, Explicit(String)) => CastBoolToStringExplicit,
             (Bool, JsonbAny) => CastJsonbOrNullToJsonb,
 
+            // INT16
+            (Int16, Implicit(Int32)) => CastInt16ToInt32,
+            (Int16, Implicit(Int64)) => CastInt16ToInt64,
+            (Int16, Implicit(Float32)) => CastInt16ToFloat32,
+            (Int16, Implicit(Float64)) => CastInt16ToFloat64,
+            (Int16, Implicit(Decimal(0, 0))) => CastOp::F(|_ecx, e, to_type| {
+                let (_, s) = to_type.scalar_type().unwrap_decimal_parts();
+                Ok(rescale_decimal(e.call_unary(CastInt16ToDecimal), 0, s))
+            }),
+            (Int16, Explicit(String)) => CastInt16ToString,
+            (Int16, JsonbAny) => CastOp::F(to_jsonb_any_f64_cast),
+
             //INT32
             (Int32, Explicit(Bool)) => CastInt32ToBool,
+            (Int32, Explicit(Int16)) => CastInt32ToInt16,
             (Int32, Implicit(Int64)) => CastInt32ToInt64,
             (Int32, Implicit(Float32)) => CastInt32ToFloat32,
             (Int32, Implicit(Float64)) => CastInt32ToFloat64,
@@ -209,6 +263,7 @@ lazy_static! {
 
             // INT64
             (Int64, Explicit(Bool)) => CastInt64ToBool,
+            (Int64, Explicit(Int16)) => CastInt64ToInt16,
             (Int64, Explicit(Int32)) => CastInt64ToInt32,
             (Int64, Implicit(Decimal(0, 0))) => CastOp::F(|_ecx, e, to_type| {
                 let (_, s) = to_type.scalar_type().unwrap_decimal_parts();
@@ -220,6 +275,7 @@ lazy_static! {
             (Int64, JsonbAny) => CastOp::F(to_jsonb_any_f64_cast),
 
             // FLOAT32
+            (Float32, Explicit(Int32)) => CastFloat32ToInt32,
             (Float32, Explicit(Int64)) => CastFloat32ToInt64,
             (Float32, Implicit(Float64)) => CastFloat32ToFloat64,
             (Float32, Explicit(Decimal(0, 0))) => CastOp::F(|_ecx, e, to_type| {
@@ -234,6 +290,7 @@ lazy_static! {
 
             // FLOAT64
             (Float64, Explicit(Int32)) => CastFloat64ToInt32,
+            (Float64, Explicit(Float32)) => CastFloat64ToFloat32,
             (Float64, Explicit(Int64)) => CastFloat64ToInt64,
             (Float64, Explicit(Decimal(0, 0))) => CastOp::F(|_ecx, e, to_type| {
                 let (_, s) = to_type.scalar_type().unwrap_decimal_parts();
@@ -310,10 +367,12 @@ lazy_static! {
 
             // BYTES
             (Bytes, Explicit(String)) => CastBytesToString,
+            (Bytes, Explicit(Uuid)) => CastBytesToUuid,
             (Bytes, JsonbAny) => CastOp::F(to_jsonb_any_string_cast),
 
             // STRING
             (String, Explicit(Bool)) => CastStringToBool,
+            (String, Explicit(Int16)) => CastStringToInt16,
             (String, Explicit(Int32)) => CastStringToInt32,
             (String, Explicit(Int64)) => CastStringToInt64,
             (String, Explicit(Float32)) => CastStringToFloat32,
@@ -328,9 +387,28 @@ lazy_static! {
             (String, Explicit(TimestampTz)) => CastStringToTimestampTz,
             (String, Explicit(Interval)) => CastStringToInterval,
             (String, Explicit(Bytes)) => CastStringToBytes,
+            (String, Explicit(Uuid)) => CastStringToUuid,
             (String, Explicit(Jsonb)) => CastStringToJsonb,
+            (String, Explicit(Char { length: None })) => CastOp::F(to_char_cast),
+            (String, Explicit(VarChar { length: None })) => CastOp::F(to_varchar_cast),
             (String, JsonbAny) => CastJsonbOrNullToJsonb,
 
+            // CHAR
+            (Char { length: None }, Implicit(String)) => CastCharToString,
+            (Char { length: None }, Implicit(Char { length: None })) => CastOp::F(to_char_cast),
+            (Char { length: None }, Implicit(VarChar { length: None })) => CastOp::F(to_varchar_cast),
+            (Char { length: None }, JsonbAny) => CastOp::F(to_jsonb_any_string_cast),
+
+            // VARCHAR
+            (VarChar { length: None }, Implicit(String)) => CastVarCharToString,
+            (VarChar { length: None }, Implicit(VarChar { length: None })) => CastOp::F(to_varchar_cast),
+            (VarChar { length: None }, JsonbAny) => CastOp::F(to_jsonb_any_string_cast),
+
+            // UUID
+            (Uuid, Explicit(String)) => CastUuidToString,
+            (Uuid, Explicit(Bytes)) => CastUuidToBytes,
+            (Uuid, JsonbAny) => CastOp::F(to_jsonb_any_string_cast),
+
             // RECORD
             (Record { fields: vec![] }, JsonbAny) => CastOp::F(to_jsonb_any_record_cast),
 
@@ -360,7 +438,8 @@ pub fn get_cast<'a>(from: &ScalarType, cast_to: &CastTo) -> Option<&'a CastOp> {
 
     let cast_to = match cast_to {
         Implicit(t) => Implicit(t.desaturate()),
-        Explicit(t) => Explicit(t.desaturate()),
+        // Assignment casts are planned using the explicit cast catalog.
+        Explicit(t) | Assignment(t) => Explicit(t.desaturate()),
         JsonbAny => JsonbAny,
     };
 
@@ -400,16 +479,20 @@ pub fn rescale_decimal(expr: ScalarExpr, s1: u8, s2: u8) -> ScalarExpr {
 fn guess_compatible_cast_type(types: &[ScalarType]) -> Option<&ScalarType> {
     types.iter().max_by_key(|scalar_type| match scalar_type {
         // [`TypeCategory::Numeric`]
-        ScalarType::Int32 => 0,
-        ScalarType::Int64 => 1,
-        ScalarType::Decimal(_, _) => 2,
-        ScalarType::Float32 => 3,
-        ScalarType::Float64 => 4,
+        ScalarType::Int16 => 0,
+        ScalarType::Int32 => 1,
+        ScalarType::Int64 => 2,
+        ScalarType::Decimal(_, _) => 3,
+        ScalarType::Float32 => 4,
+        ScalarType::Float64 => 5,
         // [`TypeCategory::DateTime`]
-        ScalarType::Date => 5,
-        ScalarType::Timestamp => 6,
-        ScalarType::TimestampTz => 7,
-        _ => 8,
+        ScalarType::Date => 6,
+        ScalarType::Timestamp => 7,
+        ScalarType::TimestampTz => 8,
+        // [`TypeCategory::String`]
+        ScalarType::Char { .. } => 9,
+        ScalarType::VarChar { .. } => 10,
+        _ => 11,
     })
 }
 
@@ -455,6 +538,10 @@ pub fn guess_best_common_type(types: &[Option<ScalarType>]) -> Option<ScalarType
                 }
             }
             return Some(ScalarType::Decimal(38, max_s));
+        } else if let ScalarType::Char { .. } | ScalarType::VarChar { .. } = btt {
+            // Values of differing lengths are unified by dropping the length
+            // constraint, as PostgreSQL does.
+            return Some(btt.desaturate());
         } else {
             return Some(btt.clone());
         }
@@ -548,6 +635,41 @@ pub fn plan_coerce<'a>(
             }
         }
 
+        (LiteralArray(exprs), coerce_to) => {
+            let elem_type = match coerce_to {
+                Plain(ScalarType::Array(typ)) => *typ,
+                Plain(_) if exprs.is_empty() => bail!("cannot determine type of empty array"),
+                Plain(_) => {
+                    let types: Vec<_> = exprs
+                        .iter()
+                        .map(|e| ecx.column_type(e).map(|t| t.scalar_type))
+                        .collect();
+                    match guess_best_common_type(&types) {
+                        Some(typ) => typ,
+                        None => bail!("cannot determine homogeneous type for array elements"),
+                    }
+                }
+                JsonbAny => bail!("cannot coerce array literal to jsonb type"),
+            };
+            if let ScalarType::Array(_) | ScalarType::List(_) = elem_type {
+                unsupported!(format!("arrays of type {}", elem_type));
+            }
+            let mut out = vec![];
+            for e in exprs {
+                let e = plan_coerce(ecx, e, Plain(elem_type.clone()))?;
+                out.push(plan_cast(
+                    "ARRAY",
+                    ecx,
+                    e,
+                    CastTo::Implicit(elem_type.clone()),
+                )?);
+            }
+            ScalarExpr::CallVariadic {
+                func: VariadicFunc::ArrayCreate { elem_type },
+                exprs: out,
+            }
+        }
+
         (LiteralRecord(exprs), coerce_to) => {
             let arity = exprs.len();
             let coercions = match coerce_to {
@@ -601,6 +723,17 @@ pub fn plan_cast<'a>(
 
     let cast_op = match get_cast(&from_scalar_type, &cast_to) {
         Some(cast_op) => cast_op,
+        // Any type can be explicitly cast to or from the length-constrained
+        // string types by way of its text representation.
+        None if !matches!(cast_to, CastTo::Implicit(_) | CastTo::JsonbAny)
+            && (is_char_or_varchar(&from_scalar_type)
+                || is_char_or_varchar(&cast_to.scalar_type()))
+            && get_cast(&from_scalar_type, &CastTo::Explicit(ScalarType::String)).is_some()
+            && get_cast(&ScalarType::String, &cast_to).is_some() =>
+        {
+            let expr = plan_cast(caller_name, ecx, expr, CastTo::Explicit(ScalarType::String))?;
+            return plan_cast(caller_name, ecx, expr, cast_to);
+        }
         None => bail!(
             "{} does not support {}casting from {} to {}",
             caller_name,
@@ -616,3 +749,7 @@ pub fn plan_cast<'a>(
 
     cast_op.gen_expr(ecx, expr, cast_to)
 }
+
+fn is_char_or_varchar(typ: &ScalarType) -> bool {
+    matches!(typ, ScalarType::Char { .. } | ScalarType::VarChar { .. })
+}
@@ -0,0 +1,162 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Provides convenience functions for working with upstream PostgreSQL
+//! databases from the `sql` package.
+
+use failure::{bail, ResultExt};
+use log::error;
+use tokio_postgres::types::Type;
+use tokio_postgres::{Client, NoTls};
+
+use repr::adt::decimal::MAX_DECIMAL_PRECISION;
+use sql_parser::ast::{ColumnDef, ColumnOption, ColumnOptionDef, DataType, Ident};
+
+/// Connects to the PostgreSQL database described by `conn`, a libpq-style
+/// connection string.
+async fn connect(conn: &str) -> Result<Client, failure::Error> {
+    let (client, connection) = tokio_postgres::connect(conn, NoTls)
+        .await
+        .with_context(|e| format!("connecting to PostgreSQL: {}", e))?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            error!("PostgreSQL connection error: {}", e);
+        }
+    });
+    Ok(client)
+}
+
+/// Verifies that the table `namespace.table` can be replicated through
+/// `publication` and returns its column definitions.
+///
+/// Changes can only be turned into retractions if PostgreSQL reports the
+/// complete old version of each updated or deleted row, so the table must
+/// have `REPLICA IDENTITY FULL`.
+pub async fn fetch_table_columns(
+    conn: &str,
+    publication: &str,
+    namespace: &str,
+    table: &str,
+) -> Result<Vec<ColumnDef>, failure::Error> {
+    let client = connect(conn).await?;
+
+    let rows = client
+        .query(
+            "SELECT c.oid, c.relreplident
+             FROM pg_catalog.pg_class c
+             JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+             WHERE n.nspname = $1 AND c.relname = $2 AND c.relkind = 'r'",
+            &[&namespace, &table],
+        )
+        .await?;
+    let (oid, replica_identity): (u32, i8) = match rows.first() {
+        Some(row) => (row.get(0), row.get(1)),
+        None => bail!("PostgreSQL table {}.{} does not exist", namespace, table),
+    };
+    if replica_identity != b'f' as i8 {
+        bail!(
+            "PostgreSQL table {}.{} must have REPLICA IDENTITY FULL",
+            namespace,
+            table
+        );
+    }
+
+    let published = client
+        .query(
+            "SELECT 1 FROM pg_catalog.pg_publication_tables
+             WHERE pubname = $1 AND schemaname = $2 AND tablename = $3",
+            &[&publication, &namespace, &table],
+        )
+        .await?;
+    if published.is_empty() {
+        bail!(
+            "PostgreSQL table {}.{} is not part of publication {}",
+            namespace,
+            table,
+            publication
+        );
+    }
+
+    let rows = client
+        .query(
+            "SELECT attname, atttypid, atttypmod, attnotnull
+             FROM pg_catalog.pg_attribute
+             WHERE attrelid = $1 AND attnum > 0 AND NOT attisdropped
+             ORDER BY attnum",
+            &[&oid],
+        )
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let name: String = row.get(0);
+            let oid: u32 = row.get(1);
+            let typmod: i32 = row.get(2);
+            let not_null: bool = row.get(3);
+            let mut options = vec![];
+            if not_null {
+                options.push(ColumnOptionDef {
+                    name: None,
+                    option: ColumnOption::NotNull,
+                });
+            }
+            ColumnDef {
+                name: Ident::new(name),
+                data_type: data_type_from_oid(oid, typmod),
+                collation: None,
+                options,
+            }
+        })
+        .collect())
+}
+
+/// Determines the type of a column with the PostgreSQL type `oid` and type
+/// modifier `typmod`.
+///
+/// Logical replication transmits values in their text format, so types that
+/// have no Materialize counterpart are mirrored as text.
+fn data_type_from_oid(oid: u32, typmod: i32) -> DataType {
+    let name = match Type::from_oid(oid) {
+        Some(ty) => ty.name(),
+        None => return DataType::Text,
+    };
+    match name {
+        "bool" => DataType::Boolean,
+        "int2" => DataType::SmallInt,
+        "int4" => DataType::Int,
+        "int8" => DataType::BigInt,
+        "float4" => DataType::Real,
+        "float8" => DataType::Double,
+        // An unconstrained numeric can hold values of any scale, which no
+        // Materialize decimal can represent exactly.
+        "numeric" if typmod >= 4 => {
+            let precision = ((typmod - 4) >> 16) as u64;
+            let scale = ((typmod - 4) & 0xffff) as u64;
+            if precision > MAX_DECIMAL_PRECISION.into() {
+                DataType::Text
+            } else {
+                DataType::Decimal(Some(precision), Some(scale))
+            }
+        }
+        // Length-constrained string types store their length plus four in
+        // their type modifier.
+        "bpchar" if typmod >= 4 => DataType::Char(Some((typmod - 4) as u64)),
+        "varchar" if typmod >= 4 => DataType::Varchar(Some((typmod - 4) as u64)),
+        "varchar" => DataType::Varchar(None),
+        "date" => DataType::Date,
+        "time" => DataType::Time,
+        "timestamp" => DataType::Timestamp,
+        "timestamptz" => DataType::TimestampTz,
+        "interval" => DataType::Interval,
+        "bytea" => DataType::Bytea,
+        "json" | "jsonb" => DataType::Jsonb,
+        "uuid" => DataType::Uuid,
+        _ => DataType::Text,
+    }
+}
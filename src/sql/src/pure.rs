@@ -19,6 +19,7 @@ use sql_parser::ast::{AvroSchema, Connector, CsrSeed, Format, Ident, Statement};
 
 use crate::kafka_util;
 use crate::normalize;
+use crate::postgres_util;
 
 /// Removes dependencies on external state from `stmt`: inlining schemas in
 /// files, fetching schemas from registries, and so on. The [`Statement`]
@@ -67,6 +68,19 @@ pub async fn purify(mut stmt: Statement) -> Result<Statement, failure::Error> {
                 let path = path.clone();
                 file = Some(tokio::fs::File::open(path).await?);
             }
+            Connector::Postgres {
+                conn,
+                publication,
+                namespace,
+                table,
+                columns,
+            } => {
+                let fetched =
+                    postgres_util::fetch_table_columns(conn, publication, namespace, table).await?;
+                if columns.is_empty() {
+                    *columns = fetched;
+                }
+            }
             _ => (),
         }
 
@@ -75,6 +89,13 @@ pub async fn purify(mut stmt: Statement) -> Result<Statement, failure::Error> {
             purify_format(format, connector, col_names, None, &config_options).await?;
         }
     }
+    if let Statement::CreateSink {
+        format: Some(Format::Protobuf { schema, .. }),
+        ..
+    } = &mut stmt
+    {
+        purify_protobuf_schema(schema).await?;
+    }
     Ok(stmt)
 }
 
@@ -123,14 +144,7 @@ async fn purify_format(
             }
             _ => {}
         },
-        Some(Format::Protobuf { schema, .. }) => {
-            if let sql_parser::ast::Schema::File(path) = schema {
-                let descriptors = tokio::fs::read(path).await?;
-                let mut buf = String::new();
-                strconv::format_bytes(&mut buf, &descriptors);
-                *schema = sql_parser::ast::Schema::Inline(buf);
-            }
-        }
+        Some(Format::Protobuf { schema, .. }) => purify_protobuf_schema(schema).await?,
         Some(Format::Csv {
             header_row,
             delimiter,
@@ -158,6 +172,18 @@ async fn purify_format(
     Ok(())
 }
 
+async fn purify_protobuf_schema(
+    schema: &mut sql_parser::ast::Schema,
+) -> Result<(), failure::Error> {
+    if let sql_parser::ast::Schema::File(path) = schema {
+        let descriptors = tokio::fs::read(path).await?;
+        let mut buf = String::new();
+        strconv::format_bytes(&mut buf, &descriptors);
+        *schema = sql_parser::ast::Schema::Inline(buf);
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct Schema {
     pub key_schema: Option<String>,
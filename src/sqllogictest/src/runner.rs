@@ -49,6 +49,7 @@ use coord::{ExecuteResponse, TimestampConfig};
 use dataflow_types::PeekResponse;
 use ore::option::OptionExt;
 use ore::thread::{JoinHandleExt, JoinOnDropHandle};
+use repr::adt::char;
 use repr::{ColumnName, ColumnType, RelationDesc, Row, ScalarType};
 use sql::ast::Statement;
 
@@ -322,18 +323,25 @@ fn format_row(
                 d.unwrap_bool().to_string()
             }
 
+            (Type::Integer, ScalarType::Int16) => d.unwrap_int16().to_string(),
             (Type::Integer, ScalarType::Int32) => d.unwrap_int32().to_string(),
             (Type::Integer, ScalarType::Int64) => d.unwrap_int64().to_string(),
             (Type::Integer, ScalarType::Decimal(_, s)) => {
                 let d = d.unwrap_decimal().with_scale(*s);
                 format!("{:.0}", d)
             }
+            (Type::Integer, ScalarType::Float32) => format!("{:.0}", d.unwrap_float32().trunc()),
             (Type::Integer, ScalarType::Float64) => format!("{:.0}", d.unwrap_float64().trunc()),
             (Type::Integer, ScalarType::String) => "0".to_owned(),
             (Type::Integer, ScalarType::Bool) => i8::from(d.unwrap_bool()).to_string(),
 
+            (Type::Real, ScalarType::Int16) => format!("{:.3}", d.unwrap_int16()),
             (Type::Real, ScalarType::Int32) => format!("{:.3}", d.unwrap_int32()),
             (Type::Real, ScalarType::Int64) => format!("{:.3}", d.unwrap_int64()),
+            (Type::Real, ScalarType::Float32) => match mode {
+                Mode::Standard => format!("{:.3}", d.unwrap_float32()),
+                Mode::Cockroach => format!("{}", d.unwrap_float32()),
+            },
             (Type::Real, ScalarType::Float64) => match mode {
                 Mode::Standard => format!("{:.3}", d.unwrap_float64()),
                 Mode::Cockroach => format!("{}", d.unwrap_float64()),
@@ -346,8 +354,10 @@ fn format_row(
                 }
             }
 
+            (Type::Text, ScalarType::Int16) => format!("{}", d.unwrap_int16()),
             (Type::Text, ScalarType::Int32) => format!("{}", d.unwrap_int32()),
             (Type::Text, ScalarType::Int64) => format!("{}", d.unwrap_int64()),
+            (Type::Text, ScalarType::Float32) => format!("{:.3}", d.unwrap_float32()),
             (Type::Text, ScalarType::Float64) => format!("{:.3}", d.unwrap_float64()),
             // Bytes are printed as text iff they are valid UTF-8. This
             // seems guaranteed to confuse everyone, but it is required for
@@ -358,9 +368,15 @@ fn format_row(
                 Ok(s) => s.to_owned(),
                 Err(_) => format!("{:?}", d.unwrap_bytes()),
             },
-            (Type::Text, ScalarType::String) => match d.unwrap_str() {
-                "" => "(empty)".to_owned(),
-                s => s.to_owned(),
+            (Type::Text, ScalarType::String) | (Type::Text, ScalarType::VarChar { .. }) => {
+                match d.unwrap_str() {
+                    "" => "(empty)".to_owned(),
+                    s => s.to_owned(),
+                }
+            }
+            (Type::Text, ScalarType::Char { length }) => match char::pad(d.unwrap_str(), *length) {
+                s if s.is_empty() => "(empty)".to_owned(),
+                s => s,
             },
             (Type::Text, _) => {
                 let mut buf = BytesMut::new();
@@ -31,15 +31,16 @@ use std::env;
 use chrono::Utc;
 use failure::{bail, format_err};
 use tokio_postgres::types::FromSql;
+use uuid::Uuid;
 
 use pgrepr::Jsonb;
+use repr::adt::char;
 use repr::adt::decimal::Significand;
-use repr::{ColumnType, Datum, RelationDesc, RelationType, Row, RowPacker, ScalarType};
-use sql::ast::{ColumnOption, DataType, ObjectType, Statement, TableConstraint};
+use repr::{Datum, RelationDesc, Row, RowPacker, ScalarType};
+use sql::ast::{DataType, ObjectType, Statement};
 use sql::catalog::Catalog;
 use sql::names::FullName;
-use sql::normalize;
-use sql::plan::{scalar_type_from_sql, MutationKind, Plan, PlanContext, StatementContext};
+use sql::plan::{scalar_type_from_sql, MutationKind, Params, Plan, PlanContext, StatementContext};
 
 pub struct Postgres {
     client: tokio_postgres::Client,
@@ -111,7 +112,10 @@ END $$;
     pub fn can_handle(&self, stmt: &Statement) -> bool {
         match stmt {
             Statement::CreateTable { .. }
-            | Statement::DropObjects { .. }
+            | Statement::DropObjects {
+                object_type: ObjectType::Table,
+                ..
+            }
             | Statement::Delete { .. }
             | Statement::Insert { .. }
             | Statement::Update { .. } => true,
@@ -127,86 +131,22 @@ END $$;
     ) -> Result<Plan, failure::Error> {
         let scx = StatementContext { pcx, catalog };
         Ok(match stmt {
-            Statement::CreateTable {
-                name,
-                columns,
-                constraints,
-                if_not_exists,
-                ..
-            } => {
+            Statement::CreateTable { columns, .. } => {
                 let sql_types: Vec<_> = columns
                     .iter()
                     .map(|column| column.data_type.clone())
                     .collect();
-
-                let names: Vec<_> = columns
-                    .iter()
-                    .map(|c| Some(sql::normalize::column_name(c.name.clone())))
-                    .collect();
-
-                // Build initial relation type that handles declared data types
-                // and NOT NULL constraints.
-                let mut typ = RelationType::new(
-                    columns
-                        .iter()
-                        .map(|c| {
-                            let ty = scalar_type_from_sql(&c.data_type)?;
-                            let nullable =
-                                !c.options.iter().any(|o| o.option == ColumnOption::NotNull);
-                            Ok(ColumnType::new(ty).nullable(nullable))
-                        })
-                        .collect::<Result<Vec<_>, failure::Error>>()?,
-                );
-
-                // Handle column-level UNIQUE and PRIMARY KEY constraints.
-                // PRIMARY KEY implies UNIQUE and NOT NULL.
-                for (index, column) in columns.iter().enumerate() {
-                    for option in column.options.iter() {
-                        if let ColumnOption::Unique { is_primary } = option.option {
-                            typ = typ.with_key(vec![index]);
-                            if is_primary {
-                                typ.column_types[index].nullable = false;
-                            }
-                        }
-                    }
-                }
-
-                // Handle table-level UNIQUE and PRIMARY KEY constraints.
-                // PRIMARY KEY implies UNIQUE and NOT NULL.
-                for constraint in constraints {
-                    if let TableConstraint::Unique {
-                        name: _,
-                        columns,
-                        is_primary,
-                    } = constraint
-                    {
-                        let mut key = vec![];
-                        for column in columns {
-                            let name = normalize::column_name(column.clone());
-                            match names.iter().position(|n| n.as_ref() == Some(&name)) {
-                                None => bail!("unknown column {} in unique constraint", name),
-                                Some(i) => key.push(i),
-                            }
-                        }
-                        if *is_primary {
-                            for i in key.iter() {
-                                typ.column_types[*i].nullable = false;
-                            }
-                        }
-                        typ = typ.with_key(key);
-                    }
-                }
-
+                let params = Params {
+                    datums: Row::pack(&[]),
+                    types: vec![],
+                };
+                let plan = sql::plan::plan(pcx, catalog, stmt.clone(), &params)?;
                 self.client.execute(&*stmt.to_string(), &[]).await?;
-                let name = scx.allocate_name(normalize::object_name(name.clone())?);
-                let desc = RelationDesc::new(typ, names);
-                self.table_types
-                    .insert(name.clone(), (sql_types, desc.clone()));
-                Plan::CreateTable {
-                    name,
-                    desc,
-                    if_not_exists: *if_not_exists,
+                if let Plan::CreateTable { name, table, .. } = &plan {
+                    self.table_types
+                        .insert(name.clone(), (sql_types, table.desc.clone()));
                 }
+                plan
             }
             Statement::DropObjects {
                 names,
@@ -338,12 +278,16 @@ fn push_column(
             let bool = get_column_inner::<bool>(postgres_row, i, nullable)?;
             row.push(bool.into());
         }
-        DataType::Char(_) | DataType::Varchar(_) | DataType::Text => {
+        DataType::Char(_) => {
+            let string = get_column_inner::<String>(postgres_row, i, nullable)?;
+            row.push(string.as_deref().map(char::strip_padding).into());
+        }
+        DataType::Varchar(_) | DataType::Text => {
             let string = get_column_inner::<String>(postgres_row, i, nullable)?;
             row.push(string.as_deref().into());
         }
         DataType::SmallInt => {
-            let i = get_column_inner::<i16>(postgres_row, i, nullable)?.map(i32::from);
+            let i = get_column_inner::<i16>(postgres_row, i, nullable)?;
             row.push(i.into());
         }
         DataType::Int => {
@@ -356,7 +300,7 @@ fn push_column(
         }
         DataType::Float(p) => {
             if p.unwrap_or(53) <= 24 {
-                let f = get_column_inner::<f32>(postgres_row, i, nullable)?.map(f64::from);
+                let f = get_column_inner::<f32>(postgres_row, i, nullable)?;
                 row.push(f.into());
             } else {
                 let f = get_column_inner::<f64>(postgres_row, i, nullable)?;
@@ -364,7 +308,7 @@ fn push_column(
             }
         }
         DataType::Real => {
-            let f = get_column_inner::<f32>(postgres_row, i, nullable)?.map(f64::from);
+            let f = get_column_inner::<f32>(postgres_row, i, nullable)?;
             row.push(f.into());
         }
         DataType::Double => {
@@ -417,6 +361,10 @@ fn push_column(
             let bytes = get_column_inner::<Vec<u8>>(postgres_row, i, nullable)?;
             row.push(bytes.as_deref().into());
         }
+        DataType::Uuid => {
+            let u = get_column_inner::<Uuid>(postgres_row, i, nullable)?;
+            row.push(u.into());
+        }
         DataType::Jsonb => {
             let jsonb = get_column_inner::<Jsonb>(postgres_row, i, nullable)?;
             if let Some(jsonb) = jsonb {
@@ -36,6 +36,7 @@ mod avro_ocf;
 mod file;
 mod kafka;
 mod kinesis;
+mod postgres;
 mod sleep;
 mod sql;
 
@@ -57,6 +58,8 @@ pub struct Config {
     pub aws_credentials: AwsCredentials,
     pub materialized_pgconfig: tokio_postgres::Config,
     pub materialized_catalog_path: Option<PathBuf>,
+    pub reset_materialized: bool,
+    pub seed: Option<u32>,
 }
 
 impl Default for Config {
@@ -85,6 +88,8 @@ impl Default for Config {
                 tokio_postgres::Config::new().host("localhost").port(6875),
             ),
             materialized_catalog_path: None,
+            reset_materialized: true,
+            seed: None,
         }
     }
 }
@@ -329,6 +334,9 @@ pub fn build(cmds: Vec<PosCommand>, state: &State) -> Result<Vec<PosAction>, Err
                     }
                     "kinesis-ingest" => Box::new(kinesis::build_ingest(builtin).map_err(wrap_err)?),
                     "kinesis-verify" => Box::new(kinesis::build_verify(builtin).map_err(wrap_err)?),
+                    "postgres-execute" => {
+                        Box::new(postgres::build_execute(builtin).map_err(wrap_err)?)
+                    }
                     "set-sql-timeout" => {
                         let duration = builtin.args.string("duration").map_err(wrap_err)?;
                         if duration.to_lowercase() == "default" {
@@ -413,7 +421,7 @@ fn substitute_vars(msg: &str, vars: &HashMap<String, String>) -> Result<String,
 pub async fn create_state(
     config: &Config,
 ) -> Result<(State, impl Future<Output = Result<(), Error>>), Error> {
-    let seed = rand::thread_rng().gen();
+    let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
     let temp_dir = tempfile::tempdir().err_ctx("creating temporary directory".into())?;
 
     let data_dir = if let Some(path) = &config.materialized_catalog_path {
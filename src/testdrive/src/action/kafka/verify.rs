@@ -22,18 +22,28 @@ use crate::action::{Action, State};
 use crate::format::avro;
 use crate::parser::BuiltinCommand;
 
+pub enum SinkFormat {
+    Avro,
+    Json,
+}
+
 pub enum SinkConsistencyFormat {
     Debezium,
 }
 
 pub struct VerifyAction {
+    format: SinkFormat,
     sink: String,
     consistency: Option<SinkConsistencyFormat>,
     expected_messages: Vec<String>,
 }
 
 pub fn build_verify(mut cmd: BuiltinCommand) -> Result<VerifyAction, String> {
-    let _format = cmd.args.string("format")?;
+    let format = match cmd.args.string("format")?.as_str() {
+        "avro" => SinkFormat::Avro,
+        "json" => SinkFormat::Json,
+        f => return Err(format!("unknown sink format {}", f)),
+    };
     let sink = cmd.args.string("sink")?;
     let consistency = match cmd.args.opt_string("consistency").as_deref() {
         Some("debezium") => Some(SinkConsistencyFormat::Debezium),
@@ -44,6 +54,7 @@ pub fn build_verify(mut cmd: BuiltinCommand) -> Result<VerifyAction, String> {
     let expected_messages = cmd.input;
     cmd.args.done()?;
     Ok(VerifyAction {
+        format,
         sink,
         consistency,
         expected_messages,
@@ -78,21 +89,13 @@ impl Action for VerifyAction {
 
         println!("Verifying results in Kafka topic {}", topic);
 
-        let schema = state
-            .ccsr_client
-            .get_schema_by_subject(&format!("{}-value", topic))
-            .await
-            .map_err(|e| format!("fetching schema: {}", e))?
-            .raw;
-
         let mut config = ClientConfig::new();
         config.set("bootstrap.servers", &state.kafka_url);
         config.set("auto.offset.reset", "earliest");
         config.set("group.id", "materialize-testdrive");
-
-        let schema =
-            avro::parse_schema(&schema).map_err(|e| format!("parsing avro schema: {}", e))?;
-        let schema = &schema;
+        // Messages from transactions that were aborted, or that have not yet
+        // been committed, are not part of the sink's output.
+        config.set("isolation.level", "read_committed");
 
         let consumer: StreamConsumer = config
             .create()
@@ -114,34 +117,181 @@ impl Action for VerifyAction {
         // were missing.
         while let Some(Ok(message)) = message_stream.next().await {
             let message = message.map_err(|e| e.to_string())?;
+            actual_messages.push((
+                message.key().map(|k| k.to_vec()),
+                message.payload().map(|p| p.to_vec()),
+            ));
+        }
 
-            let mut bytes = match message.payload() {
-                None => return Err("empty message payload".into()),
-                Some(bytes) => bytes,
-            };
+        match self.format {
+            SinkFormat::Avro => {
+                let expected = parse_expected_messages(&self.expected_messages)?;
+
+                // Keyed messages, as written by upsert sinks, have their keys
+                // validated against the key schema.
+                if expected.iter().any(|(key, _)| key.is_some()) {
+                    let key_schema = fetch_avro_schema(state, &format!("{}-key", topic)).await?;
+                    let expected_keys = expected
+                        .iter()
+                        .map(|(key, _)| match key {
+                            Some(key) => Ok(key.to_string()),
+                            None => Err("expected messages must all have keys or all lack keys"),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let actual_keys = actual_messages
+                        .iter()
+                        .map(|(key, _payload)| decode_avro(&key_schema, key.as_deref()))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    avro::validate_sink(&key_schema, &expected_keys, &actual_keys)?;
+                }
 
-            if bytes.len() < 5 {
-                return Err(format!(
-                    "avro datum is too few bytes: expected at least 5 bytes, got {}",
-                    bytes.len()
-                ));
+                // A `null` value matches a message without a payload, which
+                // is how upsert sinks write deletions.
+                let schema = fetch_avro_schema(state, &format!("{}-value", topic)).await?;
+                let expected_values: Vec<_> = expected
+                    .iter()
+                    .filter(|(_, value)| !value.is_null())
+                    .map(|(_, value)| value.to_string())
+                    .collect();
+                let actual_values = actual_messages
+                    .iter()
+                    .filter(|(_key, payload)| payload.is_some())
+                    .map(|(_key, payload)| decode_avro(&schema, payload.as_deref()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                avro::validate_sink(&schema, &expected_values, &actual_values)?;
+
+                let expected_tombstones: Vec<_> = expected
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, value))| value.is_null())
+                    .map(|(i, _)| i)
+                    .collect();
+                let actual_tombstones: Vec<_> = actual_messages
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, payload))| payload.is_none())
+                    .map(|(i, _)| i)
+                    .collect();
+                if expected_tombstones != actual_tombstones {
+                    return Err(format!(
+                        "expected messages {:?} to lack payloads, but messages {:?} lack payloads",
+                        expected_tombstones, actual_tombstones
+                    ));
+                }
+                Ok(())
             }
-            let magic = bytes[0];
-            let _schema_id = BigEndian::read_i32(&bytes[1..5]);
-            bytes = &bytes[5..];
-
-            if magic != 0 {
-                return Err(format!(
-                    "wrong avro serialization magic: expected 0, got {}",
-                    bytes[0]
-                ));
+            SinkFormat::Json => validate_json_sink(&self.expected_messages, &actual_messages),
+        }
+    }
+}
+
+async fn fetch_avro_schema(state: &State, subject: &str) -> Result<avro::Schema, String> {
+    let schema = state
+        .ccsr_client
+        .get_schema_by_subject(subject)
+        .await
+        .map_err(|e| format!("fetching schema: {}", e))?
+        .raw;
+    avro::parse_schema(&schema).map_err(|e| format!("parsing avro schema: {}", e))
+}
+
+fn decode_avro(schema: &avro::Schema, payload: Option<&[u8]>) -> Result<avro::Value, String> {
+    let mut bytes = match payload {
+        None => return Err("empty message payload".into()),
+        Some(bytes) => bytes,
+    };
+
+    if bytes.len() < 5 {
+        return Err(format!(
+            "avro datum is too few bytes: expected at least 5 bytes, got {}",
+            bytes.len()
+        ));
+    }
+    let magic = bytes[0];
+    let _schema_id = BigEndian::read_i32(&bytes[1..5]);
+    bytes = &bytes[5..];
+
+    if magic != 0 {
+        return Err(format!(
+            "wrong avro serialization magic: expected 0, got {}",
+            bytes[0]
+        ));
+    }
+
+    avro::from_avro_datum(schema, &mut bytes)
+        .map_err(|e| format!("from_avro_datum: {}", e.to_string()))
+}
+
+/// Parses expected messages into their keys and payloads.
+///
+/// Each expected message is either a single JSON value, which must match the
+/// payload of a message without a key, or a pair of JSON values separated by
+/// whitespace, which must match the key and payload of a keyed message. A
+/// `null` payload matches a message without a payload.
+fn parse_expected_messages(
+    expected: &[String],
+) -> Result<Vec<(Option<serde_json::Value>, serde_json::Value)>, String> {
+    expected
+        .iter()
+        .map(|line| {
+            let mut values = serde_json::Deserializer::from_str(line)
+                .into_iter::<serde_json::Value>()
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("parsing expected message as json: {}", e))?;
+            match values.len() {
+                1 => Ok((None, values.remove(0))),
+                2 => {
+                    let payload = values.remove(1);
+                    Ok((Some(values.remove(0)), payload))
+                }
+                n => Err(format!(
+                    "expected message must contain one or two JSON values, but found {}",
+                    n
+                )),
             }
+        })
+        .collect()
+}
 
-            let datum = avro::from_avro_datum(schema, &mut bytes)
-                .map_err(|e| format!("from_avro_datum: {}", e.to_string()))?;
-            actual_messages.push(datum);
+/// Validates the keys and payloads of JSON-encoded messages, which are
+/// expected as described in [`parse_expected_messages`].
+fn validate_json_sink(
+    expected: &[String],
+    actual: &[(Option<Vec<u8>>, Option<Vec<u8>>)],
+) -> Result<(), String> {
+    let parse = |bytes: &[u8]| {
+        serde_json::from_slice::<serde_json::Value>(bytes)
+            .map_err(|e| format!("parsing sink message as json: {}", e))
+    };
+    let expected = parse_expected_messages(expected)?;
+    let actual = actual
+        .iter()
+        .map(|(key, payload)| {
+            let key = key.as_deref().map(parse).transpose()?;
+            let payload = match payload {
+                None => serde_json::Value::Null,
+                Some(payload) => parse(payload)?,
+            };
+            Ok((key, payload))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let mut expected = expected.iter();
+    let mut actual = actual.iter();
+    for (i, (e, a)) in (&mut expected).zip(&mut actual).enumerate() {
+        if e != a {
+            return Err(format!(
+                "record {} did not match\nexpected:\n{:?}\n\nactual:\n{:?}",
+                i, e, a
+            ));
         }
-
-        avro::validate_sink(schema, &self.expected_messages, &actual_messages)
+    }
+    let expected: Vec<_> = expected.map(|e| format!("{:?}", e)).collect();
+    let actual: Vec<_> = actual.map(|a| format!("{:?}", a)).collect();
+    if !expected.is_empty() {
+        Err(format!("missing records:\n{}", expected.join("\n")))
+    } else if !actual.is_empty() {
+        Err(format!("extra records:\n{}", actual.join("\n")))
+    } else {
+        Ok(())
     }
 }
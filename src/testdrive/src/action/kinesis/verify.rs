@@ -16,6 +16,7 @@ use itertools::Itertools;
 use rusoto_kinesis::{GetRecordsInput, Kinesis, KinesisClient};
 
 use aws_util::kinesis::{get_shard_ids, get_shard_iterator};
+use ore::retry;
 
 use crate::action::{Action, State};
 use crate::parser::BuiltinCommand;
@@ -47,59 +48,70 @@ impl Action for VerifyAction {
     async fn redo(&self, state: &mut State) -> Result<(), String> {
         let stream_name = format!("testdrive-{}-{}", self.stream_prefix, state.seed);
 
-        let mut shard_iterators = get_shard_iterators(&state.kinesis_client, &stream_name).await?;
-        let timer = Instant::now();
-        let mut records: HashSet<String> = HashSet::new();
-        while let Some(iterator) = shard_iterators.pop_front() {
-            if let Some(iterator) = &iterator {
-                let output = state
-                    .kinesis_client
-                    .get_records(GetRecordsInput {
-                        limit: None,
-                        shard_iterator: iterator.clone(),
-                    })
-                    .await
-                    .map_err(|e| format!("getting Kinesis records: {}", e))?;
-                for record in output.records {
-                    records.insert(
-                        String::from_utf8(record.data.to_vec()).map_err(|e| {
-                            format!("converting Kinesis record bytes to utf8: {}", e)
-                        })?,
-                    );
-                }
-                match output.millis_behind_latest {
-                    // Test hack!
-                    // Assume all records have already been written to the stream. Once you've
-                    // caught up, you're done with that shard.
-                    // NOTE: this is not true for real Kinesis streams as data could still be
-                    // arriving.
-                    Some(0) => (),
-                    _ => shard_iterators.push_back(output.next_shard_iterator),
-                };
-                if timer.elapsed() > DEFAULT_KINESIS_TIMEOUT {
-                    // Unable to read all Kinesis records in the default
-                    // time allotted -- fail.
-                    return Err(format!(
-                        "timeout reading from Kinesis stream: {}",
-                        stream_name
-                    ));
-                }
+        // Records may still be arriving, e.g. from a Kinesis sink, so retry
+        // until the expected records are present.
+        retry::retry_for(DEFAULT_KINESIS_TIMEOUT, |_| async {
+            let records = read_records(&state.kinesis_client, &stream_name).await?;
+
+            // For now, we don't guarantee any type of ordering!
+            if records != self.expected_records {
+                let missing_records = &self.expected_records - &records;
+                let extra_records = &records - &self.expected_records;
+                return Err(format!(
+                    "kinesis records did not match:\nmissing:\n{}\nextra:\n{}",
+                    missing_records.iter().join("\n"),
+                    extra_records.iter().join("\n")
+                ));
             }
-        }
 
-        // For now, we don't guarantee any type of ordering!
-        if records != self.expected_records {
-            let missing_records = &self.expected_records - &records;
-            let extra_records = &records - &self.expected_records;
-            return Err(format!(
-                "kinesis records did not match:\nmissing:\n{}\nextra:\n{}",
-                missing_records.iter().join("\n"),
-                extra_records.iter().join("\n")
-            ));
-        }
+            Ok(())
+        })
+        .await
+    }
+}
 
-        Ok(())
+async fn read_records(
+    kinesis_client: &KinesisClient,
+    stream_name: &str,
+) -> Result<HashSet<String>, String> {
+    let mut shard_iterators = get_shard_iterators(kinesis_client, stream_name).await?;
+    let timer = Instant::now();
+    let mut records: HashSet<String> = HashSet::new();
+    while let Some(iterator) = shard_iterators.pop_front() {
+        if let Some(iterator) = &iterator {
+            let output = kinesis_client
+                .get_records(GetRecordsInput {
+                    limit: None,
+                    shard_iterator: iterator.clone(),
+                })
+                .await
+                .map_err(|e| format!("getting Kinesis records: {}", e))?;
+            for record in output.records {
+                records.insert(
+                    String::from_utf8(record.data.to_vec())
+                        .map_err(|e| format!("converting Kinesis record bytes to utf8: {}", e))?,
+                );
+            }
+            match output.millis_behind_latest {
+                // Test hack!
+                // Assume all records have already been written to the stream. Once you've
+                // caught up, you're done with that shard.
+                // NOTE: this is not true for real Kinesis streams as data could still be
+                // arriving.
+                Some(0) => (),
+                _ => shard_iterators.push_back(output.next_shard_iterator),
+            };
+            if timer.elapsed() > DEFAULT_KINESIS_TIMEOUT {
+                // Unable to read all Kinesis records in the default
+                // time allotted -- fail.
+                return Err(format!(
+                    "timeout reading from Kinesis stream: {}",
+                    stream_name
+                ));
+            }
+        }
     }
+    Ok(records)
 }
 
 async fn get_shard_iterators(
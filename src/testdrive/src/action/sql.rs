@@ -336,13 +336,18 @@ fn decode_row(row: Row) -> Result<Vec<String>, String> {
         out.push(
             match *ty {
                 Type::BOOL => row.get::<_, Option<bool>>(i).map(|x| x.to_string()),
-                Type::CHAR | Type::TEXT => row.get::<_, Option<String>>(i),
+                Type::CHAR | Type::BPCHAR | Type::TEXT | Type::VARCHAR => {
+                    row.get::<_, Option<String>>(i)
+                }
                 Type::BYTEA => row.get::<_, Option<Vec<u8>>>(i).map(|x| {
                     let s = x.into_iter().map(ascii::escape_default).flatten().collect();
                     String::from_utf8(s).unwrap()
                 }),
+                Type::INT2 => row.get::<_, Option<i16>>(i).map(|x| x.to_string()),
                 Type::INT4 => row.get::<_, Option<i32>>(i).map(|x| x.to_string()),
                 Type::INT8 => row.get::<_, Option<i64>>(i).map(|x| x.to_string()),
+                Type::FLOAT4 => row.get::<_, Option<f32>>(i).map(|x| x.to_string()),
+                Type::FLOAT8 => row.get::<_, Option<f64>>(i).map(|x| x.to_string()),
                 Type::NUMERIC => row.get::<_, Option<Numeric>>(i).map(|x| x.to_string()),
                 Type::TIMESTAMP => row
                     .get::<_, Option<chrono::NaiveDateTime>>(i)
@@ -91,6 +91,10 @@ pub fn from_json(json: &JsonValue, schema: SchemaNode) -> Result<Value, String>
                 scale: *scale,
             }))
         }
+        (JsonValue::String(s), SchemaPiece::Uuid) => {
+            let u = s.parse().map_err(|_| format!("invalid uuid: {}", s))?;
+            Ok(Value::Uuid(u))
+        }
         (JsonValue::String(s), SchemaPiece::Json) => {
             let j = serde_json::from_str(s).map_err(|e| e.to_string())?;
             Ok(Value::Json(j))
@@ -87,6 +87,17 @@ async fn run() -> Result<(), Error> {
         "validate the on-disk state of the materialized catalog",
         "PATH",
     );
+    opts.optflag(
+        "",
+        "no-reset",
+        "don't drop the existing materialized state before running each script",
+    );
+    opts.optopt(
+        "",
+        "seed",
+        "the value of ${testdrive.seed}, rather than a random one",
+        "N",
+    );
     opts.optflag("h", "help", "show this usage information");
     let usage_details = opts.usage("usage: testdrive [options] FILE");
     let opts = opts
@@ -195,6 +206,14 @@ async fn run() -> Result<(), Error> {
     if let Some(path) = opts.opt_str("validate-catalog") {
         config.materialized_catalog_path = Some(path.into());
     }
+    config.reset_materialized = !opts.opt_present("no-reset");
+    if let Some(seed) = opts.opt_str("seed") {
+        config.seed = Some(seed.parse().map_err(|e| Error::General {
+            ctx: "parsing seed".into(),
+            cause: Some(Box::new(e)),
+            hints: vec![],
+        })?);
+    }
 
     if opts.free.is_empty() {
         testdrive::run_stdin(&config).await
@@ -75,6 +75,10 @@ impl ColumnKnowledge {
                 }
                 body_knowledge
             }
+            RelationExpr::Iterate { id: _, body } => {
+                // References to the previous iterate are only known by their type.
+                ColumnKnowledge::harvest(body, knowledge)?
+            }
             RelationExpr::Project { input, outputs } => {
                 let input_knowledge = ColumnKnowledge::harvest(input, knowledge)?;
                 outputs
@@ -201,7 +205,8 @@ impl ColumnKnowledge {
                         optimize(&mut aggregate.expr, &input.typ(), &input_knowledge[..])?;
                     // This could be improved.
                     let knowledge = match aggregate.func {
-                        AggregateFunc::MaxInt32
+                        AggregateFunc::MaxInt16
+                        | AggregateFunc::MaxInt32
                         | AggregateFunc::MaxInt64
                         | AggregateFunc::MaxFloat32
                         | AggregateFunc::MaxFloat64
@@ -211,6 +216,7 @@ impl ColumnKnowledge {
                         | AggregateFunc::MaxDate
                         | AggregateFunc::MaxTimestamp
                         | AggregateFunc::MaxTimestampTz
+                        | AggregateFunc::MinInt16
                         | AggregateFunc::MinInt32
                         | AggregateFunc::MinInt64
                         | AggregateFunc::MinFloat32
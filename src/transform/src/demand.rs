@@ -75,6 +75,12 @@ impl Demand {
 
                 self.action(value, needs, gets);
             }
+            RelationExpr::Iterate { id: _, body } => {
+                // The body's results are fed back into the body, and so each of
+                // its columns may be demanded by the next iteration.
+                let arity = body.arity();
+                self.action(body, (0..arity).collect(), gets);
+            }
             RelationExpr::Project { input, outputs } => {
                 self.action(
                     input,
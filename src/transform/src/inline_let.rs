@@ -74,6 +74,18 @@ impl InlineLet {
             *relation = body.take_safely();
             // might be another Let in the body so have to recur here
             self.action(relation, lets);
+        } else if let RelationExpr::Iterate { id: _, body } = relation {
+            // bindings in the body may depend on the previous iterate, so they
+            // must not be lifted out of the body
+            let mut body_lets = vec![];
+            self.action(body, &mut body_lets);
+            for (id, value) in body_lets.into_iter().rev() {
+                **body = RelationExpr::Let {
+                    id,
+                    value: Box::new(value),
+                    body: Box::new(body.take_safely()),
+                };
+            }
         } else {
             relation.visit1_mut(|child| self.action(child, lets));
         }
@@ -35,7 +35,7 @@ impl crate::Transform for JoinImplementation {
         for (k, v) in args.indexes {
             arranged.insert(Id::Global(*k), v.clone());
         }
-        self.action_recursive(relation, &mut arranged);
+        self.action_recursive(relation, &mut arranged, true);
         Ok(())
     }
 }
@@ -45,13 +45,17 @@ impl JoinImplementation {
     ///
     /// This method accumulates state about let-bound arrangements, so that
     /// join operators can more accurately assess their available arrangements.
+    ///
+    /// Delta queries are only considered if `delta_queries` is set. They rely
+    /// on totally ordered timestamps, which the bodies of iterations lack.
     pub fn action_recursive(
         &self,
         relation: &mut RelationExpr,
         arranged: &mut HashMap<Id, Vec<Vec<ScalarExpr>>>,
+        delta_queries: bool,
     ) {
         if let RelationExpr::Let { id, value, body } = relation {
-            self.action_recursive(value, arranged);
+            self.action_recursive(value, arranged, delta_queries);
             match &**value {
                 RelationExpr::ArrangeBy { keys, .. } => {
                     arranged.insert(Id::Local(*id), keys.clone());
@@ -64,16 +68,23 @@ impl JoinImplementation {
                 }
                 _ => {}
             }
-            self.action_recursive(body, arranged);
+            self.action_recursive(body, arranged, delta_queries);
             arranged.remove(&Id::Local(*id));
+        } else if let RelationExpr::Iterate { body, .. } = relation {
+            self.action_recursive(body, arranged, false);
         } else {
-            relation.visit1_mut(|e| self.action_recursive(e, arranged));
-            self.action(relation, arranged);
+            relation.visit1_mut(|e| self.action_recursive(e, arranged, delta_queries));
+            self.action(relation, arranged, delta_queries);
         }
     }
 
     /// Determines the join implementation for join operators.
-    pub fn action(&self, relation: &mut RelationExpr, indexes: &HashMap<Id, Vec<Vec<ScalarExpr>>>) {
+    pub fn action(
+        &self,
+        relation: &mut RelationExpr,
+        indexes: &HashMap<Id, Vec<Vec<ScalarExpr>>>,
+        delta_queries: bool,
+    ) {
         if let RelationExpr::Join { inputs, .. } = relation {
             // Common information of broad utility.
             // TODO: Figure out how to package this up for everyone who uses it.
@@ -148,13 +159,17 @@ impl JoinImplementation {
             // Determine if we can perform delta queries with the existing arrangements.
             // We could defer the execution if we are sure we know we want one input,
             // but we could imagine wanting the best from each and then comparing the two.
-            let delta_query_plan = delta_queries::plan(
-                relation,
-                &arities,
-                &prior_arities,
-                &available_arrangements,
-                &unique_keys,
-            );
+            let delta_query_plan = if delta_queries {
+                delta_queries::plan(
+                    relation,
+                    &arities,
+                    &prior_arities,
+                    &available_arrangements,
+                    &unique_keys,
+                )
+            } else {
+                None
+            };
             let differential_plan = differential::plan(
                 relation,
                 &arities,
@@ -139,6 +139,15 @@ impl LiteralLifting {
                 gets.remove(&id);
                 result
             }
+            RelationExpr::Iterate { id: _, body } => {
+                // Literals lifted from the body must be restored within the
+                // body, as the body's results are fed back into the body.
+                let literals = self.action(body, gets);
+                if !literals.is_empty() {
+                    **body = body.take_dangerous().map(literals);
+                }
+                Vec::new()
+            }
             RelationExpr::Project { input, outputs } => {
                 // We do not want to lift literals around projections.
                 // Projections are the highest lifted operator and lifting
@@ -76,6 +76,11 @@ impl NonNullRequirements {
                     self.action(value, need, gets);
                 }
             }
+            RelationExpr::Iterate { id: _, body } => {
+                // The body's results are fed back into the body, and so no
+                // rows may be discarded on account of our requirements.
+                self.action(body, HashSet::new(), gets);
+            }
             RelationExpr::Project { input, outputs } => {
                 self.action(
                     input,
@@ -117,7 +122,9 @@ impl NonNullRequirements {
                     | TableFunc::JsonbArrayElements { .. }
                     | TableFunc::GenerateSeries(_)
                     | TableFunc::RegexpExtract(_)
-                    | TableFunc::CsvExtract(_) => {
+                    | TableFunc::CsvExtract(_)
+                    | TableFunc::UnnestList { .. }
+                    | TableFunc::UnnestArray { .. } => {
                         for expr in exprs {
                             expr.non_null_requirements(&mut columns);
                         }
@@ -63,6 +63,11 @@ impl ProjectionLifting {
                 self.action(body, gets);
                 gets.remove(&id);
             }
+            RelationExpr::Iterate { id: _, body } => {
+                // Projections cannot be lifted out of the body, as the body's
+                // results are fed back into the body.
+                self.action(body, gets);
+            }
             RelationExpr::Project { input, outputs } => {
                 self.action(input, gets);
                 if let RelationExpr::Project {
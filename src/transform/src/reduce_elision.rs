@@ -42,10 +42,17 @@ impl ReduceElision {
         } = relation
         {
             let input_type = input.typ();
-            if input_type.keys.iter().any(|keys| {
-                keys.iter()
-                    .all(|k| group_key.contains(&crate::ScalarExpr::Column(*k)))
-            }) {
+            // Window functions produce a list of results even for groups of
+            // a single row, and cannot be replaced by their argument.
+            let has_window = aggregates
+                .iter()
+                .any(|a| matches!(a.func, expr::AggregateFunc::Window { .. }));
+            if !has_window
+                && input_type.keys.iter().any(|keys| {
+                    keys.iter()
+                        .all(|k| group_key.contains(&crate::ScalarExpr::Column(*k)))
+                })
+            {
                 use expr::{AggregateFunc, UnaryFunc};
                 use repr::Datum;
                 let map_scalars = aggregates
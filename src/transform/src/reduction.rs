@@ -42,6 +42,12 @@ impl FoldConstants {
             RelationExpr::Constant { .. } => { /* handled after match */ }
             RelationExpr::Get { .. } => {}
             RelationExpr::Let { .. } => { /* constant prop done in InlineLet */ }
+            RelationExpr::Iterate { id: _, body } => {
+                // A constant body does not depend on the previous iterate.
+                if let RelationExpr::Constant { .. } = &**body {
+                    *relation = body.take_dangerous();
+                }
+            }
             RelationExpr::Reduce {
                 input,
                 group_key,
@@ -58,6 +58,18 @@ impl UpdateLet {
                 }
                 *id = new_id;
             }
+            RelationExpr::Iterate { id, body } => {
+                // The body refers to its own previous result, and so must be
+                // rewritten with the new identifier already in place.
+                let new_id = LocalId::new(id_gen.allocate_id());
+                let prev = remap.insert(id.clone(), (new_id, body.typ()));
+                self.action(body, remap, id_gen);
+                remap.remove(id);
+                if let Some(prev_stuff) = prev {
+                    remap.insert(id.clone(), prev_stuff);
+                }
+                *id = new_id;
+            }
             RelationExpr::Get { id, typ } => {
                 if let Id::Local(local_id) = id {
                     if let Some((new_id, new_type)) = remap.get(local_id) {